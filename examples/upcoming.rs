@@ -0,0 +1,33 @@
+//! Print the next five deadlines across my courses, for a status bar.
+//!
+//! Uses the same config as canvas-tui: `cargo run --example upcoming`.
+
+use anyhow::Result;
+use canvas_tui::config::Config;
+use chrono::{Local, Utc};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let client = Config::load()?.client()?;
+    let now = Utc::now();
+
+    let mut upcoming = Vec::new();
+    for course in client.list_courses().await? {
+        let assignments = client.list_assignments(course.id, true, false, None).await?;
+        let code = course.course_code.or(course.name).unwrap_or_default();
+        for a in assignments.items {
+            let Some(due) = a.due_at.filter(|due| *due > now) else {
+                continue;
+            };
+            if !a.is_submitted() && !a.is_unpublished() {
+                upcoming.push((due, code.clone(), a.name.unwrap_or_default()));
+            }
+        }
+    }
+    upcoming.sort();
+
+    for (due, course, name) in upcoming.iter().take(5) {
+        println!("{}  {course}  {name}", due.with_timezone(&Local).format("%a %b %d %H:%M"));
+    }
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! How far this computer's clock is from Canvas's, measured from the `Date`
+//! header of its responses.  A clock that is off makes every countdown and
+//! "due today" confidently wrong, so the app warns once the difference is
+//! past `SKEW_THRESHOLD` and can correct for it.
+
+use chrono::{DateTime, Duration, Utc};
+use reqwest::header::{HeaderMap, DATE};
+
+/// Skew below this is left alone: the header is to the second and the
+/// response took a moment to arrive.
+pub const SKEW_THRESHOLD: Duration = Duration::minutes(2);
+
+/// Canvas's time minus `local_now` (positive when this clock is slow), from
+/// a response's `Date` header.  `None` without a header that parses.
+pub fn skew_from(headers: &HeaderMap, local_now: DateTime<Utc>) -> Option<Duration> {
+    let date = headers.get(DATE)?.to_str().ok()?;
+    let server = DateTime::parse_from_rfc2822(date).ok()?;
+    Some(server.with_timezone(&Utc) - local_now)
+}
+
+/// Whether `skew` is enough to warn about.
+pub fn significant(skew: Duration) -> bool {
+    skew.abs() >= SKEW_THRESHOLD
+}
+
+/// `skew` in words, to the minute: "41m", "2h 5m", "1d 3h".
+pub fn describe(skew: Duration) -> String {
+    let mins = skew.num_minutes().abs();
+    let (days, hours, mins) = (mins / 1440, mins / 60 % 24, mins % 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {mins}m")
+    } else {
+        format!("{mins}m")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use reqwest::header::HeaderValue;
+
+    fn date_header(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(DATE, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    fn local(h: u32, m: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 4, 1, h, m, s).unwrap()
+    }
+
+    #[test]
+    fn skew_is_server_time_minus_local_time() {
+        let headers = date_header("Tue, 01 Apr 2025 12:41:00 GMT");
+        assert_eq!(skew_from(&headers, local(12, 0, 0)), Some(Duration::minutes(41)));
+        assert_eq!(skew_from(&headers, local(13, 0, 30)), Some(Duration::seconds(-19 * 60 - 30)));
+        // Offsets other than GMT are honoured.
+        let headers = date_header("Tue, 01 Apr 2025 14:41:00 +0200");
+        assert_eq!(skew_from(&headers, local(12, 41, 0)), Some(Duration::zero()));
+    }
+
+    #[test]
+    fn missing_or_unreadable_dates_give_no_skew() {
+        assert_eq!(skew_from(&HeaderMap::new(), local(12, 0, 0)), None);
+        for bad in ["", "yesterday", "2025-04-01T12:41:00Z", "Tue, 32 Apr 2025 12:41:00 GMT"] {
+            assert_eq!(skew_from(&date_header(bad), local(12, 0, 0)), None, "{bad}");
+        }
+    }
+
+    #[test]
+    fn only_skew_past_the_threshold_is_significant() {
+        assert!(!significant(Duration::zero()));
+        assert!(!significant(Duration::seconds(119)));
+        assert!(!significant(Duration::seconds(-119)));
+        assert!(significant(Duration::minutes(2)));
+        assert!(significant(Duration::minutes(-41)));
+    }
+
+    #[test]
+    fn descriptions_round_to_the_minute_either_way() {
+        assert_eq!(describe(Duration::seconds(59)), "0m");
+        assert_eq!(describe(Duration::minutes(41)), "41m");
+        assert_eq!(describe(Duration::minutes(-41)), "41m");
+        assert_eq!(describe(Duration::minutes(125)), "2h 5m");
+        assert_eq!(describe(Duration::minutes(27 * 60 + 10)), "1d 3h");
+    }
+}
@@ -0,0 +1,59 @@
+//! Telling a maintenance page from an API answer.  While Canvas is down for
+//! maintenance some endpoints answer with an HTML "we'll be back" page,
+//! sometimes even with status 200, where JSON was expected.
+
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+
+/// Whether the response is an HTML page rather than JSON.
+pub fn is_html(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.trim_start().to_ascii_lowercase().starts_with("text/html"))
+}
+
+/// The `<title>` of an HTML page, whitespace collapsed.
+pub fn page_title(body: &str) -> Option<String> {
+    let lower = body.to_ascii_lowercase();
+    let open = lower.find("<title")?;
+    let start = open + lower[open..].find('>')? + 1;
+    let end = start + lower[start..].find("</title")?;
+    let title = body[start..end].split_whitespace().collect::<Vec<_>>().join(" ");
+    (!title.is_empty()).then_some(title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn content_type(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn html_is_told_from_json_by_content_type() {
+        assert!(is_html(&content_type("text/html")));
+        assert!(is_html(&content_type(" Text/HTML; charset=utf-8")));
+        assert!(!is_html(&content_type("application/json; charset=utf-8")));
+        assert!(!is_html(&content_type("text/plain")));
+        assert!(!is_html(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn page_title_is_found_in_any_case_with_whitespace_collapsed() {
+        let page = "<html><HEAD><Title lang=\"en\">\n  Canvas is\n down for  maintenance </TITLE>";
+        assert_eq!(page_title(page).as_deref(), Some("Canvas is down for maintenance"));
+        assert_eq!(page_title("<title>Übung – Wartung</title>").as_deref(), Some("Übung – Wartung"));
+    }
+
+    #[test]
+    fn pages_without_a_usable_title_have_none() {
+        assert_eq!(page_title("<html><body>Back soon</body></html>"), None);
+        assert_eq!(page_title("<title>   </title>"), None);
+        assert_eq!(page_title("<title>Never closed"), None);
+        assert_eq!(page_title(""), None);
+    }
+}
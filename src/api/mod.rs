@@ -1,14 +1,32 @@
+pub mod clock;
+mod maintenance;
+pub mod oauth;
+mod options;
+mod paginated;
 mod pagination;
+mod throttle;
 
 use anyhow::{Context, Result};
-use reqwest::{Client, Response, StatusCode};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::sync::OnceCell;
 use url::Url;
 
 use crate::models::*;
+use oauth::{OAuthSession, OAuthTokens};
 use pagination::parse_link_header;
 
+pub use options::{clamp_per_page, ApiOptions};
+pub use paginated::{PageResult, PaginatedList};
+pub use throttle::DEFAULT_MAX_CONCURRENT;
+
 // ─── Error types ────────────────────────────────────────────────────────────
 
+/// Why a request to Canvas failed.
 #[derive(Debug, thiserror::Error)]
 pub enum CanvasError {
     #[error("HTTP {status}: {message}")]
@@ -17,39 +35,369 @@ pub enum CanvasError {
     RateLimited { retry_after: f64 },
     #[error("Unauthorized – check your API token")]
     Unauthorized,
+    /// An HTML page came back where JSON was expected, as during Canvas
+    /// maintenance windows.
+    #[error("Canvas appears to be in maintenance{}", title_suffix(.title))]
+    Maintenance { title: Option<String> },
+    /// Shared so waiters on a coalesced request all see the network error.
     #[error("Network error: {0}")]
-    Network(#[from] reqwest::Error),
+    Network(Arc<reqwest::Error>),
     #[error("{0}")]
     Other(#[from] anyhow::Error),
 }
 
+impl CanvasError {
+    /// A copy for another waiter on a shared request.  `Other` errors can't
+    /// be cloned, so they keep their message only.
+    fn duplicate(&self) -> Self {
+        match self {
+            Self::Api { status, message } => Self::Api {
+                status: *status,
+                message: message.clone(),
+            },
+            Self::RateLimited { retry_after } => Self::RateLimited {
+                retry_after: *retry_after,
+            },
+            Self::Unauthorized => Self::Unauthorized,
+            Self::Maintenance { title } => Self::Maintenance {
+                title: title.clone(),
+            },
+            Self::Network(e) => Self::Network(e.clone()),
+            Self::Other(_) => Self::Other(anyhow::anyhow!("{self}")),
+        }
+    }
+}
+
+impl From<reqwest::Error> for CanvasError {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Network(Arc::new(e))
+    }
+}
+
+fn title_suffix(title: &Option<String>) -> String {
+    title.as_ref().map(|t| format!(" ({t})")).unwrap_or_default()
+}
+
+/// Raw page bodies of a list request.
+#[derive(Debug, Default)]
+struct Pages {
+    bodies: Vec<String>,
+    /// Pagination was stopped early by an item cap.
+    more: bool,
+}
+
+/// Pages of a list request, shareable between concurrent callers.
+type SharedPages = Result<Arc<Pages>, Arc<CanvasError>>;
+
+/// How much of an undecodable body goes into the log.
+const BAD_PAYLOAD_EXCERPT: usize = 2048;
+
+/// One page of a list response.  Errors say where in the JSON decoding
+/// stopped (e.g. `[3].due_at`).
+fn decode_page<T: serde::de::DeserializeOwned>(
+    body: &str,
+) -> Result<Vec<T>, serde_path_to_error::Error<serde_json::Error>> {
+    serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(body))
+}
+
+/// Items of a list request made with an item cap.
+#[derive(Debug)]
+pub struct Capped<T> {
+    pub items: Vec<T>,
+    /// There were more items than the cap allowed.
+    pub truncated: bool,
+    /// A page after the first didn't decode, so `items` only holds the
+    /// pages before it.
+    pub partial: bool,
+}
+
 // ─── Client ─────────────────────────────────────────────────────────────────
 
+/// OAuth2 state shared by a client and its clones.
+#[derive(Debug)]
+struct OAuthState {
+    session: Mutex<OAuthSession>,
+    /// Held while refreshing, so a burst of 401s refreshes once.
+    refreshing: tokio::sync::Mutex<()>,
+    /// Tokens from the latest refresh, until `take_refreshed_tokens`.
+    unsaved: Mutex<Option<OAuthTokens>>,
+}
+
+impl OAuthState {
+    /// The access token expires within a minute (or already has).
+    fn expires_soon(&self) -> bool {
+        let session = self.session.lock().unwrap_or_else(PoisonError::into_inner);
+        session
+            .expires_at
+            .is_some_and(|at| at - chrono::Duration::minutes(1) <= Utc::now())
+    }
+}
+
+/// A client for one Canvas instance's REST API, signed in with one token.
+/// Clones share the token, the request counter and the rate limit pacing,
+/// so a clone can be handed to each background task.
 #[derive(Debug, Clone)]
 pub struct CanvasClient {
     client: Client,
     base_url: Url,
-    token: String,
+    /// The access token, shared by all clones so a refresh reaches them all.
+    token: Arc<Mutex<String>>,
+    /// Set when logged in with OAuth2 (`--login`), to renew the token.
+    oauth: Option<Arc<OAuthState>>,
+    /// Admin masquerading: act as this user on every request.
+    as_user_id: Option<u64>,
+    /// Let submissions through while masquerading.
+    allow_masquerade_submit: bool,
+    /// Where responses that fail to decode are saved.
+    payload_dir: Option<PathBuf>,
+    /// Requests sent so far, shared by all clones (for sync stats).
+    requests: Arc<AtomicU64>,
+    /// How far Canvas's clock is ahead of ours, from the latest response
+    /// with a `Date` header; shared by all clones.
+    clock_skew: Arc<Mutex<Option<chrono::Duration>>>,
+    /// List requests currently being fetched, by URL, shared by all clones.
+    in_flight: Arc<Mutex<HashMap<String, Arc<OnceCell<SharedPages>>>>>,
+    /// Concurrency and rate limit pacing, shared by all clones.
+    throttle: Arc<throttle::Throttle>,
+    options: ApiOptions,
 }
 
 impl CanvasClient {
+    /// A client for the instance at `base_url`
+    /// (`https://school.instructure.com`) with an API access token.
     pub fn new(base_url: &str, token: &str) -> Result<Self> {
         let base_url = Url::parse(base_url)
             .with_context(|| format!("Invalid Canvas URL: {base_url}"))?;
 
         let client = Client::builder()
-            .user_agent("canvas-tui/0.1.0")
+            .user_agent(crate::version::USER_AGENT)
             .build()?;
 
         Ok(Self {
             client,
             base_url,
-            token: token.to_string(),
+            token: Arc::new(Mutex::new(token.to_string())),
+            oauth: None,
+            as_user_id: None,
+            allow_masquerade_submit: false,
+            payload_dir: None,
+            requests: Arc::new(AtomicU64::new(0)),
+            clock_skew: Arc::default(),
+            in_flight: Arc::default(),
+            throttle: Arc::new(throttle::Throttle::new(DEFAULT_MAX_CONCURRENT)),
+            options: ApiOptions::default(),
         })
     }
 
+    /// Use `options` for page sizes, optional includes and the concurrency
+    /// limit.  Clones made before this keep the old limit.
+    pub fn with_options(self, options: ApiOptions) -> Self {
+        Self {
+            throttle: Arc::new(throttle::Throttle::new(options.max_concurrent_requests)),
+            options,
+            ..self
+        }
+    }
+
+    /// Save responses that fail to decode in `dir`.
+    pub fn with_payload_dir(self, dir: PathBuf) -> Self {
+        Self {
+            payload_dir: Some(dir),
+            ..self
+        }
+    }
+
+    /// Renew the access token through `session` when it expires.
+    pub fn with_oauth(self, session: OAuthSession) -> Self {
+        let oauth = OAuthState {
+            session: Mutex::new(session),
+            refreshing: tokio::sync::Mutex::new(()),
+            unsaved: Mutex::new(None),
+        };
+        Self {
+            oauth: Some(Arc::new(oauth)),
+            ..self
+        }
+    }
+
+    /// Make every request as user `as_user_id` (Canvas masquerading, for
+    /// admins).  Submissions are refused unless `allow_submit`.
+    pub fn with_masquerade(self, as_user_id: u64, allow_submit: bool) -> Self {
+        Self {
+            as_user_id: Some(as_user_id),
+            allow_masquerade_submit: allow_submit,
+            ..self
+        }
+    }
+
+    /// The user being masqueraded as, if any.
+    pub fn as_user_id(&self) -> Option<u64> {
+        self.as_user_id
+    }
+
+    /// Whether `url` is on the Canvas host rather than, say, file storage.
+    fn is_ours(&self, url: &Url) -> bool {
+        url.host_str() == self.base_url.host_str()
+    }
+
+    /// `url` with `as_user_id` added when masquerading.  URLs that already
+    /// carry it (pagination links) and other hosts (file storage) are left
+    /// alone.
+    fn masqueraded(&self, mut url: Url) -> Url {
+        let Some(id) = self.as_user_id else {
+            return url;
+        };
+        if self.is_ours(&url) && !url.query_pairs().any(|(k, _)| k == "as_user_id") {
+            url.query_pairs_mut().append_pair("as_user_id", &id.to_string());
+        }
+        url
+    }
+
+    /// `request` to `url` with the configured extra headers, unless it goes
+    /// to another host, which must not see them.
+    fn with_extra_headers(&self, request: RequestBuilder, url: &Url) -> RequestBuilder {
+        if self.options.extra_headers.is_empty() || !self.is_ours(url) {
+            return request;
+        }
+        request.headers(self.options.extra_headers.clone())
+    }
+
+    /// Submitting as someone else is refused unless explicitly allowed.
+    fn ensure_submit_allowed(&self) -> Result<(), CanvasError> {
+        match self.as_user_id {
+            Some(id) if !self.allow_masquerade_submit => Err(CanvasError::Other(anyhow::anyhow!(
+                "Not submitting while masquerading as user {id} \
+                 (set allow_masquerade_submit = true to allow it)"
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Finish an OAuth2 login: trade the authorize redirect's `code` for
+    /// tokens.
+    pub async fn exchange_oauth_code(
+        &self,
+        key: &oauth::DeveloperKey,
+        redirect_uri: &str,
+        code: &str,
+    ) -> Result<OAuthTokens, CanvasError> {
+        oauth::exchange_code(&self.client, &self.base_url, key, redirect_uri, code).await
+    }
+
+    /// Tokens from a refresh that haven't been saved to the config yet.
+    pub fn take_refreshed_tokens(&self) -> Option<OAuthTokens> {
+        self.oauth.as_ref()?.unsaved.lock().unwrap_or_else(PoisonError::into_inner).take()
+    }
+
+    fn access_token(&self) -> String {
+        self.token.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// Send the request `build` makes for the current access token.  With
+    /// OAuth, an expired token is refreshed first, and a 401 refreshes it
+    /// and retries once.
+    async fn send_authed(
+        &self,
+        build: impl Fn(&str) -> RequestBuilder,
+    ) -> Result<Response, CanvasError> {
+        if let Some(oauth) = &self.oauth {
+            if oauth.expires_soon() {
+                self.refresh_access_token(&self.access_token()).await?;
+            }
+        }
+        let token = self.access_token();
+        let result = self.send_throttled(build(&token)).await;
+        if !matches!(result, Err(CanvasError::Unauthorized)) || self.oauth.is_none() {
+            return result;
+        }
+
+        self.refresh_access_token(&token).await?;
+        self.send_throttled(build(&self.access_token())).await
+    }
+
+    /// Send `request` once the throttle lets it through, noting the rate
+    /// limit budget its response reports.
+    async fn send_throttled(&self, request: RequestBuilder) -> Result<Response, CanvasError> {
+        let _slot = self.throttle.acquire().await;
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let resp = request.send().await?;
+        self.throttle
+            .record(resp.headers(), resp.status() == StatusCode::TOO_MANY_REQUESTS);
+        Self::check_status(resp).await
+    }
+
+    /// Replace `rejected` with a fresh access token.  Concurrent callers
+    /// wait for one refresh instead of each making their own.
+    async fn refresh_access_token(&self, rejected: &str) -> Result<(), CanvasError> {
+        let Some(oauth) = &self.oauth else {
+            return Err(CanvasError::Unauthorized);
+        };
+        let _refreshing = oauth.refreshing.lock().await;
+        if self.access_token() != rejected {
+            return Ok(());
+        }
+
+        let (key, refresh_token) = {
+            let session = oauth.session.lock().unwrap_or_else(PoisonError::into_inner);
+            (session.key.clone(), session.refresh_token.clone())
+        };
+        let tokens = oauth::refresh(&self.client, &self.base_url, &key, &refresh_token)
+            .await
+            .map_err(|e| {
+                tracing::warn!("OAuth token refresh failed: {e}");
+                CanvasError::Unauthorized
+            })?;
+        tracing::info!("OAuth access token refreshed");
+
+        *self.token.lock().unwrap_or_else(PoisonError::into_inner) = tokens.access_token.clone();
+        {
+            let mut session = oauth.session.lock().unwrap_or_else(PoisonError::into_inner);
+            session.refresh_token = tokens.refresh_token.clone();
+            session.expires_at = tokens.expires_at;
+        }
+        *oauth.unsaved.lock().unwrap_or_else(PoisonError::into_inner) = Some(tokens);
+        Ok(())
+    }
+
+    /// Signed in with OAuth2 (`--login`) rather than an API token.
+    pub fn uses_oauth(&self) -> bool {
+        self.oauth.is_some()
+    }
+
+    /// When the OAuth2 access token expires; `None` for an API token or
+    /// when Canvas didn't say.
+    pub fn token_expires_at(&self) -> Option<DateTime<Utc>> {
+        let oauth = self.oauth.as_ref()?;
+        oauth.session.lock().unwrap_or_else(PoisonError::into_inner).expires_at
+    }
+
+    /// The Canvas instance's URL, for resolving relative links in content.
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// Total API requests sent by this client and its clones.
+    pub fn request_count(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    /// Canvas's time minus ours, as of the latest response that said;
+    /// `None` before any did.
+    pub fn clock_skew(&self) -> Option<chrono::Duration> {
+        *self.clock_skew.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// A clone whose `request_count` starts from zero and counts only its
+    /// own requests (and its clones'), for timing concurrent work.
+    pub fn with_own_counter(&self) -> Self {
+        Self {
+            requests: Arc::default(),
+            ..self.clone()
+        }
+    }
+
     fn api_url(&self, path: &str) -> Result<Url> {
-        let full = format!("/api/v1{}", path);
+        let full = format!("{}{}", self.options.api_prefix, path);
         self.base_url
             .join(&full)
             .with_context(|| format!("Bad API path: {path}"))
@@ -65,15 +413,12 @@ impl CanvasClient {
         path: &str,
         body: &B,
     ) -> Result<Response, CanvasError> {
-        let url = self.api_url(path).map_err(CanvasError::Other)?;
-        let resp = self
-            .client
-            .post(url)
-            .bearer_auth(&self.token)
-            .json(body)
-            .send()
-            .await?;
-        Self::check_status(resp).await
+        let url = self.masqueraded(self.api_url(path).map_err(CanvasError::Other)?);
+        self.send_authed(|token| {
+            let request = self.client.post(url.clone()).bearer_auth(token).json(body);
+            self.with_extra_headers(request, &url)
+        })
+        .await
     }
 
     async fn check_status(resp: Response) -> Result<Response, CanvasError> {
@@ -92,6 +437,9 @@ impl CanvasClient {
                     .unwrap_or(1.0);
                 Err(CanvasError::RateLimited { retry_after: retry })
             }
+            StatusCode::SERVICE_UNAVAILABLE if maintenance::is_html(resp.headers()) => {
+                Err(Self::maintenance_error(resp).await)
+            }
             s if s.is_client_error() || s.is_server_error() => {
                 let status = s.as_u16();
                 let message = resp.text().await.unwrap_or_default();
@@ -101,68 +449,296 @@ impl CanvasClient {
         }
     }
 
+    /// `resp`, unless it is an HTML page where the API should have answered
+    /// with JSON.  Not part of `check_status`, as downloads may well be HTML.
+    async fn expect_json(resp: Response) -> Result<Response, CanvasError> {
+        if maintenance::is_html(resp.headers()) {
+            return Err(Self::maintenance_error(resp).await);
+        }
+        Ok(resp)
+    }
+
+    async fn maintenance_error(resp: Response) -> CanvasError {
+        let url = resp.url().clone();
+        let body = resp.text().await.unwrap_or_default();
+        let title = maintenance::page_title(&body);
+        tracing::warn!("{url}: HTML page instead of JSON ({})", title.as_deref().unwrap_or("no title"));
+        CanvasError::Maintenance { title }
+    }
+
+    /// GET `path` and decode its JSON body.
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, CanvasError> {
+        let resp = Self::expect_json(self.get(path).await?).await?;
+        Ok(resp.json().await?)
+    }
+
     async fn get_url(&self, url: Url) -> Result<Response, CanvasError> {
+        let url = self.masqueraded(url);
         let resp = self
-            .client
-            .get(url)
-            .bearer_auth(&self.token)
-            .send()
+            .send_authed(|token| {
+                self.with_extra_headers(self.client.get(url.clone()).bearer_auth(token), &url)
+            })
             .await?;
-        Self::check_status(resp).await
+        if let Some(skew) = clock::skew_from(resp.headers(), Utc::now()) {
+            *self.clock_skew.lock().unwrap_or_else(PoisonError::into_inner) = Some(skew);
+        }
+        Ok(resp)
+    }
+
+    /// GET every page of a list endpoint.  Identical requests already in
+    /// flight are joined rather than repeated (see `fetch_pages_shared`).
+    /// Any page that doesn't decode fails the whole list.
+    async fn get_all_pages<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&str, &str)],
+    ) -> Result<Vec<T>, CanvasError> {
+        let capped = self.get_pages_capped(path, params, None).await?;
+        if capped.partial {
+            return Err(CanvasError::Other(anyhow::anyhow!(
+                "Unexpected response for {path}: a later page did not decode"
+            )));
+        }
+        Ok(capped.items)
     }
 
-    async fn get_paginated<T: serde::de::DeserializeOwned>(
+    /// Like `get_all_pages`, but stops once `max_items` items have arrived,
+    /// and hands back the pages before one that doesn't decode (see
+    /// `Capped::partial`).
+    async fn get_pages_capped<T: serde::de::DeserializeOwned>(
         &self,
         path: &str,
         params: &[(&str, &str)],
-    ) -> Result<(Vec<T>, Option<String>), CanvasError> {
+        max_items: Option<usize>,
+    ) -> Result<Capped<T>, CanvasError> {
         let mut url = self.api_url(path).map_err(CanvasError::Other)?;
         for (k, v) in params {
             url.query_pairs_mut().append_pair(k, v);
         }
-        let resp = self.get_url(url).await?;
-        let next = parse_link_header(resp.headers()).next;
-        let items: Vec<T> = resp.json().await?;
-        Ok((items, next))
+
+        let pages = self.fetch_pages_shared(url, max_items).await?;
+        let mut items = Vec::new();
+        let mut truncated = pages.more;
+        let mut partial = false;
+        for (n, body) in pages.bodies.iter().enumerate() {
+            match decode_page::<T>(body) {
+                Ok(page) => items.extend(page),
+                Err(e) => {
+                    self.report_bad_payload(path, n + 1, &e, body);
+                    // Keep the pages before it, unless there are none.
+                    if n == 0 {
+                        return Err(CanvasError::Other(anyhow::anyhow!(
+                            "Unexpected response for {path}: {e}"
+                        )));
+                    }
+                    partial = true;
+                    break;
+                }
+            }
+        }
+        if let Some(max) = max_items {
+            truncated |= items.len() > max;
+            items.truncate(max);
+        }
+        Ok(Capped { items, truncated, partial })
     }
 
-    async fn get_all_pages<T: serde::de::DeserializeOwned>(
+    /// Raw page bodies starting at `url` (up to `max_items` items).  The
+    /// first caller for a URL fetches; concurrent callers for the same URL
+    /// and cap wait for its result.  The entry is dropped once done, so
+    /// later calls fetch afresh.
+    async fn fetch_pages_shared(
         &self,
-        path: &str,
-        params: &[(&str, &str)],
-    ) -> Result<Vec<T>, CanvasError> {
-        let mut all = Vec::new();
-        let (items, mut next) = self.get_paginated(path, params).await?;
-        all.extend(items);
+        url: Url,
+        max_items: Option<usize>,
+    ) -> Result<Arc<Pages>, CanvasError> {
+        let key = match max_items {
+            Some(max) => format!("{url} (first {max})"),
+            None => url.to_string(),
+        };
+        let cell = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key.clone())
+            .or_default()
+            .clone();
+
+        let result = cell
+            .get_or_init(|| self.fetch_pages(url, max_items))
+            .await
+            .clone();
+
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(PoisonError::into_inner);
+        if in_flight.get(&key).is_some_and(|c| Arc::ptr_eq(c, &cell)) {
+            in_flight.remove(&key);
+        }
+        drop(in_flight);
+
+        result.map_err(|e| e.duplicate())
+    }
+
+    async fn fetch_pages(&self, url: Url, max_items: Option<usize>) -> SharedPages {
+        let mut pages = Pages::default();
+        let mut seen = 0;
+        let mut next = Some(url);
+        while let Some(url) = next.take() {
+            let per_page = url
+                .query_pairs()
+                .find(|(k, _)| k == "per_page")
+                .and_then(|(_, v)| v.parse::<usize>().ok());
+            let resp = self.get_url(url.clone()).await.map_err(Arc::new)?;
+            let resp = Self::expect_json(resp).await.map_err(Arc::new)?;
+            let links = parse_link_header(resp.headers(), &url);
+            if let Some(link) = &links.next {
+                let url = Url::parse(link).map_err(|e| {
+                    Arc::new(CanvasError::Other(anyhow::anyhow!("Bad pagination URL: {e}")))
+                })?;
+                next = Some(url);
+            }
+            let body = resp.text().await.map_err(|e| Arc::new(e.into()))?;
+            // Count without building the items; a body that isn't a list
+            // fails properly when the caller deserializes it.
+            let count = serde_json::from_str::<Vec<serde::de::IgnoredAny>>(&body)
+                .map_or(0, |items| items.len());
 
-        while let Some(next_url) = next.take() {
-            let url = Url::parse(&next_url)
-                .map_err(|e| CanvasError::Other(anyhow::anyhow!("Bad pagination URL: {e}")))?;
-            let resp = self.get_url(url).await?;
-            next = parse_link_header(resp.headers()).next;
-            let items: Vec<T> = resp.json().await?;
-            all.extend(items);
+            // A full page with no next link usually means the Link header
+            // wasn't understood, unless it says this is the last page.
+            let known_last = links.current.is_some() && links.current == links.last;
+            if next.is_none() && per_page == Some(count) && !known_last {
+                tracing::warn!(
+                    "{url}: full page of {count} items but no next link; results may be incomplete"
+                );
+            }
+
+            if let Some(max) = max_items {
+                seen += count;
+                if seen >= max && next.is_some() {
+                    pages.more = true;
+                    next = None;
+                }
+            }
+            pages.bodies.push(body);
         }
+        Ok(Arc::new(pages))
+    }
 
-        Ok(all)
+    /// Log a page that didn't decode, and save the whole body under
+    /// `payload_dir` for a bug report.
+    fn report_bad_payload(
+        &self,
+        path: &str,
+        page: usize,
+        error: &serde_path_to_error::Error<serde_json::Error>,
+        body: &str,
+    ) {
+        let mut excerpt_end = body.len().min(BAD_PAYLOAD_EXCERPT);
+        while !body.is_char_boundary(excerpt_end) {
+            excerpt_end -= 1;
+        }
+        let saved = self.payload_dir.as_ref().and_then(|dir| {
+            let name = format!(
+                "{}{}-page{page}.json",
+                Utc::now().format("%Y%m%dT%H%M%S"),
+                path.replace(|c: char| !c.is_ascii_alphanumeric(), "_"),
+            );
+            let file = dir.join(name);
+            std::fs::create_dir_all(dir)
+                .and_then(|()| std::fs::write(&file, body))
+                .map_err(|e| tracing::warn!("saving bad response to {}: {e}", file.display()))
+                .ok()
+                .map(|()| file)
+        });
+        tracing::warn!(
+            "{path} page {page} did not decode: {error} (saved to {}); body starts: {}",
+            saved.map_or_else(|| "nowhere".into(), |f| f.display().to_string()),
+            &body[..excerpt_end]
+        );
     }
 
     // ── Courses ─────────────────────────────────────────────────────────
 
+    /// My active courses, with their term, enrollments and scores.
     pub async fn list_courses(&self) -> Result<Vec<Course>, CanvasError> {
+        let per_page = self.options.courses_per_page.to_string();
+        let mut params: Vec<(&str, &str)> = vec![("enrollment_state", "active")];
+        if self.options.include_total_students {
+            params.push(("include[]", "total_students"));
+        }
+        params.extend([
+            ("include[]", "term"),
+            ("include[]", "enrollments"),
+            ("include[]", "total_scores"),
+            ("per_page", &per_page),
+        ]);
+        self.get_all_pages("/courses", &params).await
+    }
+
+    // ── Files ────────────────────────────────────────────────────────────
+
+    /// Stream `url` to `dest`, returning the number of bytes written.  Data
+    /// goes to `dest` + ".part" first and is renamed into place only once
+    /// complete, so an interrupted download never looks finished.
+    pub async fn download_file(&self, url: &str, dest: &std::path::Path) -> Result<u64, CanvasError> {
+        use std::io::Write;
+
+        let url = Url::parse(url)
+            .with_context(|| format!("Bad download URL: {url}"))
+            .map_err(CanvasError::Other)?;
+        let mut resp = self.get_url(url).await?;
+
+        let mut part_name = dest.as_os_str().to_owned();
+        part_name.push(".part");
+        let part = std::path::PathBuf::from(part_name);
+        // Also removes the partial file when the download is dropped midway,
+        // as a cancelled bulk download does.
+        let mut guard = PartFile(Some(part.clone()));
+        let result: Result<u64, CanvasError> = async {
+            let mut file = std::fs::File::create(&part)
+                .with_context(|| format!("Could not create {}", part.display()))?;
+            let mut written = 0u64;
+            while let Some(chunk) = resp.chunk().await? {
+                file.write_all(&chunk)
+                    .with_context(|| format!("Could not write {}", part.display()))?;
+                written += chunk.len() as u64;
+            }
+            std::fs::rename(&part, dest)
+                .with_context(|| format!("Could not move download to {}", dest.display()))?;
+            Ok(written)
+        }
+        .await;
+        if result.is_ok() {
+            guard.0 = None;
+        }
+        result
+    }
+
+    /// Every file of a course, in all its folders.
+    pub async fn list_course_files(&self, course_id: u64) -> Result<Vec<FileAttachment>, CanvasError> {
         self.get_all_pages(
-            "/courses",
-            &[
-                ("enrollment_state", "active"),
-                ("include[]", "total_students"),
-                ("include[]", "term"),
-                ("include[]", "enrollments"),
-                ("per_page", "50"),
-            ],
+            &format!("/courses/{course_id}/files"),
+            &[("per_page", "100")],
         )
         .await
     }
 
+    /// Every folder of a course, for placing its files.
+    pub async fn list_course_folders(&self, course_id: u64) -> Result<Vec<Folder>, CanvasError> {
+        self.get_all_pages(
+            &format!("/courses/{course_id}/folders"),
+            &[("per_page", "100")],
+        )
+        .await
+    }
+
+    // ── Groups ───────────────────────────────────────────────────────────
+
+    /// Groups the current user belongs to, across all courses.
+    pub async fn list_own_groups(&self) -> Result<Vec<Group>, CanvasError> {
+        self.get_all_pages("/users/self/groups", &[("per_page", "50")])
+            .await
+    }
+
     // ── Pages ────────────────────────────────────────────────────────────
 
     pub async fn list_pages(&self, course_id: u64) -> Result<Vec<Page>, CanvasError> {
@@ -174,30 +750,68 @@ impl CanvasClient {
     }
 
     pub async fn get_page(&self, course_id: u64, page_url: &str) -> Result<Page, CanvasError> {
-        let resp = self
-            .get(&format!("/courses/{course_id}/pages/{page_url}"))
-            .await?;
-        Ok(resp.json().await?)
+        self.get_json(&format!("/courses/{course_id}/pages/{page_url}"))
+            .await
     }
 
     // ── Assignments ─────────────────────────────────────────────────────
 
+    /// Assignments of a course, at most `max_items` of them.  Without
+    /// `include_description` Canvas leaves the (often large) descriptions
+    /// out; see `get_assignment`.
     pub async fn list_assignments(
         &self,
         course_id: u64,
         include_submission: bool,
-    ) -> Result<Vec<Assignment>, CanvasError> {
+        include_description: bool,
+        max_items: Option<usize>,
+    ) -> Result<Capped<Assignment>, CanvasError> {
+        let per_page = self.options.assignments_per_page.to_string();
         let mut params: Vec<(&str, &str)> = vec![
-            ("per_page", "50"),
+            ("per_page", &per_page),
             ("order_by", "due_at"),
         ];
         if include_submission {
             params.push(("include[]", "submission"));
+            if self.options.include_submission_comments {
+                params.push(("include[]", "submission_comments"));
+            }
         }
-        self.get_all_pages(&format!("/courses/{course_id}/assignments"), &params)
+        if self.options.include_score_statistics {
+            params.push(("include[]", "score_statistics"));
+        }
+        if !include_description {
+            params.push(("exclude_response_fields[]", "description"));
+        }
+        self.get_pages_capped(&format!("/courses/{course_id}/assignments"), &params, max_items)
             .await
     }
 
+    /// A single assignment, with my submission.
+    pub async fn get_assignment(
+        &self,
+        course_id: u64,
+        assignment_id: u64,
+    ) -> Result<Assignment, CanvasError> {
+        self.get_json(&format!(
+            "/courses/{course_id}/assignments/{assignment_id}?include[]=submission"
+        ))
+        .await
+    }
+
+    /// Peer reviews of an assignment visible to me, with the reviewed user.
+    pub async fn list_peer_reviews(
+        &self,
+        course_id: u64,
+        assignment_id: u64,
+    ) -> Result<Vec<PeerReview>, CanvasError> {
+        self.get_all_pages(
+            &format!("/courses/{course_id}/assignments/{assignment_id}/peer_reviews"),
+            &[("include[]", "user"), ("per_page", "50")],
+        )
+        .await
+    }
+
     // ── Calendar ────────────────────────────────────────────────────────
 
     pub async fn list_calendar_events(
@@ -206,10 +820,11 @@ impl CanvasClient {
         start_date: &str,
         end_date: &str,
     ) -> Result<Vec<CalendarEvent>, CanvasError> {
+        let per_page = self.options.calendar_per_page.to_string();
         let mut params: Vec<(&str, &str)> = vec![
             ("start_date", start_date),
             ("end_date", end_date),
-            ("per_page", "50"),
+            ("per_page", &per_page),
             ("type", "event"),
         ];
         for code in context_codes {
@@ -224,10 +839,11 @@ impl CanvasClient {
         start_date: &str,
         end_date: &str,
     ) -> Result<Vec<CalendarEvent>, CanvasError> {
+        let per_page = self.options.calendar_per_page.to_string();
         let mut params: Vec<(&str, &str)> = vec![
             ("start_date", start_date),
             ("end_date", end_date),
-            ("per_page", "50"),
+            ("per_page", &per_page),
             ("type", "assignment"),
         ];
         for code in context_codes {
@@ -242,8 +858,9 @@ impl CanvasClient {
         &self,
         context_codes: &[String],
     ) -> Result<Vec<DiscussionTopic>, CanvasError> {
+        let per_page = self.options.announcements_per_page.to_string();
         let mut params: Vec<(&str, &str)> = vec![
-            ("per_page", "25"),
+            ("per_page", &per_page),
             ("latest_only", "false"),
         ];
         for code in context_codes {
@@ -252,11 +869,98 @@ impl CanvasClient {
         self.get_all_pages("/announcements", &params).await
     }
 
+    /// Announcements posted from `start` to `end` (`YYYY-MM-DD`), to be
+    /// fetched a page at a time.
+    pub fn older_announcements(
+        &self,
+        context_codes: &[String],
+        start: &str,
+        end: &str,
+    ) -> Result<PaginatedList<DiscussionTopic>, CanvasError> {
+        let per_page = self.options.announcements_per_page.to_string();
+        let mut params: Vec<(&str, &str)> = vec![
+            ("per_page", &per_page),
+            ("latest_only", "false"),
+            ("start_date", start),
+            ("end_date", end),
+        ];
+        for code in context_codes {
+            params.push(("context_codes[]", code));
+        }
+        self.paginated("/announcements", &params)
+    }
+
+    /// Unread announcements across all courses and groups, as Canvas counts
+    /// them for its own badge.
+    pub async fn unread_announcement_total(&self) -> Result<u64, CanvasError> {
+        let summary: Vec<ActivitySummary> =
+            self.get_json("/users/self/activity_stream/summary").await?;
+        Ok(summary
+            .iter()
+            .filter(|s| s.kind.as_deref() == Some("Announcement"))
+            .filter_map(|s| s.unread_count)
+            .sum())
+    }
+
+    /// The course colors picked on the Canvas dashboard, as `"#rrggbb"` by
+    /// course id.  Groups and unparseable codes are left out.
+    pub async fn course_colors(&self) -> Result<BTreeMap<u64, String>, CanvasError> {
+        let colors: CustomColors = self.get_json("/users/self/colors").await?;
+        Ok(colors
+            .custom_colors
+            .into_iter()
+            .filter_map(|(code, color)| {
+                let id = code.strip_prefix("course_")?.parse().ok()?;
+                Some((id, color))
+            })
+            .collect())
+    }
+
+    /// Institution-wide notices currently shown to the user.
+    pub async fn list_account_notifications(
+        &self,
+    ) -> Result<Vec<AccountNotification>, CanvasError> {
+        let per_page = self.options.announcements_per_page.to_string();
+        self.get_all_pages("/accounts/self/account_notifications", &[("per_page", &per_page)])
+            .await
+    }
+
+    /// Top-level replies to a discussion topic.
+    pub async fn list_discussion_entries(
+        &self,
+        course_id: u64,
+        topic_id: u64,
+    ) -> Result<Vec<DiscussionEntry>, CanvasError> {
+        let per_page = self.options.discussions_per_page.to_string();
+        self.get_all_pages(
+            &format!("/courses/{course_id}/discussion_topics/{topic_id}/entries"),
+            &[("per_page", &per_page)],
+        )
+        .await
+    }
+
+    /// Post a plain-text reply to a discussion topic.
+    pub async fn post_discussion_entry(
+        &self,
+        course_id: u64,
+        topic_id: u64,
+        text: &str,
+    ) -> Result<DiscussionEntry, CanvasError> {
+        let body = serde_json::json!({ "message": text_to_html(text) });
+        let resp = self
+            .post_json(
+                &format!("/courses/{course_id}/discussion_topics/{topic_id}/entries"),
+                &body,
+            )
+            .await?;
+        Ok(resp.json().await?)
+    }
+
     // ── User / Profile ──────────────────────────────────────────────────
 
+    /// The signed-in user (or the masqueraded one).
     pub async fn get_self(&self) -> Result<User, CanvasError> {
-        let resp = self.get("/users/self").await?;
-        Ok(resp.json().await?)
+        self.get_json("/users/self").await
     }
 
     // ── Submission (create) ──────────────────────────────────────────────
@@ -269,16 +973,11 @@ impl CanvasClient {
         assignment_id: u64,
         text: &str,
     ) -> Result<Submission, CanvasError> {
-        let html = format!(
-            "<pre>{}</pre>",
-            text.replace('&', "&amp;")
-                .replace('<', "&lt;")
-                .replace('>', "&gt;")
-        );
+        self.ensure_submit_allowed()?;
         let body = serde_json::json!({
             "submission": {
                 "submission_type": "online_text_entry",
-                "body": html
+                "body": text_to_html(text)
             }
         });
         let resp = self
@@ -290,6 +989,29 @@ impl CanvasClient {
         Ok(resp.json().await?)
     }
 
+    /// Create an event on the user's personal calendar.
+    pub async fn create_calendar_event(
+        &self,
+        user_id: u64,
+        title: &str,
+        start_at: DateTime<Utc>,
+        end_at: DateTime<Utc>,
+        description: Option<&str>,
+    ) -> Result<CalendarEvent, CanvasError> {
+        let mut event = serde_json::json!({
+            "context_code": format!("user_{user_id}"),
+            "title": title,
+            "start_at": start_at.to_rfc3339(),
+            "end_at": end_at.to_rfc3339(),
+        });
+        if let Some(description) = description {
+            event["description"] = description.into();
+        }
+        let body = serde_json::json!({ "calendar_event": event });
+        let resp = self.post_json("/calendar_events", &body).await?;
+        Ok(resp.json().await?)
+    }
+
     /// Submit a URL.
     pub async fn submit_url(
         &self,
@@ -297,6 +1019,7 @@ impl CanvasClient {
         assignment_id: u64,
         url: &str,
     ) -> Result<Submission, CanvasError> {
+        self.ensure_submit_allowed()?;
         let body = serde_json::json!({
             "submission": {
                 "submission_type": "online_url",
@@ -312,13 +1035,17 @@ impl CanvasClient {
         Ok(resp.json().await?)
     }
 
-    /// Full three-step file upload + submission.
-    pub async fn submit_file(
+    /// Steps 1 and 2 of a file submission: request an upload slot and push
+    /// the file bytes to it.  Returns the uploaded file's ID, which is then
+    /// passed to [`submit_uploaded_files`](Self::submit_uploaded_files).
+    /// Nothing is visible to the instructor until that final call.
+    pub async fn upload_submission_file(
         &self,
         course_id: u64,
         assignment_id: u64,
         file_path: &std::path::Path,
-    ) -> Result<Submission, CanvasError> {
+    ) -> Result<u64, CanvasError> {
+        self.ensure_submit_allowed()?;
         let filename = file_path
             .file_name()
             .and_then(|n| n.to_str())
@@ -350,13 +1077,22 @@ impl CanvasClient {
         // Step 2 — upload bytes to the slot URL.
         // Use a client that does NOT follow redirects so we can re-add auth on
         // the Canvas confirmation redirect.
-        let file_id = self.upload_bytes_to_slot(&slot, filename, content_type, data).await?;
+        self.upload_bytes_to_slot(&slot, filename, content_type, data).await
+    }
 
-        // Step 3 — create the submission with the uploaded file ID.
+    /// Step 3 of a file submission: create the submission from files
+    /// previously uploaded with [`upload_submission_file`](Self::upload_submission_file).
+    pub async fn submit_uploaded_files(
+        &self,
+        course_id: u64,
+        assignment_id: u64,
+        file_ids: &[u64],
+    ) -> Result<Submission, CanvasError> {
+        self.ensure_submit_allowed()?;
         let sub_body = serde_json::json!({
             "submission": {
                 "submission_type": "online_upload",
-                "file_ids": [file_id]
+                "file_ids": file_ids
             }
         });
         let resp = self
@@ -390,12 +1126,15 @@ impl CanvasClient {
         // Build a no-redirect client for the raw upload (S3/similar).
         let upload_client = reqwest::Client::builder()
             .redirect(reqwest::redirect::Policy::none())
-            .user_agent("canvas-tui/0.1.0")
+            .user_agent(crate::version::USER_AGENT)
             .build()
-            .map_err(CanvasError::Network)?;
+            .map_err(CanvasError::from)?;
 
-        let resp = upload_client
-            .post(&slot.upload_url)
+        let upload_url = Url::parse(&slot.upload_url)
+            .with_context(|| format!("Bad upload URL: {}", slot.upload_url))
+            .map_err(CanvasError::Other)?;
+        let resp = self
+            .with_extra_headers(upload_client.post(upload_url.clone()), &upload_url)
             .multipart(form)
             .send()
             .await?;
@@ -414,13 +1153,11 @@ impl CanvasClient {
                     ))
                 })?
                 .to_string();
+            let location = Url::parse(&location)
+                .with_context(|| format!("Bad upload redirect: {location}"))
+                .map_err(CanvasError::Other)?;
 
-            let confirm = self
-                .client
-                .get(&location)
-                .bearer_auth(&self.token)
-                .send()
-                .await?;
+            let confirm = self.get_url(location).await?;
             let file: UploadedFile = confirm.json().await?;
             return Ok(file.id);
         }
@@ -437,8 +1174,24 @@ impl CanvasClient {
     }
 }
 
+/// A download's ".part" file, removed on drop unless taken out.
+struct PartFile(Option<PathBuf>);
+
+impl Drop for PartFile {
+    fn drop(&mut self) {
+        if let Some(path) = self.0.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
 fn mime_from_ext(path: &std::path::Path) -> &'static str {
-    match path.extension().and_then(|e| e.to_str()).unwrap_or("") {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    match ext.as_str() {
         "pdf" => "application/pdf",
         "doc" => "application/msword",
         "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
@@ -462,3 +1215,306 @@ fn mime_from_ext(path: &std::path::Path) -> &'static str {
         _ => "application/octet-stream",
     }
 }
+
+/// Wrap plain text from $EDITOR as preformatted HTML for Canvas.
+fn text_to_html(text: &str) -> String {
+    format!(
+        "<pre>{}</pre>",
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{Reply, TestServer};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn concurrent_calls_for_one_list_share_a_request() {
+        let server = TestServer::start(|_| {
+            Reply::json(r#"[{"id": 1, "name": "Biology"}]"#).delayed(Duration::from_millis(100))
+        })
+        .await;
+        let client = CanvasClient::new(&server.url, "t").unwrap();
+
+        let (a, b) = tokio::join!(client.list_courses(), client.list_courses());
+        assert_eq!(a.unwrap()[0].id, 1);
+        assert_eq!(b.unwrap()[0].id, 1);
+        assert_eq!(server.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn every_waiter_on_a_shared_request_sees_a_network_error() {
+        // Accept each connection, then hang up without answering.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    drop(socket);
+                });
+            }
+        });
+        let client = CanvasClient::new(&url, "t").unwrap();
+
+        let (a, b) = tokio::join!(client.list_courses(), client.list_courses());
+        for result in [a, b] {
+            let err = result.unwrap_err();
+            assert!(matches!(err, CanvasError::Network(_)), "{err:?}");
+        }
+    }
+
+    const MAINTENANCE_PAGE: &str =
+        "<html><head><title>Canvas is down for maintenance</title></head><body>Back soon</body></html>";
+
+    fn maintenance_title(err: CanvasError) -> Option<String> {
+        match err {
+            CanvasError::Maintenance { title } => title,
+            other => panic!("expected a maintenance error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn an_html_page_where_json_was_expected_is_maintenance() {
+        let server = TestServer::start(|request| match request.path() {
+            "/api/v1/users/self" => Reply::html(200, MAINTENANCE_PAGE),
+            "/api/v1/courses" => Reply::html(503, MAINTENANCE_PAGE),
+            _ => Reply::html(200, "<p>no title</p>"),
+        })
+        .await;
+        let client = CanvasClient::new(&server.url, "t").unwrap();
+
+        let single = client.get_self().await.unwrap_err();
+        assert_eq!(maintenance_title(single).as_deref(), Some("Canvas is down for maintenance"));
+        let list = client.list_courses().await.unwrap_err();
+        assert_eq!(maintenance_title(list).as_deref(), Some("Canvas is down for maintenance"));
+        let untitled = client.list_assignments(1, true, false, None).await.unwrap_err();
+        assert_eq!(maintenance_title(untitled), None);
+    }
+
+    #[tokio::test]
+    async fn a_json_error_is_not_maintenance() {
+        let server = TestServer::start(|_| Reply::status(503, r#"{"errors": []}"#)).await;
+        let client = CanvasClient::new(&server.url, "t").unwrap();
+
+        let err = client.list_courses().await.unwrap_err();
+        assert!(matches!(err, CanvasError::Api { status: 503, .. }), "{err:?}");
+    }
+
+    /// Lists whose second page doesn't decode.
+    async fn bad_second_page() -> TestServer {
+        TestServer::start(|request| {
+            if request.target.contains("page=2") {
+                return Reply::json(r#"[{"id": "not a number"}]"#);
+            }
+            let next = format!("<{}?page=2>; rel=\"next\"", request.path());
+            Reply::json(r#"[{"id": 1, "name": "First"}]"#).header("Link", &next)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn a_bad_later_page_is_partial_not_truncated() {
+        let server = bad_second_page().await;
+        let client = CanvasClient::new(&server.url, "t").unwrap();
+
+        let capped = client.list_assignments(1, true, false, None).await.unwrap();
+        assert_eq!(capped.items.len(), 1);
+        assert!(capped.partial);
+        assert!(!capped.truncated);
+        let err = client.list_courses().await.unwrap_err();
+        assert!(matches!(err, CanvasError::Other(_)), "{err:?}");
+    }
+
+    fn oauth_client(url: &str, expires_at: Option<DateTime<Utc>>) -> CanvasClient {
+        CanvasClient::new(url, "old-token").unwrap().with_oauth(OAuthSession {
+            key: oauth::DeveloperKey {
+                client_id: "id".into(),
+                client_secret: "secret".into(),
+            },
+            refresh_token: "refresh-1".into(),
+            expires_at,
+        })
+    }
+
+    fn bearer(request: &crate::test_server::Request) -> &str {
+        request.headers.get("authorization").map_or("", String::as_str)
+    }
+
+    /// A Canvas that accepts `new-token` only, and whose token endpoint
+    /// answers with `token_reply`.
+    async fn canvas_with_token_endpoint(token_reply: Reply) -> TestServer {
+        TestServer::start(move |request| match request.path() {
+            "/login/oauth2/token" => token_reply.clone(),
+            _ if bearer(request) == "Bearer new-token" => Reply::json(r#"[{"id": 1}]"#),
+            _ => Reply::status(401, r#"{"errors":[{"message":"Invalid access token."}]}"#),
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn an_expired_token_is_refreshed_before_the_request() {
+        let server = canvas_with_token_endpoint(Reply::json(
+            r#"{"access_token": "new-token", "expires_in": 3600}"#,
+        ))
+        .await;
+        let expired = Utc::now() - chrono::Duration::minutes(5);
+        let client = oauth_client(&server.url, Some(expired));
+
+        client.list_courses().await.unwrap();
+        let log = server.log();
+        assert_eq!(log.len(), 2, "{log:?}");
+        assert_eq!(log[0].path(), "/login/oauth2/token");
+        assert!(log[0].body.contains("grant_type=refresh_token"));
+        assert!(log[0].body.contains("refresh_token=refresh-1"));
+        assert_eq!(bearer(&log[1]), "Bearer new-token");
+
+        let tokens = client.take_refreshed_tokens().unwrap();
+        assert_eq!(tokens.access_token, "new-token");
+        // Canvas sent no new refresh token, so the old one stays.
+        assert_eq!(tokens.refresh_token, "refresh-1");
+        assert!(tokens.expires_at.unwrap() > Utc::now() + chrono::Duration::minutes(59));
+        assert_eq!(client.token_expires_at(), tokens.expires_at);
+        assert!(client.take_refreshed_tokens().is_none());
+    }
+
+    #[tokio::test]
+    async fn a_401_refreshes_and_retries_once() {
+        let server = canvas_with_token_endpoint(Reply::json(
+            r#"{"access_token": "new-token", "refresh_token": "refresh-2"}"#,
+        ))
+        .await;
+        let client = oauth_client(&server.url, None);
+
+        client.list_courses().await.unwrap();
+        let paths: Vec<String> = server.log().iter().map(|r| r.path().to_string()).collect();
+        assert_eq!(paths, ["/api/v1/courses", "/login/oauth2/token", "/api/v1/courses"]);
+        assert_eq!(client.take_refreshed_tokens().unwrap().refresh_token, "refresh-2");
+    }
+
+    #[tokio::test]
+    async fn concurrent_401s_refresh_once() {
+        let server = canvas_with_token_endpoint(
+            Reply::json(r#"{"access_token": "new-token"}"#).delayed(Duration::from_millis(50)),
+        )
+        .await;
+        let client = oauth_client(&server.url, None);
+        let contexts = ["course_1".to_string()];
+
+        let (a, b, c) = tokio::join!(
+            client.list_courses(),
+            client.list_assignments(1, false, false, None),
+            client.list_announcements(&contexts),
+        );
+        a.unwrap();
+        b.unwrap();
+        c.unwrap();
+        let refreshes = server.log().iter().filter(|r| r.path() == "/login/oauth2/token").count();
+        assert_eq!(refreshes, 1);
+    }
+
+    #[tokio::test]
+    async fn a_failed_refresh_is_unauthorized_without_retrying() {
+        let server = canvas_with_token_endpoint(Reply::status(
+            400,
+            r#"{"error": "invalid_grant", "error_description": "refresh_token not found"}"#,
+        ))
+        .await;
+        let client = oauth_client(&server.url, None);
+
+        let err = client.list_courses().await.unwrap_err();
+        assert!(matches!(err, CanvasError::Unauthorized), "{err:?}");
+        let paths: Vec<String> = server.log().iter().map(|r| r.path().to_string()).collect();
+        assert_eq!(paths, ["/api/v1/courses", "/login/oauth2/token"]);
+        assert!(client.take_refreshed_tokens().is_none());
+    }
+
+    #[tokio::test]
+    async fn an_api_token_is_not_refreshed() {
+        let server = canvas_with_token_endpoint(Reply::json(r#"{"access_token": "new-token"}"#)).await;
+        let client = CanvasClient::new(&server.url, "old-token").unwrap();
+
+        let err = client.list_courses().await.unwrap_err();
+        assert!(matches!(err, CanvasError::Unauthorized), "{err:?}");
+        assert_eq!(server.requests(), 1);
+    }
+
+    /// Fire 50 calls at once from clones of `client`, each on its own task,
+    /// at a server that takes a while to answer.
+    async fn fifty_calls(client: &CanvasClient) {
+        let calls: Vec<_> = (1..=50)
+            .map(|id| {
+                let client = client.clone();
+                tokio::spawn(async move { client.get_assignment(1, id).await })
+            })
+            .collect();
+        for call in calls {
+            call.await.unwrap().unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn fifty_simultaneous_calls_stay_within_the_limit() {
+        let server = TestServer::start(|_| {
+            Reply::json(r#"{"id": 1, "name": "Essay"}"#).delayed(Duration::from_millis(20))
+        })
+        .await;
+        let client = CanvasClient::new(&server.url, "t").unwrap();
+
+        fifty_calls(&client).await;
+        assert_eq!(server.requests(), 50);
+        assert!(server.max_concurrent() <= DEFAULT_MAX_CONCURRENT, "{}", server.max_concurrent());
+        assert!(server.max_concurrent() > 1);
+    }
+
+    #[tokio::test]
+    async fn the_limit_follows_the_options() {
+        let server = TestServer::start(|_| {
+            Reply::json(r#"{"id": 1, "name": "Essay"}"#).delayed(Duration::from_millis(20))
+        })
+        .await;
+        let options = ApiOptions { max_concurrent_requests: 2, ..ApiOptions::default() };
+        let client = CanvasClient::new(&server.url, "t").unwrap().with_options(options);
+
+        fifty_calls(&client).await;
+        assert_eq!(server.requests(), 50);
+        assert_eq!(server.max_concurrent(), 2);
+    }
+
+    #[tokio::test]
+    async fn a_low_budget_spaces_requests_out() {
+        let server = TestServer::start(|_| {
+            Reply::json(r#"{"id": 1, "name": "Essay"}"#).header("X-Rate-Limit-Remaining", "40.5")
+        })
+        .await;
+        let client = CanvasClient::new(&server.url, "t").unwrap();
+
+        // The first response reports the low budget; the next three wait.
+        let started = std::time::Instant::now();
+        for id in 1..=4 {
+            client.get_assignment(1, id).await.unwrap();
+        }
+        assert!(started.elapsed() >= Duration::from_millis(700), "{:?}", started.elapsed());
+    }
+
+    #[tokio::test]
+    async fn the_date_header_sets_the_clock_skew() {
+        let server = TestServer::start(|_| {
+            let ahead = Utc::now() + chrono::Duration::minutes(41);
+            let date = ahead.format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+            Reply::json(r#"[{"id": 1, "name": "Biology"}]"#).header("Date", &date)
+        })
+        .await;
+        let client = CanvasClient::new(&server.url, "t").unwrap();
+        assert_eq!(client.clock_skew(), None);
+
+        client.list_courses().await.unwrap();
+        let skew = client.with_own_counter().clock_skew().unwrap();
+        let off = (skew - chrono::Duration::minutes(41)).num_seconds();
+        assert!((-2..=1).contains(&off), "{skew}");
+    }
+}
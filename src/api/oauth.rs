@@ -0,0 +1,146 @@
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use url::Url;
+
+use super::CanvasError;
+
+/// A Canvas developer key, as configured by `oauth_client_id` and
+/// `oauth_client_secret`.
+#[derive(Debug, Clone)]
+pub struct DeveloperKey {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+/// Tokens from a login or a refresh.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuthTokens {
+    pub access_token: String,
+    /// Canvas only issues one at login; refreshes keep using it.
+    pub refresh_token: String,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// What the client needs to renew its access token on its own.
+#[derive(Debug, Clone)]
+pub struct OAuthSession {
+    pub key: DeveloperKey,
+    pub refresh_token: String,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    /// Seconds; Canvas access tokens usually last an hour.
+    expires_in: Option<i64>,
+}
+
+/// Where to send the user to approve access.  Canvas redirects to
+/// `redirect_uri` with `code` and `state` once they do.
+pub fn authorize_url(base_url: &Url, key: &DeveloperKey, redirect_uri: &str, state: &str) -> Url {
+    let mut url = base_url.join("/login/oauth2/auth").expect("static path");
+    url.query_pairs_mut()
+        .append_pair("client_id", &key.client_id)
+        .append_pair("response_type", "code")
+        .append_pair("redirect_uri", redirect_uri)
+        .append_pair("state", state);
+    url
+}
+
+/// Trade the `code` from the authorize redirect for tokens.
+pub(super) async fn exchange_code(
+    http: &Client,
+    base_url: &Url,
+    key: &DeveloperKey,
+    redirect_uri: &str,
+    code: &str,
+) -> Result<OAuthTokens, CanvasError> {
+    let resp = request_token(
+        http,
+        base_url,
+        &[
+            ("grant_type", "authorization_code"),
+            ("client_id", &key.client_id),
+            ("client_secret", &key.client_secret),
+            ("redirect_uri", redirect_uri),
+            ("code", code),
+        ],
+    )
+    .await?;
+    let refresh_token = resp.refresh_token.ok_or_else(|| {
+        CanvasError::Other(anyhow::anyhow!("Canvas did not return a refresh token"))
+    })?;
+    Ok(OAuthTokens {
+        access_token: resp.access_token,
+        refresh_token,
+        expires_at: expiry(resp.expires_in),
+    })
+}
+
+/// A new access token for `refresh_token`.
+pub(super) async fn refresh(
+    http: &Client,
+    base_url: &Url,
+    key: &DeveloperKey,
+    refresh_token: &str,
+) -> Result<OAuthTokens, CanvasError> {
+    let resp = request_token(
+        http,
+        base_url,
+        &[
+            ("grant_type", "refresh_token"),
+            ("client_id", &key.client_id),
+            ("client_secret", &key.client_secret),
+            ("refresh_token", refresh_token),
+        ],
+    )
+    .await?;
+    Ok(OAuthTokens {
+        access_token: resp.access_token,
+        refresh_token: resp.refresh_token.unwrap_or_else(|| refresh_token.to_string()),
+        expires_at: expiry(resp.expires_in),
+    })
+}
+
+async fn request_token(
+    http: &Client,
+    base_url: &Url,
+    form: &[(&str, &str)],
+) -> Result<TokenResponse, CanvasError> {
+    let url = base_url.join("/login/oauth2/token").expect("static path");
+    let resp = http.post(url).form(form).send().await?;
+    let status = resp.status();
+    if !status.is_success() {
+        // The body is a JSON error such as `invalid_grant`; keep it for the log.
+        let message = resp.text().await.unwrap_or_default();
+        return Err(CanvasError::Api {
+            status: status.as_u16(),
+            message: format!("token request failed: {message}"),
+        });
+    }
+    Ok(resp.json().await?)
+}
+
+/// When a token lasting `expires_in` seconds runs out; `None` when Canvas
+/// didn't say or the value is out of range.
+fn expiry(expires_in: Option<i64>) -> Option<DateTime<Utc>> {
+    Utc::now().checked_add_signed(chrono::Duration::try_seconds(expires_in?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expiry_counts_from_now_and_ignores_absurd_values() {
+        let at = expiry(Some(3600)).unwrap();
+        let from_now = at - Utc::now();
+        assert!(from_now > chrono::Duration::minutes(59) && from_now <= chrono::Duration::hours(1));
+        assert_eq!(expiry(None), None);
+        assert_eq!(expiry(Some(i64::MAX)), None);
+        assert_eq!(expiry(Some(i64::MIN)), None);
+    }
+}
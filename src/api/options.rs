@@ -0,0 +1,64 @@
+use reqwest::header::HeaderMap;
+
+/// Default for the `api.api_prefix` config key.
+const DEFAULT_API_PREFIX: &str = "/api/v1";
+
+/// Largest `per_page` Canvas honours; bigger values are silently capped
+/// server-side, so we cap them here and say so.
+pub const MAX_PER_PAGE: u32 = 100;
+
+/// Page sizes and optional `include[]`s for the list requests, and how
+/// many requests may run at once, so instances that allow bigger pages (or
+/// throttle big ones) can be tuned.  Self-hosted instances can also move
+/// the API and ask for extra headers.
+/// The defaults are what the client has always sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiOptions {
+    pub courses_per_page: u32,
+    pub assignments_per_page: u32,
+    /// Both calendar requests (events and assignment deadlines).
+    pub calendar_per_page: u32,
+    pub announcements_per_page: u32,
+    /// Discussion replies.
+    pub discussions_per_page: u32,
+    /// Course enrollment counts (shown in the course details).
+    pub include_total_students: bool,
+    /// Per-assignment score statistics; slow on large courses.
+    pub include_score_statistics: bool,
+    /// Comments on my submissions, alongside the submission.
+    pub include_submission_comments: bool,
+    /// Requests in flight at once, across the client and its clones.
+    pub max_concurrent_requests: usize,
+    /// Where the API lives on the host, without a trailing slash.
+    pub api_prefix: String,
+    /// Sent with every request to the Canvas host.
+    pub extra_headers: HeaderMap,
+}
+
+impl Default for ApiOptions {
+    fn default() -> Self {
+        Self {
+            courses_per_page: 50,
+            assignments_per_page: 50,
+            calendar_per_page: 50,
+            announcements_per_page: 25,
+            discussions_per_page: 50,
+            include_total_students: true,
+            include_score_statistics: false,
+            include_submission_comments: false,
+            max_concurrent_requests: super::DEFAULT_MAX_CONCURRENT,
+            api_prefix: DEFAULT_API_PREFIX.to_string(),
+            extra_headers: HeaderMap::new(),
+        }
+    }
+}
+
+/// `value` limited to 1..=`MAX_PER_PAGE`, with a warning naming `key` when
+/// it had to be changed.
+pub fn clamp_per_page(key: &str, value: u32) -> u32 {
+    let clamped = value.clamp(1, MAX_PER_PAGE);
+    if clamped != value {
+        tracing::warn!("{key} = {value} is out of range 1–{MAX_PER_PAGE}; using {clamped}");
+    }
+    clamped
+}
@@ -0,0 +1,266 @@
+//! A list fetched a page at a time as the user gets to it, rather than all
+//! at once during a sync.  The UI asks for the next page with
+//! `load_next_page`, polls the oneshot it gets back and hands the result to
+//! `receive`; `prefetch` asks for it early, once the selection is past the
+//! middle of what's loaded, so scrolling on rarely has to wait.
+
+use tokio::sync::oneshot;
+use url::Url;
+
+use super::{decode_page, CanvasClient, CanvasError};
+
+/// One fetched page and the link to the page after it.
+#[derive(Debug)]
+pub struct ListPage<T> {
+    pub items: Vec<T>,
+    pub next: Option<Url>,
+}
+
+/// What a page fetch sends back: the page, or why it failed.
+pub type PageResult<T> = Result<ListPage<T>, String>;
+
+#[derive(Debug)]
+pub struct PaginatedList<T> {
+    items: Vec<T>,
+    /// The page to fetch next; `None` once the last one is in.
+    next: Option<Url>,
+    /// A page is being fetched, so no second request is started for it.
+    loading: bool,
+    /// Pages received so far, to number the next in logs.
+    pages: usize,
+    /// Why the latest fetch failed.  Prefetching stops until a
+    /// `load_next_page` retries it.
+    error: Option<String>,
+}
+
+impl<T: serde::de::DeserializeOwned + Send + 'static> PaginatedList<T> {
+    /// A list whose first page is `first`; nothing is fetched yet.
+    pub fn new(first: Url) -> Self {
+        Self {
+            items: Vec::new(),
+            next: Some(first),
+            loading: false,
+            pages: 0,
+            error: None,
+        }
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// There are pages left to fetch.
+    pub fn has_more(&self) -> bool {
+        self.next.is_some()
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Start fetching the next page in the background.  `None` when every
+    /// page is in or one is already on its way.
+    pub fn load_next_page(
+        &mut self,
+        client: &CanvasClient,
+    ) -> Option<oneshot::Receiver<PageResult<T>>> {
+        if self.loading {
+            return None;
+        }
+        let url = self.next.clone()?;
+        self.loading = true;
+        self.error = None;
+        let (tx, rx) = oneshot::channel();
+        let client = client.clone();
+        let number = self.pages + 1;
+        tokio::spawn(async move {
+            let page = client.get_list_page(url, number).await.map_err(|e| e.to_string());
+            let _ = tx.send(page);
+        });
+        Some(rx)
+    }
+
+    /// `load_next_page` once `position` (an index into `items`) is past
+    /// the middle of what's loaded, unless the latest fetch failed.
+    pub fn prefetch(
+        &mut self,
+        client: &CanvasClient,
+        position: usize,
+    ) -> Option<oneshot::Receiver<PageResult<T>>> {
+        if self.error.is_some() || position.saturating_mul(2) < self.items.len() {
+            return None;
+        }
+        self.load_next_page(client)
+    }
+
+    /// Add a fetched page.  After a failure the items so far are kept and
+    /// the same page is fetched again next time.
+    pub fn receive(&mut self, result: PageResult<T>) {
+        self.loading = false;
+        match result {
+            Ok(page) => {
+                self.items.extend(page.items);
+                self.pages += 1;
+                self.next = page.next;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+}
+
+impl CanvasClient {
+    /// The list endpoint `path` with `params`, to be fetched a page at a
+    /// time.
+    pub fn paginated<T: serde::de::DeserializeOwned + Send + 'static>(
+        &self,
+        path: &str,
+        params: &[(&str, &str)],
+    ) -> Result<PaginatedList<T>, CanvasError> {
+        let mut url = self.api_url(path).map_err(CanvasError::Other)?;
+        for (k, v) in params {
+            url.query_pairs_mut().append_pair(k, v);
+        }
+        Ok(PaginatedList::new(url))
+    }
+
+    /// GET page `number` of a list at `url` and its next link.
+    async fn get_list_page<T: serde::de::DeserializeOwned>(
+        &self,
+        url: Url,
+        number: usize,
+    ) -> Result<ListPage<T>, CanvasError> {
+        let resp = Self::expect_json(self.get_url(url.clone()).await?).await?;
+        let next = super::parse_link_header(resp.headers(), &url)
+            .next
+            .map(|link| Url::parse(&link))
+            .transpose()
+            .map_err(|e| CanvasError::Other(anyhow::anyhow!("Bad pagination URL: {e}")))?;
+        let body = resp.text().await?;
+        let items = decode_page(&body).map_err(|e| {
+            self.report_bad_payload(url.path(), number, &e, &body);
+            CanvasError::Other(anyhow::anyhow!("Unexpected response for {}: {e}", url.path()))
+        })?;
+        Ok(ListPage { items, next })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::test_server::{Reply, TestServer};
+
+    const PAGES: usize = 5;
+
+    /// `PAGES` pages of two items at `/api/v1/items?page=<n>`.  With
+    /// `fail_page_3`, the first request for page 3 gets a 500.
+    async fn server(fail_page_3: bool) -> TestServer {
+        let failed = Arc::new(AtomicBool::new(!fail_page_3));
+        TestServer::start(move |req| {
+            let page: usize = req.target.rsplit("page=").next().unwrap().parse().unwrap();
+            if page == 3 && !failed.swap(true, Ordering::SeqCst) {
+                return Reply::status(500, "oops");
+            }
+            let reply = Reply::json(format!("[{}, {}]", page * 10, page * 10 + 1));
+            if page < PAGES {
+                let next = format!("</api/v1/items?page={}>; rel=\"next\"", page + 1);
+                reply.header("Link", &next)
+            } else {
+                reply
+            }
+        })
+        .await
+    }
+
+    fn list(client: &CanvasClient) -> PaginatedList<Value> {
+        client.paginated("/items", &[("page", "1")]).unwrap()
+    }
+
+    async fn receive(list: &mut PaginatedList<Value>, rx: oneshot::Receiver<PageResult<Value>>) {
+        list.receive(rx.await.unwrap());
+    }
+
+    fn pages_requested(server: &TestServer, page: usize) -> usize {
+        let target = format!("/api/v1/items?page={page}");
+        server.log().iter().filter(|r| r.target == target).count()
+    }
+
+    #[tokio::test]
+    async fn pages_load_until_the_last() {
+        let server = server(false).await;
+        let client = CanvasClient::new(&server.url, "t").unwrap();
+        let mut list = list(&client);
+        assert!(list.items().is_empty() && list.has_more());
+
+        while let Some(rx) = list.load_next_page(&client) {
+            assert!(list.is_loading());
+            receive(&mut list, rx).await;
+        }
+        assert_eq!(list.items().len(), 2 * PAGES);
+        assert_eq!(list.items()[..3], [10, 11, 20]);
+        assert!(!list.has_more() && !list.is_loading());
+        assert!(list.prefetch(&client, 2 * PAGES).is_none());
+        assert_eq!(server.requests(), PAGES);
+    }
+
+    #[tokio::test]
+    async fn a_failed_page_keeps_the_items_and_is_retried() {
+        let server = server(true).await;
+        let client = CanvasClient::new(&server.url, "t").unwrap();
+        let mut list = list(&client);
+
+        for _ in 0..3 {
+            let rx = list.load_next_page(&client).unwrap();
+            receive(&mut list, rx).await;
+        }
+        assert!(list.error().is_some_and(|e| e.contains("500")), "{:?}", list.error());
+        assert_eq!(list.items().len(), 4);
+        assert!(list.has_more() && !list.is_loading());
+        // No prefetching until the user asks again.
+        assert!(list.prefetch(&client, 3).is_none());
+
+        let rx = list.load_next_page(&client).unwrap();
+        assert!(list.error().is_none());
+        receive(&mut list, rx).await;
+        assert_eq!(list.items()[4..], [30, 31]);
+        assert_eq!(pages_requested(&server, 3), 2);
+        assert_eq!(pages_requested(&server, 4), 0);
+    }
+
+    #[tokio::test]
+    async fn prefetches_start_past_the_middle_and_never_twice() {
+        let server = server(false).await;
+        let client = CanvasClient::new(&server.url, "t").unwrap();
+        let mut list = list(&client);
+        let rx = list.load_next_page(&client).unwrap();
+        receive(&mut list, rx).await;
+
+        // Two items loaded: the first isn't past the middle, the second is.
+        assert!(list.prefetch(&client, 0).is_none());
+        let rx = list.prefetch(&client, 1).unwrap();
+        assert!(list.prefetch(&client, 1).is_none());
+        assert!(list.load_next_page(&client).is_none());
+        receive(&mut list, rx).await;
+
+        // Scrolling through everything, a prefetch at every step.
+        let mut position = 1;
+        while position < list.items().len() {
+            if let Some(rx) = list.prefetch(&client, position) {
+                assert!(list.prefetch(&client, position).is_none());
+                receive(&mut list, rx).await;
+            }
+            position += 1;
+        }
+        assert_eq!(list.items().len(), 2 * PAGES);
+        assert_eq!(server.requests(), PAGES);
+        assert!((1..=PAGES).all(|page| pages_requested(&server, page) == 1));
+    }
+}
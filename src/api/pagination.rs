@@ -1,39 +1,193 @@
 use crate::models::PaginationLinks;
 use reqwest::header::HeaderMap;
+use url::Url;
 
-/// Parse the `Link` header returned by the Canvas API.
-pub fn parse_link_header(headers: &HeaderMap) -> PaginationLinks {
+/// Parse the `Link` headers (RFC 8288) returned by the Canvas API.
+/// Parameters may come in any order and spacing, quoted or not, and a
+/// `rel` may list several relation types.  Relative targets are resolved
+/// against `request_url`, so every link returned is absolute.
+pub fn parse_link_header(headers: &HeaderMap, request_url: &Url) -> PaginationLinks {
     let mut links = PaginationLinks::default();
 
-    let Some(header) = headers.get("link").and_then(|v| v.to_str().ok()) else {
-        return links;
-    };
+    for header in headers.get_all("link").iter().filter_map(|v| v.to_str().ok()) {
+        for (target, rels) in link_values(header) {
+            let Ok(url) = request_url.join(target) else {
+                continue;
+            };
+            for rel in rels.split_whitespace() {
+                let slot = match rel.to_ascii_lowercase().as_str() {
+                    "current" => &mut links.current,
+                    "next" => &mut links.next,
+                    "prev" | "previous" => &mut links.prev,
+                    "first" => &mut links.first,
+                    "last" => &mut links.last,
+                    _ => continue,
+                };
+                // The first link for a relation wins, as the RFC suggests.
+                slot.get_or_insert_with(|| url.to_string());
+            }
+        }
+    }
+
+    links
+}
+
+/// The `(target, rel)` pairs of one header value, skipping link-values
+/// without a `rel`.
+fn link_values(header: &str) -> Vec<(&str, &str)> {
+    let mut values = Vec::new();
+    let mut rest = header;
+
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        let Some(after_lt) = rest.strip_prefix('<') else {
+            // Not a link-value; skip to the next one.
+            match rest.find(',') {
+                Some(comma) => {
+                    rest = &rest[comma + 1..];
+                    continue;
+                }
+                None => break,
+            }
+        };
+        let Some(gt) = after_lt.find('>') else {
+            break;
+        };
+        let target = after_lt[..gt].trim();
+        rest = &after_lt[gt + 1..];
 
-    for part in header.split(',') {
-        let mut segments = part.split(';');
-        let url = segments
-            .next()
-            .map(|s| s.trim().trim_start_matches('<').trim_end_matches('>').to_string());
-        let rel = segments.next().and_then(|s| {
-            let s = s.trim();
-            if s.starts_with("rel=") {
-                Some(s.trim_start_matches("rel=").trim_matches('"').to_string())
-            } else {
-                None
+        let mut rel = None;
+        while let Some(after_semi) = rest.trim_start().strip_prefix(';') {
+            let (name, value, remaining) = link_param(after_semi);
+            if rel.is_none() && name.eq_ignore_ascii_case("rel") {
+                rel = Some(value);
             }
-        });
-
-        if let (Some(url), Some(rel)) = (url, rel) {
-            match rel.as_str() {
-                "current" => links.current = Some(url),
-                "next" => links.next = Some(url),
-                "prev" => links.prev = Some(url),
-                "first" => links.first = Some(url),
-                "last" => links.last = Some(url),
+            rest = remaining;
+        }
+        if let Some(rel) = rel {
+            values.push((target, rel));
+        }
+
+        // Skip anything unparseable up to the next link-value.
+        match rest.find(',') {
+            Some(comma) => rest = &rest[comma + 1..],
+            None => break,
+        }
+    }
+    values
+}
+
+/// One `name=value` parameter (value quoted, unquoted or absent) and the
+/// text after it.
+fn link_param(s: &str) -> (&str, &str, &str) {
+    let s = s.trim_start();
+    let name_end = s
+        .find(|c: char| c == '=' || c == ';' || c == ',' || c.is_whitespace())
+        .unwrap_or(s.len());
+    let name = &s[..name_end];
+    let rest = s[name_end..].trim_start();
+
+    let Some(after_eq) = rest.strip_prefix('=') else {
+        return (name, "", rest);
+    };
+    let after_eq = after_eq.trim_start();
+    if let Some(quoted) = after_eq.strip_prefix('"') {
+        let mut escaped = false;
+        for (i, c) in quoted.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => return (name, &quoted[..i], &quoted[i + 1..]),
                 _ => {}
             }
         }
+        (name, quoted, "")
+    } else {
+        let end = after_eq.find([';', ',']).unwrap_or(after_eq.len());
+        (name, after_eq[..end].trim_end(), &after_eq[end..])
     }
+}
 
-    links
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderValue, LINK};
+
+    fn parse(values: &[&str]) -> PaginationLinks {
+        let mut headers = HeaderMap::new();
+        for v in values {
+            headers.append(LINK, HeaderValue::from_str(v).unwrap());
+        }
+        let request = Url::parse("https://canvas.example.edu/api/v1/courses?page=2").unwrap();
+        parse_link_header(&headers, &request)
+    }
+
+    #[test]
+    fn canvas_style_header() {
+        let links = parse(&[
+            "<https://canvas.example.edu/api/v1/courses?page=2&per_page=10>; rel=\"current\",\
+             <https://canvas.example.edu/api/v1/courses?page=3&per_page=10>; rel=\"next\",\
+             <https://canvas.example.edu/api/v1/courses?page=1&per_page=10>; rel=\"prev\",\
+             <https://canvas.example.edu/api/v1/courses?page=1&per_page=10>; rel=\"first\",\
+             <https://canvas.example.edu/api/v1/courses?page=5&per_page=10>; rel=\"last\"",
+        ]);
+        let page = |n: u32| Some(format!("https://canvas.example.edu/api/v1/courses?page={n}&per_page=10"));
+        assert_eq!(links.current, page(2));
+        assert_eq!(links.next, page(3));
+        assert_eq!(links.prev, page(1));
+        assert_eq!(links.first, page(1));
+        assert_eq!(links.last, page(5));
+    }
+
+    #[test]
+    fn parameters_in_any_order_spacing_and_quoting() {
+        let links = parse(&[
+            r#"< /a >;title="x; y, z" ; REL = next , </b>;rel=last;type="text/html""#,
+        ]);
+        assert_eq!(links.next.as_deref(), Some("https://canvas.example.edu/a"));
+        assert_eq!(links.last.as_deref(), Some("https://canvas.example.edu/b"));
+    }
+
+    #[test]
+    fn several_relation_types_and_several_headers() {
+        let links = parse(&[
+            r#"<https://canvas.example.edu/p1>; rel="first prev""#,
+            r#"<https://canvas.example.edu/p3>; rel="next""#,
+        ]);
+        assert_eq!(links.first.as_deref(), Some("https://canvas.example.edu/p1"));
+        assert_eq!(links.prev.as_deref(), Some("https://canvas.example.edu/p1"));
+        assert_eq!(links.next.as_deref(), Some("https://canvas.example.edu/p3"));
+    }
+
+    #[test]
+    fn relative_targets_resolve_against_the_request() {
+        let links = parse(&[r#"<?page=3>; rel="next", <courses?page=9>; rel="last""#]);
+        assert_eq!(
+            links.next.as_deref(),
+            Some("https://canvas.example.edu/api/v1/courses?page=3")
+        );
+        assert_eq!(
+            links.last.as_deref(),
+            Some("https://canvas.example.edu/api/v1/courses?page=9")
+        );
+    }
+
+    #[test]
+    fn first_link_for_a_relation_wins() {
+        let links = parse(&[r#"<https://a.example/1>; rel=next, <https://a.example/2>; rel=next"#]);
+        assert_eq!(links.next.as_deref(), Some("https://a.example/1"));
+    }
+
+    #[test]
+    fn malformed_values_are_skipped() {
+        let links = parse(&[
+            r#"garbage, <https://a.example/norel>; title="x", <https://a.example/next>; rel="next", <https://a.example/open"#,
+        ]);
+        assert_eq!(links.next.as_deref(), Some("https://a.example/next"));
+        assert_eq!(links.current, None);
+        assert_eq!(links.last, None);
+
+        let none = parse(&[]);
+        assert_eq!((none.next, none.prev, none.first), (None, None, None));
+    }
 }
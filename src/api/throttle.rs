@@ -0,0 +1,136 @@
+//! One limit on the requests of a client and all its clones, so parallel
+//! syncs, detail fetches and downloads can't together drain the token's
+//! Canvas throttle bucket.  At most `max_concurrent` requests are in
+//! flight, and once Canvas reports the bucket running low, request starts
+//! are spaced out until it refills.
+
+use reqwest::header::HeaderMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio::time::Instant;
+
+/// Default for the `api.max_concurrent_requests` config key.
+pub const DEFAULT_MAX_CONCURRENT: usize = 6;
+
+/// Below this much of the bucket (`X-Rate-Limit-Remaining`; a fresh one
+/// holds 700), requests are spaced out.
+const LOW_BUDGET: f64 = 150.0;
+
+/// Time between request starts while the bucket is low.
+const LOW_BUDGET_SPACING: Duration = Duration::from_millis(250);
+
+#[derive(Debug)]
+pub(super) struct Throttle {
+    slots: Semaphore,
+    state: Mutex<ThrottleState>,
+}
+
+#[derive(Debug, Default)]
+struct ThrottleState {
+    /// What Canvas last said is left of the bucket.
+    remaining: Option<f64>,
+    /// When the latest request was let through.
+    last_start: Option<Instant>,
+}
+
+impl Throttle {
+    pub(super) fn new(max_concurrent: usize) -> Self {
+        Self {
+            slots: Semaphore::new(max_concurrent.max(1)),
+            state: Mutex::default(),
+        }
+    }
+
+    /// Wait for a free slot, and while the bucket is low for the spacing
+    /// since the previous start.  The request holds the permit until its
+    /// response has arrived.
+    pub(super) async fn acquire(&self) -> SemaphorePermit<'_> {
+        let permit = self
+            .slots
+            .acquire()
+            .await
+            .expect("the throttle's semaphore is never closed");
+        let start = {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            let now = Instant::now();
+            let low = state.remaining.is_some_and(|r| r < LOW_BUDGET);
+            let start = match state.last_start {
+                Some(last) if low => now.max(last + LOW_BUDGET_SPACING),
+                _ => now,
+            };
+            state.last_start = Some(start);
+            start
+        };
+        tokio::time::sleep_until(start).await;
+        permit
+    }
+
+    /// Note the bucket level a response reports.  A 429 counts as empty.
+    pub(super) fn record(&self, headers: &HeaderMap, rate_limited: bool) {
+        let reported = headers
+            .get("x-rate-limit-remaining")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse::<f64>().ok());
+        let remaining = if rate_limited { Some(0.0) } else { reported };
+        let Some(remaining) = remaining else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let was_low = state.remaining.is_some_and(|r| r < LOW_BUDGET);
+        if remaining < LOW_BUDGET && !was_low {
+            tracing::info!("Canvas rate limit budget low ({remaining:.0} left); spacing out requests");
+        }
+        state.remaining = Some(remaining);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remaining(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-rate-limit-remaining", value.parse().unwrap());
+        headers
+    }
+
+    /// How long two back-to-back requests take to be let through.
+    async fn two_starts(throttle: &Throttle) -> Duration {
+        let started = Instant::now();
+        drop(throttle.acquire().await);
+        drop(throttle.acquire().await);
+        started.elapsed()
+    }
+
+    #[tokio::test]
+    async fn requests_are_spaced_only_while_the_budget_is_low() {
+        let throttle = Throttle::new(4);
+        assert!(two_starts(&throttle).await < LOW_BUDGET_SPACING);
+
+        throttle.record(&remaining("600"), false);
+        assert!(two_starts(&throttle).await < LOW_BUDGET_SPACING);
+
+        throttle.record(&remaining("149.9"), false);
+        assert!(two_starts(&throttle).await >= LOW_BUDGET_SPACING);
+
+        // Unreadable values leave the last level alone.
+        throttle.record(&remaining("lots"), false);
+        assert!(two_starts(&throttle).await >= LOW_BUDGET_SPACING);
+
+        throttle.record(&remaining(" 700 "), false);
+        assert!(two_starts(&throttle).await < LOW_BUDGET_SPACING);
+    }
+
+    #[tokio::test]
+    async fn a_429_counts_as_an_empty_budget() {
+        let throttle = Throttle::new(4);
+        throttle.record(&remaining("600"), true);
+        assert!(two_starts(&throttle).await >= LOW_BUDGET_SPACING);
+    }
+
+    #[test]
+    fn a_zero_limit_still_lets_one_request_through() {
+        assert_eq!(Throttle::new(0).slots.available_permits(), 1);
+    }
+}
@@ -1,43 +1,786 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::hash::Hasher;
+use std::path::Path;
 
-use crate::models::{Assignment, CalendarEvent, Course, DiscussionTopic, User};
+use crate::api::{CanvasClient, CanvasError};
+use crate::config::Paths;
+use crate::models::{Assignment, CalendarEvent, Course, DiscussionTopic, PeerReview, User};
 
 // ─── Cached payload ──────────────────────────────────────────────────────────
 
+/// Version of the cache layout written by this build.  Version 2 keys
+/// assignments on course id; older caches are migrated by `load_cache`.
+pub const CACHE_VERSION: u32 = 2;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheData {
+    /// Layout version (`CACHE_VERSION`); missing in caches before 2.
+    #[serde(default)]
+    pub version: u32,
     pub cached_at: DateTime<Utc>,
+    /// Host of the Canvas instance this was synced from; missing in caches
+    /// from before it was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canvas_host: Option<String>,
+    /// The user this was synced as.
     pub user: Option<User>,
+    // Collections default to empty so a cache missing one still loads.
+    #[serde(default)]
     pub courses: Vec<Course>,
-    /// Flat list of (course_name, assignments) preserved from the API fetch.
-    pub assignments: Vec<(String, Vec<Assignment>)>,
+    /// Each course's assignments, in course order.
+    #[serde(default)]
+    pub assignments: Vec<CourseAssignments>,
+    #[serde(default)]
     pub calendar_events: Vec<CalendarEvent>,
+    #[serde(default)]
     pub announcements: Vec<DiscussionTopic>,
+    /// Unread announcements as Canvas counts them, when it said.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unread_announcements: Option<u64>,
+    /// Course colors picked on the Canvas dashboard, by course id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub course_colors: BTreeMap<u64, String>,
+    /// Peer reviews I'm assigned, by assignment id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub peer_reviews: BTreeMap<u64, Vec<PeerReview>>,
+    /// Names of the groups I'm in, for labelling group announcements and
+    /// events.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub group_names: BTreeMap<u64, String>,
+    /// Courses whose assignments were cut off at `max_assignments_per_course`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub truncated_courses: Vec<u64>,
+    /// When each assignment first turned up in a sync.
+    #[serde(default, skip_serializing_if = "FirstSeen::is_empty")]
+    pub first_seen: FirstSeen,
+    /// `content_hash()` of this data, stored so the next sync can tell
+    /// whether anything changed without reading the file back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<u64>,
 }
 
-// ─── Path ────────────────────────────────────────────────────────────────────
+impl CacheData {
+    /// Hash of everything except `cached_at` and `content_hash`.  The
+    /// models don't implement `Hash`, so this hashes their JSON as it is
+    /// produced.  `DefaultHasher` isn't stable across Rust releases; a
+    /// different build just rewrites the cache once.
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = HashWriter(std::collections::hash_map::DefaultHasher::new());
+        let content = (
+            &self.user,
+            &self.courses,
+            &self.assignments,
+            &self.calendar_events,
+            &self.announcements,
+            &self.unread_announcements,
+            &self.course_colors,
+            &self.group_names,
+            &self.peer_reviews,
+            &self.truncated_courses,
+            &self.first_seen,
+        );
+        // Writing into a hasher can't fail, and the models always serialize.
+        let _ = serde_json::to_writer(&mut hasher, &content);
+        hasher.0.finish()
+    }
+
+    /// The instance this cache was synced from, when it isn't `host`.  A
+    /// cache that doesn't say (from an older version) is taken as `host`'s.
+    pub fn foreign_host(&self, host: Option<&str>) -> Option<&str> {
+        let cached = self.canvas_host.as_deref()?;
+        (host.is_some_and(|h| !h.eq_ignore_ascii_case(cached))).then_some(cached)
+    }
+}
+
+// ─── Course assignments ──────────────────────────────────────────────────────
+
+/// One course's assignments.  Keyed on the course id, so a course renamed
+/// in Canvas (or two courses with the same nickname) stays one group; the
+/// name is only for display.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "StoredCourseAssignments")]
+pub struct CourseAssignments {
+    pub course_id: u64,
+    pub name: String,
+    pub assignments: Vec<Assignment>,
+}
 
-fn cache_path() -> Option<PathBuf> {
-    dirs::cache_dir().map(|d| d.join("canvas-tui").join("cache.json"))
+/// A `CourseAssignments` as read from a cache of any version.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredCourseAssignments {
+    Keyed {
+        course_id: u64,
+        name: String,
+        #[serde(default)]
+        assignments: Vec<Assignment>,
+    },
+    /// Before version 2: `[course name, assignments]`.
+    Named(String, Vec<Assignment>),
+}
+
+impl From<StoredCourseAssignments> for CourseAssignments {
+    fn from(stored: StoredCourseAssignments) -> Self {
+        match stored {
+            StoredCourseAssignments::Keyed { course_id, name, assignments } => {
+                Self { course_id, name, assignments }
+            }
+            // The id comes from the assignments, or else from the course
+            // list in `CacheData::migrate`; 0 until then.
+            StoredCourseAssignments::Named(name, assignments) => Self {
+                course_id: assignments.iter().find_map(|a| a.course_id).unwrap_or(0),
+                name,
+                assignments,
+            },
+        }
+    }
+}
+
+impl CacheData {
+    /// Bring a cache of an older layout up to `CACHE_VERSION`: give groups
+    /// read without an id their course's id (looked up by name, and dropped
+    /// when there is none; the next sync fetches them again), name each
+    /// group after its course as it is now, and merge groups that turn out
+    /// to be the same course under different names.
+    pub fn migrate(&mut self) {
+        if self.version >= CACHE_VERSION {
+            return;
+        }
+        let courses = &self.courses;
+        let mut merged: Vec<CourseAssignments> = Vec::new();
+        for mut group in std::mem::take(&mut self.assignments) {
+            if group.course_id == 0 {
+                let Some(course) = courses.iter().find(|c| c.name.as_deref() == Some(group.name.as_str())) else {
+                    tracing::info!("dropping cached assignments of unknown course {:?}", group.name);
+                    continue;
+                };
+                group.course_id = course.id;
+            }
+            if let Some(name) = courses
+                .iter()
+                .find(|c| c.id == group.course_id)
+                .and_then(|c| c.name.clone())
+            {
+                group.name = name;
+            }
+            match merged.iter_mut().find(|g| g.course_id == group.course_id) {
+                Some(existing) => {
+                    for a in group.assignments {
+                        if !existing.assignments.iter().any(|e| e.id == a.id) {
+                            existing.assignments.push(a);
+                        }
+                    }
+                }
+                None => merged.push(group),
+            }
+        }
+        self.assignments = merged;
+        self.version = CACHE_VERSION;
+    }
+}
+
+// ─── First seen ──────────────────────────────────────────────────────────────
+
+/// When each assignment first turned up in a sync, kept from sync to sync
+/// so an assignment added late can be told from one that was always there.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FirstSeen {
+    /// The first sync that found any assignments.  Those were already
+    /// there, so their time says nothing about when they were added.
+    pub since: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub at: BTreeMap<u64, DateTime<Utc>>,
+}
+
+impl FirstSeen {
+    pub fn is_empty(&self) -> bool {
+        self.since.is_none() && self.at.is_empty()
+    }
+
+    /// Note the assignments in `ids` that weren't seen before as first
+    /// seen `now`.
+    pub fn record(&mut self, ids: impl IntoIterator<Item = u64>, now: DateTime<Utc>) {
+        let mut ids = ids.into_iter().peekable();
+        if ids.peek().is_none() {
+            return;
+        }
+        self.since.get_or_insert(now);
+        for id in ids {
+            self.at.entry(id).or_insert(now);
+        }
+    }
+
+    /// When assignment `id` was first seen, unless it was already there
+    /// when tracking started.
+    pub fn seen_at(&self, id: u64) -> Option<DateTime<Utc>> {
+        self.at.get(&id).copied().filter(|t| Some(*t) != self.since)
+    }
+}
+
+/// Lets `serde_json` write straight into a `Hasher`.
+struct HashWriter<H>(H);
+
+impl<H: Hasher> std::io::Write for HashWriter<H> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+// ─── Pruning ─────────────────────────────────────────────────────────────────
+
+/// Descriptions longer than this many bytes are cut short in the cache.
+pub const MAX_CACHED_DESCRIPTION: usize = 16 * 1024;
+
+/// Announcements kept in the cache per course, newest first.
+pub const MAX_CACHED_ANNOUNCEMENTS_PER_COURSE: usize = 30;
+
+/// Appended to a description cut short by `CacheData::prune`; the detail
+/// pane re-fetches the assignment when it sees it.
+pub const TRUNCATED_MARKER: &str = "<p>… (shortened in the offline cache)</p>";
+
+/// Default for the `cache_keep_ended_days` config key.
+pub const DEFAULT_KEEP_ENDED_DAYS: u64 = 60;
+
+/// What `CacheData::prune` removed, for the log.
+#[derive(Debug, Default)]
+pub struct PruneReport {
+    pub ended_courses: usize,
+    pub descriptions: usize,
+    pub announcements: usize,
+    pub first_seen: usize,
+}
+
+impl CacheData {
+    /// Shrink the data before it is cached: drop the assignments of courses
+    /// that ended more than `keep_ended_days` ago (the courses themselves,
+    /// with their grades, stay), shorten long descriptions, keep only the
+    /// newest announcements of each course, and forget when assignments no
+    /// longer cached were first seen once that is as old as the cutoff.
+    pub fn prune(&mut self, keep_ended_days: u64, now: DateTime<Utc>) -> PruneReport {
+        let mut report = PruneReport::default();
+        // A `keep_ended_days` too large to count back from `now` keeps
+        // everything.
+        let cutoff = i64::try_from(keep_ended_days)
+            .ok()
+            .and_then(chrono::Duration::try_days)
+            .and_then(|keep| now.checked_sub_signed(keep));
+
+        let ended: Vec<u64> = self
+            .courses
+            .iter()
+            .filter(|c| course_end(c).zip(cutoff).is_some_and(|(end, cutoff)| end < cutoff))
+            .map(|c| c.id)
+            .collect();
+        self.assignments.retain(|group| {
+            let keep = !ended.contains(&group.course_id);
+            report.ended_courses += usize::from(!keep);
+            keep
+        });
+        let kept: std::collections::HashSet<u64> = self
+            .assignments
+            .iter()
+            .flat_map(|group| group.assignments.iter().map(|a| a.id))
+            .collect();
+        self.peer_reviews.retain(|id, _| kept.contains(id));
+        let before = self.first_seen.at.len();
+        self.first_seen
+            .at
+            .retain(|id, seen| kept.contains(id) || cutoff.is_none_or(|cutoff| *seen >= cutoff));
+        report.first_seen = before - self.first_seen.at.len();
+
+        for a in self.assignments.iter_mut().flat_map(|group| group.assignments.iter_mut()) {
+            if let Some(desc) = a.description.as_mut() {
+                if desc.len() > MAX_CACHED_DESCRIPTION && !is_truncated(desc) {
+                    let mut cut = MAX_CACHED_DESCRIPTION;
+                    while !desc.is_char_boundary(cut) {
+                        cut -= 1;
+                    }
+                    desc.truncate(cut);
+                    desc.push_str(TRUNCATED_MARKER);
+                    report.descriptions += 1;
+                }
+            }
+        }
+
+        // Keep the newest announcements of each course, in the original order.
+        let mut by_age: Vec<usize> = (0..self.announcements.len()).collect();
+        by_age.sort_by_key(|&i| std::cmp::Reverse(self.announcements[i].posted_at));
+        let mut per_course: std::collections::HashMap<Option<&str>, usize> = Default::default();
+        let mut keep = vec![false; self.announcements.len()];
+        for i in by_age {
+            let count = per_course
+                .entry(self.announcements[i].context_code.as_deref())
+                .or_default();
+            *count += 1;
+            keep[i] = *count <= MAX_CACHED_ANNOUNCEMENTS_PER_COURSE;
+        }
+        let before = self.announcements.len();
+        let mut keep = keep.into_iter();
+        self.announcements.retain(|_| keep.next().unwrap_or(true));
+        report.announcements = before - self.announcements.len();
+
+        report
+    }
+}
+
+/// When a course ended: the later of its own and its term's end date, so a
+/// course extended past its term isn't treated as over.
+fn course_end(course: &Course) -> Option<DateTime<Utc>> {
+    let term_end = course.term.as_ref().and_then(|t| t.end_at);
+    course.end_at.max(term_end)
+}
+
+/// Whether `description` was shortened by `CacheData::prune`.
+pub fn is_truncated(description: &str) -> bool {
+    description.ends_with(TRUNCATED_MARKER)
 }
 
 // ─── I/O ─────────────────────────────────────────────────────────────────────
 
-pub fn load_cache() -> Option<CacheData> {
-    let path = cache_path()?;
-    let contents = std::fs::read_to_string(&path).ok()?;
-    serde_json::from_str(&contents).ok()
+/// Load the cache for `paths`'s instance.  A cache from before caches were
+/// kept per instance is first moved into place, with the files kept beside
+/// it, if it says it was synced from this instance; one that doesn't say is
+/// left for `adopt_untagged_legacy_cache`.  The caller still checks
+/// `CacheData::foreign_host`.
+pub fn load_cache(paths: &Paths) -> Option<CacheData> {
+    let legacy_host = legacy_cache(paths).and_then(|cache| cache.canvas_host);
+    if let (Some(cached), Some(host)) = (legacy_host, paths.canvas_host.as_deref()) {
+        if cached.eq_ignore_ascii_case(host) {
+            adopt_legacy_files(paths);
+        }
+    }
+    read_cache(&paths.cache_file())
+}
+
+fn read_cache(path: &Path) -> Option<CacheData> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let mut data: CacheData = serde_json::from_str(&contents).ok()?;
+    data.migrate();
+    Some(data)
+}
+
+/// The shared `cache.json` of older versions, unless this instance has a
+/// cache of its own already.
+fn legacy_cache(paths: &Paths) -> Option<CacheData> {
+    let (legacy, file) = (paths.legacy_cache_file(), paths.cache_file());
+    if legacy == file || file.exists() {
+        return None;
+    }
+    read_cache(&legacy)
+}
+
+/// What `adopt_untagged_legacy_cache` made of an older version's cache.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LegacyCache {
+    /// There is none that doesn't say where it's from.
+    None,
+    /// It was synced as the account `client` signs in to, and is now this
+    /// instance's.
+    Adopted,
+    /// It was synced as someone else, perhaps on another instance, and
+    /// stays where it is.
+    NotOurs,
 }
 
-pub fn save_cache(data: &CacheData) -> Result<()> {
-    let path = cache_path().ok_or_else(|| anyhow!("Could not determine cache directory"))?;
+/// Move an older version's cache that doesn't say which instance it was
+/// synced from into place, with the files kept beside it, if the profile
+/// shows it was synced as the same user.  A failed profile fetch leaves it
+/// to be checked on the next start.
+pub async fn adopt_untagged_legacy_cache(
+    paths: &Paths,
+    client: &CanvasClient,
+) -> Result<LegacyCache, CanvasError> {
+    let Some(legacy) = legacy_cache(paths).filter(|cache| cache.canvas_host.is_none()) else {
+        return Ok(LegacyCache::None);
+    };
+    let me = client.get_self().await?;
+    if legacy.user.is_none_or(|user| user.id != me.id) {
+        tracing::warn!("the old cache wasn't synced as user {}; leaving it", me.id);
+        return Ok(LegacyCache::NotOurs);
+    }
+    adopt_legacy_files(paths);
+    Ok(LegacyCache::Adopted)
+}
+
+/// Rename each file of older versions to this instance's name for it,
+/// unless that exists already.
+fn adopt_legacy_files(paths: &Paths) {
+    for (legacy, file) in paths.legacy_files() {
+        if legacy == file || file.exists() || !legacy.exists() {
+            continue;
+        }
+        match std::fs::rename(&legacy, &file) {
+            Ok(()) => tracing::info!("moved {} to {}", legacy.display(), file.display()),
+            Err(e) => tracing::warn!("moving {} to {}: {e}", legacy.display(), file.display()),
+        }
+    }
+}
+
+/// Just the first-seen record from the cache file, for when the rest of
+/// it doesn't load (say, after its format changed): everything else comes
+/// back with the next sync, but first-seen times can't be fetched again.
+pub fn load_first_seen(paths: &Paths) -> FirstSeen {
+    #[derive(Deserialize)]
+    struct Partial {
+        #[serde(default)]
+        first_seen: FirstSeen,
+    }
+    std::fs::read_to_string(paths.cache_file())
+        .ok()
+        .and_then(|s| serde_json::from_str::<Partial>(&s).ok())
+        .map(|p| p.first_seen)
+        .unwrap_or_default()
+}
+
+/// Write `data` to `path`, unless its content hash equals `previous_hash`
+/// (the hash of what was last written) and the file is still there.  Sets
+/// `data.content_hash` and returns whether the file was written.  A skipped
+/// write leaves the file's `cached_at` at the last change.
+pub fn save_cache(path: &Path, data: &mut CacheData, previous_hash: Option<u64>) -> Result<bool> {
+    let hash = data.content_hash();
+    data.content_hash = Some(hash);
+    if previous_hash == Some(hash) && path.exists() {
+        tracing::info!("cache unchanged; not rewriting {}", path.display());
+        return Ok(false);
+    }
+
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
     let json = serde_json::to_string_pretty(data)?;
-    std::fs::write(&path, json)?;
-    Ok(())
+    std::fs::write(path, json)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn data(value: serde_json::Value) -> CacheData {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn sample() -> CacheData {
+        data(json!({
+            "version": CACHE_VERSION,
+            "cached_at": "2025-04-01T12:00:00Z",
+            "user": null,
+            "courses": [{ "id": 1, "name": "Biology" }],
+            "assignments": [{ "course_id": 1, "name": "Biology", "assignments": [{ "id": 10 }] }],
+        }))
+    }
+
+    #[test]
+    fn content_hash_ignores_when_the_data_was_cached() {
+        let a = sample();
+        let mut b = sample();
+        b.cached_at += chrono::Duration::hours(3);
+        assert_eq!(a.content_hash(), b.content_hash());
+        b.courses[0].name = Some("Chemistry".into());
+        assert_ne!(a.content_hash(), b.content_hash());
+    }
+
+    #[test]
+    fn unchanged_data_is_not_rewritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        let mut first = sample();
+        assert!(save_cache(&path, &mut first, None).unwrap());
+        let written = std::fs::read_to_string(&path).unwrap();
+        let mtime = std::fs::metadata(&path).unwrap().modified().unwrap();
+
+        let mut again = sample();
+        again.cached_at += chrono::Duration::hours(1);
+        assert!(!save_cache(&path, &mut again, first.content_hash).unwrap());
+        assert_eq!(again.content_hash, first.content_hash);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), written);
+        assert_eq!(std::fs::metadata(&path).unwrap().modified().unwrap(), mtime);
+    }
+
+    #[test]
+    fn changed_or_missing_files_are_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("cache.json");
+        let mut first = sample();
+        assert!(save_cache(&path, &mut first, None).unwrap());
+
+        let mut changed = sample();
+        changed.courses[0].name = Some("Chemistry".into());
+        assert!(save_cache(&path, &mut changed, first.content_hash).unwrap());
+        assert!(std::fs::read_to_string(&path).unwrap().contains("Chemistry"));
+
+        std::fs::remove_file(&path).unwrap();
+        let mut same = changed.clone();
+        assert!(save_cache(&path, &mut same, changed.content_hash).unwrap());
+        assert!(path.exists());
+    }
+
+    /// A cache grown over years: an old course, one that just ended, a
+    /// current one, an oversized description and too many announcements.
+    fn bloated() -> CacheData {
+        let long = format!("<p>{}</p>", "é".repeat(MAX_CACHED_DESCRIPTION));
+        let announcements: Vec<_> = (0..40)
+            .map(|i| {
+                json!({
+                    "id": 100 + i,
+                    "context_code": "course_3",
+                    "posted_at": format!("2025-03-{:02}T00:00:00Z", 1 + i % 28),
+                })
+            })
+            .collect();
+        data(json!({
+            "version": CACHE_VERSION,
+            "cached_at": "2025-04-01T12:00:00Z",
+            "user": null,
+            "courses": [
+                { "id": 1, "name": "Old", "end_at": "2022-05-01T00:00:00Z" },
+                { "id": 2, "name": "Recent", "end_at": "2025-03-01T00:00:00Z" },
+                { "id": 3, "name": "Current", "end_at": "2024-01-01T00:00:00Z",
+                  "term": { "id": 9, "end_at": "2025-06-01T00:00:00Z" } },
+            ],
+            "assignments": [
+                { "course_id": 1, "name": "Old", "assignments": [{ "id": 10 }] },
+                { "course_id": 2, "name": "Recent", "assignments": [{ "id": 20 }] },
+                { "course_id": 3, "name": "Current", "assignments": [{ "id": 30, "description": long }] },
+            ],
+            "peer_reviews": { "10": [{ "id": 1 }], "30": [{ "id": 2 }] },
+            "announcements": announcements,
+            "first_seen": { "since": "2022-01-01T00:00:00Z", "at": {
+                "10": "2022-01-01T00:00:00Z",
+                "11": "2022-02-01T00:00:00Z",
+                "12": "2025-03-20T00:00:00Z",
+                "30": "2024-09-01T00:00:00Z",
+            } },
+        }))
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2025-04-01T12:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn prune_trims_a_bloated_cache() {
+        let mut cache = bloated();
+        let report = cache.prune(DEFAULT_KEEP_ENDED_DAYS, now());
+
+        // Only the course that ended years ago loses its assignments; a
+        // course whose term runs on counts as current.
+        let groups: Vec<u64> = cache.assignments.iter().map(|g| g.course_id).collect();
+        assert_eq!(groups, [2, 3]);
+        assert_eq!(cache.courses.len(), 3);
+        assert_eq!(report.ended_courses, 1);
+        assert_eq!(cache.peer_reviews.keys().copied().collect::<Vec<_>>(), [30]);
+        assert_eq!(cache.first_seen.at.keys().copied().collect::<Vec<_>>(), [12, 30]);
+        assert_eq!(report.first_seen, 2);
+
+        let desc = cache.assignments[1].assignments[0].description.as_deref().unwrap();
+        assert!(is_truncated(desc));
+        assert!(desc.len() <= MAX_CACHED_DESCRIPTION + TRUNCATED_MARKER.len());
+        assert_eq!(report.descriptions, 1);
+
+        assert_eq!(cache.announcements.len(), MAX_CACHED_ANNOUNCEMENTS_PER_COURSE);
+        assert_eq!(report.announcements, 10);
+        let oldest_kept = cache.announcements.iter().filter_map(|a| a.posted_at).min().unwrap();
+        let dropped_before: DateTime<Utc> = "2025-03-06T00:00:00Z".parse().unwrap();
+        assert!(oldest_kept >= dropped_before, "{oldest_kept}");
+
+        // Pruning again finds nothing more to do.
+        let again = cache.prune(DEFAULT_KEEP_ENDED_DAYS, now());
+        assert_eq!(
+            (again.ended_courses, again.descriptions, again.announcements, again.first_seen),
+            (0, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn prune_with_a_huge_keep_keeps_every_course() {
+        for keep in [u64::MAX, i64::MAX as u64, 1 << 40, 200_000_000] {
+            let mut cache = bloated();
+            let report = cache.prune(keep, now());
+            assert_eq!(report.ended_courses, 0, "{keep}");
+            assert_eq!(report.first_seen, 0, "{keep}");
+            assert_eq!(cache.assignments.len(), 3, "{keep}");
+            // The other limits still apply.
+            assert_eq!(report.descriptions, 1, "{keep}");
+        }
+    }
+
+    #[test]
+    fn prune_with_no_keep_drops_every_ended_course() {
+        let mut cache = bloated();
+        cache.prune(0, now());
+        let groups: Vec<u64> = cache.assignments.iter().map(|g| g.course_id).collect();
+        assert_eq!(groups, [3]);
+    }
+
+    fn paths(dir: &Path, host: Option<&str>, as_user_id: Option<u64>) -> Paths {
+        Paths {
+            cache_dir: dir.to_path_buf(),
+            download_dir: dir.join("downloads"),
+            as_user_id,
+            canvas_host: host.map(str::to_string),
+        }
+    }
+
+    /// `sample` as written by a version that recorded `host`, or by one
+    /// from before hosts were recorded.
+    fn write_cache(path: &Path, host: Option<&str>) {
+        let mut value = serde_json::to_value(sample()).unwrap();
+        value["user"] = json!({ "id": 3, "name": "Sam" });
+        if let Some(host) = host {
+            value["canvas_host"] = json!(host);
+        }
+        std::fs::write(path, value.to_string()).unwrap();
+    }
+
+    #[test]
+    fn foreign_host_is_a_different_recorded_host() {
+        let mut cache = sample();
+        // Older caches don't say, and are taken as ours.
+        assert_eq!(cache.foreign_host(Some("school.instructure.com")), None);
+
+        cache.canvas_host = Some("School.Instructure.com".into());
+        assert_eq!(cache.foreign_host(Some("school.instructure.com")), None);
+        assert_eq!(cache.foreign_host(None), None);
+        assert_eq!(cache.foreign_host(Some("other.instructure.com")), Some("School.Instructure.com"));
+    }
+
+    #[test]
+    fn the_host_round_trips_and_is_left_out_when_unknown() {
+        let mut cache = sample();
+        assert!(!serde_json::to_string(&cache).unwrap().contains("canvas_host"));
+        cache.canvas_host = Some("school.instructure.com".into());
+        let back: CacheData = serde_json::from_str(&serde_json::to_string(&cache).unwrap()).unwrap();
+        assert_eq!(back.canvas_host.as_deref(), Some("school.instructure.com"));
+    }
+
+    /// A Canvas whose profile says the token is user `id`'s.
+    async fn signed_in_as(id: u64) -> (crate::test_server::TestServer, CanvasClient) {
+        use crate::test_server::{Reply, TestServer};
+        let server =
+            TestServer::start(move |_| Reply::json(format!(r#"{{"id": {id}, "name": "Sam"}}"#)))
+                .await;
+        let client = CanvasClient::new(&server.url, "t").unwrap();
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn an_untagged_legacy_cache_moves_once_the_profile_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), Some("school.instructure.com"), None);
+        assert_eq!(paths.cache_file(), dir.path().join("cache-school.instructure.com.json"));
+        write_cache(&paths.legacy_cache_file(), None);
+        std::fs::write(dir.path().join("estimates.json"), "{}").unwrap();
+
+        // Not shown before anyone has said whose it is.
+        assert!(load_cache(&paths).is_none());
+        assert!(paths.legacy_cache_file().exists());
+
+        let (_server, client) = signed_in_as(3).await;
+        let adopted = adopt_untagged_legacy_cache(&paths, &client).await.unwrap();
+        assert_eq!(adopted, LegacyCache::Adopted);
+        let cache = load_cache(&paths).unwrap();
+        assert_eq!(cache.courses[0].name.as_deref(), Some("Biology"));
+        assert!(!paths.legacy_cache_file().exists());
+        assert_eq!(
+            paths.estimates_file(),
+            dir.path().join("estimates-school.instructure.com.json")
+        );
+        assert!(paths.estimates_file().exists());
+        assert!(!dir.path().join("estimates.json").exists());
+    }
+
+    #[tokio::test]
+    async fn another_users_untagged_legacy_cache_stays_put() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), Some("school.instructure.com"), None);
+        write_cache(&paths.legacy_cache_file(), None);
+        std::fs::write(dir.path().join("estimates.json"), "{}").unwrap();
+
+        let (_server, client) = signed_in_as(4).await;
+        let adopted = adopt_untagged_legacy_cache(&paths, &client).await.unwrap();
+        assert_eq!(adopted, LegacyCache::NotOurs);
+        assert!(load_cache(&paths).is_none());
+        assert!(paths.legacy_cache_file().exists());
+        assert!(dir.path().join("estimates.json").exists());
+        assert!(!paths.estimates_file().exists());
+    }
+
+    #[tokio::test]
+    async fn a_tagged_legacy_cache_needs_no_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), Some("school.instructure.com"), None);
+        write_cache(&paths.legacy_cache_file(), Some("school.instructure.com"));
+        assert!(load_cache(&paths).is_some());
+
+        // Nothing is left to check, so no request is made.
+        let (server, client) = signed_in_as(3).await;
+        let adopted = adopt_untagged_legacy_cache(&paths, &client).await.unwrap();
+        assert_eq!(adopted, LegacyCache::None);
+        assert_eq!(server.requests(), 0);
+    }
+
+    #[test]
+    fn a_legacy_cache_from_the_same_host_moves_while_masquerading() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), Some("school.instructure.com"), Some(7));
+        assert_eq!(paths.legacy_cache_file(), dir.path().join("cache-as-user-7.json"));
+        write_cache(&paths.legacy_cache_file(), Some("SCHOOL.instructure.com"));
+        std::fs::write(dir.path().join("sync-history-as-user-7.json"), "[]").unwrap();
+
+        assert!(load_cache(&paths).is_some());
+        assert_eq!(
+            paths.cache_file(),
+            dir.path().join("cache-school.instructure.com-as-user-7.json")
+        );
+        assert!(paths.cache_file().exists());
+        assert_eq!(
+            paths.sync_history_file(),
+            dir.path().join("sync-history-school.instructure.com-as-user-7.json")
+        );
+        assert!(paths.sync_history_file().exists());
+    }
+
+    #[test]
+    fn another_hosts_legacy_cache_stays_put() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), Some("school.instructure.com"), None);
+        write_cache(&paths.legacy_cache_file(), Some("other.instructure.com"));
+
+        assert!(load_cache(&paths).is_none());
+        assert!(paths.legacy_cache_file().exists());
+        assert!(!paths.cache_file().exists());
+    }
+
+    #[test]
+    fn an_existing_instance_cache_wins_over_the_legacy_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), Some("school.instructure.com"), None);
+        write_cache(&paths.legacy_cache_file(), None);
+        let mut ours = sample();
+        ours.courses[0].name = Some("Chemistry".into());
+        std::fs::write(paths.cache_file(), serde_json::to_string(&ours).unwrap()).unwrap();
+
+        let cache = load_cache(&paths).unwrap();
+        assert_eq!(cache.courses[0].name.as_deref(), Some("Chemistry"));
+        assert!(paths.legacy_cache_file().exists());
+    }
+
+    #[test]
+    fn without_a_host_the_legacy_file_is_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), None, None);
+        assert_eq!(paths.cache_file(), paths.legacy_cache_file());
+        write_cache(&paths.cache_file(), Some("other.instructure.com"));
+        assert!(load_cache(&paths).is_some());
+    }
 }
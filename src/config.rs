@@ -1,22 +1,282 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use crate::api::oauth::{DeveloperKey, OAuthSession, OAuthTokens};
+use crate::api::{clamp_per_page, ApiOptions, CanvasClient};
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderName, HeaderValue};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     pub canvas_url: String,
+    /// A manually generated token, or the access token from `--login`.
+    #[serde(default)]
     pub api_token: String,
+    /// Where the cache file lives.  `~` and `$VARS` are expanded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_dir: Option<String>,
+    /// Where downloaded files are saved.  `~` and `$VARS` are expanded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_dir: Option<String>,
+    /// Re-sync in the background this many minutes after the last sync.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval_minutes: Option<u64>,
+    /// How many days ahead the dashboard's Upcoming list shows before
+    /// folding the rest behind a "… N more" row.  Defaults to 30.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dashboard_days: Option<u64>,
+    /// Calendar events whose titles contain any of these (case-insensitive)
+    /// are highlighted.  Defaults to exam / midterm / final.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_highlight: Option<Vec<String>>,
+    /// Calendar events whose titles contain any of these are dimmed and can
+    /// be hidden.  Defaults to office hours / lecture.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_deemphasize: Option<Vec<String>>,
+    /// Trim the offline cache (old courses' assignments, long descriptions,
+    /// old announcements).  Defaults to true.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_prune: Option<bool>,
+    /// Keep the assignments of courses that ended up to this many days ago
+    /// in the cache.  Defaults to 60.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_keep_ended_days: Option<u64>,
+    /// Stop fetching a course's assignments after this many; the rest can
+    /// be loaded on demand.  Unlimited by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_assignments_per_course: Option<usize>,
+    /// The Schedule list's pinned "Due soon" section: `duplicate` (the
+    /// default) also keeps its assignments in the list below, `move` takes
+    /// them out of it, `off` hides the section.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_soon_section: Option<DueSoonSection>,
+    /// j/k past the end of a list go to the other end.  Defaults to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrap_navigation: Option<bool>,
+    /// Long assignment names in the Schedule and Dashboard lists continue
+    /// on a second line instead of being cut short.  Defaults to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrap_names: Option<bool>,
+    /// Countdowns go by Canvas's clock rather than this computer's when
+    /// the two differ by more than two minutes.  Defaults to false: the
+    /// difference is only warned about.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correct_clock_skew: Option<bool>,
+    /// Show cross-listed sections that Canvas lists as separate courses
+    /// as one course.  Defaults to true.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_cross_listed: Option<bool>,
+    /// Print the next assignments, missing work and the sync time to the
+    /// terminal after quitting.  Defaults to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_summary: Option<bool>,
+    /// Split the Schedule list, when sorted by due date, under Overdue /
+    /// Today / Tomorrow / This week / Next week / Later / No date headers.
+    /// Defaults to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_sections: Option<bool>,
+    /// Go to the top of a list with `gg`, as in vim, instead of a single
+    /// `g`.  Defaults to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vim_gg: Option<bool>,
+    /// Colors the terminal can show: `truecolor`, `256` or `16`.  Left
+    /// out, it is guessed from `COLORTERM` and `TERM`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_mode: Option<ColorMode>,
+    /// Editor for text submissions, with arguments (`code --wait`).
+    /// Overrides `$VISUAL` and `$EDITOR`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    /// Directories whose newest files are offered when submitting a file
+    /// upload (`["~/Documents/coursework", "~/Downloads"]`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submit_watch_dirs: Option<Vec<String>>,
+    /// Hours of work assumed for an assignment without an estimate (`e`).
+    /// Defaults to 2.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_default_hours: Option<f32>,
+    /// Hours of work a day past which the dashboard's time budget flags
+    /// the day.  Defaults to 4.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_capacity_hours: Option<f32>,
+    /// Replacement labels for status bar hints, by hint name (`refresh =
+    /// "actualiser"`), to shorten or translate them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint_labels: Option<BTreeMap<String, String>>,
+    /// Shell commands `x` runs on matching assignments
+    /// (`[[open_handlers]]` tables), first match wins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_handlers: Option<Vec<OpenHandler>>,
+    /// Request tuning for the Canvas API (`[api]` table).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api: Option<ApiSettings>,
+    /// Developer key for `canvas-tui --login`, for institutions that don't
+    /// allow generating access tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth_client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth_client_secret: Option<String>,
+    /// Local port the login redirect comes back to.  Defaults to 8765; the
+    /// developer key must allow `http://localhost:<port>/callback`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth_redirect_port: Option<u16>,
+    /// Saved by `--login` and refreshes; not meant to be edited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth_refresh_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oauth_expires_at: Option<DateTime<Utc>>,
+    /// Admins: see Canvas as this user (masquerading).  Cached separately.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_user_id: Option<u64>,
+    /// Allow submitting while masquerading.  Defaults to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_masquerade_submit: Option<bool>,
+    /// `--as-user` from the command line; overrides `as_user_id` and is
+    /// never saved.
+    #[serde(skip)]
+    pub as_user_override: Option<u64>,
+}
+
+/// Redirect port for `--login` when `oauth_redirect_port` isn't set.
+pub const DEFAULT_OAUTH_REDIRECT_PORT: u16 = 8765;
+
+/// Values of the `due_soon_section` key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DueSoonSection {
+    #[default]
+    Duplicate,
+    Move,
+    Off,
+}
+
+/// Values of the `color_mode` key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorMode {
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    #[serde(rename = "256")]
+    Ansi256,
+    #[serde(rename = "16")]
+    Ansi16,
+}
+
+impl ColorMode {
+    /// The config value naming this mode.
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorMode::TrueColor => "truecolor",
+            ColorMode::Ansi256 => "256",
+            ColorMode::Ansi16 => "16",
+        }
+    }
+}
+
+/// One `[[open_handlers]]` table: a command template and the assignments
+/// it is for.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenHandler {
+    /// Text the course's code or name contains (case-insensitive).
+    /// Left out, any course.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub course: Option<String>,
+    /// Text the assignment's name contains (case-insensitive).  Left out,
+    /// any assignment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignment: Option<String>,
+    /// A shell command line; `{assignment_name}`, `{course_code}`,
+    /// `{html_url}` and `{due_iso}` are replaced, quoted, so they go
+    /// outside any quotes.
+    pub command: String,
+    /// Start it in the background instead of giving it the terminal.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub detach: bool,
+    /// Show the expanded command in the status bar instead of running it.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+}
+
+/// Most requests `api.max_concurrent_requests` lets run at once.
+const MAX_CONCURRENT_REQUESTS: usize = 32;
+
+/// Headers `api.extra_headers` may not set: the client sends the token
+/// itself, and a second credential would fight it.
+const RESERVED_HEADERS: [&str; 2] = ["authorization", "proxy-authorization"];
+
+/// `api.api_prefix` as a path starting with `/` and without a trailing one
+/// (`/` alone, or empty, puts the API at the root).
+fn api_prefix(prefix: &str) -> Result<String> {
+    let trimmed = prefix.trim().trim_matches('/');
+    if trimmed.contains(['?', '#']) || trimmed.contains(char::is_whitespace) {
+        anyhow::bail!("api.api_prefix = {prefix:?} is not a URL path");
+    }
+    Ok(if trimmed.is_empty() { String::new() } else { format!("/{trimmed}") })
+}
+
+/// One `api.extra_headers` entry, with an `env:VAR` value read from the
+/// environment.  Values are marked sensitive so they stay out of logs.
+fn extra_header(name: &str, value: &str) -> Result<(HeaderName, HeaderValue)> {
+    let header = HeaderName::from_bytes(name.as_bytes())
+        .with_context(|| format!("api.extra_headers: {name:?} is not a valid header name"))?;
+    if RESERVED_HEADERS.contains(&header.as_str()) {
+        anyhow::bail!(
+            "api.extra_headers: {name} would conflict with the API token; \
+             set api_token (or use --login) instead"
+        );
+    }
+    let value = match value.strip_prefix("env:") {
+        Some(var) => std::env::var(var)
+            .with_context(|| format!("api.extra_headers.{name}: ${var} is not set"))?,
+        None => value.to_string(),
+    };
+    let mut value = HeaderValue::from_str(&value)
+        .with_context(|| format!("api.extra_headers.{name}: the value is not a valid header value"))?;
+    value.set_sensitive(true);
+    Ok((header, value))
+}
+
+/// The `[api]` table: page sizes (1–100), optional includes, the
+/// concurrency limit and, for self-hosted instances, where the API lives
+/// and extra headers to send.  Anything left out keeps the client's default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub courses_per_page: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignments_per_page: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar_per_page: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub announcements_per_page: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discussions_per_page: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_total_students: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_score_statistics: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_submission_comments: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<usize>,
+    /// The API's path on the host, `/api/v1` unless moved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_prefix: Option<String>,
+    /// Header name to value; `env:VAR` reads the value from `$VAR`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra_headers: Option<BTreeMap<String, String>>,
 }
 
 impl Config {
+    /// The config file, else `CANVAS_URL` and `CANVAS_API_TOKEN` from the
+    /// environment.
     pub fn load() -> Result<Self> {
         if let Some(path) = Self::config_path() {
             if path.exists() {
-                let contents = std::fs::read_to_string(&path)
-                    .with_context(|| format!("Failed to read config at {}", path.display()))?;
-                let config: Config = toml::from_str(&contents)
-                    .with_context(|| "Failed to parse config.toml")?;
-                return Ok(config);
+                return Self::load_file(&path);
             }
         }
 
@@ -28,40 +288,754 @@ impl Config {
         Ok(Self {
             canvas_url,
             api_token,
+            ..Self::default()
+        })
+    }
+
+    /// Whether a config file is present (even if it fails to parse).
+    pub fn file_exists() -> bool {
+        Self::config_path().is_some_and(|p| p.exists())
+    }
+
+    /// What can still be read from a config file that doesn't parse:
+    /// `canvas_url`, `api_token`, `cache_dir`, `download_dir` and
+    /// `as_user_id` (so a masquerading admin stays on the user's cache),
+    /// with the rest at their defaults, so the app can start on its cache
+    /// until the file is fixed.  `None` without a file or a `canvas_url` in
+    /// it.  Never `save` the result; `save_tokens` leaves the file alone.
+    pub fn salvage() -> Option<Self> {
+        let contents = std::fs::read_to_string(Self::config_path()?).ok()?;
+        salvage_from(&contents)
+    }
+
+    fn load_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config at {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| "Failed to parse config.toml")
+    }
+
+    /// Keys that differ between `self` and `other` but only take effect
+    /// after a restart (they were baked into the client or paths at startup).
+    pub fn restart_required_changes(&self, other: &Config) -> Vec<&'static str> {
+        let mut changed = Vec::new();
+        if self.canvas_url != other.canvas_url {
+            changed.push("canvas_url");
+        }
+        if self.api_token != other.api_token {
+            changed.push("api_token");
+        }
+        if self.cache_dir != other.cache_dir {
+            changed.push("cache_dir");
+        }
+        if self.download_dir != other.download_dir {
+            changed.push("download_dir");
+        }
+        if self.api != other.api {
+            changed.push("api");
+        }
+        if self.as_user_id != other.as_user_id {
+            changed.push("as_user_id");
+        }
+        if self.allow_masquerade_submit != other.allow_masquerade_submit {
+            changed.push("allow_masquerade_submit");
+        }
+        if self.oauth_client_id != other.oauth_client_id
+            || self.oauth_client_secret != other.oauth_client_secret
+        {
+            changed.push("oauth developer key");
+        }
+        changed
+    }
+
+    /// Copy the settings that can change while running from `other`.
+    pub fn apply_live_settings(&mut self, other: &Config) {
+        self.refresh_interval_minutes = other.refresh_interval_minutes;
+        self.dashboard_days = other.dashboard_days;
+        self.event_highlight = other.event_highlight.clone();
+        self.event_deemphasize = other.event_deemphasize.clone();
+        self.cache_prune = other.cache_prune;
+        self.cache_keep_ended_days = other.cache_keep_ended_days;
+        self.max_assignments_per_course = other.max_assignments_per_course;
+        self.editor = other.editor.clone();
+        self.submit_watch_dirs = other.submit_watch_dirs.clone();
+        self.estimate_default_hours = other.estimate_default_hours;
+        self.daily_capacity_hours = other.daily_capacity_hours;
+        self.hint_labels = other.hint_labels.clone();
+        self.wrap_navigation = other.wrap_navigation;
+        self.wrap_names = other.wrap_names;
+        self.vim_gg = other.vim_gg;
+        self.date_sections = other.date_sections;
+        self.correct_clock_skew = other.correct_clock_skew;
+        self.merge_cross_listed = other.merge_cross_listed;
+        self.exit_summary = other.exit_summary;
+        self.open_handlers = other.open_handlers.clone();
+        self.due_soon_section = other.due_soon_section;
+        self.color_mode = other.color_mode;
+    }
+
+    /// A client for this config's Canvas instance and token, renewing the
+    /// token itself when logged in with OAuth.
+    pub fn client(&self) -> Result<CanvasClient> {
+        let mut client = CanvasClient::new(&self.canvas_url, &self.api_token)?
+            .with_options(self.api_options()?);
+        if let Some(session) = self.oauth_session() {
+            client = client.with_oauth(session);
+        }
+        if let Some(id) = self.masquerade_user() {
+            client = client.with_masquerade(id, self.allow_masquerade_submit.unwrap_or(false));
+        }
+        Ok(client)
+    }
+
+    /// The user to masquerade as: `--as-user`, else `as_user_id`.
+    pub fn masquerade_user(&self) -> Option<u64> {
+        self.as_user_override.or(self.as_user_id)
+    }
+
+    pub fn developer_key(&self) -> Option<DeveloperKey> {
+        Some(DeveloperKey {
+            client_id: self.oauth_client_id.clone()?,
+            client_secret: self.oauth_client_secret.clone()?,
+        })
+    }
+
+    fn oauth_session(&self) -> Option<OAuthSession> {
+        Some(OAuthSession {
+            key: self.developer_key()?,
+            refresh_token: self.oauth_refresh_token.clone()?,
+            expires_at: self.oauth_expires_at,
         })
     }
 
+    /// Store tokens from a login or refresh (call `save` to persist them).
+    pub fn set_oauth_tokens(&mut self, tokens: OAuthTokens) {
+        self.api_token = tokens.access_token;
+        self.oauth_refresh_token = Some(tokens.refresh_token);
+        self.oauth_expires_at = tokens.expires_at;
+    }
+
+    /// Client options from the `[api]` table; out-of-range page sizes are
+    /// clamped with a warning.  Fails on an unusable `api_prefix` or extra
+    /// header.
+    pub fn api_options(&self) -> Result<ApiOptions> {
+        let mut options = ApiOptions::default();
+        let Some(api) = &self.api else {
+            return Ok(options);
+        };
+        let page_sizes = [
+            ("courses_per_page", api.courses_per_page, &mut options.courses_per_page),
+            ("assignments_per_page", api.assignments_per_page, &mut options.assignments_per_page),
+            ("calendar_per_page", api.calendar_per_page, &mut options.calendar_per_page),
+            ("announcements_per_page", api.announcements_per_page, &mut options.announcements_per_page),
+            ("discussions_per_page", api.discussions_per_page, &mut options.discussions_per_page),
+        ];
+        for (key, value, target) in page_sizes {
+            if let Some(value) = value {
+                *target = clamp_per_page(&format!("api.{key}"), value);
+            }
+        }
+        if let Some(on) = api.include_total_students {
+            options.include_total_students = on;
+        }
+        if let Some(on) = api.include_score_statistics {
+            options.include_score_statistics = on;
+        }
+        if let Some(on) = api.include_submission_comments {
+            options.include_submission_comments = on;
+        }
+        if let Some(n) = api.max_concurrent_requests {
+            options.max_concurrent_requests = n.clamp(1, MAX_CONCURRENT_REQUESTS);
+            if n != options.max_concurrent_requests {
+                tracing::warn!(
+                    "api.max_concurrent_requests = {n} is out of range 1–{MAX_CONCURRENT_REQUESTS}; using {}",
+                    options.max_concurrent_requests
+                );
+            }
+        }
+        if let Some(prefix) = &api.api_prefix {
+            options.api_prefix = api_prefix(prefix)?;
+        }
+        for (name, value) in api.extra_headers.iter().flatten() {
+            let (name, value) = extra_header(name, value)?;
+            options.extra_headers.insert(name, value);
+        }
+        Ok(options)
+    }
+
     pub fn generate_default() -> Result<PathBuf> {
         let path = Self::config_path()
             .with_context(|| "Could not determine config directory")?;
 
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
         let default = Config {
             canvas_url: "https://your-school.instructure.com".into(),
             api_token: "your-api-token-here".into(),
+            ..Config::default()
         };
 
         let toml_str = toml::to_string_pretty(&default)?;
-        std::fs::write(&path, toml_str)?;
+        write_private(&path, &toml_str)?;
         Ok(path)
     }
 
     /// Persist this config to disk (creates parent directories if needed).
+    /// The file holds the API token, so it is only readable by its owner.
+    /// This replaces the whole file; to store new tokens in an existing
+    /// one, use `save_tokens`.
     pub fn save(&self) -> Result<()> {
         let path = Self::config_path()
             .with_context(|| "Could not determine config directory")?;
-        if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
         let toml_str = toml::to_string_pretty(self)?;
-        std::fs::write(&path, toml_str)?;
-        Ok(())
+        write_private(&path, &toml_str)
+    }
+
+    /// Write `api_token` and the OAuth refresh token and expiry into the
+    /// config file, leaving the rest of it as the user wrote it.  The other
+    /// settings held here may be stale (restart-only keys edited since
+    /// startup) or salvaged from a file that doesn't parse, so they are
+    /// never written back.  Without a file, saves the whole config.
+    pub fn save_tokens(&self) -> Result<()> {
+        let path = Self::config_path()
+            .with_context(|| "Could not determine config directory")?;
+        if !path.exists() {
+            return self.save();
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config at {}", path.display()))?;
+        write_private(&path, &with_tokens(&contents, self))
+    }
+
+    /// Returns a warning if the config file exists and group or other users
+    /// have any access to it (reading the token, or writing a different
+    /// URL or token into it).  Always `None` on non-Unix targets.
+    pub fn permission_warning() -> Option<String> {
+        let path = Self::config_path()?;
+        insecure_mode(&path).map(|mode| {
+            format!(
+                "{} is accessible by other users (mode {mode:o}) — run: chmod 600 {}",
+                path.display(),
+                path.display()
+            )
+        })
     }
 
     fn config_path() -> Option<PathBuf> {
         dirs::config_dir().map(|d| d.join("canvas-tui").join("config.toml"))
     }
 }
+
+/// `Config::salvage` for `contents`: as a TOML table when only a value is
+/// wrong, or line by line when the TOML itself is broken.
+fn salvage_from(contents: &str) -> Option<Config> {
+    let table = toml::from_str::<toml::Table>(contents).ok();
+    let value = |key: &str| -> Option<toml::Value> {
+        if let Some(table) = &table {
+            return table.get(key).cloned();
+        }
+        contents.lines().find_map(|line| {
+            let (name, _) = line.split_once('=')?;
+            if name.trim() != key {
+                return None;
+            }
+            // One `key = value` line is a TOML document of its own.
+            toml::from_str::<toml::Table>(line).ok()?.remove(key)
+        })
+    };
+    let string = |key: &str| value(key)?.as_str().map(str::to_string);
+    Some(Config {
+        canvas_url: string("canvas_url").filter(|u| !u.is_empty())?,
+        api_token: string("api_token").unwrap_or_default(),
+        cache_dir: string("cache_dir"),
+        download_dir: string("download_dir"),
+        as_user_id: value("as_user_id")
+            .and_then(|v| v.as_integer())
+            .and_then(|id| u64::try_from(id).ok()),
+        ..Config::default()
+    })
+}
+
+/// `contents` with the token keys of `config` set (or removed when it has
+/// none).  A file that isn't valid TOML is edited line by line, like
+/// `salvage_from` reads it, so a token saved while the user is fixing a
+/// typo elsewhere isn't lost.
+fn with_tokens(contents: &str, config: &Config) -> String {
+    let expires_at = config
+        .oauth_expires_at
+        .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+    let keys = [
+        ("api_token", Some(config.api_token.as_str())),
+        ("oauth_refresh_token", config.oauth_refresh_token.as_deref()),
+        ("oauth_expires_at", expires_at.as_deref()),
+    ];
+
+    if let Ok(mut doc) = contents.parse::<toml_edit::DocumentMut>() {
+        for (key, value) in keys {
+            match (value, doc.get_mut(key).and_then(toml_edit::Item::as_value_mut)) {
+                // Keep the comment after the old value.
+                (Some(value), Some(old)) => {
+                    let decor = old.decor().clone();
+                    *old = value.into();
+                    *old.decor_mut() = decor;
+                }
+                (Some(value), None) => doc[key] = toml_edit::value(value),
+                (None, _) => {
+                    doc.remove(key);
+                }
+            }
+        }
+        return doc.to_string();
+    }
+
+    let mut lines: Vec<String> = contents.lines().map(str::to_string).collect();
+    for (key, value) in keys {
+        let line = value.map(|v| format!("{key} = {}", toml_edit::Value::from(v)));
+        let found = lines
+            .iter()
+            .position(|l| l.split_once('=').is_some_and(|(name, _)| name.trim() == key));
+        match (found, line) {
+            (Some(i), Some(line)) => lines[i] = line,
+            (Some(i), None) => {
+                lines.remove(i);
+            }
+            // At the top, where it can't land in a table.
+            (None, Some(line)) => lines.insert(0, line),
+            (None, None) => {}
+        }
+    }
+    let mut edited = lines.join("\n");
+    edited.push('\n');
+    edited
+}
+
+// ─── Live reload ─────────────────────────────────────────────────────────────
+
+/// Detects edits to config.toml by polling its modification time, which is
+/// cheap enough to do on every tick of the main loop.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    pub fn new() -> Option<Self> {
+        let path = Config::config_path()?;
+        let modified = mtime(&path);
+        Some(Self { path, modified })
+    }
+
+    /// Returns the re-parsed config if the file changed since the last call.
+    /// A parse error is returned as `Some(Err(..))` so the caller can keep
+    /// running with the previous config.
+    pub fn poll(&mut self) -> Option<Result<Config>> {
+        let modified = mtime(&self.path);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        // A deleted file isn't an edit we can apply.
+        modified?;
+        Some(Config::load_file(&self.path))
+    }
+
+    /// Forget about changes made by the app itself (e.g. `Config::save`).
+    pub fn mark_seen(&mut self) {
+        self.modified = mtime(&self.path);
+    }
+}
+
+fn mtime(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// ─── Resolved paths ──────────────────────────────────────────────────────────
+
+/// Stems of the files `Paths::user_file` names.
+const USER_FILES: [&str; 5] =
+    ["cache", "estimates", "event-links", "archived-announcements", "sync-history"];
+
+/// On-disk locations resolved once at startup from the config (or the
+/// platform defaults when a key is unset).
+#[derive(Debug, Clone)]
+pub struct Paths {
+    pub cache_dir: PathBuf,
+    pub download_dir: PathBuf,
+    /// Masqueraded user, whose data is cached apart from the admin's own.
+    pub as_user_id: Option<u64>,
+    /// Host of `canvas_url`.  Each instance has its own cache and side
+    /// files, so using one never overwrites another's.
+    pub canvas_host: Option<String>,
+}
+
+impl Paths {
+    /// Resolve and validate the cache and download directories.  Both are
+    /// created if missing; an error is returned if either is not writable.
+    pub fn resolve(config: Option<&Config>) -> Result<Self> {
+        let cache_dir = match config.and_then(|c| c.cache_dir.as_deref()) {
+            Some(raw) => expand_path(raw).with_context(|| format!("Invalid cache_dir '{raw}'"))?,
+            None => dirs::cache_dir()
+                .with_context(|| "Could not determine cache directory")?
+                .join("canvas-tui"),
+        };
+        let download_dir = match config.and_then(|c| c.download_dir.as_deref()) {
+            Some(raw) => {
+                expand_path(raw).with_context(|| format!("Invalid download_dir '{raw}'"))?
+            }
+            None => dirs::download_dir()
+                .map(|d| d.join("canvas-tui"))
+                .unwrap_or_else(|| cache_dir.join("downloads")),
+        };
+
+        ensure_writable(&cache_dir)
+            .with_context(|| format!("cache_dir {} is not writable", cache_dir.display()))?;
+        ensure_writable(&download_dir).with_context(|| {
+            format!("download_dir {} is not writable", download_dir.display())
+        })?;
+
+        Ok(Self {
+            cache_dir,
+            download_dir,
+            as_user_id: config.and_then(Config::masquerade_user),
+            canvas_host: config.and_then(|c| {
+                let url = url::Url::parse(c.canvas_url.trim()).ok()?;
+                url.host_str().map(str::to_ascii_lowercase)
+            }),
+        })
+    }
+
+    /// `cache-<host>.json`, or `cache-<host>-as-user-<id>.json` while
+    /// masquerading.
+    pub fn cache_file(&self) -> PathBuf {
+        self.user_file("cache")
+    }
+
+    /// Where versions before per-instance caches kept the cache.
+    pub fn legacy_cache_file(&self) -> PathBuf {
+        self.legacy_user_file("cache")
+    }
+
+    /// `<stem>-<host>.json` in the cache directory, or
+    /// `<stem>-<host>-as-user-<id>.json` while masquerading, so neither
+    /// another instance nor the admin's own account shares the file.
+    fn user_file(&self, stem: &str) -> PathBuf {
+        match &self.canvas_host {
+            Some(host) => self.legacy_user_file(&format!("{stem}-{host}")),
+            None => self.legacy_user_file(stem),
+        }
+    }
+
+    /// `user_file` as named before files were kept per instance.
+    fn legacy_user_file(&self, stem: &str) -> PathBuf {
+        match self.as_user_id {
+            Some(id) => self.cache_dir.join(format!("{stem}-as-user-{id}.json")),
+            None => self.cache_dir.join(format!("{stem}.json")),
+        }
+    }
+
+    /// Each file named by `legacy_user_file`, with where it goes now.
+    pub fn legacy_files(&self) -> Vec<(PathBuf, PathBuf)> {
+        USER_FILES
+            .iter()
+            .map(|stem| (self.legacy_user_file(stem), self.user_file(stem)))
+            .collect()
+    }
+
+    /// Effort estimates entered with `e`.
+    pub fn estimates_file(&self) -> PathBuf {
+        self.user_file("estimates")
+    }
+
+    /// Calendar events linked to undated assignments with `l`.
+    pub fn event_links_file(&self) -> PathBuf {
+        self.user_file("event-links")
+    }
+
+    /// Announcements archived with `x`.
+    pub fn archived_announcements_file(&self) -> PathBuf {
+        self.user_file("archived-announcements")
+    }
+
+    /// How the last few syncs went.
+    pub fn sync_history_file(&self) -> PathBuf {
+        self.user_file("sync-history")
+    }
+
+    /// Where API responses that failed to decode are kept for bug reports.
+    pub fn payload_dir(&self) -> PathBuf {
+        self.cache_dir.join("bad-responses")
+    }
+
+    pub fn log_file(&self) -> PathBuf {
+        self.cache_dir.join("canvas-tui.log")
+    }
+}
+
+/// Expand a leading `~` and any `$VAR` / `${VAR}` references in a path.
+pub fn expand_path(raw: &str) -> Result<PathBuf> {
+    let mut out = String::with_capacity(raw.len());
+    let mut rest = raw;
+
+    if rest == "~" || rest.starts_with("~/") || (cfg!(windows) && rest.starts_with("~\\")) {
+        let home = dirs::home_dir().with_context(|| "Could not determine home directory")?;
+        out.push_str(&home.to_string_lossy());
+        rest = &rest[1..];
+    }
+
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let (name, consumed) = if let Some(braced) = after.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .with_context(|| "Unterminated ${ in path")?;
+            (&braced[..end], end + 2)
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], end)
+        };
+        if name.is_empty() {
+            out.push('$');
+        } else {
+            let value = std::env::var(name)
+                .with_context(|| format!("Environment variable ${name} is not set"))?;
+            out.push_str(&value);
+        }
+        rest = &after[consumed..];
+    }
+    out.push_str(rest);
+
+    Ok(PathBuf::from(out))
+}
+
+/// Create `dir` if needed and confirm a file can be written inside it.
+fn ensure_writable(dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let probe = dir.join(".canvas-tui-write-test");
+    std::fs::write(&probe, b"")?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+// ─── File permissions ────────────────────────────────────────────────────────
+
+/// Write `contents` to `path`, creating the parent directory as 0700 and the
+/// file as 0600 on Unix.  Existing files are tightened to 0600 as well.
+fn write_private(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        create_private_dir(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    // `mode` only applies when the file is created; fix up older files too.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+
+    file.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+    std::fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)
+}
+
+/// The file's permission bits if group or other have any access to it.
+#[cfg(unix)]
+fn insecure_mode(path: &Path) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    let mode = std::fs::metadata(path).ok()?.permissions().mode() & 0o777;
+    (mode & 0o077 != 0).then_some(mode)
+}
+
+#[cfg(not(unix))]
+fn insecure_mode(_path: &Path) -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(unix)]
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn salvage_reads_connection_keys_when_a_value_is_wrong() {
+        let config = salvage_from(
+            "canvas_url = \"https://school.instructure.com\"\n\
+             api_token = \"secret\"\n\
+             as_user_id = 12345\n\
+             refresh_interval_minutes = \"often\"\n\
+             [api]\n\
+             courses_per_page = 50\n",
+        )
+        .unwrap();
+        assert_eq!(config.canvas_url, "https://school.instructure.com");
+        assert_eq!(config.api_token, "secret");
+        assert_eq!(config.as_user_id, Some(12345));
+        assert_eq!(config.refresh_interval_minutes, None);
+    }
+
+    #[test]
+    fn salvage_reads_connection_keys_from_broken_toml() {
+        let config = salvage_from(
+            "canvas_url = 'https://school.instructure.com'\n\
+             api_token = \"secret\"\n\
+             cache_dir = \"/tmp/canvas\"\n\
+             download_dir = \"~/Canvas files\"\n\
+             as_user_id = 12345\n\
+             [broken\n",
+        )
+        .unwrap();
+        assert_eq!(config.canvas_url, "https://school.instructure.com");
+        assert_eq!(config.api_token, "secret");
+        assert_eq!(config.cache_dir.as_deref(), Some("/tmp/canvas"));
+        assert_eq!(config.download_dir.as_deref(), Some("~/Canvas files"));
+        assert_eq!(config.as_user_id, Some(12345));
+    }
+
+    #[test]
+    fn salvage_needs_a_canvas_url() {
+        assert!(salvage_from("api_token = \"secret\"\n[broken").is_none());
+        assert!(salvage_from("canvas_url = \"\"").is_none());
+    }
+
+    fn with_new_tokens(refresh: Option<&str>) -> Config {
+        Config {
+            canvas_url: "https://stale.instructure.com".into(),
+            api_token: "new-token".into(),
+            oauth_refresh_token: refresh.map(str::to_string),
+            oauth_expires_at: refresh.map(|_| "2025-04-01T12:00:00Z".parse().unwrap()),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn saving_tokens_keeps_the_rest_of_the_file() {
+        let file = "# my school\n\
+                    canvas_url = \"https://school.instructure.com\"\n\
+                    api_token = \"old\" # from the profile page\n\
+                    as_user_id = 42\n\
+                    \n\
+                    [api]\n\
+                    courses_per_page = 50\n";
+        let edited = with_tokens(file, &with_new_tokens(Some("refresh")));
+        assert!(edited.starts_with("# my school\ncanvas_url = \"https://school.instructure.com\"\n"));
+        assert!(edited.contains("api_token = \"new-token\" # from the profile page\n"));
+        let config: Config = toml::from_str(&edited).unwrap();
+        assert_eq!(config.canvas_url, "https://school.instructure.com");
+        assert_eq!(config.api_token, "new-token");
+        assert_eq!(config.as_user_id, Some(42));
+        assert_eq!(config.api.unwrap().courses_per_page, Some(50));
+        assert_eq!(config.oauth_refresh_token.as_deref(), Some("refresh"));
+        assert_eq!(config.oauth_expires_at, Some("2025-04-01T12:00:00Z".parse().unwrap()));
+    }
+
+    #[test]
+    fn saving_a_plain_token_drops_old_oauth_keys() {
+        let file = "canvas_url = \"https://school.instructure.com\"\n\
+                    api_token = \"old\"\n\
+                    oauth_refresh_token = \"refresh\"\n\
+                    oauth_expires_at = \"2025-04-01T12:00:00Z\"\n";
+        let edited = with_tokens(file, &with_new_tokens(None));
+        assert_eq!(
+            edited,
+            "canvas_url = \"https://school.instructure.com\"\napi_token = \"new-token\"\n"
+        );
+    }
+
+    #[test]
+    fn saving_tokens_into_broken_toml_edits_only_their_lines() {
+        let file = "canvas_url = \"https://school.instructure.com\"\n\
+                    api_token = \"old\"\n\
+                    download_dir = \"~/Canvas\"\n\
+                    [broken\n";
+        let edited = with_tokens(file, &with_new_tokens(Some("re\"fresh")));
+        assert_eq!(
+            edited,
+            "oauth_expires_at = \"2025-04-01T12:00:00Z\"\n\
+             oauth_refresh_token = 're\"fresh'\n\
+             canvas_url = \"https://school.instructure.com\"\n\
+             api_token = \"new-token\"\n\
+             download_dir = \"~/Canvas\"\n\
+             [broken\n"
+        );
+        let salvaged = salvage_from(&edited).unwrap();
+        assert_eq!(salvaged.api_token, "new-token");
+    }
+
+    #[cfg(unix)]
+    fn mode(path: &Path) -> u32 {
+        std::fs::metadata(path).unwrap().permissions().mode() & 0o777
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_private_creates_owner_only_file_and_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("canvas-tui");
+        let path = dir.join("config.toml");
+        write_private(&path, "api_token = \"secret\"").unwrap();
+
+        assert_eq!(mode(&dir), 0o700);
+        assert_eq!(mode(&path), 0o600);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "api_token = \"secret\"");
+        assert_eq!(insecure_mode(&path), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_private_tightens_an_existing_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(&path, "old").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        assert_eq!(insecure_mode(&path), Some(0o644));
+
+        write_private(&path, "new").unwrap();
+        assert_eq!(mode(&path), 0o600);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn insecure_mode_flags_any_group_or_other_access() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("config.toml");
+        std::fs::write(&path, "").unwrap();
+        for (bits, flagged) in [
+            (0o600, false),
+            (0o400, false),
+            (0o640, true),
+            (0o604, true),
+            (0o620, true),
+            (0o602, true),
+        ] {
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(bits)).unwrap();
+            assert_eq!(insecure_mode(&path), flagged.then_some(bits), "mode {bits:o}");
+        }
+        assert_eq!(insecure_mode(&tmp.path().join("missing.toml")), None);
+    }
+}
@@ -0,0 +1,30 @@
+//! The Canvas LMS client behind the canvas-tui terminal app, for use in
+//! other programs such as status bar widgets.
+//!
+//! [`config::Config::load`] reads the same `config.toml` as the app, and
+//! [`config::Config::client`] turns it into an [`api::CanvasClient`]; the
+//! responses are the types in [`models`].  [`cache`] reads and writes the
+//! app's offline cache, so a widget can show what the app last synced
+//! without making requests.
+//!
+//! ```no_run
+//! # async fn run() -> anyhow::Result<()> {
+//! let config = canvas_tui::config::Config::load()?;
+//! let client = config.client()?;
+//! for course in client.list_courses().await? {
+//!     println!("{}", course.name.unwrap_or_default());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod api;
+pub mod cache;
+pub mod config;
+pub mod models;
+pub mod version;
+// Test helpers shared by the library's and the binary's tests; each
+// uses only part of them.
+#[cfg(test)]
+#[allow(dead_code)]
+mod test_server;
@@ -0,0 +1,116 @@
+use anyhow::{bail, Context, Result};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use url::Url;
+
+use crate::api::oauth;
+use crate::config::{Config, DEFAULT_OAUTH_REDIRECT_PORT};
+
+/// How long to wait for the user to approve access in the browser.
+const APPROVAL_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// `canvas-tui --login`: sign in through Canvas's OAuth2 authorization-code
+/// flow and save the tokens, for institutions where students can't generate
+/// access tokens.  Needs `canvas_url`, `oauth_client_id` and
+/// `oauth_client_secret` in the config.
+pub async fn run() -> Result<()> {
+    let mut config = Config::load()?;
+    let Some(key) = config.developer_key() else {
+        bail!(
+            "Set oauth_client_id and oauth_client_secret in config.toml first \
+             (ask your Canvas admin for a developer key)"
+        );
+    };
+    let base_url = Url::parse(&config.canvas_url)
+        .with_context(|| format!("Invalid Canvas URL: {}", config.canvas_url))?;
+
+    let port = config.oauth_redirect_port.unwrap_or(DEFAULT_OAUTH_REDIRECT_PORT);
+    let redirect_uri = format!("http://localhost:{port}/callback");
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .await
+        .with_context(|| format!("Could not listen on port {port} for the login redirect"))?;
+
+    let state = format!("{:016x}", RandomState::new().hash_one(std::time::SystemTime::now()));
+    let authorize = oauth::authorize_url(&base_url, &key, &redirect_uri, &state);
+    println!("Opening Canvas in your browser to approve access…");
+    println!("If it doesn't open, visit:");
+    println!("  {authorize}");
+    let _ = crate::tui::open_in_browser(authorize.as_str());
+
+    let code = tokio::time::timeout(APPROVAL_TIMEOUT, wait_for_code(&listener, &state))
+        .await
+        .context("Timed out waiting for the browser to come back")??;
+
+    let client = config.client()?;
+    let tokens = client
+        .exchange_oauth_code(&key, &redirect_uri, &code)
+        .await
+        .context("Canvas did not accept the login")?;
+    config.set_oauth_tokens(tokens);
+
+    let user = config.client()?.get_self().await?;
+    config.save_tokens()?;
+    println!(
+        "Logged in as {}. Tokens saved; canvas-tui renews them automatically.",
+        user.name.as_deref().unwrap_or("you")
+    );
+    Ok(())
+}
+
+/// Accept connections until the authorize redirect arrives, and return its
+/// `code`.  Other requests (a browser asking for a favicon) get a 404.
+async fn wait_for_code(listener: &TcpListener, state: &str) -> Result<String> {
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let mut buf = vec![0; 8192];
+        let n = stream.read(&mut buf).await?;
+        let request = String::from_utf8_lossy(&buf[..n]);
+        let target = request
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix("GET "))
+            .and_then(|rest| rest.split_whitespace().next())
+            .unwrap_or("");
+        let Ok(url) = Url::parse("http://localhost").and_then(|base| base.join(target)) else {
+            respond(&mut stream, "400 Bad Request", "Bad request.").await;
+            continue;
+        };
+        if url.path() != "/callback" {
+            respond(&mut stream, "404 Not Found", "Not found.").await;
+            continue;
+        }
+
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.into_owned())
+        };
+        if param("state").as_deref() != Some(state) {
+            respond(&mut stream, "400 Bad Request", "Login state mismatch; try again.").await;
+            bail!("Login redirect had the wrong state; try again");
+        }
+        if let Some(error) = param("error") {
+            respond(&mut stream, "200 OK", "Access was not granted. You can close this tab.").await;
+            bail!("Canvas login failed: {error}");
+        }
+        let Some(code) = param("code") else {
+            respond(&mut stream, "400 Bad Request", "No authorization code.").await;
+            bail!("Login redirect had no authorization code");
+        };
+        respond(&mut stream, "200 OK", "Logged in to canvas-tui. You can close this tab.").await;
+        return Ok(code);
+    }
+}
+
+async fn respond(stream: &mut tokio::net::TcpStream, status: &str, message: &str) {
+    let body = format!("<!doctype html><title>canvas-tui</title><p>{message}</p>");
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+}
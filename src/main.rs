@@ -1,39 +1,84 @@
-mod api;
-mod cache;
-mod config;
-mod models;
+mod login;
 mod tui;
+mod wizard;
+// Test helpers shared by the library's and the binary's tests; each
+// uses only part of them.
+#[cfg(test)]
+#[allow(dead_code)]
+mod test_server;
 
-use anyhow::Result;
+use canvas_tui::{api, cache, config, models, version};
+
+use anyhow::{Context, Result};
 use crossterm::{
-    event::{Event, KeyEvent},
+    event::KeyEvent,
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io::{self, Write};
+use std::io::{self, IsTerminal};
 use std::time::Duration;
 
-use api::CanvasClient;
-use config::Config;
-use tui::{App, SubmissionState};
+use config::{Config, ConfigWatcher, Paths};
+use tokio::sync::oneshot;
+use tui::{App, SubmitResult, Tab};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args: Vec<String> = std::env::args().collect();
     if args.iter().any(|a| a == "--init") {
-        let path = Config::generate_default()?;
-        println!("Generated config file at: {}", path.display());
-        println!("Edit it with your Canvas URL and API token, then run canvas-tui.");
+        // Without a terminal to prompt on, fall back to a template to edit.
+        if !io::stdin().is_terminal() || args.iter().any(|a| a == "--template") {
+            let path = Config::generate_default()?;
+            println!("Generated config file at: {}", path.display());
+            println!("Edit it with your Canvas URL and API token, then run canvas-tui.");
+        } else {
+            wizard::run().await?;
+            println!("Run canvas-tui to start.");
+        }
+        return Ok(());
+    }
+
+    if args.iter().any(|a| a == "--login") {
+        login::run().await?;
+        return Ok(());
+    }
+
+    if args.iter().any(|a| a == "--version" || a == "-V") {
+        println!("{}", version::long_version());
+        return Ok(());
+    }
+
+    if args.iter().any(|a| a == "--color-test") {
+        let configured = Config::load().ok().and_then(|c| c.color_mode);
+        let mode = configured.unwrap_or_else(tui::ui::theme::detect_color_mode);
+        tui::ui::theme::print_color_test(mode, configured.is_none());
+        return Ok(());
+    }
+
+    if let Some(out) = report_arg(&args) {
+        if let Err(e) = write_report(&args, &out) {
+            eprintln!("Error: {e:#}");
+            std::process::exit(1);
+        }
         return Ok(());
     }
 
     if args.iter().any(|a| a == "--help" || a == "-h") {
         println!("canvas-tui — A terminal UI for Canvas LMS");
+        println!("{}", version::long_version());
         println!();
         println!("USAGE:");
-        println!("  canvas-tui           Start the TUI");
-        println!("  canvas-tui --init    Generate a default config file");
+        println!("  canvas-tui           Start the TUI (runs setup on first launch)");
+        println!("  canvas-tui --init    Run interactive setup (add --template for a file to edit)");
+        println!("  canvas-tui --login   Sign in through Canvas OAuth2 (needs a developer key in the config)");
+        println!("  canvas-tui --as-user <id>  Admins: masquerade as a user (cached separately)");
+        println!("  canvas-tui --course <name>  Open on a course (unique part of its name or code)");
+        println!("  canvas-tui --tab <name>  Open on a tab: dashboard, courses, schedule, announcements");
+        println!("  canvas-tui --assignment <id>  Open the Schedule on an assignment (Canvas id)");
+        println!("  canvas-tui --color-test  Show the colors in use and exit");
+        println!("  canvas-tui --report <file>  Write a Markdown report of the synced data (- for stdout)");
+        println!("  canvas-tui --version Print version, commit and build date");
         println!();
         println!("CONFIG:");
         println!("  File: ~/.config/canvas-tui/config.toml");
@@ -41,15 +86,63 @@ async fn main() -> Result<()> {
         println!();
         println!("KEYBINDINGS:");
         println!("  Tab / Shift+Tab   Switch tabs");
-        println!("  1-5               Jump to tab");
+        for tab in Tab::ALL {
+            println!("  {:<18}Jump to {}", tab.number(), tab.title());
+        }
         println!("  j / k / Up / Down Navigate lists");
-        println!("  g / G             Jump to top / bottom");
+        println!("  gg / Home         Jump to top (also g alone, unless vim_gg is set)");
+        println!("  ge / G / End      Jump to bottom");
+        println!("  <count> j/k/G/gg  Repeat a move, or go to row <count>");
+        println!("                    (counts start with a digit above {})", Tab::ALL.len());
         println!("  q / Ctrl+C        Quit");
         return Ok(());
     }
 
-    // Try to load config; if it fails we may still have cache to show.
-    let config = Config::load();
+    let start = match start_args(&args) {
+        Ok(start) => start,
+        Err(e) => {
+            eprintln!("Error: {e:#}");
+            std::process::exit(1);
+        }
+    };
+
+    // First launch (no config file, no env vars): set up interactively
+    // before the TUI takes over the terminal.
+    let mut config_error = None;
+    let mut config = match Config::load() {
+        Ok(cfg) => cfg,
+        Err(_) if !Config::file_exists() => wizard::run().await?,
+        // A file that doesn't parse still shows the cache; it is reloaded
+        // live once fixed.
+        Err(e) => match Config::salvage() {
+            Some(cfg) => {
+                config_error = Some(format!("{e:#}"));
+                cfg
+            }
+            None => {
+                eprintln!("Error: {e:#}");
+                std::process::exit(1);
+            }
+        },
+    };
+    match as_user_arg(&args) {
+        Ok(id) => config.as_user_override = id,
+        Err(e) => {
+            eprintln!("Error: {e:#}");
+            std::process::exit(1);
+        }
+    }
+
+    let paths = match Paths::resolve(Some(&config)) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("Error: {e:#}");
+            eprintln!("Fix cache_dir / download_dir in your config.toml and try again.");
+            std::process::exit(1);
+        }
+    };
+
+    init_logging(&paths);
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -57,19 +150,150 @@ async fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let result = run_app(&mut terminal, config).await;
+    let result = run_app(&mut terminal, config, config_error, paths, start).await;
 
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
-    if let Err(e) = result {
-        eprintln!("Error: {e:#}");
+    match result {
+        Err(e) => eprintln!("Error: {e:#}"),
+        Ok(exit) => {
+            if let Some(submission) = exit.submission {
+                wait_for_submission(submission).await;
+            }
+            // Only for a person to read; not mixed into piped output.
+            if let Some(summary) = exit.summary.filter(|_| io::stdout().is_terminal()) {
+                print!("{summary}");
+            }
+        }
     }
 
     Ok(())
 }
 
+/// The user id from `--as-user <id>` or `--as-user=<id>`, if given.
+fn as_user_arg(args: &[String]) -> Result<Option<u64>> {
+    let value = args.iter().enumerate().find_map(|(i, a)| {
+        if a == "--as-user" {
+            Some(args.get(i + 1).map(String::as_str).unwrap_or(""))
+        } else {
+            a.strip_prefix("--as-user=")
+        }
+    });
+    value
+        .map(|v| {
+            v.parse()
+                .with_context(|| format!("--as-user needs a numeric Canvas user id, got '{v}'"))
+        })
+        .transpose()
+}
+
+/// Where the TUI opens: `--course`, `--tab` and `--assignment`.
+struct StartAt {
+    course: Option<String>,
+    tab: Option<Tab>,
+    assignment: Option<u64>,
+}
+
+fn start_args(args: &[String]) -> Result<StartAt> {
+    let value = |flag: &str| {
+        let prefix = format!("{flag}=");
+        args.iter().enumerate().find_map(|(i, a)| {
+            if a == flag {
+                Some(args.get(i + 1).map(String::as_str).unwrap_or(""))
+            } else {
+                a.strip_prefix(prefix.as_str())
+            }
+        })
+    };
+    let tab = value("--tab")
+        .map(|v| {
+            Tab::from_name(v).with_context(|| {
+                format!("--tab needs one of dashboard, courses, schedule, announcements, got '{v}'")
+            })
+        })
+        .transpose()?;
+    let assignment = value("--assignment")
+        .map(|v| {
+            v.parse().with_context(|| {
+                format!("--assignment needs a numeric Canvas assignment id, got '{v}'")
+            })
+        })
+        .transpose()?;
+    Ok(StartAt { course: course_arg(args), tab, assignment })
+}
+
+/// The query from `--course <name>` or `--course=<name>`, if given.
+fn course_arg(args: &[String]) -> Option<String> {
+    args.iter().enumerate().find_map(|(i, a)| {
+        if a == "--course" {
+            args.get(i + 1).cloned()
+        } else {
+            a.strip_prefix("--course=").map(str::to_string)
+        }
+    })
+}
+
+/// The output path from `--report <file>` or `--report=<file>`, if given.
+fn report_arg(args: &[String]) -> Option<String> {
+    args.iter().enumerate().find_map(|(i, a)| {
+        if a == "--report" {
+            Some(args.get(i + 1).cloned().unwrap_or_default())
+        } else {
+            a.strip_prefix("--report=").map(str::to_string)
+        }
+    })
+}
+
+/// Write the Markdown report of the cached data (that of `--as-user`, if
+/// given) to `out`, or to stdout for `-`.
+fn write_report(args: &[String], out: &str) -> Result<()> {
+    if out.is_empty() {
+        anyhow::bail!("--report needs a file to write, or - for stdout");
+    }
+    let mut config = Config::load()?;
+    config.as_user_override = as_user_arg(args)?;
+    let paths = Paths::resolve(Some(&config))?;
+    let cache = cache::load_cache(&paths)
+        .context("no synced data yet; start canvas-tui once to sync, then try again")?;
+    if let Some(host) = cache.foreign_host(paths.canvas_host.as_deref()) {
+        anyhow::bail!("the cache belongs to {host}; start canvas-tui once to sync, then try again");
+    }
+    let report = tui::report::markdown_report(
+        &cache.courses,
+        &cache.assignments,
+        Some(cache.cached_at),
+        chrono::Utc::now(),
+    );
+    if out == "-" {
+        print!("{report}");
+    } else {
+        std::fs::write(out, report).with_context(|| format!("writing {out}"))?;
+        println!("Report written to {out}");
+    }
+    Ok(())
+}
+
+/// Send `tracing` output to a log file next to the cache (the terminal is
+/// owned by the TUI).  The file is truncated each run and starts with the
+/// build info.  Set `CANVAS_TUI_LOG=debug` for more detail.
+fn init_logging(paths: &Paths) {
+    let Ok(file) = std::fs::File::create(paths.log_file()) else {
+        return;
+    };
+    let level = std::env::var("CANVAS_TUI_LOG")
+        .ok()
+        .and_then(|v| v.parse::<tracing::Level>().ok())
+        .unwrap_or(tracing::Level::INFO);
+    tracing_subscriber::fmt()
+        .with_writer(std::sync::Mutex::new(file))
+        .with_ansi(false)
+        .with_max_level(level)
+        .init();
+    tracing::info!("{}", version::long_version());
+}
+
 /// Prompt the user for their Canvas API token outside the TUI (raw mode
 /// suspended).  Returns the trimmed token string.
 fn prompt_api_token(
@@ -86,12 +310,7 @@ fn prompt_api_token(
     println!("║   Account → Settings → New Access Token             ║");
     println!("╚══════════════════════════════════════════════════════╝");
     println!();
-    print!("Paste your API token: ");
-    io::stdout().flush()?;
-
-    let mut token = String::new();
-    io::stdin().read_line(&mut token)?;
-    let token = token.trim().to_string();
+    let token = wizard::read_hidden("Paste your API token (input is hidden): ")?;
 
     // Restore the TUI.
     enable_raw_mode()?;
@@ -101,84 +320,106 @@ fn prompt_api_token(
     Ok(token)
 }
 
-/// Prompt for the Canvas base URL outside the TUI.
-fn prompt_canvas_url(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-) -> Result<String> {
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
-
-    println!();
-    println!("╔══════════════════════════════════════════════════════╗");
-    println!("║   No configuration found.                           ║");
-    println!("║   Let's set up your Canvas connection.              ║");
-    println!("╚══════════════════════════════════════════════════════╝");
-    println!();
-    print!("Canvas URL (e.g. https://school.instructure.com): ");
-    io::stdout().flush()?;
+/// How long quitting waits for a submission that is still being sent.
+const QUIT_GRACE: Duration = Duration::from_secs(3);
+
+/// After quitting mid-submission (and leaving the alternate screen), give
+/// the request a few seconds to finish and say how it went.
+async fn wait_for_submission(submission: oneshot::Receiver<SubmitResult>) {
+    println!(
+        "Waiting up to {}s for the submission to finish…",
+        QUIT_GRACE.as_secs()
+    );
+    match tokio::time::timeout(QUIT_GRACE, submission).await {
+        Ok(Ok(result)) if result.receipt.as_ref().is_some_and(|r| r.attempt_unchanged) => {
+            eprintln!("{}", result.message)
+        }
+        Ok(Ok(result)) if result.success => println!("Submission successful."),
+        Ok(Ok(result)) => eprintln!("{}", result.message),
+        Ok(Err(_)) => eprintln!("The submission ended without a result; check Canvas."),
+        Err(_) => eprintln!(
+            "The submission hasn't finished and may or may not complete; check Canvas."
+        ),
+    }
+}
 
-    let mut url = String::new();
-    io::stdin().read_line(&mut url)?;
-    let url = url.trim().to_string();
+/// How long the first start after upgrading waits for the profile that
+/// says whether the old, shared cache is this user's.
+const LEGACY_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
 
-    enable_raw_mode()?;
-    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
-    terminal.clear()?;
-
-    Ok(url)
+/// What is left to do once the TUI has closed and the terminal is back.
+struct Exit {
+    /// The submission still being sent, if the user quit during one.
+    submission: Option<oneshot::Receiver<SubmitResult>>,
+    /// The `exit_summary` text, when it is on.
+    summary: Option<String>,
 }
 
+/// Run the TUI until quit.  `config_error` is why config.toml didn't
+/// load, when `config` was only salvaged from it.
 async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    config_result: Result<Config>,
-) -> Result<()> {
-    // Resolve config — if missing, prompt interactively for URL + token.
-    let config = match config_result {
-        Ok(cfg) => cfg,
-        Err(_) => {
-            // No config at all. If we have cache, show it while we prompt.
-            if let Some(cached) = cache::load_cache() {
-                // We need a placeholder client; we'll replace it after prompting.
-                let url = prompt_canvas_url(terminal)?;
-                let token = prompt_api_token(terminal)?;
-                let cfg = Config {
-                    canvas_url: url,
-                    api_token: token,
-                };
-                let _ = cfg.save();
-
-                let client = CanvasClient::new(&cfg.canvas_url, &cfg.api_token)?;
-                let mut app = App::new(client);
-                app.load_from_cache(cached);
-                app.start_fetch();
-                app.status_message = "Config saved — syncing with new token…".into();
-                return run_main_loop(terminal, app, cfg).await;
+    config: Config,
+    config_error: Option<String>,
+    paths: Paths,
+    start: StartAt,
+) -> Result<Exit> {
+    let client = config.client()?.with_payload_dir(paths.payload_dir());
+    let mut cached = cache::load_cache(&paths);
+    let mut legacy_note = None;
+    if cached.is_none() {
+        let check = cache::adopt_untagged_legacy_cache(&paths, &client);
+        match tokio::time::timeout(LEGACY_CHECK_TIMEOUT, check).await {
+            Ok(Ok(cache::LegacyCache::Adopted)) => cached = cache::load_cache(&paths),
+            Ok(Ok(cache::LegacyCache::NotOurs)) => {
+                legacy_note = Some("The old cache.json is another user's — starting fresh.")
             }
-
-            // No cache either — must prompt.
-            let url = prompt_canvas_url(terminal)?;
-            let token = prompt_api_token(terminal)?;
-            let cfg = Config {
-                canvas_url: url,
-                api_token: token,
-            };
-            let _ = cfg.save();
-            cfg
+            Ok(Ok(cache::LegacyCache::None)) => {}
+            Ok(Err(e)) => tracing::warn!("checking whose the old cache is: {e}"),
+            Err(_) => tracing::warn!("checking whose the old cache is: timed out"),
         }
-    };
-
-    let client = CanvasClient::new(&config.canvas_url, &config.api_token)?;
-    let mut app = App::new(client);
+    }
+    let foreign = cached
+        .as_ref()
+        .and_then(|c| c.foreign_host(paths.canvas_host.as_deref()))
+        .map(str::to_string);
+    let mut app = App::new(client, paths);
+    app.apply_config(&config);
+    if let Some(tab) = start.tab {
+        app.active_tab = tab;
+    }
+    app.pending_course = start.course;
+    app.pending_assignment = start.assignment;
 
     // Show cached data instantly, then kick off a background sync.
-    if let Some(cached) = cache::load_cache() {
+    if let Some(host) = foreign {
+        tracing::warn!("cache belongs to {host}; ignoring it");
+        app.start_fetch();
+        app.status_message = format!("Cache belongs to {host} — starting fresh.");
+    } else if let Some(cached) = cached {
         app.load_from_cache(cached);
         app.start_fetch();
         app.status_message = "Showing cached data — syncing in background…".into();
     } else {
+        app.first_seen = cache::load_first_seen(&app.paths);
         app.start_fetch();
+        if let Some(note) = legacy_note {
+            app.status_message = note.into();
+        }
+    }
+
+    app.find_recovered_drafts();
+
+    if let Some(warning) = Config::permission_warning() {
+        tracing::warn!("{warning}");
+        app.status_message = format!("Warning: {warning}");
     }
+    if let Some(e) = config_error {
+        tracing::warn!("config not loaded: {e}");
+        app.status_message = format!("Config not loaded: {e} — using defaults until it's fixed.");
+    }
+    app.select_pending_course();
+    app.select_pending_assignment();
 
     run_main_loop(terminal, app, config).await
 }
@@ -187,68 +428,61 @@ async fn run_main_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     mut app: App,
     mut config: Config,
-) -> Result<()> {
+) -> Result<Exit> {
     terminal.draw(|f| tui::ui::render(f, &mut app))?;
+    let mut config_watcher = ConfigWatcher::new();
 
     loop {
         app.frame_count = app.frame_count.wrapping_add(1);
         terminal.draw(|f| tui::ui::render(f, &mut app))?;
 
-        if let Some(event) = tui::event::poll_event(Duration::from_millis(100))? {
-            if let Event::Key(KeyEvent {
-                code, modifiers, ..
-            }) = event
-            {
-                tui::event::handle_key(&mut app, code, modifiers);
-            }
+        if let Some(KeyEvent {
+            code, modifiers, ..
+        }) = tui::event::poll_event(Duration::from_millis(100))?.and_then(tui::event::key_press)
+        {
+            tui::event::handle_key(&mut app, code, modifiers);
         }
+        tui::event::expire_chord(&mut app);
 
         if !app.running {
             break;
         }
 
-        // ── $EDITOR launch (text-entry submissions) ───────────────────
-        if app.launch_editor {
-            app.launch_editor = false;
-
-            let tmp_path = std::env::temp_dir().join("canvas-tui-submission.txt");
-
-            // Suspend the TUI so the editor owns the terminal.
-            disable_raw_mode()?;
-            execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-            terminal.show_cursor()?;
-
-            let editor = std::env::var("VISUAL")
-                .or_else(|_| std::env::var("EDITOR"))
-                .unwrap_or_else(|_| "nano".into());
-
-            let _ = std::process::Command::new(&editor)
-                .arg(&tmp_path)
-                .status();
-
-            let content = std::fs::read_to_string(&tmp_path).unwrap_or_default();
-            let _ = std::fs::remove_file(&tmp_path);
-
-            // Restore the TUI.
-            enable_raw_mode()?;
-            execute!(terminal.backend_mut(), EnterAlternateScreen)?;
-            terminal.clear()?;
+        // ── External programs (editor, pager) ──────────────────────────
+        if let Some(request) = app.external_command.take() {
+            let result = request
+                .command()
+                .and_then(|cmd| tui::external::suspend_and_run(terminal, cmd));
+            app.finish_external_command(request, result);
+        }
 
-            if content.trim().is_empty() {
-                app.submission_state = SubmissionState::TypePicker;
-                app.status_message =
-                    "Editor closed with no content — submission cancelled.".into();
-            } else {
-                app.submission_input = content;
-                app.submission_state = SubmissionState::TextPreview;
+        // ── Clipboard (copy mode) ───────────────────────────────────────
+        if let Some(text) = app.clipboard.take() {
+            if let Err(e) = tui::clipboard::copy_osc52(terminal.backend_mut(), &text) {
+                app.status_message = format!("Couldn't copy: {e}");
             }
         }
 
         // Apply completed fetch/submission/course-detail results without blocking.
-        app.poll_fetch_result();
+        if app.poll_fetch_result() {
+            app.select_pending_course();
+            app.select_pending_assignment();
+        }
         app.poll_submission_result();
+        app.poll_submission_group();
+        app.poll_discussion_entries();
+        app.poll_older_announcements();
+        app.poll_profile_disk();
+        app.poll_download();
+        app.poll_bulk_download();
+        app.poll_open_handler();
         app.poll_course_pages();
         app.poll_course_detail();
+        app.poll_event_form();
+        app.ensure_full_description();
+        app.poll_full_description();
+        app.poll_load_all();
+        app.poll_calendar_assignment();
 
         // ── Re-authentication prompt ──────────────────────────────────
         if app.needs_reauth {
@@ -260,12 +494,20 @@ async fn run_main_loop(
                     "No token entered — still using cached data. Press r to retry.".into();
             } else {
                 config.api_token = token;
-                let _ = config.save();
+                let saved = config.save_tokens();
+                if saved.is_ok() {
+                    if let Some(w) = config_watcher.as_mut() {
+                        w.mark_seen();
+                    }
+                }
 
-                match CanvasClient::new(&config.canvas_url, &config.api_token) {
+                match config.client() {
                     Ok(new_client) => {
-                        app.client = new_client;
-                        app.status_message = "Token updated — syncing…".into();
+                        app.client = new_client.with_payload_dir(app.paths.payload_dir());
+                        app.status_message = match saved {
+                            Ok(()) => "Token updated — syncing…".into(),
+                            Err(e) => format!("Token updated but not saved: {e:#} — syncing…"),
+                        };
                         app.start_fetch();
                     }
                     Err(e) => {
@@ -275,11 +517,57 @@ async fn run_main_loop(
             }
         }
 
-        if app.needs_refresh {
-            app.needs_refresh = false;
-            app.start_fetch();
+        // ── OAuth token refreshed by the client ──────────────────────
+        if let Some(tokens) = app.client.take_refreshed_tokens() {
+            config.set_oauth_tokens(tokens);
+            match config.save_tokens() {
+                Ok(()) => {
+                    if let Some(w) = config_watcher.as_mut() {
+                        w.mark_seen();
+                    }
+                }
+                Err(e) => tracing::warn!("saving refreshed OAuth tokens: {e:#}"),
+            }
+        }
+
+        // ── Config live-reload ────────────────────────────────────────
+        if let Some(reloaded) = config_watcher.as_mut().and_then(|w| w.poll()) {
+            match reloaded {
+                Ok(new_config) => {
+                    let pending = config.restart_required_changes(&new_config);
+                    config.apply_live_settings(&new_config);
+                    app.apply_config(&config);
+                    app.status_message = if pending.is_empty() {
+                        "Config reloaded.".into()
+                    } else {
+                        format!("Config reloaded — changed {} requires restart.", pending.join(", "))
+                    };
+                }
+                Err(e) => {
+                    app.status_message =
+                        format!("Config not reloaded: {e:#} — keeping previous settings.");
+                }
+            }
+        }
+
+        // ── Periodic background sync ──────────────────────────────────
+        if let Some(mins) = config.refresh_interval_minutes.filter(|m| *m > 0) {
+            let interval = Duration::from_secs(mins * 60);
+            let due = app
+                .last_fetch_started
+                .is_none_or(|t| t.elapsed() >= interval);
+            if due && !app.loading {
+                app.needs_refresh = true;
+            }
+        }
+
+        app.start_requested_fetch();
+
+        // ── Retry of sections a sync couldn't fetch ───────────────────
+        if app.stale_retry_due() {
+            app.retry_stale_sections();
         }
     }
 
-    Ok(())
+    Ok(Exit { submission: app.submission_rx.take(), summary: app.exit_summary() })
 }
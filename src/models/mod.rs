@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 pub struct Course {
     pub id: u64,
     pub name: Option<String>,
+    /// The name the course was created with, when `name` is my nickname.
+    pub original_name: Option<String>,
     pub course_code: Option<String>,
     pub workflow_state: Option<String>,
     pub start_at: Option<DateTime<Utc>>,
@@ -14,6 +16,49 @@ pub struct Course {
     pub enrollments: Option<Vec<Enrollment>>,
     pub total_students: Option<u64>,
     pub term: Option<Term>,
+    /// The student information system's id, e.g. "2026FA-PHYS-2021-001";
+    /// Canvas only sends it to some users.
+    pub sis_course_id: Option<String>,
+}
+
+impl Course {
+    fn student_enrollment(&self) -> Option<&Enrollment> {
+        self.enrollments.as_deref()?.iter().find(|e| {
+            matches!(e.enrollment_type.as_deref(), Some("student" | "StudentEnrollment"))
+        })
+    }
+
+    /// Whether I teach, TA or design this course rather than take it: a
+    /// teacher, TA or designer enrollment and no student one.
+    pub fn is_staff(&self) -> bool {
+        let staff = self.enrollments.as_deref().unwrap_or(&[]).iter().any(|e| {
+            matches!(
+                e.enrollment_type.as_deref(),
+                Some(
+                    "teacher" | "TeacherEnrollment" | "ta" | "TaEnrollment" | "designer"
+                        | "DesignerEnrollment"
+                )
+            )
+        });
+        staff && self.student_enrollment().is_none()
+    }
+
+    /// My current score in percent, if Canvas shares it.
+    pub fn current_score(&self) -> Option<f64> {
+        self.student_enrollment()?.computed_current_score
+    }
+
+    /// My current grade in the course, e.g. "93.2% A-", "93.2%" or "A-".
+    /// `None` for non-student enrollments or when grades are hidden.
+    pub fn current_grade(&self) -> Option<String> {
+        let enrollment = self.student_enrollment()?;
+        match (enrollment.computed_current_score, enrollment.computed_current_grade.as_deref()) {
+            (Some(score), Some(grade)) => Some(format!("{score:.1}% {grade}")),
+            (Some(score), None) => Some(format!("{score:.1}%")),
+            (None, Some(grade)) => Some(grade.to_string()),
+            (None, None) => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,12 +91,95 @@ pub struct Assignment {
     pub lock_at: Option<DateTime<Utc>>,
     pub unlock_at: Option<DateTime<Utc>>,
     pub points_possible: Option<f64>,
+    /// "points", "pass_fail", "percent", "letter_grade", "gpa_scale" or
+    /// "not_graded".
+    pub grading_type: Option<String>,
+    /// Set for group assignments: submissions count for the whole group.
+    pub group_category_id: Option<u64>,
+    /// The linked topic for `discussion_topic` assignments.
+    pub discussion_topic: Option<DiscussionTopicRef>,
     pub course_id: Option<u64>,
     pub submission_types: Option<Vec<String>>,
     pub has_submitted_submissions: Option<bool>,
     pub html_url: Option<String>,
     pub published: Option<bool>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
     pub submission: Option<Submission>,
+    /// Students review each other's submissions (see `PeerReview`).
+    pub peer_reviews: Option<bool>,
+    /// Reviews each student is assigned, with automatic peer reviews.
+    pub peer_review_count: Option<u64>,
+    /// When reviews are due, if after `due_at` (automatic peer reviews only).
+    pub peer_reviews_assign_at: Option<DateTime<Utc>>,
+    /// Extensions uploads are limited to, without the dot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_extensions: Option<Vec<String>>,
+    /// Most submission attempts; -1 (or absent) for unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_attempts: Option<i64>,
+    /// Submissions go through Turnitin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turnitin_enabled: Option<bool>,
+}
+
+impl Assignment {
+    /// Unpublished assignments only reach staff tokens; students can't see
+    /// them yet.
+    pub fn is_unpublished(&self) -> bool {
+        self.published == Some(false)
+    }
+
+    /// When the assignment opens, if that is still after `now`: it can't be
+    /// submitted (and isn't missing) before then.
+    pub fn locked_until(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.unlock_at.filter(|unlock| *unlock > now)
+    }
+
+    /// Whether my submission is in (or already graded).
+    pub fn is_submitted(&self) -> bool {
+        self.submission.as_ref().is_some_and(|s| {
+            s.submitted_at.is_some()
+                || matches!(s.workflow_state.as_deref(), Some("submitted" | "graded"))
+        })
+    }
+
+    /// When this assignment's peer reviews are due: `peer_reviews_assign_at`
+    /// when it falls after the due date, as Canvas treats it, else `due_at`.
+    pub fn peer_reviews_due_at(&self) -> Option<DateTime<Utc>> {
+        match (self.peer_reviews_assign_at, self.due_at) {
+            (Some(reviews), Some(due)) if reviews > due => Some(reviews),
+            (reviews, None) => reviews,
+            (_, due) => due,
+        }
+    }
+}
+
+/// A submission someone is asked to review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerReview {
+    pub id: u64,
+    /// Who reviews.
+    pub assessor_id: Option<u64>,
+    /// Whose submission is reviewed.
+    pub user_id: Option<u64>,
+    /// "assigned" or "completed".
+    pub workflow_state: Option<String>,
+    /// The reviewed student, with `include[]=user`.
+    pub user: Option<UserDisplay>,
+}
+
+impl PeerReview {
+    pub fn is_completed(&self) -> bool {
+        self.workflow_state.as_deref() == Some("completed")
+    }
+}
+
+/// The short user object Canvas embeds in other objects.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserDisplay {
+    pub id: u64,
+    pub display_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,6 +195,15 @@ pub struct Submission {
     pub late: Option<bool>,
     pub missing: Option<bool>,
     pub attempt: Option<u64>,
+    /// What Canvas recorded for a URL submission.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// The HTML of a text entry submission.
+    #[serde(default)]
+    pub body: Option<String>,
+    /// The files of an upload submission.
+    #[serde(default)]
+    pub attachments: Vec<FileAttachment>,
 }
 
 // ─── Calendar Events ────────────────────────────────────────────────────────
@@ -88,6 +225,27 @@ pub struct CalendarEvent {
     pub assignment: Option<AssignmentEventDetail>,
 }
 
+impl CalendarEvent {
+    /// The Canvas course ID from a `course_<id>` context code.  Events from
+    /// user or group calendars have none.
+    pub fn course_id(&self) -> Option<u64> {
+        self.context_code
+            .as_deref()?
+            .strip_prefix("course_")?
+            .parse()
+            .ok()
+    }
+
+    /// The Canvas group ID from a `group_<id>` context code.
+    pub fn group_id(&self) -> Option<u64> {
+        self.context_code
+            .as_deref()?
+            .strip_prefix("group_")?
+            .parse()
+            .ok()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssignmentEventDetail {
     pub id: Option<u64>,
@@ -112,6 +270,112 @@ pub struct DiscussionTopic {
     pub html_url: Option<String>,
     pub is_announcement: Option<bool>,
     pub context_code: Option<String>,
+    pub attachments: Option<Vec<FileAttachment>>,
+}
+
+/// One line of `/users/self/activity_stream/summary`: how many items of a
+/// type (`"Announcement"`, `"Message"`, …) there are, and how many unread.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivitySummary {
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub count: Option<u64>,
+    pub unread_count: Option<u64>,
+}
+
+/// `/users/self/colors`: the colors picked for courses and groups on the
+/// Canvas dashboard, as `"#rrggbb"` by context code (`"course_12"`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomColors {
+    #[serde(default)]
+    pub custom_colors: std::collections::HashMap<String, String>,
+}
+
+/// Context code given to account notifications once they are turned into
+/// announcements (Canvas has none for them).
+pub const INSTITUTION_CONTEXT: &str = "account_self";
+
+/// An institution-wide notice from `/accounts/self/account_notifications`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountNotification {
+    pub id: u64,
+    pub subject: Option<String>,
+    pub message: Option<String>,
+    pub start_at: Option<DateTime<Utc>>,
+    pub end_at: Option<DateTime<Utc>>,
+}
+
+impl From<AccountNotification> for DiscussionTopic {
+    /// Shown among the announcements, under "Institution".
+    fn from(n: AccountNotification) -> Self {
+        Self {
+            id: n.id,
+            title: n.subject,
+            message: n.message,
+            posted_at: n.start_at,
+            delayed_post_at: None,
+            user_name: None,
+            discussion_subentry_count: None,
+            read_state: None,
+            unread_count: None,
+            html_url: None,
+            is_announcement: Some(true),
+            context_code: Some(INSTITUTION_CONTEXT.into()),
+            attachments: None,
+        }
+    }
+}
+
+/// A file attached to an announcement or discussion post, or one of a
+/// course's files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileAttachment {
+    pub id: u64,
+    pub display_name: Option<String>,
+    pub filename: Option<String>,
+    pub size: Option<u64>,
+    /// Download URL (includes a verifier; still sent with the token).
+    pub url: Option<String>,
+    /// Only for course files: the folder it is in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder_id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl FileAttachment {
+    pub fn name(&self) -> &str {
+        self.display_name
+            .as_deref()
+            .or(self.filename.as_deref())
+            .unwrap_or("attachment")
+    }
+}
+
+/// The part of a discussion topic Canvas embeds in a discussion assignment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscussionTopicRef {
+    pub id: u64,
+    pub title: Option<String>,
+}
+
+/// A top-level reply in a discussion topic.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscussionEntry {
+    pub id: u64,
+    pub user_name: Option<String>,
+    pub message: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+// ─── Groups ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Group {
+    pub id: u64,
+    pub name: Option<String>,
+    pub group_category_id: Option<u64>,
+    pub course_id: Option<u64>,
 }
 
 // ─── User / Profile ─────────────────────────────────────────────────────────
@@ -126,6 +390,15 @@ pub struct User {
     pub avatar_url: Option<String>,
 }
 
+/// A folder of a course's files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Folder {
+    pub id: u64,
+    pub name: Option<String>,
+    /// The path from the course's root folder, "course files/Week 1".
+    pub full_name: Option<String>,
+}
+
 // ─── Pages ──────────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
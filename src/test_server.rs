@@ -0,0 +1,187 @@
+//! A small HTTP server for tests, standing in for Canvas.  Each request is
+//! answered by a closure; the server counts the requests and how many were
+//! being answered at once.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// A request as the server saw it.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    /// Path and query, as sent.
+    pub target: String,
+    /// Header names in lowercase.
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn path(&self) -> &str {
+        self.target.split('?').next().unwrap_or_default()
+    }
+}
+
+/// What the server answers with.
+#[derive(Debug, Clone)]
+pub struct Reply {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    /// Time to wait before answering.
+    pub delay: Duration,
+}
+
+impl Reply {
+    pub fn json(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            headers: vec![("Content-Type".into(), "application/json".into())],
+            body: body.into(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn status(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            ..Self::json(body)
+        }
+    }
+
+    /// An HTML page, as Canvas serves while down for maintenance.
+    pub fn html(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type".into(), "text/html; charset=utf-8".into())],
+            ..Self::json(body)
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn delayed(self, delay: Duration) -> Self {
+        Self { delay, ..self }
+    }
+}
+
+type Respond = dyn Fn(&Request) -> Reply + Send + Sync;
+
+#[derive(Default)]
+struct Counters {
+    requests: AtomicUsize,
+    active: AtomicUsize,
+    max_active: AtomicUsize,
+    log: Mutex<Vec<Request>>,
+}
+
+/// The running server; it stops with the test's runtime.
+pub struct TestServer {
+    /// `http://127.0.0.1:<port>`
+    pub url: String,
+    counters: Arc<Counters>,
+}
+
+impl TestServer {
+    pub async fn start(respond: impl Fn(&Request) -> Reply + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let counters = Arc::new(Counters::default());
+        let respond: Arc<Respond> = Arc::new(respond);
+        let shared = counters.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let (counters, respond) = (shared.clone(), respond.clone());
+                tokio::spawn(async move {
+                    let Some(request) = read_request(&mut socket).await else {
+                        return;
+                    };
+                    counters.requests.fetch_add(1, Ordering::SeqCst);
+                    let active = counters.active.fetch_add(1, Ordering::SeqCst) + 1;
+                    counters.max_active.fetch_max(active, Ordering::SeqCst);
+                    let reply = respond(&request);
+                    counters.log.lock().unwrap().push(request);
+                    tokio::time::sleep(reply.delay).await;
+                    counters.active.fetch_sub(1, Ordering::SeqCst);
+                    let _ = socket.write_all(&encode(&reply)).await;
+                    let _ = socket.shutdown().await;
+                });
+            }
+        });
+        Self { url, counters }
+    }
+
+    /// Requests answered (or being answered) so far.
+    pub fn requests(&self) -> usize {
+        self.counters.requests.load(Ordering::SeqCst)
+    }
+
+    /// The most requests that were being answered at the same time.
+    pub fn max_concurrent(&self) -> usize {
+        self.counters.max_active.load(Ordering::SeqCst)
+    }
+
+    /// Every request so far, in the order they were answered.
+    pub fn log(&self) -> Vec<Request> {
+        self.counters.log.lock().unwrap().clone()
+    }
+}
+
+async fn read_request(socket: &mut tokio::net::TcpStream) -> Option<Request> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+        let n = socket.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+    let head = String::from_utf8_lossy(&buf[..head_end]).into_owned();
+    let mut lines = head.lines();
+    let mut start = lines.next()?.split(' ');
+    let (method, target) = (start.next()?.to_string(), start.next()?.to_string());
+    let headers: HashMap<String, String> = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(k, v)| (k.trim().to_ascii_lowercase(), v.trim().to_string()))
+        .collect();
+    let length: usize = headers.get("content-length").and_then(|l| l.parse().ok()).unwrap_or(0);
+    let mut body = buf[head_end + 4..].to_vec();
+    while body.len() < length {
+        let n = socket.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    Some(Request {
+        method,
+        target,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn encode(reply: &Reply) -> Vec<u8> {
+    let mut out = format!("HTTP/1.1 {} X\r\n", reply.status);
+    for (name, value) in &reply.headers {
+        out += &format!("{name}: {value}\r\n");
+    }
+    out += &format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        reply.body.len(),
+        reply.body
+    );
+    out.into_bytes()
+}
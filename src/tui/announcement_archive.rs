@@ -0,0 +1,34 @@
+//! Announcements archived with `x` on the Announcements tab.  They move
+//! behind a "show archived" row and out of the unread counts; being read
+//! already, they have nothing left to say.  Kept by id in a local file and
+//! never sent to Canvas.
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use crate::models::DiscussionTopic;
+
+/// Forget the archived ids no announcement in `announcements` has any
+/// more.  Returns whether any were dropped.
+pub fn prune(archived: &mut BTreeSet<u64>, announcements: &[DiscussionTopic]) -> bool {
+    let before = archived.len();
+    archived.retain(|id| announcements.iter().any(|a| a.id == *id));
+    archived.len() != before
+}
+
+/// Archived announcement ids.  A missing or unreadable file is none.
+pub fn load_archived(path: &Path) -> BTreeSet<u64> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_archived(path: &Path, archived: &BTreeSet<u64>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(archived)?)?;
+    Ok(())
+}
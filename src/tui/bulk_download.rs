@@ -0,0 +1,158 @@
+//! Downloading all of a course's files at once (`B` on the Courses tab)
+//! into `download_dir/<course>/<folder>/`, mirroring its folders.  Files
+//! whose local copy has the same size and modified time are skipped, so
+//! running it again before an exam only fetches what changed.
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinSet;
+
+use crate::api::CanvasClient;
+use crate::models::{FileAttachment, Folder};
+
+/// Files downloaded at the same time.
+const CONCURRENT_DOWNLOADS: usize = 3;
+
+/// What the download task reports back to the app.
+#[derive(Debug)]
+pub enum BulkEvent {
+    /// The file list is in: `total` files (`bytes` in all) to fetch,
+    /// `skipped` already up to date.
+    Planned { total: usize, bytes: u64, skipped: usize },
+    /// One file saved.
+    Saved { bytes: u64 },
+    /// One file that couldn't be saved, by its path in the course.
+    Failed { name: String, error: String },
+    /// The file list couldn't be fetched; nothing was downloaded.
+    ListFailed(String),
+    Finished,
+}
+
+/// A file to fetch and where it goes.
+#[derive(Debug)]
+struct Job {
+    /// Its path below the course folder, for the summary.
+    name: String,
+    url: Option<String>,
+    dest: PathBuf,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+/// List the course's files and download those not up to date in `dir`,
+/// reporting on `tx`.  Aborting the task stops it: no new downloads start
+/// and those in flight are dropped, which removes their partial files.
+pub async fn run(client: CanvasClient, course_id: u64, dir: PathBuf, tx: mpsc::UnboundedSender<BulkEvent>) {
+    let listing = tokio::try_join!(
+        client.list_course_folders(course_id),
+        client.list_course_files(course_id),
+    );
+    let (folders, files) = match listing {
+        Ok(listing) => listing,
+        Err(e) => {
+            let _ = tx.send(BulkEvent::ListFailed(e.to_string()));
+            return;
+        }
+    };
+    let total_files = files.len();
+    let jobs = plan(&dir, &folders, files);
+    let _ = tx.send(BulkEvent::Planned {
+        total: jobs.len(),
+        bytes: jobs.iter().map(|(_, size)| size).sum(),
+        skipped: total_files - jobs.len(),
+    });
+
+    let slots = Arc::new(Semaphore::new(CONCURRENT_DOWNLOADS));
+    let mut downloads = JoinSet::new();
+    for (job, _) in jobs {
+        let Ok(permit) = slots.clone().acquire_owned().await else {
+            break;
+        };
+        let client = client.clone();
+        let tx = tx.clone();
+        downloads.spawn(async move {
+            let _permit = permit;
+            let event = match fetch(&client, &job).await {
+                Ok(bytes) => BulkEvent::Saved { bytes },
+                Err(error) => BulkEvent::Failed { name: job.name, error },
+            };
+            let _ = tx.send(event);
+        });
+    }
+    while downloads.join_next().await.is_some() {}
+    let _ = tx.send(BulkEvent::Finished);
+}
+
+async fn fetch(client: &CanvasClient, job: &Job) -> Result<u64, String> {
+    let url = job.url.as_deref().ok_or("Canvas gave no download link")?;
+    if let Some(parent) = job.dest.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let bytes = client.download_file(url, &job.dest).await.map_err(|e| e.to_string())?;
+    // Stamp the copy with Canvas's time, for the next run's skip check.
+    if let Some(updated) = job.updated_at {
+        let stamped = std::fs::File::options()
+            .write(true)
+            .open(&job.dest)
+            .and_then(|f| f.set_modified(SystemTime::from(updated)));
+        if let Err(e) = stamped {
+            tracing::warn!("setting the time of {}: {e}", job.dest.display());
+        }
+    }
+    Ok(bytes)
+}
+
+/// The files to fetch, with their sizes, leaving out those whose copy in
+/// `dir` is up to date.
+fn plan(dir: &Path, folders: &[Folder], files: Vec<FileAttachment>) -> Vec<(Job, u64)> {
+    let folder_dirs: HashMap<u64, PathBuf> = folders
+        .iter()
+        .map(|f| (f.id, folder_path(f.full_name.as_deref().or(f.name.as_deref()).unwrap_or(""))))
+        .collect();
+    files
+        .into_iter()
+        .filter_map(|file| {
+            let folder = file.folder_id.and_then(|id| folder_dirs.get(&id)).cloned().unwrap_or_default();
+            let rel = folder.join(super::safe_file_name(file.name(), "file"));
+            let dest = dir.join(&rel);
+            let size = file.size.unwrap_or(0);
+            if up_to_date(&dest, file.size, file.updated_at) {
+                return None;
+            }
+            let job = Job {
+                name: rel.display().to_string(),
+                url: file.url,
+                dest,
+                updated_at: file.updated_at,
+            };
+            Some((job, size))
+        })
+        .collect()
+}
+
+/// A folder's place below the course folder: its `full_name` without the
+/// root folder ("course files"), each part made safe as a file name.
+fn folder_path(full_name: &str) -> PathBuf {
+    full_name
+        .split('/')
+        .skip(1)
+        .filter(|part| !part.is_empty())
+        .map(|part| super::safe_file_name(part, "folder"))
+        .collect()
+}
+
+/// Whether `dest` has the size and modified time Canvas gives.  A file
+/// without either is fetched again.
+fn up_to_date(dest: &Path, size: Option<u64>, updated_at: Option<DateTime<Utc>>) -> bool {
+    let (Some(size), Some(updated)) = (size, updated_at) else {
+        return false;
+    };
+    let Ok(meta) = std::fs::metadata(dest) else {
+        return false;
+    };
+    let modified = meta.modified().ok().map(DateTime::<Utc>::from);
+    meta.len() == size && modified.is_some_and(|m| m.timestamp() == updated.timestamp())
+}
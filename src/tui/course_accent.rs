@@ -0,0 +1,186 @@
+//! A color per course, drawn as a one-cell stripe at the left of its rows
+//! so a glance down a list shows which course each row is.  The color is
+//! the one picked on the Canvas dashboard when the sync fetched it, else
+//! one of `PALETTE` chosen by hashing the course id, so it stays the same
+//! from run to run.  With only 16 colors the stripes would blur into the
+//! status colors, so each palette slot has its own glyph instead.  The
+//! user's initials badge in the header takes its color from the same
+//! palette, by user id.
+
+use ratatui::style::Color;
+use std::collections::{BTreeMap, HashMap};
+
+use super::ui::theme::TRUECOLOR;
+use crate::config::ColorMode;
+use crate::models::Course;
+
+/// Hues kept clear of amber, the status colors (green, orange, red) and the
+/// informational blue.
+const PALETTE: [(u8, u8, u8); 8] = [
+    (167, 130, 235), // violet
+    (225, 110, 190), // magenta
+    (70, 190, 180),  // teal
+    (235, 150, 160), // rose
+    (190, 170, 255), // lavender
+    (120, 110, 220), // indigo
+    (180, 120, 160), // mauve
+    (140, 160, 180), // slate
+];
+
+/// The stripe for each palette slot with 16 colors, where the shape tells
+/// courses apart.  All line shapes, so none reads as a row's icon or the
+/// unread dot.
+const GLYPHS: [&str; 8] = ["▎", "┃", "╎", "┆", "▌", "▐", "║", "╏"];
+
+/// The stripe drawn when a row has no course.
+pub const NO_STRIPE: &str = " ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CourseAccent {
+    pub rgb: (u8, u8, u8),
+    /// The palette slot: the hashed one, or for a Canvas color the nearest.
+    pub index: usize,
+}
+
+impl CourseAccent {
+    /// The accent for `course_id`, from its Canvas color (`"#rrggbb"`) if
+    /// that parses.
+    pub fn new(course_id: u64, canvas_color: Option<&str>) -> Self {
+        match canvas_color.and_then(parse_hex) {
+            Some(rgb) => Self { rgb, index: nearest_slot(rgb) },
+            None => {
+                let index = palette_index(course_id);
+                Self { rgb: PALETTE[index], index }
+            }
+        }
+    }
+
+    /// The stripe's text and color in `mode`.
+    pub fn stripe(self, mode: ColorMode) -> (&'static str, Color) {
+        let glyph = if mode == ColorMode::Ansi16 { GLYPHS[self.index] } else { "▎" };
+        (glyph, self.color(mode))
+    }
+
+    /// The accent in `mode`; with 16 colors a neutral one.
+    pub fn color(self, mode: ColorMode) -> Color {
+        let (r, g, b) = self.rgb;
+        match mode {
+            ColorMode::TrueColor => Color::Rgb(r, g, b),
+            ColorMode::Ansi256 => Color::Indexed(xterm_index(self.rgb)),
+            // A palette color, so `repaint` maps it like the rest.
+            ColorMode::Ansi16 => TRUECOLOR.text_dim,
+        }
+    }
+}
+
+/// Accents for `courses`, worked out once per load of the data.
+pub fn accents(courses: &[Course], colors: &BTreeMap<u64, String>) -> HashMap<u64, CourseAccent> {
+    courses
+        .iter()
+        .map(|c| (c.id, CourseAccent::new(c.id, colors.get(&c.id).map(String::as_str))))
+        .collect()
+}
+
+/// `PALETTE` slot for a course without a Canvas color.  FNV-1a rather than
+/// the std hasher, whose output may change between Rust releases.
+pub fn palette_index(course_id: u64) -> usize {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in course_id.to_le_bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    (hash % PALETTE.len() as u64) as usize
+}
+
+/// `"#rrggbb"` (or `"#rgb"`) as its components.
+fn parse_hex(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.trim().strip_prefix('#').filter(|h| h.is_ascii())?;
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    match hex.len() {
+        6 => Some((channel(&hex[0..2])?, channel(&hex[2..4])?, channel(&hex[4..6])?)),
+        3 => {
+            let mut out = [0u8; 3];
+            for (slot, ch) in out.iter_mut().zip(hex.chars()) {
+                *slot = channel(&ch.to_string())? * 17;
+            }
+            Some((out[0], out[1], out[2]))
+        }
+        _ => None,
+    }
+}
+
+/// The palette slot closest to `rgb`, so a Canvas color gets a glyph too.
+fn nearest_slot(rgb: (u8, u8, u8)) -> usize {
+    let distance = |(r, g, b): (u8, u8, u8)| {
+        let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).pow(2);
+        d(r, rgb.0) + d(g, rgb.1) + d(b, rgb.2)
+    };
+    (0..PALETTE.len()).min_by_key(|&i| distance(PALETTE[i])).unwrap_or(0)
+}
+
+/// The nearest color of the xterm 6×6×6 cube.
+fn xterm_index((r, g, b): (u8, u8, u8)) -> u8 {
+    let level = |c: u8| match c {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (c - 35) / 40,
+    };
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::test_fixtures::course;
+
+    /// Pinned so a change to the hash, which would recolor everyone's
+    /// courses, shows up here.
+    #[test]
+    fn palette_slots_are_stable() {
+        let ids = [1, 2, 3, 4, 5, 6, 7, 8, 101, 12345, u64::MAX];
+        let slots: Vec<usize> = ids.iter().map(|&id| palette_index(id)).collect();
+        assert_eq!(slots, [4, 7, 6, 1, 0, 3, 2, 5, 0, 4, 5]);
+        assert_eq!(CourseAccent::new(1, None), CourseAccent { rgb: PALETTE[4], index: 4 });
+    }
+
+    #[test]
+    fn canvas_colors_win_and_get_the_nearest_slot() {
+        let accent = CourseAccent::new(1, Some(" #6E6EDC "));
+        assert_eq!(accent.rgb, (110, 110, 220));
+        assert_eq!(accent.index, 5);
+        assert_eq!(CourseAccent::new(1, Some("#0f8")).rgb, (0, 255, 136));
+        // Colors that don't parse fall back to the hash.
+        for bad in ["", "red", "#12345", "#gggggg", "#ééé"] {
+            assert_eq!(CourseAccent::new(1, Some(bad)), CourseAccent::new(1, None), "{bad}");
+        }
+    }
+
+    #[test]
+    fn accents_cover_every_course() {
+        let courses = [course(1, "Biology"), course(2, "Chemistry")];
+        let colors = BTreeMap::from([(2, "#ff0000".to_string()), (9, "#00ff00".to_string())]);
+        let accents = accents(&courses, &colors);
+        assert_eq!(accents.len(), 2);
+        assert_eq!(accents[&1], CourseAccent::new(1, None));
+        assert_eq!(accents[&2].rgb, (255, 0, 0));
+    }
+
+    #[test]
+    fn stripes_in_each_color_mode() {
+        let accent = CourseAccent::new(1, None);
+        assert_eq!(accent.stripe(ColorMode::TrueColor), ("▎", Color::Rgb(190, 170, 255)));
+        assert_eq!(accent.stripe(ColorMode::Ansi256), ("▎", Color::Indexed(147)));
+        assert_eq!(accent.stripe(ColorMode::Ansi16), ("▌", TRUECOLOR.text_dim));
+        // Every slot has its own glyph with 16 colors.
+        let glyphs: std::collections::HashSet<_> = GLYPHS.iter().collect();
+        assert_eq!(glyphs.len(), PALETTE.len());
+    }
+
+    #[test]
+    fn xterm_cube_corners() {
+        assert_eq!(xterm_index((0, 0, 0)), 16);
+        assert_eq!(xterm_index((255, 255, 255)), 231);
+        assert_eq!(xterm_index((255, 0, 0)), 196);
+        assert_eq!(xterm_index((95, 135, 175)), 16 + 36 + 12 + 3);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::models::Course;
+
+/// Why `match_course` couldn't settle on one course.
+#[derive(Debug, thiserror::Error)]
+pub enum CourseMatchError {
+    #[error("no course matches '{0}'")]
+    NoMatch(String),
+    #[error("ambiguous: matches {}", list_names(.0))]
+    Ambiguous(Vec<String>),
+}
+
+/// Find the one course `query` names.  Compared case-insensitively against
+/// each course's name, code and original name (Canvas reports a nickname as
+/// `name`), preferring an exact match, then a prefix, then a substring;
+/// the first of those that picks out a single course wins.
+pub fn match_course<'a>(courses: &'a [Course], query: &str) -> Result<&'a Course, CourseMatchError> {
+    let needle = query.trim().to_lowercase();
+    if needle.is_empty() {
+        return Err(CourseMatchError::NoMatch(query.to_string()));
+    }
+    let tests: [&dyn Fn(&str) -> bool; 3] = [
+        &|s| s == needle,
+        &|s| s.starts_with(&needle),
+        &|s| s.contains(&needle),
+    ];
+    for test in tests {
+        let found: Vec<&Course> = courses
+            .iter()
+            .filter(|c| course_labels(c).any(|label| test(&label.to_lowercase())))
+            .collect();
+        match found.as_slice() {
+            [] => continue,
+            [only] => return Ok(only),
+            many => {
+                return Err(CourseMatchError::Ambiguous(
+                    many.iter().map(|c| display_name(c)).collect(),
+                ))
+            }
+        }
+    }
+    Err(CourseMatchError::NoMatch(query.to_string()))
+}
+
+fn course_labels(course: &Course) -> impl Iterator<Item = &str> {
+    [&course.name, &course.course_code, &course.original_name]
+        .into_iter()
+        .filter_map(|s| s.as_deref())
+}
+
+fn display_name(course: &Course) -> String {
+    course
+        .name
+        .clone()
+        .or_else(|| course.course_code.clone())
+        .unwrap_or_else(|| format!("course {}", course.id))
+}
+
+/// "'A' and 'B'", "'A', 'B' and 'C'".
+fn list_names(names: &[String]) -> String {
+    let quoted: Vec<String> = names.iter().map(|n| format!("'{n}'")).collect();
+    match quoted.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {last}", rest.join(", ")),
+        _ => quoted.concat(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::test_fixtures::from_json;
+    use serde_json::json;
+
+    fn courses() -> Vec<Course> {
+        from_json(json!([
+            { "id": 1, "name": "Biology", "course_code": "BIO-101" },
+            { "id": 2, "name": "Biology Lab", "course_code": "BIO-101L" },
+            { "id": 3, "name": "My favourite", "course_code": "PHYS-2021",
+              "original_name": "Physics II" },
+            { "id": 4, "name": "Chemistry", "course_code": "CHEM-110" },
+            { "id": 5, "course_code": "CHEM-120" },
+        ]))
+    }
+
+    fn id(query: &str) -> Result<u64, CourseMatchError> {
+        match_course(&courses(), query).map(|c| c.id)
+    }
+
+    #[test]
+    fn exact_match_beats_prefix() {
+        // "biology" is also a prefix of "Biology Lab".
+        assert_eq!(id("biology").unwrap(), 1);
+        assert_eq!(id("  BIO-101L ").unwrap(), 2);
+    }
+
+    #[test]
+    fn prefix_then_substring() {
+        assert_eq!(id("chem-11").unwrap(), 4);
+        assert_eq!(id("lab").unwrap(), 2);
+    }
+
+    #[test]
+    fn code_and_original_name_match_too() {
+        assert_eq!(id("phys").unwrap(), 3);
+        assert_eq!(id("physics ii").unwrap(), 3);
+        assert_eq!(id("favourite").unwrap(), 3);
+    }
+
+    #[test]
+    fn ambiguous_queries_name_every_candidate() {
+        let err = id("chem").unwrap_err();
+        assert!(matches!(&err, CourseMatchError::Ambiguous(names) if names.len() == 2));
+        assert_eq!(err.to_string(), "ambiguous: matches 'Chemistry' and 'CHEM-120'");
+
+        let err = id("bio-10").unwrap_err();
+        assert_eq!(err.to_string(), "ambiguous: matches 'Biology' and 'Biology Lab'");
+    }
+
+    #[test]
+    fn no_match_or_empty_query() {
+        assert_eq!(id("history").unwrap_err().to_string(), "no course matches 'history'");
+        assert!(matches!(id("   "), Err(CourseMatchError::NoMatch(_))));
+        assert!(matches!(match_course(&[], "bio"), Err(CourseMatchError::NoMatch(_))));
+    }
+
+    #[test]
+    fn names_are_listed_in_english() {
+        let names = |n: &[&str]| list_names(&n.iter().map(|s| s.to_string()).collect::<Vec<_>>());
+        assert_eq!(names(&[]), "");
+        assert_eq!(names(&["A"]), "'A'");
+        assert_eq!(names(&["A", "B"]), "'A' and 'B'");
+        assert_eq!(names(&["A", "B", "C"]), "'A', 'B' and 'C'");
+    }
+}
@@ -0,0 +1,159 @@
+//! Cross-listed sections that Canvas lists as separate courses with the
+//! same assignments, which would otherwise show every assignment twice.
+//! Two courses are the same when most of the assignments of the one with
+//! fewer are also in the other (Canvas assignment ids are unique, so they
+//! can only be shared by shells of one course), or when they have the
+//! same name and SIS ids that differ only in the section number.  Each set
+//! of them is shown as the one with the lowest id, so the merge comes out
+//! the same every time and state kept by course id keeps pointing at the
+//! course shown.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::cache::CourseAssignments;
+use crate::models::Course;
+
+/// Share of the smaller course's assignments the other must have too.
+const OVERLAP: f64 = 0.5;
+
+/// Courses shown as another: each duplicate's id and the id it merges into.
+pub fn find_duplicates(
+    courses: &[Course],
+    assignments: &[CourseAssignments],
+) -> BTreeMap<u64, u64> {
+    let ids_of = |course_id: u64| -> HashSet<u64> {
+        assignments
+            .iter()
+            .filter(|g| g.course_id == course_id)
+            .flat_map(|g| g.assignments.iter().map(|a| a.id))
+            .collect()
+    };
+    let mut courses: Vec<(&Course, HashSet<u64>)> =
+        courses.iter().map(|c| (c, ids_of(c.id))).collect();
+    courses.sort_by_key(|(c, _)| c.id);
+
+    // Sets are joined through any pair in them, so A with B and B with C
+    // puts all three under A even when A and C share nothing.
+    let mut merged: BTreeMap<u64, u64> = BTreeMap::new();
+    for (i, (course, ids)) in courses.iter().enumerate() {
+        for (other, other_ids) in &courses[i + 1..] {
+            if !same_course(course, ids, other, other_ids) {
+                continue;
+            }
+            let shown = |id: u64| merged.get(&id).copied().unwrap_or(id);
+            let (a, b) = (shown(course.id), shown(other.id));
+            if a == b {
+                continue;
+            }
+            let (keep, gone) = (a.min(b), a.max(b));
+            for target in merged.values_mut().filter(|target| **target == gone) {
+                *target = keep;
+            }
+            merged.insert(gone, keep);
+        }
+    }
+    merged
+}
+
+fn same_course(a: &Course, a_ids: &HashSet<u64>, b: &Course, b_ids: &HashSet<u64>) -> bool {
+    let smaller = a_ids.len().min(b_ids.len());
+    let shared = a_ids.intersection(b_ids).count();
+    if smaller > 0 && shared as f64 >= smaller as f64 * OVERLAP {
+        return true;
+    }
+    // A number at the end may be the course's rather than a section's, so
+    // the names have to agree as well.
+    let name = |c: &Course| c.name.as_deref().map(|n| n.trim().to_lowercase());
+    match (a.sis_course_id.as_deref(), b.sis_course_id.as_deref()) {
+        (Some(a_sis), Some(b_sis)) => sis_root(a_sis) == sis_root(b_sis) && name(a) == name(b),
+        _ => false,
+    }
+}
+
+/// A SIS course id without a trailing section number: "2026FA-PHYS-2021-001"
+/// and "2026FA-PHYS-2021-002" are both "2026FA-PHYS-2021".
+fn sis_root(sis_id: &str) -> &str {
+    match sis_id.rsplit_once(['-', '_', '.']) {
+        Some((root, section))
+            if !root.is_empty()
+                && !section.is_empty()
+                && section.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            root
+        }
+        _ => sis_id,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::test_fixtures::{assignment, from_json};
+    use serde_json::json;
+
+    fn course(id: u64, name: &str, sis: Option<&str>) -> Course {
+        from_json(json!({ "id": id, "name": name, "sis_course_id": sis }))
+    }
+
+    fn group(course_id: u64, ids: &[u64]) -> CourseAssignments {
+        CourseAssignments {
+            course_id,
+            name: format!("Course {course_id}"),
+            assignments: ids.iter().map(|&id| assignment(id, course_id, "A", json!({}))).collect(),
+        }
+    }
+
+    #[test]
+    fn shared_assignments_merge_into_the_lowest_id() {
+        let courses = [course(2, "Physics 001", None), course(1, "Physics 002", None)];
+        // Two of the smaller course's three.
+        let groups = [group(1, &[10, 11, 12, 13]), group(2, &[10, 11, 99])];
+        assert_eq!(find_duplicates(&courses, &groups), BTreeMap::from([(2, 1)]));
+    }
+
+    #[test]
+    fn too_little_overlap_keeps_courses_apart() {
+        let courses = [course(1, "Physics", None), course(2, "Chemistry", None)];
+        let groups = [group(1, &[10, 11, 12]), group(2, &[10, 21, 22])];
+        assert!(find_duplicates(&courses, &groups).is_empty());
+        // Nor do two courses without assignments count as the same.
+        assert!(find_duplicates(&courses, &[]).is_empty());
+    }
+
+    #[test]
+    fn sis_ids_differing_in_the_section_merge() {
+        let courses = [
+            course(1, "Physics", Some("2026FA-PHYS-2021-001")),
+            course(2, "physics ", Some("2026FA-PHYS-2021-002")),
+            course(3, "Physics", Some("2026FA_PHYS_2021_003")),
+        ];
+        assert_eq!(find_duplicates(&courses, &[]), BTreeMap::from([(2, 1)]));
+        assert_eq!(sis_root("2026FA-PHYS-2021-001"), "2026FA-PHYS-2021");
+        assert_eq!(sis_root("2026FA.PHYS.2021.2"), "2026FA.PHYS.2021");
+        assert_eq!(sis_root("2026FA-PHYS-2021-LAB"), "2026FA-PHYS-2021-LAB");
+        assert_eq!(sis_root("-001"), "-001");
+        assert_eq!(sis_root("PHYS2021"), "PHYS2021");
+    }
+
+    #[test]
+    fn a_trailing_course_number_needs_the_same_name() {
+        // "2021" and "2022" are courses, not sections of one.
+        let courses = [
+            course(1, "Physics I", Some("2026FA-PHYS-2021")),
+            course(2, "Physics II", Some("2026FA-PHYS-2022")),
+        ];
+        assert!(find_duplicates(&courses, &[]).is_empty());
+    }
+
+    #[test]
+    fn a_chain_of_overlaps_is_one_set() {
+        let courses = [
+            course(3, "Physics C", None),
+            course(1, "Physics A", None),
+            course(2, "Physics B", None),
+        ];
+        // 1 and 3 share nothing; 2 shares with each.
+        let groups = [group(1, &[10, 11]), group(2, &[10, 11, 20, 21]), group(3, &[20, 21])];
+        assert_eq!(find_duplicates(&courses, &groups), BTreeMap::from([(2, 1), (3, 1)]));
+    }
+}
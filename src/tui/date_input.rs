@@ -0,0 +1,311 @@
+//! Parsing for dates and times typed into prompts: the schedule's `J`
+//! (jump to date) prompt and the new-event form.
+//!
+//! Dates accept the forms people actually type:
+//!   today / tomorrow / yesterday
+//!   +3d  -1w  +2m            (relative days, weeks, months)
+//!   2025-04-14  4/14  4/14/2025
+//!   apr 14  april 14  14 apr  apr 14 2025
+//!   fri  friday  next fri    (bare weekday: today or later; "next": after today)
+//!
+//! Times: 19:00  7pm  7:30pm  7:30 pm  noon  midnight  (bare "7" is 07:00)
+
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveTime, Weekday};
+
+/// Resolve `input` to a date, relative to `today`.  The error is a short
+/// message suitable for showing under the prompt.
+pub fn parse_date(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return Err("Type a date, e.g. apr 14, 2025-04-14, +2w, next mon".into());
+    }
+
+    match input.as_str() {
+        "today" | "now" => return Ok(today),
+        "tomorrow" => return Ok(today + Duration::days(1)),
+        "yesterday" => return Ok(today - Duration::days(1)),
+        _ => {}
+    }
+
+    if let Some(date) = parse_relative(&input, today) {
+        return date;
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Ok(date);
+    }
+    if let Some(date) = parse_numeric(&input, today) {
+        return date;
+    }
+    if let Some(date) = parse_weekday(&input, today) {
+        return Ok(date);
+    }
+    if let Some(date) = parse_month_day(&input, today) {
+        return date;
+    }
+    Err(format!("Don't understand '{input}'"))
+}
+
+/// `+3d`, `-1w`, `+2m` (a bare `+3` means days).
+fn parse_relative(input: &str, today: NaiveDate) -> Option<Result<NaiveDate, String>> {
+    let (sign, rest) = match input.as_bytes().first()? {
+        b'+' => (1i64, &input[1..]),
+        b'-' => (-1i64, &input[1..]),
+        _ => return None,
+    };
+    let digits_end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    let digits = &rest[..digits_end];
+    if digits.is_empty() {
+        return Some(Err(format!("Expected a number after '{}'", &input[..1])));
+    }
+    let out_of_range = || "Date out of range".to_string();
+    // Too many digits for an i64 is out of range too.
+    let Ok(n) = digits.parse::<i64>() else {
+        return Some(Err(out_of_range()));
+    };
+    // `Duration` panics past its range, so it is built with the `try_`
+    // constructors rather than checked after the fact.
+    let date = match rest[digits_end..].trim() {
+        "" | "d" | "day" | "days" => {
+            Duration::try_days(sign * n).and_then(|d| today.checked_add_signed(d))
+        }
+        "w" | "wk" | "week" | "weeks" => {
+            Duration::try_weeks(sign * n).and_then(|d| today.checked_add_signed(d))
+        }
+        "m" | "mo" | "month" | "months" => u32::try_from(n).ok().and_then(|n| {
+            if sign > 0 {
+                today.checked_add_months(Months::new(n))
+            } else {
+                today.checked_sub_months(Months::new(n))
+            }
+        }),
+        unit => return Some(Err(format!("Unknown unit '{unit}' — use d, w or m"))),
+    };
+    Some(date.ok_or_else(out_of_range))
+}
+
+/// `4/14` (this year) or `4/14/2025`.
+fn parse_numeric(input: &str, today: NaiveDate) -> Option<Result<NaiveDate, String>> {
+    let parts: Vec<&str> = input.split('/').collect();
+    if !(2..=3).contains(&parts.len()) || !parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+    let month: u32 = parts[0].parse().ok()?;
+    let day: u32 = parts[1].parse().ok()?;
+    let year = match parts.get(2) {
+        Some(y) => expand_year(y.parse().ok()?),
+        None => today.year(),
+    };
+    Some(NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| format!("No such date: {input}")))
+}
+
+/// `fri`, `friday`, `next fri`.
+fn parse_weekday(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (strictly_after, name) = match input.strip_prefix("next ") {
+        Some(rest) => (true, rest.trim()),
+        None => (false, input),
+    };
+    let target = weekday_from_name(name)?;
+    let mut days_ahead = (7 + target.num_days_from_monday() as i64
+        - today.weekday().num_days_from_monday() as i64)
+        % 7;
+    if strictly_after && days_ahead == 0 {
+        days_ahead = 7;
+    }
+    Some(today + Duration::days(days_ahead))
+}
+
+/// `apr 14`, `14 april`, `apr 14 2025`, `apr 14, 2025`.
+fn parse_month_day(input: &str, today: NaiveDate) -> Option<Result<NaiveDate, String>> {
+    let words: Vec<&str> = input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|w| !w.is_empty())
+        .collect();
+    let (month, day_word, year_word) = match words.as_slice() {
+        [m, d] | [m, d, _] if month_from_name(m).is_some() => {
+            (month_from_name(m)?, *d, words.get(2))
+        }
+        [d, m] | [d, m, _] if month_from_name(m).is_some() => {
+            (month_from_name(m)?, *d, words.get(2))
+        }
+        _ => return None,
+    };
+    let day_digits = day_word.trim_end_matches(|c: char| c.is_ascii_alphabetic()); // 14th, 1st
+    let Ok(day) = day_digits.parse::<u32>() else {
+        return Some(Err(format!("Expected a day number, got '{day_word}'")));
+    };
+    let year = match year_word {
+        Some(y) => match y.parse() {
+            Ok(y) => expand_year(y),
+            Err(_) => return Some(Err(format!("Expected a year, got '{y}'"))),
+        },
+        None => today.year(),
+    };
+    Some(NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| format!("No such date: {input}")))
+}
+
+/// Two-digit years are taken as 20xx.
+fn expand_year(y: i32) -> i32 {
+    if y < 100 {
+        2000 + y
+    } else {
+        y
+    }
+}
+
+fn month_from_name(name: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january", "february", "march", "april", "may", "june", "july", "august", "september",
+        "october", "november", "december",
+    ];
+    if name.len() < 3 {
+        return None;
+    }
+    let name = name.trim_end_matches('.');
+    MONTHS
+        .iter()
+        .position(|m| m.starts_with(name))
+        .map(|i| i as u32 + 1)
+}
+
+fn weekday_from_name(name: &str) -> Option<Weekday> {
+    const DAYS: [(&str, Weekday); 7] = [
+        ("monday", Weekday::Mon),
+        ("tuesday", Weekday::Tue),
+        ("wednesday", Weekday::Wed),
+        ("thursday", Weekday::Thu),
+        ("friday", Weekday::Fri),
+        ("saturday", Weekday::Sat),
+        ("sunday", Weekday::Sun),
+    ];
+    if name.len() < 2 {
+        return None;
+    }
+    DAYS.iter()
+        .find(|(full, _)| full.starts_with(name))
+        .map(|(_, d)| *d)
+}
+
+/// Resolve `input` to a time of day.
+pub fn parse_time(input: &str) -> Result<NaiveTime, String> {
+    let input = input.trim().to_lowercase();
+    match input.as_str() {
+        "" => return Err("Type a time, e.g. 19:00 or 7pm".into()),
+        "noon" => return Ok(NaiveTime::from_hms_opt(12, 0, 0).unwrap_or_default()),
+        "midnight" => return Ok(NaiveTime::MIN),
+        _ => {}
+    }
+
+    let (clock, meridiem) = if let Some(rest) = input.strip_suffix("am") {
+        (rest.trim(), Some(false))
+    } else if let Some(rest) = input.strip_suffix("pm") {
+        (rest.trim(), Some(true))
+    } else {
+        (input.as_str(), None)
+    };
+    let (h, m) = clock.split_once(':').unwrap_or((clock, "0"));
+    let (Ok(mut hour), Ok(minute)) = (h.parse::<u32>(), m.parse::<u32>()) else {
+        return Err(format!("Don't understand time '{input}'"));
+    };
+    match meridiem {
+        Some(_) if !(1..=12).contains(&hour) => {
+            return Err(format!("No such time: {input}"));
+        }
+        Some(pm) => hour = hour % 12 + if pm { 12 } else { 0 },
+        None => {}
+    }
+    NaiveTime::from_hms_opt(hour, minute, 0).ok_or_else(|| format!("No such time: {input}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Wednesday.
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 4, 9).unwrap()
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn parsed(input: &str) -> Result<NaiveDate, String> {
+        parse_date(input, today())
+    }
+
+    #[test]
+    fn named_days() {
+        assert_eq!(parsed("today"), Ok(today()));
+        assert_eq!(parsed(" Tomorrow "), Ok(date(2025, 4, 10)));
+        assert_eq!(parsed("yesterday"), Ok(date(2025, 4, 8)));
+    }
+
+    #[test]
+    fn relative_offsets() {
+        assert_eq!(parsed("+3"), Ok(date(2025, 4, 12)));
+        assert_eq!(parsed("+3d"), Ok(date(2025, 4, 12)));
+        assert_eq!(parsed("-1w"), Ok(date(2025, 4, 2)));
+        assert_eq!(parsed("+2 weeks"), Ok(date(2025, 4, 23)));
+        assert_eq!(parsed("+2m"), Ok(date(2025, 6, 9)));
+        assert_eq!(parsed("-1mo"), Ok(date(2025, 3, 9)));
+        assert!(parsed("+").unwrap_err().contains("Expected a number"));
+        assert!(parsed("+3y").unwrap_err().contains("Unknown unit"));
+    }
+
+    #[test]
+    fn huge_offsets_are_out_of_range_not_a_panic() {
+        for input in [
+            "+99999999999999",
+            "+9999999999999w",
+            "-99999999999999d",
+            "-9999999999999w",
+            "+99999999999m",
+            "+99999999999999999999999",
+            "+4294967296m",
+        ] {
+            assert_eq!(parsed(input), Err("Date out of range".into()), "{input}");
+        }
+    }
+
+    #[test]
+    fn absolute_dates() {
+        assert_eq!(parsed("2025-04-14"), Ok(date(2025, 4, 14)));
+        assert_eq!(parsed("4/14"), Ok(date(2025, 4, 14)));
+        assert_eq!(parsed("4/14/26"), Ok(date(2026, 4, 14)));
+        assert_eq!(parsed("apr 14"), Ok(date(2025, 4, 14)));
+        assert_eq!(parsed("April 14th"), Ok(date(2025, 4, 14)));
+        assert_eq!(parsed("14 apr"), Ok(date(2025, 4, 14)));
+        assert_eq!(parsed("apr 14, 2026"), Ok(date(2026, 4, 14)));
+        assert!(parsed("2/30").unwrap_err().contains("No such date"));
+        assert!(parsed("apr x").unwrap_err().contains("day number"));
+    }
+
+    #[test]
+    fn weekdays() {
+        // A bare weekday is today or later; `next` is strictly after today.
+        assert_eq!(parsed("wed"), Ok(today()));
+        assert_eq!(parsed("next wed"), Ok(date(2025, 4, 16)));
+        assert_eq!(parsed("fri"), Ok(date(2025, 4, 11)));
+        assert_eq!(parsed("next mon"), Ok(date(2025, 4, 14)));
+        assert_eq!(parsed("Monday"), Ok(date(2025, 4, 14)));
+    }
+
+    #[test]
+    fn nonsense_is_an_error() {
+        assert!(parsed("").is_err());
+        assert_eq!(parsed("soonish"), Err("Don't understand 'soonish'".into()));
+    }
+
+    #[test]
+    fn times() {
+        let t = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        assert_eq!(parse_time("19:00"), Ok(t(19, 0)));
+        assert_eq!(parse_time("7pm"), Ok(t(19, 0)));
+        assert_eq!(parse_time("7:30 pm"), Ok(t(19, 30)));
+        assert_eq!(parse_time("12am"), Ok(t(0, 0)));
+        assert_eq!(parse_time("noon"), Ok(t(12, 0)));
+        assert_eq!(parse_time("7"), Ok(t(7, 0)));
+        assert!(parse_time("13pm").is_err());
+        assert!(parse_time("25:00").is_err());
+    }
+}
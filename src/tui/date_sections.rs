@@ -0,0 +1,52 @@
+//! The `date_sections` headers of the Schedule list when sorted by due
+//! date: how far off each assignment's due date is, by local calendar day.
+
+use chrono::{DateTime, Datelike, Local, Utc};
+
+/// A Schedule list section, in due date order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DateSection {
+    Overdue,
+    Today,
+    Tomorrow,
+    /// Later this week, which ends on Sunday.
+    ThisWeek,
+    NextWeek,
+    Later,
+    NoDate,
+}
+
+impl DateSection {
+    /// The section of an assignment due at `due`, as of `now`.  A due time
+    /// already passed is overdue even when it was earlier today.
+    pub fn of(due: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Self {
+        let Some(due) = due else {
+            return Self::NoDate;
+        };
+        if due < now {
+            return Self::Overdue;
+        }
+        let today = now.with_timezone(&Local).date_naive();
+        let days = (due.with_timezone(&Local).date_naive() - today).num_days();
+        let left_this_week = 6 - i64::from(today.weekday().num_days_from_monday());
+        match days {
+            0 => Self::Today,
+            1 => Self::Tomorrow,
+            d if d <= left_this_week => Self::ThisWeek,
+            d if d <= left_this_week + 7 => Self::NextWeek,
+            _ => Self::Later,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Overdue => "Overdue",
+            Self::Today => "Today",
+            Self::Tomorrow => "Tomorrow",
+            Self::ThisWeek => "This week",
+            Self::NextWeek => "Next week",
+            Self::Later => "Later",
+            Self::NoDate => "No date",
+        }
+    }
+}
@@ -1,7 +1,7 @@
-use crossterm::event::{self, Event, KeyCode, KeyModifiers};
-use std::time::Duration;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::time::{Duration, Instant};
 
-use super::{App, SubmissionKind, SubmissionState, UnifiedViewMode};
+use super::{App, Focus, NavTarget, SubmissionKind, SubmissionState, UnifiedViewMode};
 
 pub fn poll_event(timeout: Duration) -> anyhow::Result<Option<Event>> {
     if event::poll(timeout)? {
@@ -11,67 +11,346 @@ pub fn poll_event(timeout: Duration) -> anyhow::Result<Option<Event>> {
     }
 }
 
+/// The key of a key press or auto-repeat.  Windows consoles also report
+/// each key's release, which must not act a second time.
+pub fn key_press(event: Event) -> Option<KeyEvent> {
+    match event {
+        Event::Key(key) if key.kind != KeyEventKind::Release => Some(key),
+        _ => None,
+    }
+}
+
+/// A typed character with the modifiers that only chose it taken off, so
+/// key bindings and text fields see the character itself.  Shift is part
+/// of the character (some terminals send `g` with Shift for `G`), and
+/// AltGr arrives as Ctrl+Alt on Windows, so `@` from AltGr+2 or Ctrl+Alt+Q
+/// is plain `@`.
+fn normalize_key(code: KeyCode, modifiers: KeyModifiers) -> (KeyCode, KeyModifiers) {
+    let KeyCode::Char(c) = code else {
+        return (code, modifiers);
+    };
+    let mut modifiers = modifiers;
+    let mut c = c;
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        // Only where the capital is one character (not `ß` → "SS").
+        let mut upper = c.to_uppercase();
+        if let (Some(u), None) = (upper.next(), upper.next()) {
+            c = u;
+        }
+        modifiers.remove(KeyModifiers::SHIFT);
+    }
+    if modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+        modifiers.remove(KeyModifiers::CONTROL | KeyModifiers::ALT);
+    }
+    (KeyCode::Char(c), modifiers)
+}
+
+/// Whether this is a Ctrl+key shortcut, which text fields don't take as
+/// typing (after `normalize_key`, so AltGr characters aren't).
+fn is_ctrl_char(code: KeyCode, modifiers: KeyModifiers) -> bool {
+    matches!(code, KeyCode::Char(_)) && modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Two-key bindings: first key, second key, and the navigation key the pair
+/// stands for.  A first key pressed alone still does its own thing once
+/// `CHORD_TIMEOUT` passes or another key follows.
+const CHORDS: [(char, char, KeyCode); 2] = [
+    ('g', 'g', KeyCode::Home),
+    ('g', 'e', KeyCode::End),
+];
+
+/// How long the first key of a chord waits for the second.
+const CHORD_TIMEOUT: Duration = Duration::from_millis(800);
+
 pub fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+    let (code, modifiers) = normalize_key(code, modifiers);
+
+    // ── Quit confirmation intercepts everything while open ────────────
+    if app.confirm_quit {
+        app.confirm_quit = false;
+        if matches!(code, KeyCode::Char('y' | 'Y')) {
+            app.running = false;
+        }
+        return;
+    }
+
     // ── Submission modal intercepts everything while open ─────────────
     if !app.submission_state.is_hidden() {
-        handle_submission_key(app, code);
+        // Ctrl+C quits from any step; `q` where it isn't typed or bound.
+        let ctrl_c = code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL);
+        if ctrl_c || (code == KeyCode::Char('q') && app.submission_unfinished()) {
+            app.request_quit();
+            return;
+        }
+        if !is_ctrl_char(code, modifiers) {
+            handle_submission_key(app, code);
+        }
         return;
     }
 
-    // ── Course pages picker intercepts while open ────────────────────
-    if app.show_course_pages_picker {
-        handle_course_pages_key(app, code);
+    // ── `:` command intercepts while typing ──────────────────────────
+    if app.command_line.is_some() {
+        handle_command_line_key(app, code, modifiers);
         return;
     }
 
-    match (code, modifiers) {
-        (KeyCode::Char('q'), _) | (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
-            app.running = false;
-            return;
+    // ── Prompts below take typing; other Ctrl+letters aren't text ────
+    let typing = app.event_form.is_some()
+        || app.estimate_prompt.is_some()
+        || app.date_jump_input.is_some();
+    if typing && is_ctrl_char(code, modifiers) {
+        return;
+    }
+
+    // ── New-event form intercepts while open ─────────────────────────
+    if app.event_form.is_some() {
+        handle_event_form_key(app, code);
+        return;
+    }
+
+    // ── Estimate prompt intercepts while open ────────────────────────
+    if app.estimate_prompt.is_some() {
+        handle_estimate_key(app, code);
+        return;
+    }
+
+    // ── Jump-to-date prompt intercepts while open ────────────────────
+    if app.date_jump_input.is_some() {
+        handle_date_jump_key(app, code);
+        return;
+    }
+
+    // ── Copy mode intercepts while selecting ─────────────────────────
+    if app.copy_selection.is_some() {
+        handle_copy_key(app, code);
+        return;
+    }
+
+    let subject = app.detail_subject();
+    let announcement_before = app.announcement_list_state.selected;
+    handle_view_key(app, code, modifiers);
+
+    // The detail pane scrolls from the top for each new item, and a new
+    // tab starts with its list focused.
+    let now = app.detail_subject();
+    if now != subject {
+        app.detail_scroll = 0;
+        app.course_announcement_state.selected = 0;
+        if now.0 != subject.0 {
+            app.pane_focus = Focus::List;
         }
-        (KeyCode::Tab, _) | (KeyCode::Right, KeyModifiers::SHIFT) => {
-            app.active_tab = app.active_tab.next();
+    }
+    // Each announcement starts with its first attachment selected.
+    if app.announcement_list_state.selected != announcement_before {
+        app.announcement_attachment = 0;
+        app.prefetch_older_announcements();
+    }
+}
+
+/// Keys for the tabs and their popups, dispatched on `App::focus`.
+fn handle_view_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+    if let Some((first, _)) = app.pending_chord.take() {
+        let count = app.pending_count.take();
+        let chord = CHORDS.iter().find(|(a, b, _)| *a == first && code == KeyCode::Char(*b));
+        if let Some((_, _, key)) = chord {
+            handle_navigation(app, *key, count, true);
             return;
         }
-        (KeyCode::BackTab, _) | (KeyCode::Left, KeyModifiers::SHIFT) => {
-            app.active_tab = app.active_tab.prev();
-            return;
+        // Not a chord after all: the first key acts alone, then this one.
+        first_key_alone(app, first, count);
+    }
+    let focus = app.focus();
+    if focus != Focus::Popup {
+        match (code, modifiers) {
+            (KeyCode::Char('q'), _) | (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
+                app.running = false;
+                return;
+            }
+            (KeyCode::Char(':'), _) => {
+                app.pending_count = None;
+                app.command_line = Some(String::new());
+                return;
+            }
+            (KeyCode::Tab, _) | (KeyCode::Right, KeyModifiers::SHIFT) => {
+                app.pending_count = None;
+                app.pending_chord = None;
+                app.active_tab = app.active_tab.next();
+                return;
+            }
+            (KeyCode::BackTab, _) | (KeyCode::Left, KeyModifiers::SHIFT) => {
+                app.pending_count = None;
+                app.pending_chord = None;
+                app.active_tab = app.active_tab.prev();
+                return;
+            }
+            (KeyCode::Char('t'), KeyModifiers::CONTROL) => {
+                app.pending_count = None;
+                app.pending_chord = None;
+                app.open_term_picker();
+                return;
+            }
+            _ => {}
         }
-        (KeyCode::Char('1'), _) => {
-            app.active_tab = super::Tab::Dashboard;
+
+        // ── Digits: count prefix while one is pending, otherwise tab jump ──
+        if let KeyCode::Char(c @ '0'..='9') = code {
+            let digit = c.to_digit(10).unwrap_or(0) as usize;
+            match (app.pending_count, super::Tab::from_digit(c)) {
+                (Some(n), _) => {
+                    app.pending_count = Some(n.saturating_mul(10).saturating_add(digit));
+                }
+                (None, Some(tab)) => app.active_tab = tab,
+                // Digits with no tab of their own start a count.
+                (None, None) if digit > 0 => app.pending_count = Some(digit),
+                (None, None) => {}
+            }
             return;
         }
-        (KeyCode::Char('2'), _) => {
-            app.active_tab = super::Tab::Courses;
+    }
+    let count = app.pending_count.take();
+    if let KeyCode::Char(c) = code {
+        if CHORDS.iter().any(|(a, _, _)| *a == c) && app.nav_target().is_some() {
+            app.pending_chord = Some((c, Instant::now()));
+            app.pending_count = count;
             return;
         }
-        (KeyCode::Char('3'), _) => {
-            app.active_tab = super::Tab::Assignments;
-            return;
+    }
+
+    if handle_navigation(app, code, count, false) {
+        return;
+    }
+    // Esc stops a running download of course files before anything else.
+    if code == KeyCode::Esc && focus != Focus::Popup && app.bulk_download.is_some() {
+        app.cancel_bulk_download();
+        return;
+    }
+    match focus {
+        Focus::Popup => handle_popup_key(app, code),
+        Focus::Detail if code == KeyCode::Esc => app.pane_focus = Focus::List,
+        Focus::List | Focus::Detail => {
+            // Courses tab: a letter no binding took jumps to the next
+            // course starting with it.
+            if !handle_tab_key(app, code) && app.active_tab == super::Tab::Courses {
+                if let KeyCode::Char(c) = code {
+                    if c.is_alphabetic() {
+                        app.jump_to_course_starting_with(c);
+                    }
+                }
+            }
         }
-        (KeyCode::Char('4'), _) => {
-            app.active_tab = super::Tab::Announcements;
-            return;
+    }
+}
+
+/// Let the first key of a chord act alone once no second key came within
+/// `CHORD_TIMEOUT`.  Called by the main loop between events.
+pub fn expire_chord(app: &mut App) {
+    if app.pending_chord.is_some_and(|(_, at)| at.elapsed() >= CHORD_TIMEOUT) {
+        if let Some((first, _)) = app.pending_chord.take() {
+            let count = app.pending_count.take();
+            first_key_alone(app, first, count);
         }
-        _ => {}
     }
+}
 
-    match code {
-        KeyCode::Down | KeyCode::Char('j') => {
-            app.active_list_state_mut().select_next();
+/// What the first key of a chord does on its own: `g` goes to the top,
+/// unless `vim_gg` keeps that for `gg`.
+fn first_key_alone(app: &mut App, key: char, count: Option<usize>) {
+    if key == 'g' && !app.vim_gg {
+        handle_navigation(app, KeyCode::Char('g'), count, false);
+    }
+}
+
+/// j/k, g/G and the arrow keys, applied to whatever `App::nav_target`
+/// says has focus.  `chord` is set for the keys `CHORDS` stand for, so
+/// `Ngg` goes to the Nth item.  Returns whether `code` was one of them.
+fn handle_navigation(app: &mut App, code: KeyCode, count: Option<usize>, chord: bool) -> bool {
+    let is_nav = matches!(
+        code,
+        KeyCode::Down
+            | KeyCode::Up
+            | KeyCode::Home
+            | KeyCode::End
+            | KeyCode::Char('j' | 'k' | 'g' | 'G')
+    );
+    if !is_nav || app.nav_target().is_none() {
+        return false;
+    }
+    let wrap = app.wrap_navigation;
+    let steps = count.unwrap_or(1).min(isize::MAX as usize) as isize;
+    match app.nav_target() {
+        Some(NavTarget::List(ls)) => match code {
+            KeyCode::Down | KeyCode::Char('j') => ls.move_by(steps, wrap),
+            KeyCode::Up | KeyCode::Char('k') => ls.move_by(-steps, wrap),
+            // `Ngg` goes to the Nth item, like `NG`.
+            KeyCode::Home | KeyCode::Char('g') => {
+                ls.selected = match count {
+                    Some(n) if chord && ls.len > 0 => n.clamp(1, ls.len) - 1,
+                    _ => 0,
+                };
+            }
+            // `NG` jumps to the Nth item, plain `G` to the last.
+            _ => {
+                if ls.len > 0 {
+                    ls.selected = count.map_or(ls.len, |n| n.clamp(1, ls.len)) - 1;
+                }
+            }
+        },
+        Some(NavTarget::Scroll(at, max)) => {
+            let lines = count.unwrap_or(1).min(u16::MAX as usize) as u16;
+            *at = match code {
+                KeyCode::Down | KeyCode::Char('j') => at.saturating_add(lines).min(max),
+                KeyCode::Up | KeyCode::Char('k') => at.saturating_sub(lines),
+                KeyCode::Home | KeyCode::Char('g') => 0,
+                _ => max,
+            };
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            app.active_list_state_mut().select_prev();
+        None => {}
+    }
+    true
+}
+
+/// Keys for the open popup, other than navigation.
+fn handle_popup_key(app: &mut App, code: KeyCode) {
+    if app.show_course_filter {
+        handle_course_filter_key(app, code);
+    } else if app.bulk_summary.is_some() {
+        if matches!(code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')) {
+            app.bulk_summary = None;
         }
-        KeyCode::Home | KeyCode::Char('g') => {
-            app.active_list_state_mut().selected = 0;
+    } else if app.show_term_picker {
+        match code {
+            KeyCode::Enter => app.pick_term(app.term_list_state.selected),
+            KeyCode::Esc | KeyCode::Char('q') => app.show_term_picker = false,
+            _ => {}
         }
-        KeyCode::End | KeyCode::Char('G') => {
-            let ls = app.active_list_state_mut();
-            if ls.len > 0 {
-                ls.selected = ls.len - 1;
-            }
+    } else if app.show_course_pages_picker {
+        handle_course_pages_key(app, code);
+    } else if app.show_sync_stats {
+        // `h` switches between the last sync and the history; its other
+        // keys close it.
+        if code == KeyCode::Char('h') {
+            app.sync_stats_history = !app.sync_stats_history;
+        } else if matches!(code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('S' | 'q')) {
+            app.show_sync_stats = false;
+        }
+    } else if app.show_profile {
+        if matches!(code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('U' | 'q')) {
+            app.show_profile = false;
         }
+    } else if app.link_picker.is_some() {
+        handle_link_picker_key(app, code);
+    } else if app.event_link_picker.is_some() {
+        handle_event_link_picker_key(app, code);
+    }
+}
+
+/// Keys for the active tab's list and detail pane, other than navigation.
+/// Returns whether `code` is bound here; a key that is bound but can't act
+/// right now (`r` during a sync) still counts.
+fn handle_tab_key(app: &mut App, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Char('w') => app.toggle_pane_focus(),
+        KeyCode::Char('v') if app.pane_focus == Focus::Detail => app.start_copy_mode(),
         KeyCode::Char('v') if app.active_tab == super::Tab::Assignments => {
             app.unified_view_mode = app.unified_view_mode.toggle();
             // Jump to today when switching into calendar view.
@@ -86,6 +365,35 @@ pub fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
                 }
             }
         }
+        KeyCode::Char('a') if app.active_tab == super::Tab::Assignments => {
+            let mut form = super::EventForm::default();
+            form.fields[1] = "today".into();
+            app.event_form = Some(form);
+        }
+        KeyCode::Char('J') if app.active_tab == super::Tab::Assignments => {
+            app.date_jump_input = Some(String::new());
+            app.date_jump_error = None;
+        }
+        KeyCode::Char('e')
+            if matches!(app.active_tab, super::Tab::Assignments | super::Tab::Dashboard) =>
+        {
+            app.open_estimate_prompt();
+        }
+        KeyCode::Char('o') if app.has_peer_review_to_open() => {
+            app.open_peer_review();
+        }
+        KeyCode::Char('l')
+            if app.active_tab == super::Tab::Assignments
+                && app.unified_view_mode == UnifiedViewMode::CalendarView =>
+        {
+            app.toggle_event_link();
+        }
+        KeyCode::Char('o')
+            if app.active_tab == super::Tab::Assignments
+                && app.unified_view_mode == UnifiedViewMode::CalendarView =>
+        {
+            app.toggle_dimmed_events();
+        }
         KeyCode::Char('s')
             if app.active_tab == super::Tab::Assignments
                 && app.unified_view_mode == UnifiedViewMode::ListView =>
@@ -94,15 +402,29 @@ pub fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
             app.assignment_list_state.selected = 0;
         }
         KeyCode::Char('f')
-            if app.active_tab == super::Tab::Assignments
-                && app.unified_view_mode == UnifiedViewMode::ListView =>
+            if matches!(app.active_tab, super::Tab::Assignments | super::Tab::Dashboard) =>
         {
-            let count = app.assignment_course_names().len();
+            let count = app.assignment_courses().len();
             app.filter_list_state.set_len(count);
             app.filter_list_state.selected = 0;
             app.show_course_filter = true;
         }
-        // Courses tab: Enter fetches page list, Esc closes detail.
+        KeyCode::Char('s') if app.active_tab == super::Tab::Courses => {
+            app.cycle_course_sort();
+        }
+        KeyCode::Char('B') if app.active_tab == super::Tab::Courses => {
+            app.start_bulk_download();
+        }
+        // Courses tab: Enter on an announcement in the focused summary
+        // opens it; otherwise Enter fetches page list, Esc closes detail.
+        KeyCode::Enter
+            if app.active_tab == super::Tab::Courses
+                && app.pane_focus == Focus::Detail
+                && app.course_summary_shown()
+                && !app.course_announcements().is_empty() =>
+        {
+            app.open_course_announcement();
+        }
         KeyCode::Enter if app.active_tab == super::Tab::Courses => {
             app.fetch_course_pages();
         }
@@ -116,28 +438,197 @@ pub fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
         KeyCode::Enter if app.active_tab == super::Tab::Assignments => {
             app.open_submission_modal();
         }
-        // Open submission modal from the dashboard overview.
-        KeyCode::Enter if app.active_tab == super::Tab::Dashboard => {
-            app.open_dashboard_submission_modal();
+        // Enter/Space on the dashboard's fold row expands or collapses it.
+        KeyCode::Enter | KeyCode::Char(' ')
+            if app.active_tab == super::Tab::Dashboard && app.dashboard_fold_selected() =>
+        {
+            app.toggle_dashboard_later();
+        }
+        // Open submission modal from the dashboard overview, or jump to a
+        // recently graded assignment in the schedule.
+        KeyCode::Enter if app.active_tab == super::Tab::Dashboard => match app.dashboard_panel {
+            super::DashboardPanel::Upcoming => app.open_dashboard_submission_modal(),
+            super::DashboardPanel::RecentlyGraded => app.open_graded_in_schedule(),
+        },
+        KeyCode::Char('v') if app.active_tab == super::Tab::Dashboard => {
+            app.toggle_dashboard_panel();
+        }
+        KeyCode::Char('[') if app.active_tab == super::Tab::Announcements => {
+            app.cycle_announcement_attachment(-1);
+        }
+        KeyCode::Char(']') if app.active_tab == super::Tab::Announcements => {
+            app.cycle_announcement_attachment(1);
+        }
+        KeyCode::Char('d') if app.active_tab == super::Tab::Announcements => {
+            app.download_announcement_attachment();
+        }
+        KeyCode::Enter | KeyCode::Char(' ')
+            if app.active_tab == super::Tab::Announcements && app.archive_fold_selected() =>
+        {
+            app.toggle_show_archived();
+        }
+        KeyCode::Enter | KeyCode::Char(' ')
+            if app.active_tab == super::Tab::Announcements && app.older_row_selected() =>
+        {
+            app.load_older_announcements();
+        }
+        KeyCode::Char('x') if app.active_tab == super::Tab::Announcements => {
+            app.archive_selected_announcement();
+        }
+        KeyCode::Char('X') if app.active_tab == super::Tab::Announcements => {
+            app.unarchive_selected_announcement();
+        }
+        KeyCode::Char('x')
+            if matches!(app.active_tab, super::Tab::Assignments | super::Tab::Dashboard) =>
+        {
+            app.run_open_handler();
+        }
+        KeyCode::Char('A')
+            if matches!(app.active_tab, super::Tab::Assignments | super::Tab::Dashboard) =>
+        {
+            app.load_all_assignments();
+        }
+        KeyCode::Char('S') => {
+            app.show_sync_stats = true;
+            app.sync_stats_history = false;
+        }
+        KeyCode::Char('U') => app.open_profile(),
+        KeyCode::Char('X') => {
+            app.export_report();
+        }
+        KeyCode::Char('D') => {
+            app.view_raw_detail();
+        }
+        KeyCode::Char('L') if app.active_tab != super::Tab::Announcements => {
+            app.open_link_picker();
         }
         KeyCode::Char('t') => {
             app.jump_to_today_active();
         }
-        KeyCode::Char('r') if !app.loading => {
-            app.needs_refresh = true;
+        KeyCode::Char('r') => {
+            if !app.loading {
+                app.needs_refresh = true;
+            }
+        }
+        _ => return false,
+    }
+    true
+}
+
+fn handle_event_form_key(app: &mut App, code: KeyCode) {
+    let Some(form) = app.event_form.as_mut() else {
+        return;
+    };
+    if form.saving {
+        return;
+    }
+    let field_count = form.fields.len();
+    match code {
+        KeyCode::Tab | KeyCode::Down => form.focus = (form.focus + 1) % field_count,
+        KeyCode::BackTab | KeyCode::Up => form.focus = (form.focus + field_count - 1) % field_count,
+        KeyCode::Char(c) => {
+            form.fields[form.focus].push(c);
+            form.error = None;
+        }
+        KeyCode::Backspace => {
+            form.fields[form.focus].pop();
+            form.error = None;
         }
+        KeyCode::Enter => app.submit_event_form(),
+        KeyCode::Esc => app.event_form = None,
         _ => {}
     }
 }
 
-fn handle_course_pages_key(app: &mut App, code: KeyCode) {
+fn handle_date_jump_key(app: &mut App, code: KeyCode) {
+    let Some(input) = app.date_jump_input.as_mut() else {
+        return;
+    };
     match code {
-        KeyCode::Down | KeyCode::Char('j') => {
-            app.course_pages_list_state.select_next();
+        KeyCode::Char(c) => {
+            input.push(c);
+            app.date_jump_error = None;
+        }
+        KeyCode::Backspace => {
+            input.pop();
+            app.date_jump_error = None;
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            app.course_pages_list_state.select_prev();
+        KeyCode::Enter => app.submit_date_jump(),
+        KeyCode::Esc => {
+            app.date_jump_input = None;
+            app.date_jump_error = None;
         }
+        _ => {}
+    }
+}
+
+fn handle_command_line_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+    // Ctrl+C quits as it does everywhere; other Ctrl+letters aren't text.
+    if is_ctrl_char(code, modifiers) {
+        if code == KeyCode::Char('c') {
+            app.command_line = None;
+            app.request_quit();
+        }
+        return;
+    }
+    let Some(input) = app.command_line.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Char(c) => input.push(c),
+        // Backspace past the `:` leaves, as in vim.
+        KeyCode::Backspace if input.is_empty() => app.command_line = None,
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Enter => app.run_command_line(),
+        KeyCode::Esc => app.command_line = None,
+        _ => {}
+    }
+}
+
+fn handle_estimate_key(app: &mut App, code: KeyCode) {
+    let Some(prompt) = app.estimate_prompt.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Char(c) => {
+            prompt.input.push(c);
+            prompt.error = None;
+        }
+        KeyCode::Backspace => {
+            prompt.input.pop();
+            prompt.error = None;
+        }
+        KeyCode::Enter => app.submit_estimate(),
+        KeyCode::Esc => app.estimate_prompt = None,
+        _ => {}
+    }
+}
+
+fn handle_course_filter_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char(' ') => {
+            let course = app
+                .assignment_courses()
+                .get(app.filter_list_state.selected)
+                .map(|(id, _)| *id);
+            if let Some(id) = course {
+                app.toggle_course_filter(id);
+            }
+        }
+        KeyCode::Char('u') => app.toggle_hide_unpublished(),
+        KeyCode::Char('a') => app.select_all_courses(),
+        KeyCode::Char('n') => app.select_no_courses(),
+        KeyCode::Enter | KeyCode::Esc | KeyCode::Char('f') | KeyCode::Char('q') => {
+            app.show_course_filter = false;
+        }
+        _ => {}
+    }
+}
+
+fn handle_course_pages_key(app: &mut App, code: KeyCode) {
+    match code {
         KeyCode::Enter => {
             app.fetch_selected_page();
         }
@@ -148,6 +639,50 @@ fn handle_course_pages_key(app: &mut App, code: KeyCode) {
     }
 }
 
+fn handle_link_picker_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => {
+            app.open_picked_link(app.link_picker_state.selected);
+        }
+        KeyCode::Char(c @ '1'..='9') => {
+            app.open_picked_link(c as usize - '1' as usize);
+        }
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('L') => {
+            app.link_picker = None;
+        }
+        _ => {}
+    }
+}
+
+fn handle_copy_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => app.move_copy_cursor(1),
+        KeyCode::Char('k') | KeyCode::Up => app.move_copy_cursor(-1),
+        KeyCode::PageDown => app.move_copy_cursor(10),
+        KeyCode::PageUp => app.move_copy_cursor(-10),
+        KeyCode::Char('g') => app.move_copy_cursor(isize::MIN),
+        KeyCode::Char('G') => app.move_copy_cursor(isize::MAX),
+        KeyCode::Char('y') | KeyCode::Enter => app.copy_selected_lines(),
+        KeyCode::Esc | KeyCode::Char('q' | 'v') => app.copy_selection = None,
+        _ => {}
+    }
+}
+
+fn handle_event_link_picker_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => {
+            app.link_picked_assignment(app.event_link_picker_state.selected);
+        }
+        KeyCode::Char(c @ '1'..='9') => {
+            app.link_picked_assignment(c as usize - '1' as usize);
+        }
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('l') => {
+            app.event_link_picker = None;
+        }
+        _ => {}
+    }
+}
+
 fn handle_submission_key(app: &mut App, code: KeyCode) {
     // Clone the current state so we can pattern-match while mutating app.
     let state = std::mem::replace(&mut app.submission_state, SubmissionState::Hidden);
@@ -156,39 +691,40 @@ fn handle_submission_key(app: &mut App, code: KeyCode) {
         // ── TypePicker ────────────────────────────────────────────────
         SubmissionState::TypePicker => match code {
             KeyCode::Up | KeyCode::Char('k') => {
-                if app.submission_cursor > 0 {
-                    app.submission_cursor -= 1;
-                }
+                app.submission_type_state.select_prev(app.wrap_navigation);
                 app.submission_state = SubmissionState::TypePicker;
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                if app.submission_cursor + 1 < app.submission_supported_kinds.len() {
-                    app.submission_cursor += 1;
-                }
+                app.submission_type_state.select_next(app.wrap_navigation);
                 app.submission_state = SubmissionState::TypePicker;
             }
             KeyCode::Enter => {
                 let kind = app
                     .submission_supported_kinds
-                    .get(app.submission_cursor)
+                    .get(app.submission_type_state.selected)
                     .cloned();
                 match kind {
                     Some(SubmissionKind::TextEntry) => {
                         app.submission_kind = Some(SubmissionKind::TextEntry);
-                        app.launch_editor = true;
-                        // State stays Hidden until the editor returns; main.rs
-                        // sets it to TextPreview or back to TypePicker.
-                        app.submission_state = SubmissionState::Hidden;
+                        app.edit_submission_text();
+                    }
+                    Some(SubmissionKind::DiscussionReply) => {
+                        app.submission_kind = Some(SubmissionKind::DiscussionReply);
+                        app.fetch_discussion_entries();
+                        app.submission_state = SubmissionState::DiscussionContext;
                     }
                     Some(SubmissionKind::Url) => {
                         app.submission_kind = Some(SubmissionKind::Url);
                         app.submission_input.clear();
+                        app.submission_url_error = None;
                         app.submission_state = SubmissionState::UrlInput;
                     }
                     Some(SubmissionKind::FileUpload) => {
                         app.submission_kind = Some(SubmissionKind::FileUpload);
-                        app.submission_input.clear();
-                        app.submission_state = SubmissionState::FileInput;
+                        app.choose_submission_file();
+                    }
+                    Some(SubmissionKind::OpenInBrowser) => {
+                        app.open_submission_in_browser();
                     }
                     None => {
                         app.submission_state = SubmissionState::TypePicker;
@@ -203,6 +739,33 @@ fn handle_submission_key(app: &mut App, code: KeyCode) {
             }
         },
 
+        // ── FilePicker — newest files in submit_watch_dirs ───────────
+        SubmissionState::FilePicker => {
+            app.submission_state = SubmissionState::FilePicker;
+            match code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    app.recent_file_state.select_prev(app.wrap_navigation);
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    app.recent_file_state.select_next(app.wrap_navigation);
+                }
+                KeyCode::Enter => {
+                    if let Some(file) = app.recent_files.get(app.recent_file_state.selected) {
+                        app.submission_input = file.path.display().to_string();
+                        app.submission_file = Some(file.path.clone());
+                        app.submission_state = SubmissionState::Confirming;
+                    }
+                }
+                KeyCode::Char('/') => {
+                    app.submission_state = SubmissionState::FileInput;
+                }
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    app.submission_state = SubmissionState::TypePicker;
+                }
+                _ => {}
+            }
+        }
+
         // ── UrlInput / FileInput (same controls) ──────────────────────
         SubmissionState::UrlInput | SubmissionState::FileInput => {
             let next_state = if matches!(state, SubmissionState::UrlInput) {
@@ -213,20 +776,52 @@ fn handle_submission_key(app: &mut App, code: KeyCode) {
             match code {
                 KeyCode::Char(c) => {
                     app.submission_input.push(c);
+                    app.submission_url_error = None;
                     app.submission_state = next_state;
                 }
                 KeyCode::Backspace => {
                     app.submission_input.pop();
+                    app.submission_url_error = None;
                     app.submission_state = next_state;
                 }
+                KeyCode::Enter if matches!(next_state, SubmissionState::FileInput) => {
+                    match super::typed_file_path(&app.submission_input) {
+                        Ok(path) => {
+                            app.submission_input = path.display().to_string();
+                            app.submission_file = Some(path);
+                            app.submission_state = SubmissionState::Confirming;
+                        }
+                        Err(e) => {
+                            app.status_message = format!("{e:#}");
+                            app.submission_state = next_state;
+                        }
+                    }
+                }
                 KeyCode::Enter if !app.submission_input.trim().is_empty() => {
-                    app.submission_state = SubmissionState::Confirming;
+                    // The confirm step shows the URL as it will be sent.
+                    match super::submission_url::normalize(&app.submission_input) {
+                        Ok(url) => {
+                            app.submission_input = url.to_string();
+                            app.submission_state = SubmissionState::Confirming;
+                        }
+                        Err(e) => {
+                            app.submission_url_error = Some(e);
+                            app.submission_state = next_state;
+                        }
+                    }
                 }
                 KeyCode::Enter => {
                     app.submission_state = next_state;
                 }
                 KeyCode::Esc => {
-                    app.submission_state = SubmissionState::TypePicker;
+                    // Back to the recent files the path prompt was opened from.
+                    let from_picker = matches!(next_state, SubmissionState::FileInput)
+                        && !app.recent_files.is_empty();
+                    app.submission_state = if from_picker {
+                        SubmissionState::FilePicker
+                    } else {
+                        SubmissionState::TypePicker
+                    };
                 }
                 _ => {
                     app.submission_state = next_state;
@@ -237,7 +832,14 @@ fn handle_submission_key(app: &mut App, code: KeyCode) {
         // ── TextPreview (content from $EDITOR) ───────────────────────
         SubmissionState::TextPreview => match code {
             KeyCode::Char('y') | KeyCode::Enter => {
-                app.submission_state = SubmissionState::TextPreview;
+                if let Some(reason) = app.submission_blocked_reason() {
+                    app.status_message = reason.into();
+                    app.submission_state = SubmissionState::TextPreview;
+                    return;
+                }
+                // Enter Submitting before spawning so a repeated key lands
+                // in the Submitting branch instead of re-confirming.
+                app.submission_state = SubmissionState::Submitting;
                 app.start_submission();
             }
             KeyCode::Char('n') | KeyCode::Esc => {
@@ -251,7 +853,14 @@ fn handle_submission_key(app: &mut App, code: KeyCode) {
         // ── Confirming (URL or file path) ─────────────────────────────
         SubmissionState::Confirming => match code {
             KeyCode::Char('y') | KeyCode::Enter => {
-                app.submission_state = SubmissionState::Confirming;
+                if let Some(reason) = app.submission_blocked_reason() {
+                    app.status_message = reason.into();
+                    app.submission_state = SubmissionState::Confirming;
+                    return;
+                }
+                // Enter Submitting before spawning so a repeated key lands
+                // in the Submitting branch instead of re-confirming.
+                app.submission_state = SubmissionState::Submitting;
                 app.start_submission();
             }
             KeyCode::Char('n') | KeyCode::Esc => {
@@ -262,21 +871,300 @@ fn handle_submission_key(app: &mut App, code: KeyCode) {
             }
         },
 
+        // ── DiscussionContext — read replies, then write one ──────────
+        SubmissionState::DiscussionContext => {
+            app.submission_state = SubmissionState::DiscussionContext;
+            match code {
+                KeyCode::Down | KeyCode::Char('j') => {
+                    app.discussion_scroll = app.discussion_scroll.saturating_add(1);
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    app.discussion_scroll = app.discussion_scroll.saturating_sub(1);
+                }
+                KeyCode::PageDown | KeyCode::Char(' ') => {
+                    app.discussion_scroll = app.discussion_scroll.saturating_add(10);
+                }
+                KeyCode::PageUp => {
+                    app.discussion_scroll = app.discussion_scroll.saturating_sub(10);
+                }
+                KeyCode::Enter | KeyCode::Char('r') => {
+                    // Same hand-off as text entry: the editor, then TextPreview.
+                    app.edit_submission_text();
+                }
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    app.discussion_entries_rx = None;
+                    app.submission_state = SubmissionState::TypePicker;
+                }
+                _ => {}
+            }
+        }
+
         // ── Done — any key dismisses ──────────────────────────────────
         SubmissionState::Done { .. } => {
             app.submission_state = SubmissionState::Hidden;
         }
 
-        // ── Submitting — Esc cancels if the rx is still pending ───────
+        // ── Submitting — Esc aborts unless the final POST is out ──────
         SubmissionState::Submitting => {
-            if code == KeyCode::Esc && app.submission_rx.is_some() {
-                app.submission_rx = None;
-                app.submission_state = SubmissionState::TypePicker;
-            } else {
-                app.submission_state = SubmissionState::Submitting;
+            app.submission_state = SubmissionState::Submitting;
+            if code == KeyCode::Esc {
+                app.cancel_submission();
             }
         }
 
         SubmissionState::Hidden => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{Reply, TestServer};
+    use crate::tui::{test_fixtures, SubmitResult};
+    use std::time::Duration;
+    use tokio::sync::oneshot;
+
+    fn press(app: &mut App, c: char) {
+        handle_key(app, KeyCode::Char(c), KeyModifiers::NONE);
+    }
+
+    /// Waits for the submission task and applies its result.
+    async fn finish_submission(app: &mut App) {
+        for _ in 0..200 {
+            if app.poll_submission_result() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("submission never finished");
+    }
+
+    /// The Courses tab with these courses, in this order.
+    fn courses_tab(dir: &std::path::Path, names: &[&str]) -> App {
+        let mut app = test_fixtures::offline_app(dir);
+        app.courses = names
+            .iter()
+            .zip(1..)
+            .map(|(name, id)| test_fixtures::course(id, name))
+            .collect();
+        app.rebuild_course_display_order();
+        app.active_tab = super::super::Tab::Courses;
+        app
+    }
+
+    fn selected_course(app: &App) -> &str {
+        let idx = app.course_display_order[app.course_list_state.selected];
+        app.courses[idx].name.as_deref().unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn unbound_letters_jump_to_courses() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = courses_tab(dir.path(), &["Algebra", "Biology", "Chemistry", "Biochem"]);
+        press(&mut app, 'b');
+        let first = selected_course(&app).to_string();
+        assert!(first.starts_with('B'));
+        // Pressing it again moves on to the next one.
+        press(&mut app, 'b');
+        assert!(selected_course(&app).starts_with('B'));
+        assert_ne!(selected_course(&app), first);
+        press(&mut app, 'C');
+        assert_eq!(selected_course(&app), "Chemistry");
+    }
+
+    #[tokio::test]
+    async fn bound_letters_keep_their_binding_on_the_courses_tab() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = courses_tab(dir.path(), &["Algebra", "Rhetoric", "Statistics", "Writing"]);
+        let sort = app.course_sort;
+        press(&mut app, 's');
+        assert_ne!(app.course_sort, sort);
+        assert_eq!(app.course_list_state.selected, 0);
+
+        // `r` during a sync does nothing rather than jumping to Rhetoric.
+        app.loading = true;
+        press(&mut app, 'r');
+        assert!(!app.needs_refresh);
+        assert_eq!(app.course_list_state.selected, 0);
+
+        press(&mut app, 'w');
+        assert_eq!(app.pane_focus, Focus::Detail);
+        assert_eq!(app.course_list_state.selected, 0);
+    }
+
+    #[test]
+    fn x_on_announcements_only_restores_archived_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::offline_app(dir.path());
+        app.announcements = vec![test_fixtures::from_json(serde_json::json!({
+            "id": 70, "title": "Exam moved", "context_code": "course_1",
+        }))];
+        app.announcement_list_state.set_len(app.announcement_list().row_count());
+        app.active_tab = super::super::Tab::Announcements;
+
+        // Not the report, which `X` saves on the other tabs.
+        press(&mut app, 'X');
+        assert_eq!(app.status_message, "This announcement isn't archived.");
+        assert!(!app.paths.download_dir.exists());
+
+        press(&mut app, 'x');
+        app.toggle_show_archived();
+        app.announcement_list_state.selected = app.announcement_list().fold_row().unwrap() + 1;
+        assert_eq!(app.selected_announcement().map(|a| a.id), Some(70));
+        press(&mut app, 'X');
+        assert_eq!(app.status_message, "Announcement restored.");
+        assert!(app.archived_announcements.is_empty());
+    }
+
+    #[tokio::test]
+    async fn repeated_confirm_keys_submit_once() {
+        let server = TestServer::start(|_| {
+            Reply::json(r#"{"id": 1, "attempt": 1, "workflow_state": "submitted"}"#)
+                .delayed(Duration::from_millis(100))
+        })
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::app(&server.url, dir.path());
+        app.submission_target = Some((1, 10));
+        app.submission_kind = Some(SubmissionKind::Url);
+        app.submission_input = "https://example.com/essay".into();
+        app.submission_state = SubmissionState::Confirming;
+
+        press(&mut app, 'y');
+        assert!(matches!(app.submission_state, SubmissionState::Submitting));
+        press(&mut app, 'y');
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        app.start_submission();
+        finish_submission(&mut app).await;
+
+        assert!(matches!(app.submission_state, SubmissionState::Done { success: true, .. }));
+        assert_eq!(server.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn repeated_confirm_keys_on_text_preview_submit_once() {
+        let server = TestServer::start(|_| {
+            Reply::json(r#"{"id": 1, "attempt": 1}"#).delayed(Duration::from_millis(100))
+        })
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::app(&server.url, dir.path());
+        app.submission_target = Some((1, 10));
+        app.submission_kind = Some(SubmissionKind::TextEntry);
+        app.submission_input = "My essay".into();
+        app.submission_state = SubmissionState::TextPreview;
+
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        press(&mut app, 'y');
+        finish_submission(&mut app).await;
+
+        assert_eq!(server.requests(), 1);
+        let posted = &server.log()[0];
+        assert_eq!(posted.method, "POST");
+        assert_eq!(posted.path(), "/api/v1/courses/1/assignments/10/submissions");
+    }
+
+    /// Type `:`, then `text`, then Enter.
+    fn run_command(app: &mut App, text: &str) {
+        press(app, ':');
+        for c in text.chars() {
+            press(app, c);
+        }
+        handle_key(app, KeyCode::Enter, KeyModifiers::NONE);
+    }
+
+    /// An app with a submission being sent.
+    fn submitting_app(dir: &std::path::Path) -> (App, oneshot::Sender<SubmitResult>) {
+        let mut app = test_fixtures::offline_app(dir);
+        let (tx, rx) = oneshot::channel();
+        app.submission_rx = Some(rx);
+        (app, tx)
+    }
+
+    #[test]
+    fn q_asks_about_an_unfinished_submission_but_q_bang_does_not() {
+        let dir = tempfile::tempdir().unwrap();
+        let (mut app, _tx) = submitting_app(dir.path());
+        run_command(&mut app, "q");
+        assert!(app.confirm_quit && app.running);
+        press(&mut app, 'n');
+        assert!(!app.confirm_quit && app.running);
+
+        run_command(&mut app, "q!");
+        assert!(!app.confirm_quit && !app.running);
+        assert!(app.skip_exit_summary);
+    }
+
+    #[test]
+    fn ctrl_c_quits_from_the_command_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::offline_app(dir.path());
+        press(&mut app, ':');
+        press(&mut app, 'e');
+        // Other Ctrl+letters aren't typed.
+        handle_key(&mut app, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(app.command_line.as_deref(), Some("e"));
+        handle_key(&mut app, KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(app.command_line.is_none());
+        assert!(!app.running);
+
+        let (mut app, _tx) = submitting_app(dir.path());
+        press(&mut app, ':');
+        handle_key(&mut app, KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(app.command_line.is_none());
+        assert!(app.confirm_quit && app.running);
+    }
+
+    /// Feed `events` through the same path as the terminal's.
+    fn feed(app: &mut App, events: impl IntoIterator<Item = KeyEvent>) {
+        for key in events.into_iter().filter_map(|e| key_press(Event::Key(e))) {
+            handle_key(app, key.code, key.modifiers);
+        }
+    }
+
+    fn key(c: char, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), modifiers)
+    }
+
+    #[test]
+    fn altgr_shift_and_dead_key_characters_are_typed() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::offline_app(dir.path());
+        app.submission_target = Some((1, 10));
+        app.submission_state = SubmissionState::UrlInput;
+        let altgr = KeyModifiers::CONTROL | KeyModifiers::ALT;
+
+        feed(
+            &mut app,
+            [
+                // AltGr, as Windows reports it.
+                key('@', altgr),
+                key('€', altgr),
+                key('{', altgr | KeyModifiers::SHIFT),
+                // A dead key then a letter arrives as the composed letter.
+                key('é', KeyModifiers::NONE),
+                key('ñ', KeyModifiers::NONE),
+                // Shift with the lowercase letter, as some terminals send it.
+                key('ä', KeyModifiers::SHIFT),
+                key('ß', KeyModifiers::SHIFT),
+                key('Q', KeyModifiers::SHIFT),
+                // Neither a release nor a Ctrl shortcut is typing.
+                KeyEvent::new_with_kind(KeyCode::Char('x'), KeyModifiers::NONE, KeyEventKind::Release),
+                key('v', KeyModifiers::CONTROL),
+            ],
+        );
+        assert_eq!(app.submission_input, "@€{éñÄßQ");
+        assert!(app.running);
+        assert!(matches!(app.submission_state, SubmissionState::UrlInput));
+    }
+
+    #[test]
+    fn altgr_characters_reach_the_command_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::offline_app(dir.path());
+        let altgr = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        feed(&mut app, [key(':', KeyModifiers::SHIFT), key('~', altgr), key('é', KeyModifiers::NONE)]);
+        assert_eq!(app.command_line.as_deref(), Some("~é"));
+    }
+}
@@ -0,0 +1,179 @@
+//! Calendar events standing in for the due date of an assignment that has
+//! none, such as an exam whose assignment shell is undated.  Kept in a local
+//! file and never sent to Canvas; the date is joined in when assignments
+//! are loaded, so sorting, countdowns and the focal item all see it.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use crate::cache::CourseAssignments;
+use crate::models::CalendarEvent;
+
+/// The event an assignment is linked to, with its title and start as last
+/// seen, so the link keeps its date once the event drops out of the synced
+/// window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventLink {
+    pub event_id: u64,
+    pub title: String,
+    pub start_at: DateTime<Utc>,
+}
+
+/// Bring each link's title and start up to date with `events`.  Returns
+/// whether any changed.  Links to events not in `events` are left alone.
+pub fn refresh(links: &mut BTreeMap<u64, EventLink>, events: &[CalendarEvent]) -> bool {
+    let mut changed = false;
+    for link in links.values_mut() {
+        let Some(event) = events.iter().find(|e| e.id == link.event_id) else {
+            continue;
+        };
+        if let Some(start_at) = event.start_at.filter(|s| *s != link.start_at) {
+            link.start_at = start_at;
+            changed = true;
+        }
+        if let Some(title) = event.title.as_ref().filter(|t| **t != link.title) {
+            link.title = title.clone();
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Give every linked assignment without a due date its event's start as
+/// one, and take it back from those no longer linked.  `applied` are the
+/// assignments whose due date came from a link last time (empty for data
+/// fresh from Canvas or the cache); a date Canvas sets itself always wins.
+/// Returns the assignments whose due date now comes from a link.
+pub fn apply(
+    assignments: &mut [CourseAssignments],
+    links: &BTreeMap<u64, EventLink>,
+    applied: &HashSet<u64>,
+) -> HashSet<u64> {
+    let mut now_applied = HashSet::new();
+    for a in assignments.iter_mut().flat_map(|group| group.assignments.iter_mut()) {
+        if applied.contains(&a.id) {
+            a.due_at = None;
+        }
+        if a.due_at.is_some() {
+            continue;
+        }
+        if let Some(link) = links.get(&a.id) {
+            a.due_at = Some(link.start_at);
+            now_applied.insert(a.id);
+        }
+    }
+    now_applied
+}
+
+/// Links by assignment id.  A missing or unreadable file is no links.
+pub fn load_event_links(path: &Path) -> BTreeMap<u64, EventLink> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_event_links(path: &Path, links: &BTreeMap<u64, EventLink>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(links)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::tui::test_fixtures::{assignment, from_json};
+
+    fn time(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn link(event_id: u64, title: &str, start_at: &str) -> EventLink {
+        EventLink { event_id, title: title.into(), start_at: time(start_at) }
+    }
+
+    fn group(assignments: Vec<crate::models::Assignment>) -> Vec<CourseAssignments> {
+        vec![CourseAssignments { course_id: 1, name: "Biology".into(), assignments }]
+    }
+
+    fn due(assignments: &[CourseAssignments], id: u64) -> Option<DateTime<Utc>> {
+        assignments[0].assignments.iter().find(|a| a.id == id).unwrap().due_at
+    }
+
+    #[test]
+    fn apply_dates_only_undated_linked_assignments() {
+        let mut assignments = group(vec![
+            assignment(1, 1, "Exam", json!({})),
+            assignment(2, 1, "Quiz", json!({ "due_at": "2025-05-02T10:00:00Z" })),
+            assignment(3, 1, "Essay", json!({})),
+        ]);
+        let links = BTreeMap::from([
+            (1, link(10, "Midterm", "2025-05-01T09:00:00Z")),
+            (2, link(11, "Quiz day", "2025-05-03T09:00:00Z")),
+        ]);
+
+        let applied = apply(&mut assignments, &links, &HashSet::new());
+        assert_eq!(applied, HashSet::from([1]));
+        assert_eq!(due(&assignments, 1), Some(time("2025-05-01T09:00:00Z")));
+        // Canvas's own date wins.
+        assert_eq!(due(&assignments, 2), Some(time("2025-05-02T10:00:00Z")));
+        assert_eq!(due(&assignments, 3), None);
+    }
+
+    #[test]
+    fn apply_follows_moved_and_removed_links() {
+        let mut assignments = group(vec![assignment(1, 1, "Exam", json!({}))]);
+        let mut links = BTreeMap::from([(1, link(10, "Midterm", "2025-05-01T09:00:00Z"))]);
+        let applied = apply(&mut assignments, &links, &HashSet::new());
+
+        links.insert(1, link(10, "Midterm", "2025-05-08T09:00:00Z"));
+        let applied = apply(&mut assignments, &links, &applied);
+        assert_eq!(due(&assignments, 1), Some(time("2025-05-08T09:00:00Z")));
+
+        links.clear();
+        assert!(apply(&mut assignments, &links, &applied).is_empty());
+        assert_eq!(due(&assignments, 1), None);
+    }
+
+    #[test]
+    fn refresh_updates_moved_and_renamed_events_only() {
+        let mut links = BTreeMap::from([
+            (1, link(10, "Midterm", "2025-05-01T09:00:00Z")),
+            (2, link(11, "Final", "2025-06-01T09:00:00Z")),
+        ]);
+        let unchanged: CalendarEvent = from_json(json!({
+            "id": 10, "title": "Midterm", "start_at": "2025-05-01T09:00:00Z",
+        }));
+        assert!(!refresh(&mut links, &[unchanged]));
+
+        let moved: CalendarEvent = from_json(json!({
+            "id": 10, "title": "Midterm (room 4)", "start_at": "2025-05-02T09:00:00Z",
+        }));
+        // An event without a start keeps the last one seen.
+        let undated: CalendarEvent = from_json(json!({ "id": 11, "title": "Final" }));
+        assert!(refresh(&mut links, &[moved, undated]));
+        assert_eq!(links[&1], link(10, "Midterm (room 4)", "2025-05-02T09:00:00Z"));
+        assert_eq!(links[&2], link(11, "Final", "2025-06-01T09:00:00Z"));
+    }
+
+    #[test]
+    fn links_round_trip_and_bad_files_are_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache/event-links.json");
+        assert!(load_event_links(&path).is_empty());
+
+        let links = BTreeMap::from([(1, link(10, "Midterm", "2025-05-01T09:00:00Z"))]);
+        save_event_links(&path, &links).unwrap();
+        assert_eq!(load_event_links(&path), links);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load_event_links(&path).is_empty());
+    }
+}
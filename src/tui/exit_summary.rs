@@ -0,0 +1,73 @@
+//! The plain-text summary printed after quitting when `exit_summary` is
+//! on, so the terminal's scrollback keeps what is due once the alternate
+//! screen is gone.  Countdowns and statuses come from the same helpers as
+//! the screens.
+
+use chrono::Local;
+
+use super::ui::countdown_timer;
+use super::{assignment_status_priority, App};
+use crate::models::Assignment;
+
+/// How many upcoming assignments are listed.
+const UPCOMING: usize = 3;
+
+/// Missing assignments listed before the rest are only counted.
+const MISSING_LIMIT: usize = 5;
+
+/// The summary for the data `app` shows, in the courses passing its filter.
+pub fn summary(app: &App) -> String {
+    let now = app.now();
+    let assignments: Vec<(&str, &Assignment)> = app
+        .assignments
+        .iter()
+        .filter(|group| app.course_passes_filter(group.course_id))
+        .flat_map(|group| group.assignments.iter().map(move |a| (group.name.as_str(), a)))
+        .filter(|(_, a)| !a.is_unpublished())
+        .collect();
+
+    let mut upcoming: Vec<_> = assignments
+        .iter()
+        .filter(|(_, a)| a.due_at.is_some_and(|d| d > now))
+        .filter(|(_, a)| assignment_status_priority(a) == 2)
+        .collect();
+    upcoming.sort_by_key(|(_, a)| a.due_at);
+    let mut missing: Vec<_> =
+        assignments.iter().filter(|(_, a)| assignment_status_priority(a) == 0).collect();
+    missing.sort_by_key(|(_, a)| a.due_at);
+
+    let mut out = match app.cached_at {
+        Some(t) => format!("Canvas — synced {}\n", t.with_timezone(&Local).format("%b %d %H:%M")),
+        None => "Canvas — not synced yet\n".to_string(),
+    };
+    if upcoming.is_empty() {
+        out.push_str("Nothing due.\n");
+    } else {
+        out.push_str("Next up:\n");
+        for (course, a) in upcoming.iter().take(UPCOMING) {
+            let countdown = a.due_at.map(|d| countdown_timer(d, now).0).unwrap_or_default();
+            out.push_str(&format!("  {}  {}  {}  in {countdown}\n", due(a), name(a), course));
+        }
+    }
+    if !missing.is_empty() {
+        out.push_str(&format!("Missing ({}):\n", missing.len()));
+        for (course, a) in missing.iter().take(MISSING_LIMIT) {
+            out.push_str(&format!("  {}  {}  {}\n", due(a), name(a), course));
+        }
+        if missing.len() > MISSING_LIMIT {
+            out.push_str(&format!("  …and {} more\n", missing.len() - MISSING_LIMIT));
+        }
+    }
+    out
+}
+
+fn name(a: &Assignment) -> &str {
+    a.name.as_deref().unwrap_or("Unnamed")
+}
+
+/// The due date as the lists show it, in local time.
+fn due(a: &Assignment) -> String {
+    a.due_at
+        .map(|d| d.with_timezone(&Local).format("%b %d %H:%M").to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,391 @@
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::{backend::Backend, Terminal};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use tempfile::TempPath;
+
+/// A program that needs the terminal to itself.  Event handlers put one in
+/// `App::external_command`; the main loop runs it with `suspend_and_run` and
+/// hands the result to `App::finish_external_command`.
+#[derive(Debug)]
+pub enum ExternalCommand {
+    /// Write the text of a text entry or discussion reply in `editor`, a
+    /// command line such as `code --wait`.  `file` is deleted when the
+    /// request is dropped, however the edit ends.
+    EditSubmission { editor: String, file: TempPath },
+    /// Show a file in the pager, then delete it.
+    PageFile { path: PathBuf },
+    /// An `open_handlers` command line, already expanded, run by the shell.
+    OpenHandler { command: String },
+}
+
+/// Start of the names of submission text files; the assignment id and the
+/// process id of the instance that made the file follow.
+const DRAFT_PREFIX: &str = "canvas-tui-submission-";
+
+/// Submission text goes in the per-user runtime directory where there is
+/// one, so it isn't in a world-writable directory.
+fn drafts_dir() -> PathBuf {
+    dirs::runtime_dir().unwrap_or_else(std::env::temp_dir)
+}
+
+/// A new, private (0600 on Unix), uniquely named file for writing the text
+/// of a submission to `assignment_id`, holding `initial`.
+pub fn create_submission_file(assignment_id: u64, initial: &str) -> io::Result<TempPath> {
+    let mut file = tempfile::Builder::new()
+        .prefix(&format!("{DRAFT_PREFIX}{assignment_id}-{}-", std::process::id()))
+        .suffix(".txt")
+        .tempfile_in(drafts_dir())?;
+    file.write_all(initial.as_bytes())?;
+    Ok(file.into_temp_path())
+}
+
+/// Submission text files left behind by instances that are no longer
+/// running (a crash, a closed terminal), by assignment id.  Empty ones are
+/// deleted, as there is nothing in them to recover.
+pub fn orphaned_drafts() -> Vec<(u64, PathBuf)> {
+    orphaned_drafts_in(&drafts_dir())
+}
+
+fn orphaned_drafts_in(dir: &Path) -> Vec<(u64, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut found = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let Some(rest) = name.to_str().and_then(|n| n.strip_prefix(DRAFT_PREFIX)) else {
+            continue;
+        };
+        let mut parts = rest.split('-');
+        let (Some(Ok(assignment_id)), Some(Ok(pid))) = (
+            parts.next().map(str::parse::<u64>),
+            parts.next().map(str::parse::<u32>),
+        ) else {
+            continue;
+        };
+        if pid == std::process::id() || still_running(pid, &path) {
+            continue;
+        }
+        if entry.metadata().is_ok_and(|m| m.len() == 0) {
+            let _ = std::fs::remove_file(&path);
+            continue;
+        }
+        found.push((assignment_id, path));
+    }
+    found
+}
+
+/// Whether the instance that made `path` may still be editing it.  Linux can
+/// ask /proc; elsewhere, a file touched in the last day counts as in use.
+fn still_running(pid: u32, path: &Path) -> bool {
+    if cfg!(target_os = "linux") {
+        return Path::new("/proc").join(pid.to_string()).exists();
+    }
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_some_and(|age| age < std::time::Duration::from_secs(24 * 60 * 60))
+}
+
+impl ExternalCommand {
+    /// The process to run.  Fails if the command line doesn't parse.
+    pub fn command(&self) -> io::Result<Command> {
+        match self {
+            ExternalCommand::EditSubmission { editor, file } => {
+                let mut cmd = command_line(editor)?;
+                cmd.arg(file);
+                Ok(cmd)
+            }
+            ExternalCommand::PageFile { path } => {
+                let default = if cfg!(windows) { "more" } else { "less" };
+                let pager = std::env::var("PAGER").unwrap_or_else(|_| default.into());
+                let mut cmd = command_line(&pager)?;
+                cmd.arg(path);
+                Ok(cmd)
+            }
+            ExternalCommand::OpenHandler { command } => {
+                Ok(super::open_handler::shell_command(command))
+            }
+        }
+    }
+}
+
+/// The editor to use: `configured` (the `editor` config key), else
+/// `$VISUAL`, else `$EDITOR`, else nano if it is installed, else vi (on
+/// Windows, Notepad).
+pub fn editor_command(configured: Option<&str>) -> String {
+    let set = |v: Option<String>| v.filter(|v| !v.trim().is_empty());
+    let fallback = if on_path("nano") {
+        "nano"
+    } else if cfg!(windows) {
+        "notepad"
+    } else {
+        "vi"
+    };
+    set(configured.map(str::to_string))
+        .or_else(|| set(std::env::var("VISUAL").ok()))
+        .or_else(|| set(std::env::var("EDITOR").ok()))
+        .unwrap_or_else(|| fallback.into())
+}
+
+/// Whether `program` is in a `PATH` directory (as `program.exe` on
+/// Windows).
+fn on_path(program: &str) -> bool {
+    let file = if cfg!(windows) { format!("{program}.exe") } else { program.to_string() };
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(&file).is_file()))
+}
+
+/// A `Command` for a command line like `code --wait` or
+/// `"/Applications/Sublime Text.app/.../subl" -w`.
+fn command_line(line: &str) -> io::Result<Command> {
+    let words = split_words(line).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, format!("unbalanced quotes in '{line}'"))
+    })?;
+    let Some((program, args)) = words.split_first() else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "empty command"));
+    };
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+    Ok(cmd)
+}
+
+/// Split `line` into words the way a POSIX shell would, minus expansions:
+/// whitespace separates words, single quotes keep everything literally,
+/// double quotes keep everything but `\"` and `\\`, and a backslash outside
+/// quotes escapes the next character.  `None` for an unclosed quote.
+///
+/// On Windows a backslash is a path separator, so it only escapes a `"`
+/// inside double quotes (`C:\Tools\vim.exe` stays as it is).
+fn split_words(line: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            '"' => word.push('"'),
+                            '\\' if !cfg!(windows) => word.push('\\'),
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' if !cfg!(windows) => {
+                in_word = true;
+                if let Some(c) = chars.next() {
+                    word.push(c);
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Some(words)
+}
+
+/// Give the terminal to `cmd` until it exits: leave raw mode and the
+/// alternate screen, run it, and come back.  The terminal is restored and
+/// cleared for a full repaint even if starting `cmd` fails or this panics.
+pub fn suspend_and_run<B: Backend + Write>(
+    terminal: &mut Terminal<B>,
+    mut cmd: Command,
+) -> io::Result<ExitStatus> {
+    disable_raw_mode()?;
+    crossterm::execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    let restore = Restore(terminal);
+    restore.0.show_cursor()?;
+    cmd.status()
+}
+
+struct Restore<'a, B: Backend + Write>(&'a mut Terminal<B>);
+
+impl<B: Backend + Write> Drop for Restore<'_, B> {
+    fn drop(&mut self) {
+        let _ = enable_raw_mode();
+        let _ = crossterm::execute!(self.0.backend_mut(), EnterAlternateScreen);
+        let _ = self.0.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program_and_args(cmd: &Command) -> (String, Vec<String>) {
+        let args = cmd.get_args().map(|a| a.to_string_lossy().into_owned()).collect();
+        (cmd.get_program().to_string_lossy().into_owned(), args)
+    }
+
+    #[test]
+    fn editor_request_runs_the_editor_on_the_file() {
+        let file = create_submission_file(7, "").unwrap();
+        let path = file.to_string_lossy().into_owned();
+        let request = ExternalCommand::EditSubmission {
+            editor: "code --wait".into(),
+            file,
+        };
+        let (program, args) = program_and_args(&request.command().unwrap());
+        assert_eq!(program, "code");
+        assert_eq!(args, ["--wait", path.as_str()]);
+    }
+
+    #[test]
+    fn editor_request_with_a_bad_command_line_fails_before_running() {
+        for editor in ["", "   ", "'unclosed"] {
+            let request = ExternalCommand::EditSubmission {
+                editor: editor.into(),
+                file: create_submission_file(7, "").unwrap(),
+            };
+            let err = request.command().unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "{editor:?}");
+        }
+    }
+
+    #[test]
+    fn dropping_an_editor_request_deletes_its_file() {
+        let file = create_submission_file(7, "draft").unwrap();
+        let path = file.to_path_buf();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "draft");
+        drop(ExternalCommand::EditSubmission { editor: "vi".into(), file });
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn whitespace_separates_words() {
+        assert_eq!(split_words("code --wait").unwrap(), ["code", "--wait"]);
+        assert_eq!(split_words("  vim\t-u  NONE \n").unwrap(), ["vim", "-u", "NONE"]);
+        assert_eq!(split_words("").unwrap(), Vec::<String>::new());
+        assert_eq!(split_words("   ").unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn quotes_keep_spaces_and_join_with_neighbours() {
+        assert_eq!(
+            split_words(r#""/Applications/Sublime Text.app/Contents/SharedSupport/bin/subl" -w"#).unwrap(),
+            ["/Applications/Sublime Text.app/Contents/SharedSupport/bin/subl", "-w"]
+        );
+        assert_eq!(split_words("'my editor' x").unwrap(), ["my editor", "x"]);
+        assert_eq!(split_words(r#"a"b c"'d e'f"#).unwrap(), ["ab cd ef"]);
+        // Empty quotes are still a word.
+        assert_eq!(split_words(r#"ed "" ''"#).unwrap(), ["ed", "", ""]);
+        assert_eq!(split_words(r#"say "it's""#).unwrap(), ["say", "it's"]);
+        assert_eq!(split_words(r#"'a "b"'"#).unwrap(), [r#"a "b""#]);
+    }
+
+    #[test]
+    fn unclosed_quotes_are_an_error() {
+        assert_eq!(split_words("'open"), None);
+        assert_eq!(split_words(r#"vim "open"#), None);
+        assert_eq!(split_words(r#""ends in escape\"#), None);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn backslashes_escape_like_a_posix_shell() {
+        assert_eq!(split_words(r"my\ editor -f").unwrap(), ["my editor", "-f"]);
+        assert_eq!(split_words(r#""a \"quoted\" \\ \n""#).unwrap(), [r#"a "quoted" \ \n"#]);
+        assert_eq!(split_words(r"'\no escape'").unwrap(), [r"\no escape"]);
+        assert_eq!(split_words(r"trailing\").unwrap(), ["trailing"]);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn backslashes_are_path_separators_on_windows() {
+        assert_eq!(split_words(r"C:\Tools\vim.exe -f").unwrap(), [r"C:\Tools\vim.exe", "-f"]);
+        assert_eq!(
+            split_words(r#""C:\Program Files\Notepad++\notepad++.exe" -multiInst"#).unwrap(),
+            [r"C:\Program Files\Notepad++\notepad++.exe", "-multiInst"]
+        );
+        assert_eq!(split_words(r#""say \"hi\"""#).unwrap(), [r#"say "hi""#]);
+    }
+
+    #[test]
+    fn submission_files_are_named_for_the_assignment_and_process() {
+        let a = create_submission_file(42, "first").unwrap();
+        let b = create_submission_file(42, "second").unwrap();
+        assert_ne!(*a, *b);
+        let name = a.file_name().unwrap().to_string_lossy().into_owned();
+        let expected_start = format!("{DRAFT_PREFIX}42-{}-", std::process::id());
+        assert!(name.starts_with(&expected_start), "{name}");
+        assert!(name.ends_with(".txt"), "{name}");
+        assert_eq!(std::fs::read_to_string(&b).unwrap(), "second");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn submission_files_are_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let file = create_submission_file(42, "secret").unwrap();
+        let mode = std::fs::metadata(&file).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn orphaned_drafts_are_found_and_empty_ones_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        // No process has this id, on Linux or anywhere.
+        let gone = u32::MAX;
+        let write = |name: &str, text: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, text).unwrap();
+            // Old enough to count as abandoned where /proc can't say.
+            let old = std::time::SystemTime::now() - std::time::Duration::from_secs(3 * 24 * 60 * 60);
+            std::fs::File::options().write(true).open(&path).unwrap().set_modified(old).unwrap();
+            path
+        };
+        let orphan = write(&format!("{DRAFT_PREFIX}7-{gone}-abc.txt"), "unsent text");
+        let empty = write(&format!("{DRAFT_PREFIX}8-{gone}-def.txt"), "");
+        let ours = write(&format!("{DRAFT_PREFIX}9-{}-ghi.txt", std::process::id()), "mine");
+        let unrelated = write("notes-7-1.txt", "x");
+        let malformed = write(&format!("{DRAFT_PREFIX}x-{gone}-jkl.txt"), "x");
+
+        let found = orphaned_drafts_in(dir.path());
+        assert_eq!(found, [(7, orphan.clone())]);
+        assert!(orphan.exists(), "kept until it is recovered");
+        assert!(!empty.exists());
+        assert!(ours.exists() && unrelated.exists() && malformed.exists());
+    }
+
+    #[test]
+    fn a_missing_drafts_directory_has_no_orphans() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(orphaned_drafts_in(&dir.path().join("missing")).is_empty());
+    }
+}
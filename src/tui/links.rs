@@ -0,0 +1,204 @@
+//! Pulling the link and image URLs out of Canvas HTML (assignment
+//! descriptions, course pages) so they can be listed and opened.
+
+use super::ui::{decode_entities, strip_html};
+use url::Url;
+
+/// A hyperlink or image found in a piece of HTML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// Link text, image alt text, or the URL when there is neither.
+    pub text: String,
+    /// Absolute URL (relative ones are resolved against the Canvas URL).
+    pub url: String,
+    pub is_image: bool,
+}
+
+/// Every `<a href>` and `<img src>` in `html`, in document order and without
+/// duplicate URLs.  Attribute values may be double-, single- or unquoted,
+/// and relative URLs are resolved against `base`.  In-page anchors,
+/// `javascript:` links and inline `data:` images are skipped.
+pub fn extract_links(html: &str, base: &Url) -> Vec<Link> {
+    let mut links: Vec<Link> = Vec::new();
+    let mut rest = html;
+
+    while let Some(lt) = rest.find('<') {
+        rest = &rest[lt + 1..];
+        let name_len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(rest.len());
+        let name = rest[..name_len].to_ascii_lowercase();
+        if name != "a" && name != "img" {
+            continue;
+        }
+        let Some((attrs, after_tag)) = split_tag(&rest[name_len..]) else {
+            break;
+        };
+        rest = after_tag;
+
+        let is_image = name == "img";
+        let Some(raw) = attr(attrs, if is_image { "src" } else { "href" }) else {
+            continue;
+        };
+        let raw = decode_entities(raw.trim());
+        if raw.is_empty() || raw.starts_with('#') {
+            continue;
+        }
+        let Ok(url) = base.join(&raw) else {
+            continue;
+        };
+        if matches!(url.scheme(), "javascript" | "data") {
+            continue;
+        }
+
+        let text = if is_image {
+            attr(attrs, "alt").map(decode_entities).unwrap_or_default()
+        } else {
+            let body_end = find_ignore_case(rest, "</a").unwrap_or(0);
+            strip_html(&rest[..body_end])
+        };
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let url = url.to_string();
+        if links.iter().any(|l| l.url == url) {
+            continue;
+        }
+        links.push(Link {
+            text: if text.is_empty() { url.clone() } else { text },
+            url,
+            is_image,
+        });
+    }
+    links
+}
+
+/// Split the remainder of an opening tag into its attribute text and
+/// whatever follows the closing `>`, skipping `>` inside quoted values.
+fn split_tag(s: &str) -> Option<(&str, &str)> {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '>') => return Some((&s[..i], &s[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Value of attribute `name` in a tag's attribute text.
+fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attrs;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+        if rest.is_empty() {
+            return None;
+        }
+        let key_len = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        let key = &rest[..key_len];
+        rest = rest[key_len..].trim_start();
+
+        let value = if let Some(after_eq) = rest.strip_prefix('=') {
+            let after_eq = after_eq.trim_start();
+            let (value, remaining) = match after_eq.chars().next() {
+                Some(q @ ('"' | '\'')) => {
+                    let body = &after_eq[1..];
+                    let end = body.find(q).unwrap_or(body.len());
+                    (&body[..end], body.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = after_eq
+                        .find(char::is_whitespace)
+                        .unwrap_or(after_eq.len());
+                    (&after_eq[..end], &after_eq[end..])
+                }
+            };
+            rest = remaining;
+            Some(value)
+        } else {
+            None
+        };
+
+        if key.eq_ignore_ascii_case(name) {
+            return value;
+        }
+    }
+}
+
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|w| w.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base() -> Url {
+        Url::parse("https://canvas.example.edu/courses/1/assignments/2").unwrap()
+    }
+
+    fn urls(html: &str) -> Vec<String> {
+        extract_links(html, &base()).into_iter().map(|l| l.url).collect()
+    }
+
+    #[test]
+    fn quoted_and_unquoted_attributes() {
+        let html = r#"<a href="https://a.example/1">one</a>
+            <A HREF='https://a.example/2'>two</A>
+            <a class=x href=https://a.example/3>three</a>"#;
+        assert_eq!(
+            urls(html),
+            ["https://a.example/1", "https://a.example/2", "https://a.example/3"]
+        );
+    }
+
+    #[test]
+    fn relative_urls_resolve_against_the_base() {
+        let html = r#"<a href="/files/9/download">abs path</a><a href="rubric">sibling</a>"#;
+        assert_eq!(
+            urls(html),
+            [
+                "https://canvas.example.edu/files/9/download",
+                "https://canvas.example.edu/courses/1/assignments/rubric",
+            ]
+        );
+    }
+
+    #[test]
+    fn text_comes_from_the_body_or_alt_and_falls_back_to_the_url() {
+        let html = r#"<a href="https://a.example/x"> The <b>syllabus</b>
+            &amp; notes </a><img src="https://a.example/p.png" alt="Diagram &lt;1&gt;">
+            <img src="https://a.example/q.png">"#;
+        let links = extract_links(html, &base());
+        assert_eq!(links[0].text, "The syllabus & notes");
+        assert!(!links[0].is_image);
+        assert_eq!(links[1].text, "Diagram <1>");
+        assert!(links[1].is_image);
+        assert_eq!(links[2].text, "https://a.example/q.png");
+    }
+
+    #[test]
+    fn anchors_scripts_data_and_duplicates_are_skipped() {
+        let html = r##"<a href="#top">top</a><a href="javascript:void(0)">js</a>
+            <img src="data:image/png;base64,AAAA"><a href="">empty</a><a name="x">no href</a>
+            <a href="https://a.example/?a=1&amp;b=2">q</a><a href="https://a.example/?a=1&b=2">again</a>"##;
+        assert_eq!(urls(html), ["https://a.example/?a=1&b=2"]);
+    }
+
+    #[test]
+    fn angle_brackets_inside_quoted_values_do_not_end_the_tag() {
+        let html = r#"<a title="a > b" href="https://a.example/">x</a>"#;
+        assert_eq!(urls(html), ["https://a.example/"]);
+    }
+
+    #[test]
+    fn unterminated_tags_are_ignored() {
+        assert!(urls(r#"text <a href="https://a.example/"#).is_empty());
+        assert!(urls("").is_empty());
+    }
+}
@@ -1,12 +1,41 @@
+pub mod announcement_archive;
+mod bulk_download;
+pub mod clipboard;
+mod course_accent;
+mod course_match;
+mod cross_listing;
+mod date_input;
+mod date_sections;
+mod exit_summary;
+pub mod event_links;
+pub mod external;
+mod links;
+mod open_handler;
+mod preflight;
+mod profile;
+pub mod recent_files;
+mod submission_url;
+pub mod report;
+pub mod sync_history;
+pub mod sync_stats;
+mod text_stats;
+pub mod time_budget;
+#[cfg(test)]
+mod test_fixtures;
 pub mod event;
 pub mod ui;
 
-use crate::api::CanvasClient;
-use crate::cache::{save_cache, CacheData};
+use crate::api::{CanvasClient, CanvasError, PageResult, PaginatedList};
+use crate::cache::{self, save_cache, CacheData, CourseAssignments, FirstSeen};
+use crate::config::{ColorMode, Config, DueSoonSection, OpenHandler, Paths};
 use crate::models::*;
 use chrono::{DateTime, Datelike, Local, Utc};
+use date_sections::DateSection;
 use ratatui::widgets::ListState as RListState;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
 use tokio::sync::oneshot;
 
 // ─── Academic Quarter Utilities ──────────────────────────────────────────────
@@ -88,29 +117,121 @@ fn course_quarter(name: Option<&str>, code: Option<&str>) -> Option<AcademicQuar
 
 // ─── Assignment Status Priority ──────────────────────────────────────────────
 
+/// The status bar message for an open handler's `command` that ended with
+/// `result`.
+fn open_handler_outcome(command: &str, result: std::io::Result<std::process::ExitStatus>) -> String {
+    match result {
+        Err(e) => format!("Could not run '{command}': {e}"),
+        Ok(status) if status.success() => format!("Finished: {command}"),
+        Ok(status) => match status.code() {
+            Some(code) => format!("'{command}' exited with status {code}."),
+            None => format!("'{command}' was killed by a signal."),
+        },
+    }
+}
+
 /// Returns a numeric priority for assignment status (lower = more urgent):
 ///   0 = missing, 1 = past due, 2 = upcoming/not submitted, 3 = submitted, 4 = graded.
+/// Unpublished and not yet open assignments are never missing or past due.
 pub fn assignment_status_priority(a: &Assignment) -> u8 {
     let now = chrono::Utc::now();
+    // Not open yet, so nothing can be missing.
+    if a.locked_until(now).is_some() {
+        return 2;
+    }
+    let past_due = !a.is_unpublished() && a.due_at.is_some_and(|d| d < now);
     if let Some(ref sub) = a.submission {
         match sub.workflow_state.as_deref() {
             Some("graded") => 4,
             Some("submitted") => 3,
             _ => {
-                if a.due_at.is_some_and(|d| d < now) {
+                if past_due {
                     if sub.missing.unwrap_or(false) { 0 } else { 1 }
                 } else {
                     2
                 }
             }
         }
-    } else if a.due_at.is_some_and(|d| d < now) {
+    } else if past_due {
         1
     } else {
         2
     }
 }
 
+// ─── Score Formatting ────────────────────────────────────────────────────────
+
+/// Points without a trailing ".0" and at most two decimals: 5, 7.5, 8.33.
+fn format_points(p: f64) -> String {
+    format!("{}", (p * 100.0).round() / 100.0)
+}
+
+fn is_pass_fail(a: &Assignment) -> bool {
+    a.grading_type.as_deref() == Some("pass_fail")
+}
+
+/// What an assignment is worth: "10 pts", "Pass/fail", "Not graded", or
+/// "Ungraded" when Canvas reports no points.
+pub fn points_label(a: &Assignment) -> String {
+    match a.points_possible {
+        _ if is_pass_fail(a) => "Pass/fail".into(),
+        _ if a.grading_type.as_deref() == Some("not_graded") => "Not graded".into(),
+        Some(p) => format!("{} pts", format_points(p)),
+        None => "Ungraded".into(),
+    }
+}
+
+/// A graded result for display, keyed on the assignment's grading type,
+/// or `None` when there is nothing to show.
+///   pass_fail               → "Complete" / "Incomplete"
+///   percent                 → "85%"
+///   letter_grade, gpa_scale → "B+ (8.5/10)", or just "B+" without a score
+///   not_graded              → "Not graded"
+///   points (the default)    → "8.5/10", or "5 pts (ungraded)" with no
+///                             points possible, or the grade as given
+pub fn format_score(a: &Assignment, score: Option<f64>, grade: Option<&str>) -> Option<String> {
+    let points = match (score, a.points_possible) {
+        (Some(s), Some(p)) if p > 0.0 => Some(format!("{}/{}", format_points(s), format_points(p))),
+        _ => None,
+    };
+    match a.grading_type.as_deref() {
+        Some("pass_fail") => {
+            let complete = match grade {
+                Some(g) if g.eq_ignore_ascii_case("complete") || g.eq_ignore_ascii_case("pass") => true,
+                Some(g) if g.eq_ignore_ascii_case("incomplete") || g.eq_ignore_ascii_case("fail") => false,
+                _ => score? > 0.0,
+            };
+            Some(if complete { "Complete" } else { "Incomplete" }.into())
+        }
+        Some("not_graded") => Some("Not graded".into()),
+        Some("percent") => match (score, a.points_possible) {
+            (Some(s), Some(p)) if p > 0.0 => Some(format!("{}%", format_points(s / p * 100.0))),
+            // Canvas sends the grade as "85%".
+            _ => grade.map(str::to_string),
+        },
+        Some("letter_grade" | "gpa_scale") => match (grade, points) {
+            (Some(g), Some(points)) => Some(format!("{g} ({points})")),
+            (Some(g), None) => Some(g.to_string()),
+            (None, points) => points,
+        },
+        _ => match score {
+            Some(s) if points.is_none() => Some(format!("{} pts (ungraded)", format_points(s))),
+            Some(_) => points,
+            None => grade.map(str::to_string),
+        },
+    }
+}
+
+/// Label for `format_score`'s result in the detail pane: "Score" for
+/// points and percentages, "Grade" for everything else.
+pub fn score_label(a: &Assignment, score: Option<f64>) -> &'static str {
+    match a.grading_type.as_deref() {
+        Some("pass_fail" | "letter_grade" | "gpa_scale" | "not_graded") => "Grade",
+        _ if score.is_some() => "Score",
+        _ => "Grade",
+    }
+}
+
 // ─── Course Code Validation ──────────────────────────────────────────────────
 
 /// Check if a string looks like a valid course code: one or more letters
@@ -155,6 +276,10 @@ pub enum SubmissionKind {
     TextEntry,
     Url,
     FileUpload,
+    /// Reply to the assignment's linked discussion topic.
+    DiscussionReply,
+    /// Offered when some accepted type can only be done on the website.
+    OpenInBrowser,
 }
 
 impl SubmissionKind {
@@ -163,6 +288,8 @@ impl SubmissionKind {
             Self::TextEntry => "Text Entry  (opens $EDITOR)",
             Self::Url => "URL Submission",
             Self::FileUpload => "File Upload",
+            Self::DiscussionReply => "Discussion reply  (opens $EDITOR)",
+            Self::OpenInBrowser => "Open in browser",
         }
     }
 
@@ -171,6 +298,7 @@ impl SubmissionKind {
             "online_text_entry" => Some(Self::TextEntry),
             "online_url" => Some(Self::Url),
             "online_upload" => Some(Self::FileUpload),
+            "discussion_topic" => Some(Self::DiscussionReply),
             _ => None,
         }
     }
@@ -184,16 +312,25 @@ pub enum SubmissionState {
     TypePicker,
     /// User is typing a URL.
     UrlInput,
+    /// Choosing one of the newest files in `submit_watch_dirs`.
+    FilePicker,
     /// User is typing a file path.
     FileInput,
     /// Showing text from $EDITOR before confirming.
     TextPreview,
     /// Final y/n confirm screen (URL or file).
     Confirming,
+    /// Reading the discussion's existing replies before writing one.
+    DiscussionContext,
     /// Background task running.
     Submitting,
-    /// API returned — show result.
-    Done { success: bool, message: String },
+    /// API returned — show result, with what Canvas recorded when it
+    /// said.
+    Done {
+        success: bool,
+        message: String,
+        receipt: Option<Box<SubmitReceipt>>,
+    },
 }
 
 impl SubmissionState {
@@ -205,6 +342,56 @@ impl SubmissionState {
 pub struct SubmitResult {
     pub success: bool,
     pub message: String,
+    pub receipt: Option<SubmitReceipt>,
+}
+
+/// The submission Canvas answered a successful submit with.
+#[derive(Debug, Clone)]
+pub struct SubmitReceipt {
+    pub submission: Submission,
+    /// The attempt number didn't go up, so Canvas most likely kept the
+    /// previous submission (seen with locked assignments).
+    pub attempt_unchanged: bool,
+}
+
+impl SubmitReceipt {
+    /// Check `submission` against the attempt number the assignment had
+    /// before submitting.
+    fn new(submission: Submission, previous_attempt: Option<u64>) -> Self {
+        let attempt_unchanged = submission.attempt.unwrap_or(0) <= previous_attempt.unwrap_or(0);
+        Self { submission, attempt_unchanged }
+    }
+}
+
+/// Shared between the UI and a running submission task so that cancelling
+/// and sending the final POST can never both win.
+///
+/// The task calls [`commit`](Self::commit) immediately before the request
+/// that makes the submission visible; Esc calls [`cancel`](Self::cancel).
+/// Whichever runs first decides the outcome.
+#[derive(Debug, Clone, Default)]
+pub struct SubmissionGate(Arc<AtomicU8>);
+
+impl SubmissionGate {
+    const PENDING: u8 = 0;
+    const CANCELLED: u8 = 1;
+    const COMMITTED: u8 = 2;
+
+    /// Mark the point of no return. Returns `false` if the user already
+    /// cancelled, in which case the task must not send the request.
+    pub fn commit(&self) -> bool {
+        self.0
+            .compare_exchange(Self::PENDING, Self::COMMITTED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    /// Request cancellation. Returns `false` if the final request has
+    /// already been sent and the result must be waited for.
+    pub fn cancel(&self) -> bool {
+        self.0
+            .compare_exchange(Self::PENDING, Self::CANCELLED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
 }
 
 // ─── Assignment Sort ─────────────────────────────────────────────────────────
@@ -237,6 +424,44 @@ impl AssignmentSort {
     }
 }
 
+// ─── Course Sort ─────────────────────────────────────────────────────────────
+
+/// Order of the Courses tab.  Purely a view ordering: `course_display_order`
+/// maps rows to `courses` indices and nothing else depends on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CourseSort {
+    /// As Canvas returns them.
+    Api,
+    Name,
+    Code,
+    /// Lowest current score first; ungraded courses last.
+    Grade,
+    /// Soonest open assignment first; courses with none last.
+    NextDue,
+}
+
+impl CourseSort {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Api => Self::Name,
+            Self::Name => Self::Code,
+            Self::Code => Self::Grade,
+            Self::Grade => Self::NextDue,
+            Self::NextDue => Self::Api,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Api => "Default",
+            Self::Name => "Name",
+            Self::Code => "Code",
+            Self::Grade => "Grade ↑",
+            Self::NextDue => "Next due",
+        }
+    }
+}
+
 // ─── Unified Schedule View Mode ──────────────────────────────────────────────
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -259,27 +484,475 @@ impl UnifiedViewMode {
 pub struct FetchResult {
     pub user: Option<User>,
     pub courses: Vec<Course>,
-    pub assignments: Vec<(String, Vec<Assignment>)>,
+    pub assignments: Vec<CourseAssignments>,
     pub calendar_events: Vec<CalendarEvent>,
+    /// Course, group and institution announcements, newest first.
     pub announcements: Vec<DiscussionTopic>,
+    /// Unread announcements as Canvas counts them (all of them, not just
+    /// those fetched); `None` when it couldn't say.
+    pub unread_announcements: Option<u64>,
+    /// Course colors picked on the Canvas dashboard, by course id.
+    pub course_colors: BTreeMap<u64, String>,
+    pub group_names: BTreeMap<u64, String>,
+    /// Peer reviews I'm assigned, by assignment id.
+    pub peer_reviews: BTreeMap<u64, Vec<PeerReview>>,
     pub fetched_at: DateTime<Utc>,
     /// Non-fatal error message to show in the status bar.
     pub error: Option<String>,
     /// The first API call returned 401 Unauthorized — token is missing or expired.
     pub is_auth_error: bool,
+    /// Canvas answered with a maintenance page somewhere; what it affected
+    /// kept its previous data.
+    pub maintenance: bool,
+    pub stats: sync_stats::SyncStats,
+    /// Content hash of the cache as saved (or found unchanged) by this sync.
+    pub cache_hash: Option<u64>,
+    /// Courses whose assignments were cut off at `max_assignments_per_course`.
+    pub truncated_courses: Vec<u64>,
+    /// Sections that failed; their previous data was kept instead.
+    pub stale: StaleSections,
+    /// The first-seen record with this sync's new assignments added.
+    pub first_seen: FirstSeen,
+    /// How each section went, for the sync history.
+    pub sections: sync_history::SectionOutcomes,
+    /// The sync history with this sync added.
+    pub history: sync_history::SyncHistory,
+    /// The data shown before was another user's and was dropped.
+    pub other_user: bool,
+}
+
+/// Parts of the data whose last fetch failed, so they still hold older
+/// data and are worth retrying.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StaleSections {
+    /// Courses whose assignments couldn't be fetched.
+    pub courses: HashSet<u64>,
+    pub calendar: bool,
+    pub announcements: bool,
+}
+
+impl StaleSections {
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+
+    pub fn count(&self) -> usize {
+        self.courses.len() + usize::from(self.calendar) + usize::from(self.announcements)
+    }
 }
 
+/// Status after a sync that ran into a Canvas maintenance page.
+const MAINTENANCE_STATUS: &str = "Canvas appears to be in maintenance — using cached data.";
+
+/// First retry of stale sections, doubling per failed retry up to the max.
+const STALE_RETRY_FIRST: std::time::Duration = std::time::Duration::from_secs(30);
+const STALE_RETRY_MAX: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
 // ─── Calendar Item ───────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
 pub struct CalendarItem {
     pub start_at: Option<DateTime<Utc>>,
     pub title: String,
-    pub item_type: &'static str, // "event" or "assignment"
+    pub item_type: &'static str, // "event", "assignment" or "peer_review"
+    /// How prominently to show an event; always `Normal` for assignments.
+    pub emphasis: EventEmphasis,
     pub course_name: Option<String>,
+    /// Canvas course ID, when the item belongs to a known course.
+    pub course_id: Option<u64>,
     pub status: Option<String>,
     /// Canvas assignment ID, set when this item originates from an assignment.
     pub assignment_id: Option<u64>,
+    /// Canvas calendar event ID, set for items that come from an event.
+    pub event_id: Option<u64>,
+    /// For peer reviews: the page where the review is done.
+    pub review_url: Option<String>,
+}
+
+/// Classification of calendar events by title (see `event_highlight` and
+/// `event_deemphasize` in the config).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventEmphasis {
+    Normal,
+    /// Routine events such as office hours; dimmed and optionally hidden.
+    Dimmed,
+    /// Exams and similar; drawn with a distinct icon in CAUTION.
+    Highlight,
+}
+
+impl EventEmphasis {
+    /// Case-insensitive substring match of `title` against the (lowercased)
+    /// patterns.  Highlight wins when both match.
+    fn classify(title: &str, highlight: &[String], deemphasize: &[String]) -> Self {
+        let title = title.to_lowercase();
+        if highlight.iter().any(|p| title.contains(p.as_str())) {
+            EventEmphasis::Highlight
+        } else if deemphasize.iter().any(|p| title.contains(p.as_str())) {
+            EventEmphasis::Dimmed
+        } else {
+            EventEmphasis::Normal
+        }
+    }
+}
+
+pub const DEFAULT_EVENT_HIGHLIGHT: [&str; 3] = ["exam", "midterm", "final"];
+pub const DEFAULT_EVENT_DEEMPHASIZE: [&str; 2] = ["office hours", "lecture"];
+
+/// Group membership for the assignment in the submission modal.  Canvas
+/// applies a group member's submission to their whole group; we check the
+/// membership up front so the modal can say who it submits for and refuse
+/// when there is no group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionGroup {
+    /// Not a group assignment.
+    Individual,
+    Checking,
+    Member(String),
+    NotMember,
+    /// The lookup failed; submission is allowed and Canvas decides.
+    Unknown(String),
+}
+
+/// The Schedule tab's new-event form (`a`).
+#[derive(Debug, Default)]
+pub struct EventForm {
+    /// Title, date, start time, end time, description.
+    pub fields: [String; 5],
+    /// Index into `fields` of the field being typed into.
+    pub focus: usize,
+    /// Validation or API error from the last attempt.
+    pub error: Option<String>,
+    /// Set while the create request is in flight.
+    pub saving: bool,
+}
+
+impl EventForm {
+    pub const LABELS: [&'static str; 5] = ["Title", "Date", "Start", "End", "Notes"];
+    pub const PLACEHOLDERS: [&'static str; 5] =
+        ["required", "today, thu, apr 14…", "7pm, 19:00", "optional — 1 hour", "optional"];
+
+    /// Check the fields and turn them into an event to create.
+    fn validate(&self) -> Result<NewEvent, String> {
+        let [title, date, start, end, notes] = &self.fields;
+        let title = title.trim();
+        if title.is_empty() {
+            return Err("Title is required".into());
+        }
+        let date = date_input::parse_date(date, Local::now().date_naive())?;
+        let start = date.and_time(date_input::parse_time(start)?);
+        let end = if end.trim().is_empty() {
+            start + chrono::Duration::hours(1)
+        } else {
+            date.and_time(date_input::parse_time(end)?)
+        };
+        if end <= start {
+            return Err("End must be after start".into());
+        }
+        let to_utc = |t: chrono::NaiveDateTime| {
+            t.and_local_timezone(Local)
+                .earliest()
+                .map(|t| t.with_timezone(&Utc))
+                .ok_or_else(|| format!("{} doesn't exist in local time", t.format("%H:%M")))
+        };
+        let notes = Some(notes.trim().to_string()).filter(|n| !n.is_empty());
+        Ok(NewEvent {
+            title: title.to_string(),
+            start_at: to_utc(start)?,
+            end_at: to_utc(end)?,
+            description: notes,
+        })
+    }
+}
+
+/// The effort estimate prompt (`e`) for an assignment.
+#[derive(Debug)]
+pub struct EstimatePrompt {
+    pub assignment_id: u64,
+    pub assignment_name: String,
+    pub input: String,
+    /// Why the last Enter was refused.
+    pub error: Option<String>,
+}
+
+/// The picker (`l` on a calendar event) of the event's course's undated
+/// assignments, one of which takes the event's start as its due date.
+#[derive(Debug)]
+pub struct EventLinkPicker {
+    pub event_id: u64,
+    pub event_title: String,
+    pub start_at: DateTime<Utc>,
+    /// Assignment ids and names.
+    pub candidates: Vec<(u64, String)>,
+}
+
+/// Copy mode's line selection (`v` in a focused detail pane), as indices
+/// into the pane's lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CopySelection {
+    /// Where the selection started.
+    pub anchor: usize,
+    /// The end j/k move.
+    pub cursor: usize,
+}
+
+impl CopySelection {
+    /// The selected lines, first to last.
+    pub fn range(&self) -> std::ops::RangeInclusive<usize> {
+        self.anchor.min(self.cursor)..=self.anchor.max(self.cursor)
+    }
+}
+
+/// The detail pane's text as of the last frame, for copy mode.
+#[derive(Debug, Default)]
+pub struct DetailText {
+    /// Each line as plain text, with the wrapped row it starts on.
+    pub lines: Vec<(String, usize)>,
+    /// Rows the pane shows at once.
+    pub height: usize,
+}
+
+/// A validated `EventForm`.
+struct NewEvent {
+    title: String,
+    start_at: DateTime<Utc>,
+    end_at: DateTime<Utc>,
+    description: Option<String>,
+}
+
+/// Default for the `dashboard_days` config key.
+pub const DEFAULT_DASHBOARD_DAYS: u64 = 30;
+
+/// How many assignments `App::description_loads` remembers.
+const DESCRIPTION_LOADS_CAP: usize = 64;
+
+/// State of an on-demand description fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DescriptionLoad {
+    Loading,
+    Loaded,
+    Failed,
+}
+
+/// How many entries the dashboard's Recently graded list shows.
+pub const RECENT_GRADES_LIMIT: usize = 25;
+
+/// How many of a course's announcements its summary on the Courses tab
+/// lists.
+pub const COURSE_ANNOUNCEMENT_COUNT: usize = 3;
+
+/// How far back older announcements go when no course has a start date.
+const OLDER_ANNOUNCEMENTS_FALLBACK_DAYS: i64 = 4 * 365;
+
+/// Which part of the screen the navigation keys (j/k, g/G, arrows) move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Focus {
+    /// The active tab's list.
+    List,
+    /// The detail pane beside it, which then scrolls.
+    Detail,
+    /// The open popup: course filter, page or link picker, sync stats.
+    Popup,
+}
+
+/// What the navigation keys move, as given by `App::nav_target`.
+pub enum NavTarget<'a> {
+    List(&'a mut ListState),
+    /// A scroll offset and how far it can go.
+    Scroll(&'a mut u16, u16),
+}
+
+/// Which list the dashboard's left pane shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DashboardPanel {
+    Upcoming,
+    RecentlyGraded,
+}
+
+impl DashboardPanel {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Upcoming => Self::RecentlyGraded,
+            Self::RecentlyGraded => Self::Upcoming,
+        }
+    }
+}
+
+/// When the assignment's submission was graded, or `None` if it isn't.
+/// Canvas leaves `graded_at` empty on some graded submissions (e.g. grades
+/// entered before submission), so `submitted_at` stands in for it.
+pub fn graded_time(a: &Assignment) -> Option<DateTime<Utc>> {
+    let sub = a.submission.as_ref()?;
+    if sub.workflow_state.as_deref() != Some("graded") {
+        return None;
+    }
+    sub.graded_at.or(sub.submitted_at)
+}
+
+/// The dashboard's Upcoming list as built by `App::dashboard_upcoming`.
+pub struct DashboardUpcoming<'a> {
+    /// Visible assignments with their course names.
+    pub items: Vec<(&'a str, &'a Assignment)>,
+    /// How many assignments fall after `window_end` (shown only when the
+    /// fold is expanded, but counted either way).
+    pub later: usize,
+    pub window_end: DateTime<Utc>,
+}
+
+impl DashboardUpcoming<'_> {
+    /// Rows in the list: the items plus the fold row, if there is one.
+    pub fn row_count(&self) -> usize {
+        self.items.len() + usize::from(self.later > 0)
+    }
+}
+
+/// The Announcements tab's list as built by `App::announcement_list`.
+pub struct AnnouncementList<'a> {
+    pub active: Vec<&'a DiscussionTopic>,
+    /// The "older announcements" row follows them, until every page of
+    /// those is in.
+    pub more: bool,
+    /// Counted on the fold row; listed after it only when `expanded`.
+    pub archived: Vec<&'a DiscussionTopic>,
+    pub expanded: bool,
+}
+
+impl<'a> AnnouncementList<'a> {
+    /// Rows in the list: the active announcements, the "older
+    /// announcements" row, the fold row if any are archived, and the
+    /// archived ones while expanded.
+    pub fn row_count(&self) -> usize {
+        let archived = if self.expanded { self.archived.len() } else { 0 };
+        self.active.len() + usize::from(self.more) + usize::from(!self.archived.is_empty()) + archived
+    }
+
+    /// Index of the "older announcements" row, if there is one.
+    pub fn more_row(&self) -> Option<usize> {
+        self.more.then_some(self.active.len())
+    }
+
+    /// Index of the "show archived" row, if there is one.
+    pub fn fold_row(&self) -> Option<usize> {
+        (!self.archived.is_empty()).then_some(self.active.len() + usize::from(self.more))
+    }
+
+    /// The announcement in row `row` (`None` on the two special rows).
+    pub fn get(&self, row: usize) -> Option<&'a DiscussionTopic> {
+        if let Some(ann) = self.active.get(row) {
+            return Some(ann);
+        }
+        let i = row.checked_sub(self.fold_row()? + 1)?;
+        if self.expanded {
+            self.archived.get(i).copied()
+        } else {
+            None
+        }
+    }
+}
+
+/// How far a bulk download of a course's files has got.
+#[derive(Debug, Clone, Default)]
+pub struct BulkProgress {
+    pub course: String,
+    /// The course's folder in the download directory.
+    pub dir: PathBuf,
+    /// Files to fetch and their total size, once listed.
+    pub planned: Option<(usize, u64)>,
+    /// Files left alone because the local copy is up to date.
+    pub skipped: usize,
+    pub saved: usize,
+    pub bytes: u64,
+    /// Files that couldn't be saved, with why.
+    pub failures: Vec<(String, String)>,
+}
+
+/// A running bulk download: its progress and the task doing it.
+pub struct BulkDownload {
+    pub progress: BulkProgress,
+    rx: tokio::sync::mpsc::UnboundedReceiver<bulk_download::BulkEvent>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+/// How far ahead the Schedule's "Due soon" section looks.
+pub const DUE_SOON_HOURS: i64 = 48;
+
+/// Whether `a` goes in the "Due soon" section: published, due within
+/// `DUE_SOON_HOURS`, not submitted yet, and submitted through Canvas at all.
+pub fn is_due_soon(a: &Assignment, now: DateTime<Utc>) -> bool {
+    let due_soon = a
+        .due_at
+        .is_some_and(|d| d > now && d <= now + chrono::Duration::hours(DUE_SOON_HOURS));
+    let submitted = a.is_submitted();
+    let offline = a
+        .submission_types
+        .as_deref()
+        .is_some_and(|types| types.iter().all(|t| matches!(t.as_str(), "none" | "on_paper")));
+    due_soon && !submitted && !offline && !a.is_unpublished()
+}
+
+/// A course's counts in the course filter popup.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CourseCounts {
+    pub assignments: usize,
+    /// In the "Due soon" section (`is_due_soon`).
+    pub due_soon: usize,
+    /// Due in the future and not submitted; courses with none are dimmed.
+    pub upcoming: usize,
+    pub unread_announcements: usize,
+}
+
+impl CourseCounts {
+    /// "(12 assignments, 3 due soon)", plus unread announcements when there
+    /// are any.
+    pub fn label(&self) -> String {
+        let mut label = format!(
+            "({} assignment{}, {} due soon",
+            self.assignments,
+            if self.assignments == 1 { "" } else { "s" },
+            self.due_soon
+        );
+        if self.unread_announcements > 0 {
+            label.push_str(&format!(
+                ", {} unread announcement{}",
+                self.unread_announcements,
+                if self.unread_announcements == 1 { "" } else { "s" }
+            ));
+        }
+        label.push(')');
+        label
+    }
+}
+
+/// The Schedule list view's rows as built by `App::schedule_rows`.  Row
+/// indexes (the list selection) run through `pinned`, then `rows`.
+pub struct ScheduleRows<'a> {
+    /// The "Due soon" section, soonest first.
+    pub pinned: Vec<(&'a str, &'a Assignment)>,
+    pub rows: Vec<(&'a str, &'a Assignment)>,
+}
+
+impl<'a> ScheduleRows<'a> {
+    pub fn len(&self) -> usize {
+        self.pinned.len() + self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, idx: usize) -> Option<(&'a str, &'a Assignment)> {
+        match idx.checked_sub(self.pinned.len()) {
+            None => self.pinned.get(idx).copied(),
+            Some(below) => self.rows.get(below).copied(),
+        }
+    }
+
+    /// Row of assignment `id`, in the pinned section if it is there.
+    pub fn position(&self, id: u64) -> Option<usize> {
+        self.pinned
+            .iter()
+            .chain(&self.rows)
+            .position(|(_, a)| a.id == id)
+    }
 }
 
 // ─── Navigation ─────────────────────────────────────────────────────────────
@@ -309,6 +982,33 @@ impl Tab {
         }
     }
 
+    /// The 1-based number shown in the tab bar and bound to its digit key.
+    pub fn number(&self) -> usize {
+        Tab::ALL.iter().position(|t| t == self).unwrap_or(0) + 1
+    }
+
+    /// Map a digit key to the tab it jumps to, if there is one.
+    pub fn from_digit(c: char) -> Option<Tab> {
+        let n = c.to_digit(10)? as usize;
+        n.checked_sub(1).and_then(|i| Tab::ALL.get(i)).copied()
+    }
+
+    /// The tab `--tab` names: its title in any case, "assignments" for the
+    /// Schedule or "grades" for Courses, where the grades are.
+    pub fn from_name(name: &str) -> Option<Tab> {
+        let name = name.to_lowercase();
+        match name.as_str() {
+            "assignments" => Some(Tab::Assignments),
+            "grades" => Some(Tab::Courses),
+            _ => Tab::ALL.into_iter().find(|t| t.title().to_lowercase() == name),
+        }
+    }
+
+    /// Key range label for the digit bindings, e.g. "1-4".
+    pub fn digit_range() -> String {
+        format!("1-{}", Tab::ALL.len())
+    }
+
     pub fn next(&self) -> Tab {
         let idx = Tab::ALL.iter().position(|t| t == self).unwrap_or(0);
         Tab::ALL[(idx + 1) % Tab::ALL.len()]
@@ -328,59 +1028,237 @@ impl Tab {
 
 pub struct App {
     pub client: CanvasClient,
+    pub paths: Paths,
     pub running: bool,
     pub active_tab: Tab,
 
     // Data
     pub user: Option<User>,
     pub courses: Vec<Course>,
-    pub assignments: Vec<(String, Vec<Assignment>)>,
+    pub assignments: Vec<CourseAssignments>,
     pub calendar_events: Vec<CalendarEvent>,
     pub calendar_items: Vec<CalendarItem>,
     pub announcements: Vec<DiscussionTopic>,
+    /// Names of my groups, by id, for labelling group items.
+    pub group_names: BTreeMap<u64, String>,
+    /// Peer reviews I'm assigned, by assignment id.
+    pub peer_reviews: BTreeMap<u64, Vec<PeerReview>>,
 
     // UI state
     pub course_list_state: ListState,
+    pub course_sort: CourseSort,
     pub dashboard_list_state: ListState,
+    /// Days ahead the dashboard's Upcoming list covers before folding.
+    pub dashboard_days: u64,
+    /// Whether the "… N more after <date>" fold is expanded.
+    pub dashboard_show_later: bool,
+    pub dashboard_panel: DashboardPanel,
+    pub graded_list_state: ListState,
+    /// Assignments graded (or re-graded) since the data shown before the
+    /// last sync, badged in the Recently graded list for this session.
+    pub newly_graded: HashSet<u64>,
+    /// When each assignment first turned up in a sync; saved in the cache.
+    pub first_seen: FirstSeen,
+    /// Lowercased title patterns for `EventEmphasis::Highlight` / `Dimmed`.
+    pub event_highlight: Vec<String>,
+    pub event_deemphasize: Vec<String>,
+    /// Leave `EventEmphasis::Dimmed` events out of the calendar.
+    pub hide_dimmed_events: bool,
+    /// Text of the schedule's jump-to-date prompt while it is open, and the
+    /// parse error from the last attempt.
+    pub date_jump_input: Option<String>,
+    pub date_jump_error: Option<String>,
+    /// New-event form, while open.
+    pub event_form: Option<EventForm>,
+    /// Effort estimate prompt, while open.
+    pub estimate_prompt: Option<EstimatePrompt>,
+    /// Effort estimates in hours by assignment id, from `Paths::estimates_file`.
+    pub estimates: BTreeMap<u64, f32>,
+    /// Calendar events linked to undated assignments, by assignment id,
+    /// from `Paths::event_links_file`.
+    pub event_links: BTreeMap<u64, event_links::EventLink>,
+    /// Assignments in `assignments` whose due date comes from a link.
+    pub linked_due: HashSet<u64>,
+    /// The assignment picker for linking an event, while open.
+    pub event_link_picker: Option<EventLinkPicker>,
+    pub event_link_picker_state: ListState,
+    /// The `estimate_default_hours` config key.
+    pub estimate_default_hours: f32,
+    /// The `daily_capacity_hours` config key.
+    pub daily_capacity_hours: f32,
+    /// The `hint_labels` config table.
+    pub hint_labels: HashMap<String, String>,
+    pub event_form_rx: Option<oneshot::Receiver<Result<CalendarEvent, String>>>,
     pub assignment_list_state: ListState,
     pub assignment_sort: AssignmentSort,
     pub unified_view_mode: UnifiedViewMode,
     pub focal_assignment_id: Option<u64>,
     pub calendar_list_state: ListState,
     pub announcement_list_state: ListState,
+    /// Announcements archived with `x`, by id, from
+    /// `Paths::archived_announcements_file`.
+    pub archived_announcements: BTreeSet<u64>,
+    /// Unread announcements as Canvas counts them, from the latest sync;
+    /// `None` when it couldn't say.
+    pub unread_announcements_total: Option<u64>,
+    /// Course colors picked on the Canvas dashboard, by course id.
+    pub course_colors: BTreeMap<u64, String>,
+    /// Each course's stripe color, from `course_colors` or its id.
+    pub course_accents: HashMap<u64, course_accent::CourseAccent>,
+    /// Whether the archived announcements are listed below their fold row.
+    pub show_archived: bool,
+    /// Announcements from before the synced ones, loaded a page at a time
+    /// from the end of the Announcements tab.  `None` until asked for.
+    pub older_announcements: Option<PaginatedList<DiscussionTopic>>,
+    pub older_announcements_rx: Option<oneshot::Receiver<PageResult<DiscussionTopic>>>,
+
+    /// Selected attachment of the selected announcement.
+    pub announcement_attachment: usize,
+    /// Selection among the course summary's announcements, moved while
+    /// the Courses tab's detail pane has focus.
+    pub course_announcement_state: ListState,
+    pub download_rx: Option<oneshot::Receiver<Result<PathBuf, String>>>,
+    /// The course files download started with `B`, while it runs.
+    pub bulk_download: Option<BulkDownload>,
+    /// How a bulk download that had failures went, shown until dismissed.
+    pub bulk_summary: Option<BulkProgress>,
 
     // Course filter for assignments tab
-    pub course_filter: HashSet<String>,
+    /// Course ids.
+    pub course_filter: HashSet<u64>,
+    /// Every course filtered out (`n` in the filter popup).  Kept apart from
+    /// `course_filter`, whose empty set means "all".
+    pub course_filter_none: bool,
     pub show_course_filter: bool,
+    /// Leave unpublished assignments (seen with staff tokens) out of every
+    /// list.
+    pub hide_unpublished: bool,
     pub filter_list_state: ListState,
 
+    /// The term (`Course.term` id) everything is narrowed to; `None` for
+    /// all terms, where the current-quarter check applies instead.
+    pub term_filter: Option<u64>,
+    pub show_term_picker: bool,
+    pub term_list_state: ListState,
+
     // Status
     pub status_message: String,
     pub loading: bool,
+    /// A sync is wanted; the main loop starts one once none is running.
     pub needs_refresh: bool,
     pub needs_reauth: bool,
     pub cached_at: Option<DateTime<Utc>>,
+    /// Content hash of the cache file as last read or written, so a sync
+    /// that changed nothing can skip rewriting it.
+    pub cache_hash: Option<u64>,
+    /// `cache_keep_ended_days` when cache pruning is on; `None` when off.
+    pub cache_prune_days: Option<u64>,
+    /// `max_assignments_per_course` from the config.
+    pub max_assignments_per_course: Option<usize>,
+    /// Courses whose assignment list was cut off at that cap, and those the
+    /// user asked to load in full (exempt from the cap for this session).
+    pub truncated_courses: HashSet<u64>,
+    pub uncapped_courses: HashSet<u64>,
+    pub load_all_rx: Option<oneshot::Receiver<LoadAllResult>>,
+    /// A calendar deadline's assignment that wasn't loaded, being fetched
+    /// to submit to (see `open_submission_modal`).
+    calendar_assignment_rx: Option<oneshot::Receiver<CalendarAssignmentResult>>,
+    /// Assignments whose full description was recently requested, oldest
+    /// first (see `ensure_full_description`), and the fetch in flight.
+    pub description_loads: VecDeque<(u64, DescriptionLoad)>,
+    pub description_rx: Option<oneshot::Receiver<(u64, Result<Assignment, String>)>>,
 
     // Background fetch channel
     pub fetch_rx: Option<oneshot::Receiver<FetchResult>>,
+    /// When the most recent fetch was started, successful or not.
+    pub last_fetch_started: Option<std::time::Instant>,
+    /// Sections the last sync couldn't fetch, when to retry just those, and
+    /// how many retries in a row have failed (for the back-off).
+    pub stale_sections: StaleSections,
+    pub stale_retry_at: Option<std::time::Instant>,
+    pub stale_retries: u32,
 
     // Submission modal
     pub submission_state: SubmissionState,
     pub submission_kind: Option<SubmissionKind>,
     pub submission_supported_kinds: Vec<SubmissionKind>,
-    pub submission_cursor: usize,
+    /// What the modal's assignment accepts, shown above the type picker.
+    pub submission_preflight: preflight::SubmissionPreflight,
+    /// Selection in the submission type picker.
+    pub submission_type_state: ListState,
     /// Shared text buffer: holds URL, file path, or editor content depending on state.
     pub submission_input: String,
+    /// Why the typed URL can't be submitted, shown in the URL prompt.
+    pub submission_url_error: Option<String>,
+    /// The file to upload, once one is picked or a typed path checked.
+    pub submission_file: Option<PathBuf>,
     /// (course_id, assignment_id) of the assignment being submitted.
     pub submission_target: Option<(u64, u64)>,
-    /// Set by event handler; consumed by the main loop to launch $EDITOR.
-    pub launch_editor: bool,
+    /// Set by event handlers; the main loop runs it with the TUI suspended.
+    pub external_command: Option<external::ExternalCommand>,
+    /// The `editor` config key.
+    pub editor: Option<String>,
+    /// The `open_handlers` config tables.
+    pub open_handlers: Vec<OpenHandler>,
+    /// The exit message of a detached open handler, while it runs.
+    pub open_handler_rx: Option<oneshot::Receiver<String>>,
+    /// The `submit_watch_dirs` config key, unexpanded.
+    pub submit_watch_dirs: Vec<String>,
+    /// Newest files in `submit_watch_dirs`, for the file picker.
+    pub recent_files: Vec<recent_files::RecentFile>,
+    /// Selection in the file picker.
+    pub recent_file_state: ListState,
+    /// The `due_soon_section` config key.
+    pub due_soon_section: DueSoonSection,
+    /// Unsent submission text from earlier runs, by assignment id; opened
+    /// in the editor the next time that assignment is submitted.
+    pub recovered_drafts: BTreeMap<u64, PathBuf>,
     pub submission_rx: Option<oneshot::Receiver<SubmitResult>>,
+    /// Abort handle for the running submission task, used by Esc.
+    pub submission_task: Option<tokio::task::AbortHandle>,
+    pub submission_gate: SubmissionGate,
+    /// Esc was accepted; waiting for the task to finish aborting.
+    pub submission_cancelling: bool,
+    /// Quit was asked for with a submission unfinished; waiting for y/n.
+    pub confirm_quit: bool,
+    pub submission_group: SubmissionGroup,
+    pub submission_group_rx: Option<oneshot::Receiver<SubmissionGroup>>,
+    /// Linked topic when the modal's assignment is a discussion.
+    pub submission_discussion_topic: Option<u64>,
+    /// Replies shown in `DiscussionContext`: `None` while loading.
+    pub discussion_entries: Option<Result<Vec<DiscussionEntry>, String>>,
+    pub discussion_entries_rx: Option<oneshot::Receiver<Result<Vec<DiscussionEntry>, String>>>,
+    pub discussion_scroll: u16,
 
     // Display-order mapping: display position → index into `self.courses`.
     // Categorized courses first, then uncategorized (no dash pattern).
     pub course_display_order: Vec<usize>,
 
+    /// `--course <query>`: the course to open on the Courses tab once the
+    /// course list has loaded.
+    pub pending_course: Option<String>,
+    /// `--assignment <id>`: the assignment to select on the Schedule tab
+    /// once assignments have loaded.
+    pub pending_assignment: Option<u64>,
+
+    /// Phase timings of the most recent sync, and whether the popup showing
+    /// them is open.
+    pub last_sync_stats: Option<sync_stats::SyncStats>,
+    pub show_sync_stats: bool,
+    /// The last few syncs, this session's and earlier ones.
+    pub sync_history: sync_history::SyncHistory,
+    /// The sync stats popup shows the history instead of the last sync.
+    pub sync_stats_history: bool,
+    /// The profile popup is open.
+    pub show_profile: bool,
+    /// The cache's size on disk, measured when the profile popup opened;
+    /// `None` until the measurement is in.
+    pub profile_disk: Option<profile::DiskUsage>,
+    profile_disk_rx: Option<oneshot::Receiver<profile::DiskUsage>>,
+    /// Links of the detail pane, while the link picker is open.
+    pub link_picker: Option<Vec<links::Link>>,
+    pub link_picker_state: ListState,
+
     // Course pages picker & detail
     pub course_pages: Vec<crate::models::Page>,
     pub show_course_pages_picker: bool,
@@ -391,6 +1269,51 @@ pub struct App {
     pub course_detail_loading: bool,
     pub course_detail_rx: Option<oneshot::Receiver<Option<String>>>,
 
+    /// Vim-style count being typed before a motion (e.g. the `5` in `5j`).
+    pub pending_count: Option<usize>,
+    /// List or Detail; see `focus()` for popups.
+    pub pane_focus: Focus,
+    /// How far the detail pane is scrolled down, in rows.
+    pub detail_scroll: u16,
+    /// The furthest `detail_scroll` can usefully go, as of the last frame.
+    pub detail_scroll_max: std::cell::Cell<u16>,
+    /// The detail pane's lines as of the last frame.
+    pub detail_text: std::cell::RefCell<DetailText>,
+    /// Copy mode's selection, while selecting.
+    pub copy_selection: Option<CopySelection>,
+    /// Text for the main loop to put on the clipboard.
+    pub clipboard: Option<String>,
+    /// The first key of a two-key binding (see `event::CHORDS`), and when
+    /// it was pressed.
+    pub pending_chord: Option<(char, std::time::Instant)>,
+    /// What has been typed after `:` (`q` or `q!`), while typing.
+    pub command_line: Option<String>,
+    /// The `wrap_navigation` config key: j/k wrap around list ends.
+    pub wrap_navigation: bool,
+    /// The `wrap_names` config key: long names in lists take two lines.
+    pub wrap_names: bool,
+    /// How far Canvas's clock is ahead of this one, when it is enough to
+    /// misjudge deadlines; measured by the latest sync.
+    pub clock_skew: Option<chrono::Duration>,
+    /// The `correct_clock_skew` config key: countdowns add `clock_skew`.
+    pub correct_clock_skew: bool,
+    /// The `merge_cross_listed` config key, applied as data is loaded.
+    pub merge_cross_listed: bool,
+    /// Courses shown as another (see `cross_listing`): each duplicate's id
+    /// and the id of the course it is shown as.
+    pub merged_courses: BTreeMap<u64, u64>,
+    /// The `vim_gg` config key: `gg`, not a single `g`, goes to the top.
+    pub vim_gg: bool,
+    /// The `date_sections` config key.
+    pub date_sections: bool,
+    /// The `exit_summary` config key.
+    pub exit_summary: bool,
+    /// Quitting with `:q!`, which leaves the exit summary out.
+    pub skip_exit_summary: bool,
+    /// Colors to draw with: the `color_mode` config key, else what the
+    /// terminal appears to support.
+    pub color_mode: ColorMode,
+
     // Incremented each frame; used to drive the loading spinner.
     pub frame_count: u64,
 }
@@ -417,18 +1340,30 @@ impl ListState {
         Self { inner, selected: 0, len: 0, needs_center: false }
     }
 
-    /// Move down — clamped at the last item (no wrap-around).
-    pub fn select_next(&mut self) {
-        if self.len > 0 && self.selected + 1 < self.len {
-            self.selected += 1;
-        }
+    /// Move down one item: past the last one to the first when `wrap`,
+    /// otherwise clamped at the last.
+    pub fn select_next(&mut self, wrap: bool) {
+        self.move_by(1, wrap);
     }
 
-    /// Move up — clamped at the first item (no wrap-around).
-    pub fn select_prev(&mut self) {
-        if self.selected > 0 {
-            self.selected -= 1;
+    /// Move up one item: past the first one to the last when `wrap`,
+    /// otherwise clamped at the first.
+    pub fn select_prev(&mut self, wrap: bool) {
+        self.move_by(-1, wrap);
+    }
+
+    /// Move `delta` items, wrapping around the ends or clamped at them.
+    pub fn move_by(&mut self, delta: isize, wrap: bool) {
+        if self.len == 0 {
+            return;
         }
+        let len = self.len as isize;
+        let at = self.selected as isize;
+        self.selected = if wrap {
+            (at + delta.rem_euclid(len)).rem_euclid(len) as usize
+        } else {
+            (at + delta.clamp(-len, len)).clamp(0, len - 1) as usize
+        };
     }
 
     pub fn set_len(&mut self, len: usize) {
@@ -440,9 +1375,15 @@ impl ListState {
 }
 
 impl App {
-    pub fn new(client: CanvasClient) -> Self {
+    pub fn new(client: CanvasClient, paths: Paths) -> Self {
+        let estimates = time_budget::load_estimates(&paths.estimates_file());
+        let event_links = event_links::load_event_links(&paths.event_links_file());
+        let archived_announcements =
+            announcement_archive::load_archived(&paths.archived_announcements_file());
+        let sync_history = sync_history::load_sync_history(&paths.sync_history_file());
         Self {
             client,
+            paths,
             running: true,
             active_tab: Tab::Dashboard,
             user: None,
@@ -451,40 +1392,147 @@ impl App {
             calendar_events: Vec::new(),
             calendar_items: Vec::new(),
             announcements: Vec::new(),
+            group_names: BTreeMap::new(),
+            peer_reviews: BTreeMap::new(),
             course_list_state: ListState::new(),
+            course_sort: CourseSort::Api,
             dashboard_list_state: ListState::new(),
+            dashboard_days: DEFAULT_DASHBOARD_DAYS,
+            dashboard_show_later: false,
+            dashboard_panel: DashboardPanel::Upcoming,
+            graded_list_state: ListState::new(),
+            newly_graded: HashSet::new(),
+            first_seen: FirstSeen::default(),
+            event_highlight: DEFAULT_EVENT_HIGHLIGHT.map(String::from).to_vec(),
+            event_deemphasize: DEFAULT_EVENT_DEEMPHASIZE.map(String::from).to_vec(),
+            hide_dimmed_events: false,
+            date_jump_input: None,
+            date_jump_error: None,
+            event_form: None,
+            estimate_prompt: None,
+            estimates,
+            event_links,
+            linked_due: HashSet::new(),
+            event_link_picker: None,
+            event_link_picker_state: ListState::new(),
+            estimate_default_hours: time_budget::DEFAULT_ESTIMATE_HOURS,
+            daily_capacity_hours: time_budget::DEFAULT_DAILY_CAPACITY_HOURS,
+            hint_labels: HashMap::new(),
+            event_form_rx: None,
             assignment_list_state: ListState::new(),
             assignment_sort: AssignmentSort::DueDateAsc,
             unified_view_mode: UnifiedViewMode::CalendarView,
             focal_assignment_id: None,
             calendar_list_state: ListState::new(),
             announcement_list_state: ListState::new(),
+            archived_announcements,
+            show_archived: false,
+            older_announcements: None,
+            unread_announcements_total: None,
+            course_colors: BTreeMap::new(),
+            course_accents: HashMap::new(),
+            older_announcements_rx: None,
+            announcement_attachment: 0,
+            course_announcement_state: ListState::new(),
+            download_rx: None,
+            bulk_download: None,
+            bulk_summary: None,
             course_filter: HashSet::new(),
+            course_filter_none: false,
             show_course_filter: false,
+            hide_unpublished: false,
             filter_list_state: ListState::new(),
+            term_filter: None,
+            show_term_picker: false,
+            term_list_state: ListState::new(),
             status_message: "Loading...".into(),
             loading: true,
             needs_refresh: false,
             needs_reauth: false,
             cached_at: None,
+            cache_hash: None,
+            cache_prune_days: Some(cache::DEFAULT_KEEP_ENDED_DAYS),
+            max_assignments_per_course: None,
+            truncated_courses: HashSet::new(),
+            uncapped_courses: HashSet::new(),
+            load_all_rx: None,
+            calendar_assignment_rx: None,
+            description_loads: VecDeque::new(),
+            description_rx: None,
             fetch_rx: None,
+            last_fetch_started: None,
+            stale_sections: StaleSections::default(),
+            stale_retry_at: None,
+            stale_retries: 0,
             submission_state: SubmissionState::Hidden,
             submission_kind: None,
             submission_supported_kinds: Vec::new(),
-            submission_cursor: 0,
+            submission_preflight: preflight::SubmissionPreflight::default(),
+            submission_type_state: ListState::new(),
             submission_input: String::new(),
+            submission_url_error: None,
+            submission_file: None,
             submission_target: None,
-            launch_editor: false,
+            external_command: None,
+            editor: None,
+            open_handlers: Vec::new(),
+            open_handler_rx: None,
+            submit_watch_dirs: Vec::new(),
+            recent_files: Vec::new(),
+            recent_file_state: ListState::new(),
+            due_soon_section: DueSoonSection::default(),
+            recovered_drafts: BTreeMap::new(),
             submission_rx: None,
+            submission_task: None,
+            submission_gate: SubmissionGate::default(),
+            submission_cancelling: false,
+            confirm_quit: false,
+            submission_group: SubmissionGroup::Individual,
+            submission_group_rx: None,
+            submission_discussion_topic: None,
+            discussion_entries: None,
+            discussion_entries_rx: None,
+            discussion_scroll: 0,
             course_display_order: Vec::new(),
+            pending_course: None,
+            pending_assignment: None,
             course_pages: Vec::new(),
             show_course_pages_picker: false,
+            last_sync_stats: None,
+            show_sync_stats: false,
+            sync_history,
+            sync_stats_history: false,
+            show_profile: false,
+            profile_disk: None,
+            profile_disk_rx: None,
+            link_picker: None,
+            link_picker_state: ListState::new(),
             course_pages_list_state: ListState::new(),
             course_pages_loading: false,
             course_pages_rx: None,
             course_detail_content: None,
             course_detail_loading: false,
             course_detail_rx: None,
+            pending_count: None,
+            pane_focus: Focus::List,
+            detail_scroll: 0,
+            detail_scroll_max: std::cell::Cell::new(0),
+            detail_text: std::cell::RefCell::default(),
+            copy_selection: None,
+            clipboard: None,
+            pending_chord: None,
+            command_line: None,
+            wrap_navigation: false,
+            clock_skew: None,
+            correct_clock_skew: false,
+            merge_cross_listed: true,
+            merged_courses: BTreeMap::new(),
+            wrap_names: false,
+            color_mode: ColorMode::TrueColor,
+            vim_gg: false,
+            date_sections: false,
+            exit_summary: false,
+            skip_exit_summary: false,
             frame_count: 0,
         }
     }
@@ -492,20 +1540,32 @@ impl App {
     /// Populate app state from a previously saved cache without making any
     /// network requests.  After this call the UI is immediately usable.
     pub fn load_from_cache(&mut self, cache: CacheData) {
+        self.cache_hash = cache.content_hash;
         self.user = cache.user;
         self.courses = cache.courses;
-        self.rebuild_course_display_order();
-
         self.assignments = cache.assignments;
-        self.recount_filtered_assignments();
-
         self.calendar_events = cache.calendar_events;
-
-        self.announcement_list_state.set_len(cache.announcements.len());
         self.announcements = cache.announcements;
+        self.merge_cross_listed_courses();
+        self.linked_due.clear();
+        self.apply_event_links();
+        self.truncated_courses = cache.truncated_courses.into_iter().collect();
+        // After assignments: the next-due sort looks at them.
+        self.rebuild_course_display_order();
+        self.recount_filtered_assignments();
+
+        self.unread_announcements_total = cache.unread_announcements;
+        self.course_colors = cache.course_colors;
+        self.course_accents = course_accent::accents(&self.courses, &self.course_colors);
+        self.announcement_list_state.set_len(self.announcement_list().row_count());
+        self.group_names = cache.group_names;
+        self.peer_reviews = cache.peer_reviews;
+        self.first_seen = cache.first_seen;
 
         self.rebuild_calendar_items();
         self.focal_assignment_id = self.compute_focal_assignment_id();
+        let graded = self.recently_graded().len();
+        self.graded_list_state.set_len(graded);
 
         let cal_idx = self.find_today_calendar_idx();
         self.calendar_list_state.selected = cal_idx;
@@ -527,8 +1587,9 @@ impl App {
             .and_then(|u| u.name.clone())
             .unwrap_or_else(|| "Student".into());
         self.status_message = format!(
-            "Hi, {}! Showing cached data from {synced} — press r to refresh.",
-            name
+            "Hi, {}! Showing cached data from {synced} — press r to refresh.{}",
+            name,
+            self.cross_listing_note()
         );
     }
 
@@ -536,16 +1597,83 @@ impl App {
     /// the event loop.  Call `poll_fetch_result` each frame to collect the
     /// result once the task finishes.  No-ops if a fetch is already running.
     pub fn start_fetch(&mut self) {
+        self.spawn_fetch(None);
+    }
+
+    /// Start the sync `needs_refresh` asks for, unless one is running: then
+    /// the request waits for it to finish rather than being dropped, so a
+    /// refresh after a submission still happens.
+    pub fn start_requested_fetch(&mut self) {
+        if self.needs_refresh && self.fetch_rx.is_none() {
+            self.needs_refresh = false;
+            self.start_fetch();
+        }
+    }
+
+    /// Whether the back-off before retrying stale sections has run out.
+    pub fn stale_retry_due(&self) -> bool {
+        self.fetch_rx.is_none()
+            && self
+                .stale_retry_at
+                .is_some_and(|at| std::time::Instant::now() >= at)
+    }
+
+    /// Sync again, fetching only the sections the last sync couldn't.
+    pub fn retry_stale_sections(&mut self) {
+        self.stale_retry_at = None;
+        if self.stale_sections.is_empty() {
+            return;
+        }
+        self.spawn_fetch(Some(self.stale_sections.clone()));
+    }
+
+    /// Start a background sync.  With `retry`, only those sections are
+    /// fetched and the rest keep their current data.
+    fn spawn_fetch(&mut self, retry: Option<StaleSections>) {
         if self.fetch_rx.is_some() {
             return;
         }
         let client = self.client.clone();
+        let job = SyncJob {
+            cache_file: self.paths.cache_file(),
+            canvas_host: self.paths.canvas_host.clone(),
+            user_id: self.user.as_ref().map(|u| u.id),
+            cache_hash: self.cache_hash,
+            prune_days: self.cache_prune_days,
+            // Descriptions are left out of light syncs; keep the ones we have.
+            known_descriptions: if self.cache_prune_days.is_some() {
+                self.known_descriptions()
+            } else {
+                HashMap::new()
+            },
+            max_assignments: self.max_assignments_per_course,
+            uncapped_courses: self.uncapped_courses.clone(),
+            previous: PreviousData {
+                assignments: self.assignments_without_links(),
+                calendar_events: self.calendar_events.clone(),
+                announcements: self.announcements.clone(),
+                unread_announcements: self.unread_announcements_total,
+                course_colors: self.course_colors.clone(),
+                group_names: self.group_names.clone(),
+                peer_reviews: self.peer_reviews.clone(),
+                truncated_courses: self.truncated_courses.clone(),
+                first_seen: self.first_seen.clone(),
+            },
+            retry,
+            history: self.sync_history.clone(),
+            history_file: self.paths.sync_history_file(),
+        };
         let (tx, rx) = oneshot::channel();
         self.fetch_rx = Some(rx);
+        self.last_fetch_started = Some(std::time::Instant::now());
         self.loading = true;
-        self.status_message = "Syncing in background…".into();
+        self.status_message = if job.retry.is_some() {
+            "Retrying sections that failed to sync…".into()
+        } else {
+            "Syncing in background…".into()
+        };
         tokio::spawn(async move {
-            let result = fetch_canvas_data(client).await;
+            let result = fetch_canvas_data(client, job).await;
             let _ = tx.send(result);
         });
     }
@@ -571,6 +1699,11 @@ impl App {
 
     fn apply_fetch_result(&mut self, result: FetchResult) {
         self.loading = false;
+        self.last_sync_stats = Some(result.stats.clone());
+        self.sync_history = result.history.clone();
+        if result.cache_hash.is_some() {
+            self.cache_hash = result.cache_hash;
+        }
 
         // Auth error — signal the main loop to prompt for a new API key.
         // Keep whatever cached data is already displayed.
@@ -581,9 +1714,15 @@ impl App {
             return;
         }
 
-        // Network / other non-fatal error — keep cached data, just show the error.
+        // Network / other non-fatal error — keep cached data, just show the
+        // error.  Unless that data was another user's.
+        let have_own_data = !self.courses.is_empty() && !result.other_user;
         if let Some(ref err) = result.error {
-            if !self.courses.is_empty() {
+            if result.maintenance && have_own_data {
+                self.status_message = MAINTENANCE_STATUS.into();
+                return;
+            }
+            if have_own_data {
                 // We already have cached data — don't overwrite it.
                 let cached_hint = self
                     .cached_at
@@ -602,19 +1741,46 @@ impl App {
         }
 
         // Success (or partial success with no cached fallback) — apply fresh data.
+        // Someone else's grades aren't news.
+        if !result.other_user {
+            self.note_newly_graded(&result.assignments);
+        }
         self.user = result.user;
         self.courses = result.courses;
-        self.rebuild_course_display_order();
-
         self.assignments = result.assignments;
-        self.recount_filtered_assignments();
-
         self.calendar_events = result.calendar_events;
-        self.announcement_list_state.set_len(result.announcements.len());
         self.announcements = result.announcements;
+        self.merge_cross_listed_courses();
+        self.linked_due.clear();
+        self.apply_event_links();
+        self.truncated_courses = result.truncated_courses.into_iter().collect();
+        // A term no course is in any more shows nothing; go back to all.
+        if self.term_filter.is_some() && self.term_filter_name().is_none() {
+            self.term_filter = None;
+        }
+        // After assignments: the next-due sort looks at them.
+        self.rebuild_course_display_order();
+        self.recount_filtered_assignments();
+        self.unread_announcements_total = result.unread_announcements;
+        self.clock_skew = self.client.clock_skew().filter(|s| crate::api::clock::significant(*s));
+        self.course_colors = result.course_colors;
+        self.course_accents = course_accent::accents(&self.courses, &self.course_colors);
+        // Only a fresh announcement list says which archived ones are gone.
+        if result.error.is_none()
+            && !result.stale.announcements
+            && announcement_archive::prune(&mut self.archived_announcements, &self.announcements)
+        {
+            self.save_archived_announcements();
+        }
+        self.announcement_list_state.set_len(self.announcement_list().row_count());
+        self.group_names = result.group_names;
+        self.peer_reviews = result.peer_reviews;
+        self.first_seen = result.first_seen;
 
         self.rebuild_calendar_items();
         self.focal_assignment_id = self.compute_focal_assignment_id();
+        let graded = self.recently_graded().len();
+        self.graded_list_state.set_len(graded);
 
         let cal_idx = self.find_today_calendar_idx();
         self.calendar_list_state.selected = cal_idx;
@@ -625,6 +1791,19 @@ impl App {
 
         self.cached_at = Some(result.fetched_at);
 
+        // Retry whatever failed, backing off while it keeps failing.
+        if result.stale.is_empty() {
+            self.stale_retries = 0;
+            self.stale_retry_at = None;
+        } else {
+            let delay = STALE_RETRY_FIRST
+                .saturating_mul(1 << self.stale_retries.min(8))
+                .min(STALE_RETRY_MAX);
+            self.stale_retries += 1;
+            self.stale_retry_at = Some(std::time::Instant::now() + delay);
+        }
+        self.stale_sections = result.stale;
+
         if result.error.is_none() {
             let name = self
                 .user
@@ -636,11 +1815,100 @@ impl App {
                 .with_timezone(&chrono::Local)
                 .format("%b %d %H:%M");
             self.status_message = format!(
-                "Welcome, {}! {} courses loaded. Synced {synced}.",
+                "Welcome, {}! {} courses loaded. Synced {synced}.{}",
                 name,
-                self.courses.len()
+                self.courses.len(),
+                self.cross_listing_note()
             );
+            match self.stale_sections.count() {
+                _ if result.maintenance => self.status_message = MAINTENANCE_STATUS.into(),
+                0 => {}
+                1 => self.status_message += " 1 section failed; showing older data, will retry.",
+                n => {
+                    self.status_message +=
+                        &format!(" {n} sections failed; showing older data, will retry.")
+                }
+            }
+        }
+        if result.other_user {
+            self.status_message += " The cached data was another user's; replaced it.";
+        }
+    }
+
+    /// Show each set of cross-listed sections as its first course: the
+    /// others leave the course list, their assignments join its own and
+    /// their events and announcements are filed under it, without
+    /// duplicates.  The cache keeps the data as Canvas sent it.
+    fn merge_cross_listed_courses(&mut self) {
+        self.merged_courses = if self.merge_cross_listed {
+            cross_listing::find_duplicates(&self.courses, &self.assignments)
+        } else {
+            BTreeMap::new()
+        };
+        if self.merged_courses.is_empty() {
+            return;
+        }
+        let merged = &self.merged_courses;
+        self.courses.retain(|c| !merged.contains_key(&c.id));
+
+        // The shown courses' groups first, so they keep their names.
+        let (groups, duplicates): (Vec<_>, Vec<_>) = std::mem::take(&mut self.assignments)
+            .into_iter()
+            .partition(|g| !merged.contains_key(&g.course_id));
+        let mut seen = HashSet::new();
+        for group in groups.into_iter().chain(duplicates) {
+            let course_id = merged.get(&group.course_id).copied().unwrap_or(group.course_id);
+            // Each assignment keeps its own `course_id`: it is where Canvas
+            // takes its submissions.
+            let assignments: Vec<Assignment> =
+                group.assignments.into_iter().filter(|a| seen.insert(a.id)).collect();
+            match self.assignments.iter_mut().find(|g| g.course_id == course_id) {
+                Some(existing) => existing.assignments.extend(assignments),
+                None => {
+                    self.assignments.push(CourseAssignments { course_id, assignments, ..group })
+                }
+            }
+        }
+
+        let refile = |code: &mut Option<String>| {
+            let id = code.as_deref().and_then(|c| c.strip_prefix("course_"))?.parse().ok()?;
+            let primary = merged.get(&id)?;
+            *code = Some(format!("course_{primary}"));
+            Some(())
+        };
+        let mut seen = HashSet::new();
+        self.calendar_events.retain(|e| seen.insert(e.id));
+        for event in &mut self.calendar_events {
+            refile(&mut event.context_code);
+        }
+        let mut seen = HashSet::new();
+        self.announcements.retain(|a| seen.insert(a.id));
+        for announcement in &mut self.announcements {
+            refile(&mut announcement.context_code);
+        }
+        self.truncated_courses = std::mem::take(&mut self.truncated_courses)
+            .into_iter()
+            .map(|id| merged.get(&id).copied().unwrap_or(id))
+            .collect();
+    }
+
+    /// " Merged 2 cross-listed sections of PHYS 2021." for each course
+    /// `merge_cross_listed_courses` merged, for the status bar.
+    fn cross_listing_note(&self) -> String {
+        let mut sections: BTreeMap<u64, usize> = BTreeMap::new();
+        for primary in self.merged_courses.values() {
+            *sections.entry(*primary).or_insert(1) += 1;
         }
+        sections
+            .into_iter()
+            .map(|(id, count)| {
+                let course = self.courses.iter().find(|c| c.id == id);
+                let name = course
+                    .and_then(|c| c.course_code.clone().or_else(|| c.name.clone()))
+                    .unwrap_or_else(|| format!("course {id}"));
+                format!(" Merged {count} cross-listed sections of {name}.")
+            })
+            .collect()
     }
 
     pub fn rebuild_calendar_items(&mut self) {
@@ -653,67 +1921,75 @@ impl App {
             .filter_map(|e| e.assignment.as_ref().and_then(|a| a.id))
             .collect();
 
-        // Build a set of current-quarter course IDs for filtering calendar events.
-        let current_course_ids: HashSet<u64> = self
-            .courses
-            .iter()
-            .filter(|c| Self::is_current_quarter_course(c))
-            .map(|c| c.id)
-            .collect();
-
-        let mut items: Vec<CalendarItem> = self
-            .calendar_events
-            .iter()
-            .filter(|e| {
-                // Keep events whose context_code matches a current-quarter course,
-                // or events with no context_code (keep by default).
-                e.context_code
-                    .as_deref()
-                    .and_then(|cc| cc.strip_prefix("course_"))
-                    .and_then(|id| id.parse::<u64>().ok())
-                    .map(|id| current_course_ids.contains(&id))
-                    .unwrap_or(true)
-            })
-            .map(|e| CalendarItem {
-                start_at: e.start_at,
-                title: e.title.clone().unwrap_or_else(|| "Untitled".into()),
-                item_type: if e.event_type.as_deref() == Some("assignment") {
-                    "assignment"
-                } else {
-                    "event"
+        let mut items: Vec<CalendarItem> = Vec::new();
+        for e in &self.calendar_events {
+            // Events from a course are resolved to its name and filtered like
+            // that course's assignments; events from courses we don't know
+            // (e.g. concluded ones) are dropped.  Events with no course
+            // context are always kept.
+            let course = match e.course_id() {
+                Some(id) => match self.courses.iter().find(|c| c.id == id) {
+                    Some(c) => Some(c),
+                    None => continue,
                 },
-                course_name: None,
+                None => None,
+            };
+            let course_name = course.map(|c| c.name.clone().unwrap_or_else(|| "Unnamed".into()));
+            if course.is_some_and(|c| !self.course_passes_filter(c.id)) {
+                continue;
+            }
+            // Group events are labelled with the group but never filtered.
+            let course_name = course_name
+                .or_else(|| e.group_id().and_then(|id| self.group_names.get(&id).cloned()));
+            let title = e.title.clone().unwrap_or_else(|| "Untitled".into());
+            let item_type = if e.event_type.as_deref() == Some("assignment") {
+                "assignment"
+            } else {
+                "event"
+            };
+            let emphasis = if item_type == "event" {
+                EventEmphasis::classify(&title, &self.event_highlight, &self.event_deemphasize)
+            } else {
+                EventEmphasis::Normal
+            };
+            if emphasis == EventEmphasis::Dimmed && self.hide_dimmed_events {
+                continue;
+            }
+            items.push(CalendarItem {
+                start_at: e.start_at,
+                title,
+                item_type,
+                emphasis,
+                course_name,
+                course_id: course.map(|c| c.id),
                 status: None,
                 assignment_id: e.assignment.as_ref().and_then(|a| a.id),
-            })
-            .collect();
+                event_id: Some(e.id),
+                review_url: None,
+            });
+        }
 
         // Merge in assignment due dates not already present (current quarter only).
-        for (course_name, assignments) in self.assignments.iter().filter(|(name, _)| self.is_current_quarter_by_name(name)) {
-            for assignment in assignments {
+        for group in self.assignments.iter().filter(|g| self.course_passes_filter(g.course_id)) {
+            for assignment in &group.assignments {
                 if assignment.due_at.is_none() {
                     continue;
                 }
-                if event_assignment_ids.contains(&assignment.id) {
+                if event_assignment_ids.contains(&assignment.id) || !self.shows_assignment(assignment) {
                     continue;
                 }
+                let unpublished = assignment.is_unpublished();
+                let past_due = !unpublished && assignment.due_at.is_some_and(|d| d < now);
 
                 let status = if let Some(ref sub) = assignment.submission {
                     match sub.workflow_state.as_deref() {
                         Some("graded") => Some(
-                            sub.score
-                                .map(|s| {
-                                    format!(
-                                        "{:.1}/{}",
-                                        s,
-                                        assignment.points_possible.unwrap_or(0.0)
-                                    )
-                                })
+                            format_score(assignment, sub.score, sub.grade.as_deref())
                                 .unwrap_or_else(|| "Graded".into()),
                         ),
                         Some("submitted") => Some("Submitted".into()),
                         _ => {
-                            if assignment.due_at.is_some_and(|d| d < now) {
+                            if past_due {
                                 if sub.missing.unwrap_or(false) {
                                     Some("Missing!".into())
                                 } else {
@@ -724,33 +2000,108 @@ impl App {
                             }
                         }
                     }
-                } else if assignment.due_at.is_some_and(|d| d < now) {
+                } else if past_due {
                     Some("Past due".into())
                 } else {
                     None
                 };
+                let name = assignment.name.as_deref().unwrap_or("Unnamed");
 
                 items.push(CalendarItem {
                     start_at: assignment.due_at,
-                    title: assignment.name.clone().unwrap_or_else(|| "Unnamed".into()),
+                    title: if unpublished {
+                        format!("{name} (unpublished)")
+                    } else {
+                        name.to_string()
+                    },
                     item_type: "assignment",
-                    course_name: Some(course_name.clone()),
+                    emphasis: if unpublished { EventEmphasis::Dimmed } else { EventEmphasis::Normal },
+                    course_name: Some(group.name.clone()),
+                    course_id: Some(group.course_id),
                     status,
                     assignment_id: Some(assignment.id),
+                    event_id: None,
+                    review_url: None,
                 });
             }
         }
 
-        items.sort_by(|a, b| a.start_at.cmp(&b.start_at));
+        // Peer reviews I owe, on the day they are due.
+        for group in self.assignments.iter().filter(|g| self.course_passes_filter(g.course_id)) {
+            for assignment in &group.assignments {
+                let Some(reviews) = self.peer_reviews.get(&assignment.id) else {
+                    continue;
+                };
+                let name = assignment.name.as_deref().unwrap_or("Unnamed");
+                for review in reviews {
+                    let whom = review
+                        .user
+                        .as_ref()
+                        .and_then(|u| u.display_name.as_deref())
+                        .unwrap_or("a classmate");
+                    items.push(CalendarItem {
+                        start_at: assignment.peer_reviews_due_at(),
+                        title: format!("Peer review: {name} ({whom})"),
+                        item_type: "peer_review",
+                        emphasis: EventEmphasis::Normal,
+                        course_name: Some(group.name.clone()),
+                        course_id: Some(group.course_id),
+                        status: review.is_completed().then(|| "Reviewed".into()),
+                        assignment_id: Some(assignment.id),
+                        event_id: None,
+                        review_url: self.peer_review_url(assignment, review),
+                    });
+                }
+            }
+        }
+
+        items.sort_by_key(|a| a.start_at);
         self.calendar_list_state.set_len(items.len());
         self.calendar_items = items;
     }
 
     /// Jump the active tab's list to the first item on or after today.
-    pub fn jump_to_today_active(&mut self) {
-        if self.active_tab != Tab::Assignments {
+    /// Remember which assignments in `fresh` are graded with a different
+    /// grading time than in the data currently shown.  Skipped when nothing
+    /// is shown yet, or everything would count as new.
+    fn note_newly_graded(&mut self, fresh: &[CourseAssignments]) {
+        if self.assignments.is_empty() {
             return;
         }
+        let before: std::collections::HashMap<u64, Option<DateTime<Utc>>> = self
+            .assignments
+            .iter()
+            .flat_map(|group| group.assignments.iter())
+            .map(|a| (a.id, graded_time(a)))
+            .collect();
+        for a in fresh.iter().flat_map(|group| group.assignments.iter()) {
+            let now = graded_time(a);
+            if now.is_some() && before.get(&a.id).is_none_or(|b| *b != now) {
+                self.newly_graded.insert(a.id);
+            }
+        }
+    }
+
+    /// `t`: select the item for "now" on the active tab — today in the
+    /// Schedule, the first entry of the Dashboard's list, and the newest
+    /// unread announcement (else the newest).  The Courses tab has none.
+    pub fn jump_to_today_active(&mut self) {
+        match self.active_tab {
+            Tab::Assignments => {}
+            Tab::Dashboard => {
+                let state = self.active_list_state_mut();
+                state.selected = 0;
+                state.needs_center = true;
+                return;
+            }
+            Tab::Announcements => {
+                self.announcement_list_state.selected = self.newest_unread_announcement_idx();
+                self.announcement_list_state.needs_center = true;
+                self.announcement_attachment = 0;
+                return;
+            }
+            Tab::Courses => return,
+        }
         match self.unified_view_mode {
             UnifiedViewMode::CalendarView => {
                 let idx = self.find_today_calendar_idx();
@@ -759,6 +2110,9 @@ impl App {
             }
             UnifiedViewMode::ListView => {
                 let idx = match self.assignment_sort {
+                    AssignmentSort::DueDateAsc if self.shows_date_sections() => {
+                        self.find_today_section_idx()
+                    }
                     AssignmentSort::DueDateAsc => self.find_today_assignment_idx(),
                     _ => 0,
                 };
@@ -768,15 +2122,27 @@ impl App {
         }
     }
 
-    /// Returns the Canvas ID of the first upcoming, incomplete assignment
-    /// (due today or later, not yet submitted/graded), used to highlight the
-    /// most actionable item across all sort modes.
+    /// Index of the newest unread announcement, or of the newest one when
+    /// all are read (the list is newest first).
+    fn newest_unread_announcement_idx(&self) -> usize {
+        self.announcement_list()
+            .active
+            .into_iter()
+            .position(|a| a.read_state.as_deref() == Some("unread"))
+            .unwrap_or(0)
+    }
+
+    /// Returns the Canvas ID of the first upcoming, incomplete, published
+    /// assignment (due today or later, not yet submitted/graded), used to
+    /// highlight the most actionable item across all sort modes.
     fn compute_focal_assignment_id(&self) -> Option<u64> {
         let today = chrono::Utc::now().date_naive();
         let mut flat: Vec<&Assignment> = self
             .assignments
             .iter()
-            .flat_map(|(_, a)| a.iter())
+            .filter(|group| self.course_in_term(group.course_id))
+            .flat_map(|group| group.assignments.iter())
+            .filter(|a| !a.is_unpublished())
             .collect();
         flat.sort_unstable_by(|a, b| match (a.due_at, b.due_at) {
             (None, None) => std::cmp::Ordering::Equal,
@@ -801,46 +2167,218 @@ impl App {
     }
 
     pub fn find_today_calendar_idx(&self) -> usize {
-        let today = chrono::Utc::now().date_naive();
+        self.find_calendar_idx_from(Local::now().date_naive())
+    }
+
+    /// Index of the first calendar item on or after `date` (local time).
+    fn find_calendar_idx_from(&self, date: chrono::NaiveDate) -> usize {
         self.calendar_items
             .iter()
             .position(|item| {
                 item.start_at
-                    .map(|d| d.date_naive() >= today)
+                    .map(|d| d.with_timezone(&Local).date_naive() >= date)
                     .unwrap_or(false)
             })
             // If everything is in the past, land on the last item.
             .unwrap_or_else(|| self.calendar_items.len().saturating_sub(1))
     }
 
-    pub fn find_today_assignment_idx(&self) -> usize {
-        let today = chrono::Utc::now().date_naive();
-        let mut flat: Vec<&Assignment> = self
-            .assignments
+    /// Whether the Schedule list is split under `date_sections` headers:
+    /// it is on and the list is sorted by due date.
+    pub fn shows_date_sections(&self) -> bool {
+        self.date_sections
+            && matches!(
+                self.assignment_sort,
+                AssignmentSort::DueDateAsc | AssignmentSort::DueDateDesc
+            )
+    }
+
+    /// Row of the first assignment under the Today header, or under the
+    /// next dated one when nothing is due today.
+    fn find_today_section_idx(&self) -> usize {
+        let now = self.now();
+        let rows = self.schedule_rows();
+        rows.rows
             .iter()
-            .flat_map(|(_, a)| a.iter())
-            .collect();
-        flat.sort_unstable_by(|a, b| match (a.due_at, b.due_at) {
+            .position(|(_, a)| {
+                let section = DateSection::of(a.due_at, now);
+                section >= DateSection::Today && section != DateSection::NoDate
+            })
+            .map_or(0, |pos| rows.pinned.len() + pos)
+    }
+
+    pub fn find_today_assignment_idx(&self) -> usize {
+        self.find_assignment_idx_from(Local::now().date_naive())
+    }
+
+    /// Row of the first assignment below the "Due soon" section due on or
+    /// after `date` (local time), for the list view's `DueDateAsc` order.
+    fn find_assignment_idx_from(&self, date: chrono::NaiveDate) -> usize {
+        let rows = self.schedule_rows();
+        let mut flat: Vec<&Assignment> = rows.rows.iter().map(|(_, a)| *a).collect();
+        flat.sort_by(|a, b| match (a.due_at, b.due_at) {
             (None, None) => std::cmp::Ordering::Equal,
             (None, _) => std::cmp::Ordering::Greater,
             (_, None) => std::cmp::Ordering::Less,
             (Some(x), Some(y)) => x.cmp(&y),
         });
         flat.iter()
-            .position(|a| a.due_at.map(|d| d.date_naive() >= today).unwrap_or(false))
-            .unwrap_or(0)
+            .position(|a| {
+                a.due_at
+                    .map(|d| d.with_timezone(&Local).date_naive() >= date)
+                    .unwrap_or(false)
+            })
+            .map_or(0, |pos| rows.pinned.len() + pos)
+    }
+
+    /// Parse the jump-to-date prompt and move the schedule selection to the
+    /// first item on or after that date.  List view switches to `DueDateAsc`
+    /// so "on or after" is meaningful.  On a parse error the prompt stays
+    /// open with the error shown.
+    pub fn submit_date_jump(&mut self) {
+        let Some(input) = self.date_jump_input.as_deref() else {
+            return;
+        };
+        let date = match date_input::parse_date(input, Local::now().date_naive()) {
+            Ok(date) => date,
+            Err(e) => {
+                self.date_jump_error = Some(e);
+                return;
+            }
+        };
+        self.date_jump_input = None;
+        self.date_jump_error = None;
+
+        match self.unified_view_mode {
+            UnifiedViewMode::CalendarView => {
+                self.calendar_list_state.selected = self.find_calendar_idx_from(date);
+                self.calendar_list_state.needs_center = true;
+            }
+            UnifiedViewMode::ListView => {
+                self.assignment_sort = AssignmentSort::DueDateAsc;
+                self.assignment_list_state.selected = self.find_assignment_idx_from(date);
+                self.assignment_list_state.needs_center = true;
+            }
+        }
+        self.status_message = format!("Jumped to {}", date.format("%a %b %d, %Y"));
+    }
+
+    /// Open the effort estimate prompt for the assignment in the detail
+    /// pane, filled in with its current estimate.
+    pub fn open_estimate_prompt(&mut self) {
+        let Some((_, a)) = self.detail_assignment() else {
+            self.status_message = "Select an assignment to estimate.".into();
+            return;
+        };
+        let input = self
+            .estimates
+            .get(&a.id)
+            .map(|h| time_budget::format_hours(*h))
+            .unwrap_or_default();
+        self.estimate_prompt = Some(EstimatePrompt {
+            assignment_id: a.id,
+            assignment_name: a.name.clone().unwrap_or_else(|| "Untitled".into()),
+            input,
+            error: None,
+        });
+    }
+
+    /// Store the estimate typed into the prompt (empty clears it) and save
+    /// the estimates file.  On a parse error the prompt stays open.
+    pub fn submit_estimate(&mut self) {
+        let Some(prompt) = self.estimate_prompt.as_mut() else {
+            return;
+        };
+        let hours = match time_budget::parse_estimate(&prompt.input) {
+            Ok(hours) => hours,
+            Err(e) => {
+                prompt.error = Some(e);
+                return;
+            }
+        };
+        let Some(prompt) = self.estimate_prompt.take() else {
+            return;
+        };
+        match hours {
+            Some(h) => {
+                self.estimates.insert(prompt.assignment_id, h);
+                self.status_message = format!(
+                    "Estimate for {}: {}",
+                    prompt.assignment_name,
+                    time_budget::format_hours(h)
+                );
+            }
+            None => {
+                self.estimates.remove(&prompt.assignment_id);
+                self.status_message = format!(
+                    "Estimate for {} cleared (counts as {})",
+                    prompt.assignment_name,
+                    time_budget::format_hours(self.estimate_default_hours)
+                );
+            }
+        }
+        if let Err(e) = time_budget::save_estimates(&self.paths.estimates_file(), &self.estimates) {
+            self.status_message = format!("Couldn't save estimates: {e:#}");
+        }
+    }
+
+    /// Hours of work `a` is expected to take, and whether that is its own
+    /// estimate rather than `estimate_default_hours`.
+    pub fn estimate_hours(&self, a: &Assignment) -> (f32, bool) {
+        match self.estimates.get(&a.id) {
+            Some(h) => (*h, true),
+            None => (self.estimate_default_hours, false),
+        }
+    }
+
+    /// Hours of work per day for the next `BUDGET_DAYS` days from today:
+    /// the estimates of published, unsubmitted assignments still to come in
+    /// courses passing the course filter (see `time_budget::daily_hours`).
+    pub fn time_budget(&self) -> Vec<f32> {
+        let now = Utc::now();
+        let items = self
+            .assignments
+            .iter()
+            .filter(|group| self.course_passes_filter(group.course_id))
+            .flat_map(|group| group.assignments.iter())
+            .filter(|a| !a.is_unpublished() && !a.is_submitted())
+            .filter_map(|a| Some((a.due_at.filter(|d| *d > now)?, self.estimate_hours(a).0)));
+        time_budget::daily_hours(items, &Local, Local::now().date_naive(), time_budget::BUDGET_DAYS)
     }
 
     /// Returns the course name and assignment reference for the currently
     /// selected index, resolving correctly across all sort modes (flat and grouped).
     pub fn get_selected_assignment(&self) -> Option<(&str, &Assignment)> {
+        self.schedule_rows().get(self.assignment_list_state.selected)
+    }
+
+    /// The list view's rows: the pinned "Due soon" section, then the
+    /// filtered assignments in `assignment_sort` order (without the pinned
+    /// ones when `due_soon_section` is `move`).
+    pub fn schedule_rows(&self) -> ScheduleRows<'_> {
+        let now = chrono::Utc::now();
+        let mut rows = self.sorted_assignments();
+        let mut pinned: Vec<(&str, &Assignment)> = Vec::new();
+        if self.due_soon_section != DueSoonSection::Off {
+            pinned = rows.iter().copied().filter(|(_, a)| is_due_soon(a, now)).collect();
+            pinned.sort_by_key(|(_, a)| a.due_at);
+        }
+        if self.due_soon_section == DueSoonSection::Move {
+            rows.retain(|(_, a)| !is_due_soon(a, now));
+        }
+        ScheduleRows { pinned, rows }
+    }
+
+    /// Filtered assignments in `assignment_sort` order.
+    fn sorted_assignments(&self) -> Vec<(&str, &Assignment)> {
         let mut flat: Vec<(&str, &Assignment)> = self
             .assignments
             .iter()
-            .filter(|(name, _)| self.course_passes_filter(name))
-            .flat_map(|(course, assignments)| {
-                assignments.iter().map(move |a| (course.as_str(), a))
+            .filter(|group| self.course_passes_filter(group.course_id))
+            .flat_map(|group| {
+                group.assignments.iter().map(move |a| (group.name.as_str(), a))
             })
+            .filter(|(_, a)| self.shows_assignment(a))
             .collect();
 
         match self.assignment_sort {
@@ -862,45 +2400,481 @@ impl App {
             AssignmentSort::Course => { /* already in course order */ }
         }
 
-        flat.into_iter().nth(self.assignment_list_state.selected)
+        flat
     }
 
     /// Look up a full (course_name, &Assignment) by Canvas assignment ID.
     /// Used by the calendar view detail panel to show complete assignment data.
     pub fn get_assignment_by_id(&self, id: u64) -> Option<(&str, &Assignment)> {
-        for (course_name, assignments) in &self.assignments {
-            if let Some(a) = assignments.iter().find(|a| a.id == id) {
-                return Some((course_name.as_str(), a));
+        for group in &self.assignments {
+            if let Some(a) = group.assignments.iter().find(|a| a.id == id) {
+                return Some((group.name.as_str(), a));
             }
         }
         None
     }
 
-    /// Returns the ordered list of course names that have assignments.
-    pub fn assignment_course_names(&self) -> Vec<&str> {
-        self.assignments.iter().map(|(name, _)| name.as_str()).collect()
+    /// The ids and names of the courses that have assignments, in order.
+    pub fn assignment_courses(&self) -> Vec<(u64, &str)> {
+        self.assignments
+            .iter()
+            .map(|group| (group.course_id, group.name.as_str()))
+            .collect()
+    }
+
+    /// Returns true if the given course passes the current filter
+    /// AND belongs to the selected term, or with no term selected to the
+    /// current academic quarter.
+    /// An empty filter set means "show all (current quarter)", unless
+    /// `course_filter_none` is set.
+    pub fn course_passes_filter(&self, course_id: u64) -> bool {
+        let in_term = match self.term_filter {
+            Some(_) => self.course_in_term(course_id),
+            None => self.is_current_quarter_course_id(course_id),
+        };
+        self.course_filter_enabled(course_id) && in_term
+    }
+
+    /// Whether course `course_id` belongs to the selected term.  Always
+    /// true with no term selected.
+    pub fn course_in_term(&self, course_id: u64) -> bool {
+        let Some(term) = self.term_filter else {
+            return true;
+        };
+        self.courses
+            .iter()
+            .find(|c| c.id == course_id)
+            .and_then(|c| c.term.as_ref())
+            .is_some_and(|t| t.id == term)
+    }
+
+    /// The distinct terms of the courses, newest first, for the term picker
+    /// (`Ctrl+T`).
+    pub fn terms(&self) -> Vec<(u64, String)> {
+        let mut terms: Vec<&Term> = Vec::new();
+        for term in self.courses.iter().filter_map(|c| c.term.as_ref()) {
+            if !terms.iter().any(|t| t.id == term.id) {
+                terms.push(term);
+            }
+        }
+        terms.sort_by(|a, b| b.start_at.cmp(&a.start_at).then(b.id.cmp(&a.id)));
+        terms
+            .into_iter()
+            .map(|t| (t.id, t.name.clone().unwrap_or_else(|| format!("Term {}", t.id))))
+            .collect()
+    }
+
+    /// The selected term's name, for the header.
+    pub fn term_filter_name(&self) -> Option<String> {
+        let id = self.term_filter?;
+        self.terms().into_iter().find(|(t, _)| *t == id).map(|(_, name)| name)
+    }
+
+    /// Open the term picker with the current choice selected.  Its first row
+    /// is "All".
+    pub fn open_term_picker(&mut self) {
+        let terms = self.terms();
+        self.term_list_state.set_len(terms.len() + 1);
+        self.term_list_state.selected = self
+            .term_filter
+            .and_then(|id| terms.iter().position(|(t, _)| *t == id))
+            .map_or(0, |pos| pos + 1);
+        self.show_term_picker = true;
+    }
+
+    /// Narrow everything to the term in row `row` of the picker (0 is
+    /// "All") and close it.
+    pub fn pick_term(&mut self, row: usize) {
+        self.term_filter = row
+            .checked_sub(1)
+            .and_then(|i| self.terms().get(i).map(|(id, _)| *id));
+        self.show_term_picker = false;
+        self.apply_term_filter();
+    }
+
+    /// Redo the lists, counts and focal assignment after the term changed.
+    pub fn apply_term_filter(&mut self) {
+        self.rebuild_course_display_order();
+        self.apply_course_filter();
+        self.announcement_list_state.set_len(self.announcement_list().row_count());
+        self.announcement_attachment = 0;
+        self.focal_assignment_id = self.compute_focal_assignment_id();
+    }
+
+    /// The Announcements tab's list: all of them (the synced ones, then any
+    /// older ones loaded), or with a term selected those of its courses
+    /// plus the group and institution ones, which belong to no term.
+    pub fn visible_announcements(&self) -> Vec<&DiscussionTopic> {
+        let synced: HashSet<u64> = self.announcements.iter().map(|a| a.id).collect();
+        let older = self
+            .older_announcements
+            .iter()
+            .flat_map(|list| list.items())
+            .filter(|a| !synced.contains(&a.id));
+        self.announcements
+            .iter()
+            .chain(older)
+            .filter(|a| {
+                let course = a
+                    .context_code
+                    .as_deref()
+                    .and_then(|c| c.strip_prefix("course_"))
+                    .and_then(|id| id.parse().ok());
+                course.is_none_or(|id| self.course_in_term(id))
+            })
+            .collect()
+    }
+
+    /// The Announcements tab's rows as built from `visible_announcements`:
+    /// the archived ones go behind the fold row.
+    pub fn announcement_list(&self) -> AnnouncementList<'_> {
+        let (archived, active) = self
+            .visible_announcements()
+            .into_iter()
+            .partition(|a| self.archived_announcements.contains(&a.id));
+        let more = self.older_announcements.as_ref().is_none_or(|list| list.has_more());
+        AnnouncementList { active, more, archived, expanded: self.show_archived }
+    }
+
+    /// The announcement selected on the Announcements tab (`None` on the
+    /// fold row).
+    pub fn selected_announcement(&self) -> Option<&DiscussionTopic> {
+        self.announcement_list().get(self.announcement_list_state.selected)
+    }
+
+    /// Whether the Announcements tab's selection is on the archive's fold row.
+    pub fn archive_fold_selected(&self) -> bool {
+        self.announcement_list().fold_row() == Some(self.announcement_list_state.selected)
+    }
+
+    /// Whether the Announcements tab's selection is on the "older
+    /// announcements" row.
+    pub fn older_row_selected(&self) -> bool {
+        self.announcement_list().more_row() == Some(self.announcement_list_state.selected)
+    }
+
+    /// Enter on the "older announcements" row: fetch the next page of
+    /// them, or the one that failed again.
+    pub fn load_older_announcements(&mut self) {
+        if self.older_announcements.is_none() {
+            match self.older_announcements_list() {
+                Ok(list) => self.older_announcements = Some(list),
+                Err(e) => {
+                    self.status_message = format!("Couldn't load older announcements: {e}");
+                    return;
+                }
+            }
+        }
+        let Some(list) = self.older_announcements.as_mut() else {
+            return;
+        };
+        if let Some(rx) = list.load_next_page(&self.client) {
+            self.older_announcements_rx = Some(rx);
+        }
+    }
+
+    /// Fetch the next page of older announcements ahead of time, once the
+    /// selection is past the middle of those loaded.
+    pub fn prefetch_older_announcements(&mut self) {
+        let Some(id) = self.selected_announcement().map(|a| a.id) else {
+            return;
+        };
+        let Some(list) = self.older_announcements.as_mut() else {
+            return;
+        };
+        let Some(position) = list.items().iter().position(|a| a.id == id) else {
+            return;
+        };
+        if let Some(rx) = list.prefetch(&self.client, position) {
+            self.older_announcements_rx = Some(rx);
+        }
+    }
+
+    pub fn poll_older_announcements(&mut self) {
+        let Some(rx) = self.older_announcements_rx.as_mut() else {
+            return;
+        };
+        let page = match rx.try_recv() {
+            Ok(page) => page,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => Err("request ended unexpectedly".into()),
+        };
+        self.older_announcements_rx = None;
+        if let Some(list) = self.older_announcements.as_mut() {
+            list.receive(page);
+        }
+        self.announcement_list_state.set_len(self.announcement_list().row_count());
+    }
+
+    /// The time countdowns count from: Canvas's, when `correct_clock_skew`
+    /// is set and this clock is off, else this computer's.
+    pub fn now(&self) -> DateTime<Utc> {
+        match self.clock_skew {
+            Some(skew) if self.correct_clock_skew => Utc::now() + skew,
+            _ => Utc::now(),
+        }
+    }
+
+    /// The color of the signed-in user's initials badge, so each account
+    /// keeps its own.
+    pub fn user_badge_color(&self) -> ratatui::style::Color {
+        let id = self.user.as_ref().map_or(0, |u| u.id);
+        course_accent::CourseAccent::new(id, None).color(self.color_mode)
+    }
+
+    /// Open the profile popup and measure the cache in the background.
+    pub fn open_profile(&mut self) {
+        self.show_profile = true;
+        self.profile_disk = None;
+        let (tx, rx) = oneshot::channel();
+        let (file, dir) = (self.paths.cache_file(), self.paths.cache_dir.clone());
+        tokio::task::spawn_blocking(move || {
+            let _ = tx.send(profile::measure(&file, &dir));
+        });
+        self.profile_disk_rx = Some(rx);
+    }
+
+    /// Take the cache measurement started by `open_profile` once it is in.
+    pub fn poll_profile_disk(&mut self) {
+        let Some(rx) = self.profile_disk_rx.as_mut() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(usage) => self.profile_disk = Some(usage),
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            // Shows as unknown rather than measuring forever.
+            Err(oneshot::error::TryRecvError::Closed) => {
+                self.profile_disk = Some(profile::DiskUsage::default())
+            }
+        }
+        self.profile_disk_rx = None;
+    }
+
+    /// Announcements of the synced courses and groups from before the
+    /// synced ones (Canvas sends only the last two weeks' by default), back
+    /// to the start of the oldest course.
+    fn older_announcements_list(&self) -> Result<PaginatedList<DiscussionTopic>, CanvasError> {
+        let context_codes: Vec<String> = self
+            .courses
+            .iter()
+            .map(|c| format!("course_{}", c.id))
+            .chain(self.group_names.keys().map(|id| format!("group_{id}")))
+            .collect();
+        let end = self
+            .announcements
+            .iter()
+            .filter(|a| a.context_code.as_deref() != Some(INSTITUTION_CONTEXT))
+            .filter_map(|a| a.posted_at)
+            .min()
+            .unwrap_or_else(Utc::now);
+        let start = self
+            .courses
+            .iter()
+            .filter_map(|c| c.start_at)
+            .min()
+            .unwrap_or(end - chrono::Duration::days(OLDER_ANNOUNCEMENTS_FALLBACK_DAYS))
+            .min(end);
+        self.client.older_announcements(
+            &context_codes,
+            &start.format("%Y-%m-%d").to_string(),
+            &end.format("%Y-%m-%d").to_string(),
+        )
+    }
+
+    /// The stripe at the left of a row of `course_id`'s, in the current
+    /// color mode; blank for rows without a course.  A merged section's
+    /// rows get the stripe of the course they are shown under.
+    pub fn course_stripe(&self, course_id: Option<u64>) -> (&'static str, ratatui::style::Color) {
+        course_id
+            .map(|id| self.merged_courses.get(&id).copied().unwrap_or(id))
+            .map(|id| {
+                self.course_accents
+                    .get(&id)
+                    .copied()
+                    .unwrap_or_else(|| course_accent::CourseAccent::new(id, None))
+            })
+            .map_or((course_accent::NO_STRIPE, ratatui::style::Color::Reset), |accent| {
+                accent.stripe(self.color_mode)
+            })
+    }
+
+    /// Unread announcements for the Dashboard and the Announcements tab:
+    /// Canvas's own count, which matches its badge, less the unread ones
+    /// archived here; or when the sync couldn't get it, those among the
+    /// fetched ones.
+    pub fn unread_announcements_shown(&self) -> u64 {
+        let Some(total) = self.unread_announcements_total else {
+            return self.unread_announcement_count() as u64;
+        };
+        let archived_unread = self
+            .announcement_list()
+            .archived
+            .iter()
+            .filter(|a| a.read_state.as_deref() == Some("unread"))
+            .count();
+        total.saturating_sub(archived_unread as u64)
+    }
+
+    /// Unread announcements among those fetched, leaving out archived ones.
+    pub fn unread_announcement_count(&self) -> usize {
+        self.announcement_list()
+            .active
+            .iter()
+            .filter(|a| a.read_state.as_deref() == Some("unread"))
+            .count()
+    }
+
+    /// Expand or collapse the archived announcements.
+    pub fn toggle_show_archived(&mut self) {
+        self.show_archived = !self.show_archived;
+        self.announcement_list_state.set_len(self.announcement_list().row_count());
+    }
+
+    /// `x`: archive the selected announcement.  The next one moves up
+    /// into its place.
+    pub fn archive_selected_announcement(&mut self) {
+        let Some(id) = self.selected_announcement().map(|a| a.id) else {
+            return;
+        };
+        if !self.archived_announcements.insert(id) {
+            return;
+        }
+        self.save_archived_announcements();
+        self.announcement_list_state.set_len(self.announcement_list().row_count());
+        self.announcement_attachment = 0;
+        self.status_message = "Announcement archived.".into();
+    }
+
+    /// `X` in the expanded archive: put the selected announcement back in
+    /// the list.
+    pub fn unarchive_selected_announcement(&mut self) {
+        let Some(id) = self.selected_announcement().map(|a| a.id) else {
+            return;
+        };
+        if !self.archived_announcements.remove(&id) {
+            self.status_message = "This announcement isn't archived.".into();
+            return;
+        }
+        self.save_archived_announcements();
+        self.announcement_list_state.set_len(self.announcement_list().row_count());
+        self.announcement_attachment = 0;
+        self.status_message = "Announcement restored.".into();
+    }
+
+    fn save_archived_announcements(&mut self) {
+        let path = self.paths.archived_announcements_file();
+        if let Err(e) = announcement_archive::save_archived(&path, &self.archived_announcements) {
+            self.status_message = format!("Could not save archived announcements: {e}");
+        }
+    }
+
+    /// Whether `a` is listed at all: unpublished assignments can be hidden
+    /// from the course filter popup.
+    pub fn shows_assignment(&self, a: &Assignment) -> bool {
+        !(self.hide_unpublished && a.is_unpublished())
+    }
+
+    /// Show or hide unpublished assignments.
+    pub fn toggle_hide_unpublished(&mut self) {
+        self.hide_unpublished = !self.hide_unpublished;
+        self.apply_course_filter();
+    }
+
+    /// Toggle one course in the filter.  An empty filter means "all", so the
+    /// first toggle turns it into an explicit list of every other course,
+    /// and re-enabling the last excluded course goes back to "all".
+    pub fn toggle_course_filter(&mut self, course_id: u64) {
+        let all: Vec<u64> = self.assignment_courses().into_iter().map(|(id, _)| id).collect();
+        if self.course_filter_none {
+            self.course_filter_none = false;
+            self.course_filter = HashSet::from([course_id]);
+        } else if self.course_filter.is_empty() {
+            self.course_filter = all.iter().copied().filter(|id| *id != course_id).collect();
+        } else if !self.course_filter.remove(&course_id) {
+            self.course_filter.insert(course_id);
+        }
+        if all.iter().all(|n| self.course_filter.contains(n)) {
+            self.course_filter.clear();
+        }
+        self.apply_course_filter();
+    }
+
+    /// Show every course (`a` in the filter popup).
+    pub fn select_all_courses(&mut self) {
+        self.course_filter_none = false;
+        self.course_filter.clear();
+        self.apply_course_filter();
+    }
+
+    /// Filter out every course (`n` in the filter popup), to then toggle
+    /// back on just the ones wanted.
+    pub fn select_no_courses(&mut self) {
+        self.course_filter_none = true;
+        self.course_filter.clear();
+        self.apply_course_filter();
+    }
+
+    /// Whether course `course_id` is checked in the filter popup.
+    pub fn course_filter_enabled(&self, course_id: u64) -> bool {
+        !self.course_filter_none
+            && (self.course_filter.is_empty() || self.course_filter.contains(&course_id))
+    }
+
+    /// What the filter popup lists beside a course: its assignments (as
+    /// listed, so without hidden unpublished ones), those in the "Due soon"
+    /// section, those still upcoming, and its unread announcements.
+    pub fn course_filter_counts(&self, course_id: u64) -> CourseCounts {
+        let now = Utc::now();
+        let mut counts = CourseCounts::default();
+        let Some(group) = self.assignments.iter().find(|g| g.course_id == course_id) else {
+            return counts;
+        };
+        for a in group.assignments.iter().filter(|a| self.shows_assignment(a)) {
+            counts.assignments += 1;
+            if is_due_soon(a, now) {
+                counts.due_soon += 1;
+            }
+            if a.due_at.is_some_and(|d| d > now) && !a.is_submitted() && !a.is_unpublished() {
+                counts.upcoming += 1;
+            }
+        }
+        let context = format!("course_{course_id}");
+        counts.unread_announcements = self
+            .announcements
+            .iter()
+            .filter(|a| {
+                !self.archived_announcements.contains(&a.id)
+                    && a.context_code.as_deref() == Some(context.as_str())
+                    && a.read_state.as_deref() == Some("unread")
+            })
+            .count();
+        counts
     }
 
-    /// Returns true if the given course name passes the current filter
-    /// AND belongs to the current academic quarter.
-    /// An empty filter set means "show all (current quarter)".
-    pub fn course_passes_filter(&self, course_name: &str) -> bool {
-        let filter_ok = self.course_filter.is_empty() || self.course_filter.contains(course_name);
-        filter_ok && self.is_current_quarter_by_name(course_name)
+    /// Refresh every view the course filter affects.
+    pub fn apply_course_filter(&mut self) {
+        self.recount_filtered_assignments();
+        self.rebuild_calendar_items();
+        let rows = self.dashboard_upcoming().row_count();
+        self.dashboard_list_state.set_len(rows);
+        let graded = self.recently_graded().len();
+        self.graded_list_state.set_len(graded);
     }
 
     /// Check if a course (by display name) belongs to the current quarter.
     /// Looks up the course in `self.courses` to also check `course_code`.
     /// Courses with no detectable quarter code are treated as current.
-    fn is_current_quarter_by_name(&self, course_name: &str) -> bool {
+    fn is_current_quarter_course_id(&self, course_id: u64) -> bool {
         let current = AcademicQuarter::current();
         // Find the matching course to get the code too.
-        let course = self.courses.iter().find(|c| {
-            c.name.as_deref() == Some(course_name)
-        });
+        let course = self.courses.iter().find(|c| c.id == course_id);
         let q = match course {
             Some(c) => course_quarter(c.name.as_deref(), c.course_code.as_deref()),
-            None => parse_quarter(course_name),
+            None => self
+                .assignments
+                .iter()
+                .find(|g| g.course_id == course_id)
+                .and_then(|g| parse_quarter(&g.name)),
         };
         match q {
             Some(q) => q == current,
@@ -918,402 +2892,2723 @@ impl App {
         }
     }
 
-    /// Recount visible assignments after filter change and clamp selection.
+    /// Recount the list view's rows after a filter change and clamp the
+    /// selection.
     pub fn recount_filtered_assignments(&mut self) {
-        let total: usize = self
-            .assignments
-            .iter()
-            .filter(|(name, _)| self.course_passes_filter(name))
-            .map(|(_, a)| a.len())
-            .sum();
+        let total = self.schedule_rows().len();
         self.assignment_list_state.set_len(total);
     }
 
-    /// Returns the course name and assignment for the currently selected
-    /// dashboard upcoming item.
-    pub fn get_selected_dashboard_assignment(&self) -> Option<(&str, &Assignment)> {
+    /// The dashboard's "Upcoming" list: published assignments due from today
+    /// on in courses passing the course filter, soonest first.  Those due after
+    /// the `dashboard_days` window are folded away unless expanded.
+    pub fn dashboard_upcoming(&self) -> DashboardUpcoming<'_> {
         let now = chrono::Utc::now();
-        let one_month = now + chrono::Duration::days(30);
+        let window_end = now + chrono::Duration::days(self.dashboard_days as i64);
         let today = now.date_naive();
 
-        let mut upcoming: Vec<(&str, &Assignment)> = self
+        let mut items: Vec<(&str, &Assignment)> = self
             .assignments
             .iter()
-            .filter(|(name, _)| self.is_current_quarter_by_name(name))
-            .flat_map(|(course, assignments)| {
-                assignments.iter().map(move |a| (course.as_str(), a))
-            })
-            .filter(|(_, a)| {
-                a.due_at.map(|d| d.date_naive() >= today && d <= one_month).unwrap_or(false)
+            .filter(|group| self.course_passes_filter(group.course_id))
+            .flat_map(|group| {
+                group.assignments.iter().map(move |a| (group.name.as_str(), a))
             })
+            .filter(|(_, a)| a.due_at.is_some_and(|d| d.date_naive() >= today))
+            .filter(|(_, a)| !a.is_unpublished())
             .collect();
+        items.sort_by_key(|(_, a)| a.due_at);
 
-        upcoming.sort_by(|a, b| match (a.1.due_at, b.1.due_at) {
-            (None, None) => std::cmp::Ordering::Equal,
-            (None, _) => std::cmp::Ordering::Greater,
-            (_, None) => std::cmp::Ordering::Less,
-            (Some(x), Some(y)) => x.cmp(&y),
-        });
+        let in_window = items
+            .iter()
+            .take_while(|(_, a)| a.due_at.is_some_and(|d| d <= window_end))
+            .count();
+        let later = items.len() - in_window;
+        if !self.dashboard_show_later {
+            items.truncate(in_window);
+        }
 
-        upcoming.into_iter().nth(self.dashboard_list_state.selected)
+        DashboardUpcoming { items, later, window_end }
     }
 
-    /// Open the submission modal from the Dashboard upcoming-assignments list.
-    pub fn open_dashboard_submission_modal(&mut self) {
-        let resolved: Option<(u64, u64, Vec<String>)> =
-            self.get_selected_dashboard_assignment().map(|(_, a)| {
-                (
-                    a.course_id.unwrap_or(0),
-                    a.id,
-                    a.submission_types.clone().unwrap_or_default(),
-                )
-            });
-        self.finish_open_submission(resolved);
+    /// Graded assignments in courses passing the course filter, most
+    /// recently graded first, capped at `RECENT_GRADES_LIMIT`.
+    pub fn recently_graded(&self) -> Vec<(&str, &Assignment, DateTime<Utc>)> {
+        let mut items: Vec<_> = self
+            .assignments
+            .iter()
+            .filter(|group| self.course_passes_filter(group.course_id))
+            .flat_map(|group| {
+                group
+                    .assignments
+                    .iter()
+                    .filter_map(move |a| Some((group.name.as_str(), a, graded_time(a)?)))
+            })
+            .collect();
+        items.sort_by_key(|(_, _, graded_at)| std::cmp::Reverse(*graded_at));
+        items.truncate(RECENT_GRADES_LIMIT);
+        items
     }
 
-    /// Open the submission modal for the currently selected assignment.
-    /// Works in both CalendarView and ListView modes.
-    /// Shows a status message and does nothing if the assignment doesn't
-    /// support any online submission type we handle.
-    pub fn open_submission_modal(&mut self) {
-        // Extract (course_id, assignment_id, submission_types) without holding borrows.
-        let resolved: Option<(u64, u64, Vec<String>)> =
-            if self.unified_view_mode == UnifiedViewMode::CalendarView {
-                // Calendar view: selected is an index into calendar_items.
-                let cal_aid = self
+    /// Returns the course name and assignment for the currently selected
+    /// item of the dashboard's active panel (`None` on the fold row).
+    pub fn get_selected_dashboard_assignment(&self) -> Option<(&str, &Assignment)> {
+        match self.dashboard_panel {
+            DashboardPanel::Upcoming => self
+                .dashboard_upcoming()
+                .items
+                .into_iter()
+                .nth(self.dashboard_list_state.selected),
+            DashboardPanel::RecentlyGraded => self
+                .recently_graded()
+                .into_iter()
+                .nth(self.graded_list_state.selected)
+                .map(|(course, a, _)| (course, a)),
+        }
+    }
+
+    /// The assignment the active tab's detail pane shows, if any.
+    pub fn detail_assignment(&self) -> Option<(&str, &Assignment)> {
+        match self.active_tab {
+            Tab::Dashboard => self.get_selected_dashboard_assignment(),
+            Tab::Assignments => match self.unified_view_mode {
+                UnifiedViewMode::ListView => self.get_selected_assignment(),
+                UnifiedViewMode::CalendarView => self
                     .calendar_items
                     .get(self.calendar_list_state.selected)
-                    .and_then(|item| item.assignment_id);
+                    .and_then(|item| item.assignment_id)
+                    .and_then(|id| self.get_assignment_by_id(id)),
+            },
+            Tab::Courses | Tab::Announcements => None,
+        }
+    }
 
-                match cal_aid {
-                    None => {
-                        self.status_message = "Selected item is not an assignment.".into();
-                        return;
-                    }
-                    Some(aid) => {
-                        // Collect what we need into owned values so the borrow ends here.
-                        self.get_assignment_by_id(aid).map(|(_, a)| {
-                            (
-                                a.course_id.unwrap_or(0),
-                                a.id,
-                                a.submission_types.clone().unwrap_or_default(),
-                            )
-                        })
-                    }
-                }
-            } else {
-                // List view: use the existing helper.
-                self.get_selected_assignment().map(|(_, a)| {
-                    (
-                        a.course_id.unwrap_or(0),
-                        a.id,
-                        a.submission_types.clone().unwrap_or_default(),
-                    )
-                })
-            };
+    /// How the full description of assignment `id` is coming along, if it
+    /// was requested recently.
+    pub fn description_load(&self, id: u64) -> Option<DescriptionLoad> {
+        self.description_loads
+            .iter()
+            .find(|(loaded, _)| *loaded == id)
+            .map(|(_, state)| *state)
+    }
 
-        self.finish_open_submission(resolved);
+    fn set_description_load(&mut self, id: u64, state: DescriptionLoad) {
+        self.description_loads.retain(|(loaded, _)| *loaded != id);
+        self.description_loads.push_back((id, state));
+        if self.description_loads.len() > DESCRIPTION_LOADS_CAP {
+            self.description_loads.pop_front();
+        }
     }
 
-    /// Shared validation and state setup for opening the submission modal.
-    fn finish_open_submission(&mut self, resolved: Option<(u64, u64, Vec<String>)>) {
-        let Some((course_id, assignment_id, types)) = resolved else {
-            self.status_message = "No assignment selected.".into();
+    /// If the detail pane shows an assignment whose description was left
+    /// out of the sync or shortened in the cache, fetch the full assignment
+    /// in the background.  Called every tick; one fetch runs at a time, and
+    /// recently requested assignments aren't fetched again.
+    pub fn ensure_full_description(&mut self) {
+        if self.description_rx.is_some() {
+            return;
+        }
+        let Some((_, a)) = self.detail_assignment() else {
             return;
         };
-
-        if course_id == 0 {
-            self.status_message = "Cannot determine course for this assignment.".into();
+        let incomplete = a.description.as_deref().is_none_or(cache::is_truncated);
+        let (Some(course_id), id) = (a.course_id, a.id) else {
+            return;
+        };
+        if !incomplete || self.description_load(id).is_some() {
             return;
         }
 
-        let supported: Vec<SubmissionKind> = types
-            .iter()
-            .filter_map(|t| SubmissionKind::from_api_type(t.as_str()))
-            .collect();
+        let client = self.client.clone();
+        let (tx, rx) = oneshot::channel();
+        self.set_description_load(id, DescriptionLoad::Loading);
+        self.description_rx = Some(rx);
+        tokio::spawn(async move {
+            let result = client
+                .get_assignment(course_id, id)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send((id, result));
+        });
+    }
 
-        if supported.is_empty() {
-            self.status_message =
-                "This assignment does not support online submission.".into();
+    /// Patch the description fetched by `ensure_full_description` into
+    /// `assignments`; the next sync carries it into the cache.
+    pub fn poll_full_description(&mut self) {
+        let Some(rx) = self.description_rx.as_mut() else {
             return;
+        };
+        let (id, result) = match rx.try_recv() {
+            Ok(r) => r,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => {
+                self.description_rx = None;
+                return;
+            }
+        };
+        self.description_rx = None;
+        match result {
+            Ok(fresh) => {
+                if let Some(a) = self
+                    .assignments
+                    .iter_mut()
+                    .flat_map(|group| group.assignments.iter_mut())
+                    .find(|a| a.id == id)
+                {
+                    a.description = fresh.description;
+                    a.updated_at = fresh.updated_at;
+                }
+                self.set_description_load(id, DescriptionLoad::Loaded);
+            }
+            Err(e) => {
+                tracing::warn!("loading description of assignment {id}: {e}");
+                self.set_description_load(id, DescriptionLoad::Failed);
+            }
         }
+    }
 
-        self.submission_target = Some((course_id, assignment_id));
-        self.submission_supported_kinds = supported;
-        self.submission_cursor = 0;
-        self.submission_input.clear();
-        self.submission_kind = None;
-        self.submission_state = SubmissionState::TypePicker;
+    /// Course of the assignment in the detail pane, when its assignment list
+    /// was cut off at `max_assignments_per_course`.
+    pub fn selected_truncated_course(&self) -> Option<u64> {
+        let (_, a) = self.detail_assignment()?;
+        a.course_id.filter(|id| self.truncated_courses.contains(id))
     }
 
-    /// Kick off a background submission task using the current
-    /// `submission_kind` and `submission_input`.
-    pub fn start_submission(&mut self) {
-        let Some((course_id, assignment_id)) = self.submission_target else {
+    /// Fetch every assignment of the detail pane's course in the background,
+    /// ignoring the per-course cap from now on.
+    pub fn load_all_assignments(&mut self) {
+        if self.load_all_rx.is_some() {
             return;
-        };
-        let Some(ref kind) = self.submission_kind.clone() else {
+        }
+        let Some(course_id) = self.selected_truncated_course() else {
+            self.status_message = "All of this course's assignments are loaded.".into();
             return;
         };
-
-        let content = self.submission_input.clone();
         let client = self.client.clone();
-        let kind = kind.clone();
-
+        let with_descriptions = self.cache_prune_days.is_none();
         let (tx, rx) = oneshot::channel();
-        self.submission_rx = Some(rx);
-        self.submission_state = SubmissionState::Submitting;
-
+        self.load_all_rx = Some(rx);
+        self.status_message = "Loading all assignments…".into();
         tokio::spawn(async move {
-            let result: Result<(), String> = match kind {
-                SubmissionKind::TextEntry => client
-                    .submit_text_entry(course_id, assignment_id, &content)
-                    .await
-                    .map(|_| ())
-                    .map_err(|e| e.to_string()),
-                SubmissionKind::Url => client
-                    .submit_url(course_id, assignment_id, &content)
-                    .await
-                    .map(|_| ())
-                    .map_err(|e| e.to_string()),
-                SubmissionKind::FileUpload => {
-                    let path = std::path::Path::new(&content);
-                    client
-                        .submit_file(course_id, assignment_id, path)
-                        .await
-                        .map(|_| ())
-                        .map_err(|e| e.to_string())
+            let result = client
+                .list_assignments(course_id, true, with_descriptions, None)
+                .await
+                .map(|capped| capped.items)
+                .map_err(|e| e.to_string());
+            let _ = tx.send((course_id, result));
+        });
+    }
+
+    pub fn poll_load_all(&mut self) {
+        let Some(rx) = self.load_all_rx.as_mut() else {
+            return;
+        };
+        let (course_id, result) = match rx.try_recv() {
+            Ok(r) => r,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => {
+                self.load_all_rx = None;
+                return;
+            }
+        };
+        self.load_all_rx = None;
+        let mut loaded = match result {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                self.status_message = format!("Loading all assignments failed: {e}");
+                return;
+            }
+        };
+
+        // Keep descriptions already loaded on demand.
+        let known = self.known_descriptions();
+        for a in loaded.iter_mut().filter(|a| a.description.is_none()) {
+            if let Some((updated_at, desc)) = known.get(&a.id) {
+                if *updated_at == a.updated_at {
+                    a.description = Some(desc.clone());
                 }
-            };
+            }
+        }
+        let count = loaded.len();
+        for a in &loaded {
+            self.linked_due.remove(&a.id);
+        }
+        self.course_group_mut(course_id).assignments = loaded;
+        self.truncated_courses.remove(&course_id);
+        self.uncapped_courses.insert(course_id);
+        self.apply_event_links();
 
-            let submit_result = match result {
-                Ok(()) => SubmitResult {
-                    success: true,
-                    message: "Submission successful! Press any key to close.".into(),
-                },
-                Err(e) => SubmitResult {
-                    success: false,
-                    message: format!("Submission failed: {e}"),
-                },
-            };
+        self.recount_filtered_assignments();
+        self.rebuild_calendar_items();
+        self.focal_assignment_id = self.compute_focal_assignment_id();
+        self.status_message = format!("Loaded all {count} assignments.");
+    }
 
-            let _ = tx.send(submit_result);
-        });
+    /// Full descriptions already loaded, for a sync that leaves them out.
+    fn known_descriptions(&self) -> HashMap<u64, (Option<DateTime<Utc>>, String)> {
+        self.assignments
+            .iter()
+            .flat_map(|group| group.assignments.iter())
+            .filter_map(|a| {
+                let desc = a.description.as_ref().filter(|d| !cache::is_truncated(d))?;
+                Some((a.id, (a.updated_at, desc.clone())))
+            })
+            .collect()
     }
 
-    /// Poll for a completed background submission. Returns true when done.
-    pub fn poll_submission_result(&mut self) -> bool {
-        let result = match self.submission_rx.as_mut() {
-            None => return false,
-            Some(rx) => match rx.try_recv() {
-                Ok(r) => r,
-                Err(oneshot::error::TryRecvError::Empty) => return false,
-                Err(oneshot::error::TryRecvError::Closed) => {
-                    self.submission_rx = None;
-                    return false;
-                }
-            },
-        };
-        self.submission_rx = None;
-        let success = result.success;
-        self.submission_state = SubmissionState::Done {
-            success,
-            message: result.message,
+    /// Links in what the active tab's detail pane shows: the assignment
+    /// description (followed by the assignment's Canvas page) or the open
+    /// course page.  Numbered the same as the pane's Links section.
+    fn detail_links(&self) -> Vec<links::Link> {
+        let base = self.client.base_url();
+        if self.active_tab == Tab::Courses {
+            return self
+                .course_detail_content
+                .as_deref()
+                .map(|html| links::extract_links(html, base))
+                .unwrap_or_default();
+        }
+        let Some((_, a)) = self.detail_assignment() else {
+            return Vec::new();
         };
-        if success {
-            self.needs_refresh = true;
+        let mut found = a
+            .description
+            .as_deref()
+            .map(|html| links::extract_links(html, base))
+            .unwrap_or_default();
+        if let Some(url) = a.html_url.as_ref().filter(|u| !found.iter().any(|l| &l.url == *u)) {
+            found.push(links::Link {
+                text: "Assignment in Canvas".into(),
+                url: url.clone(),
+                is_image: false,
+            });
         }
-        true
+        found
     }
 
-    /// Fetch the list of available pages for the currently selected course.
-    pub fn fetch_course_pages(&mut self) {
-        let Some(course) = self.selected_course() else {
+    /// Open the link picker for the detail pane's links.
+    pub fn open_link_picker(&mut self) {
+        let found = self.detail_links();
+        if found.is_empty() {
+            self.status_message = "No links here.".into();
             return;
-        };
-        let course_id = course.id;
-        let client = self.client.clone();
-        let (tx, rx) = oneshot::channel();
-        self.course_pages_rx = Some(rx);
-        self.course_pages_loading = true;
-        self.course_pages.clear();
-        self.course_detail_content = None;
-        self.show_course_pages_picker = false;
+        }
+        self.link_picker_state.set_len(found.len());
+        self.link_picker_state.selected = 0;
+        self.link_picker = Some(found);
+    }
 
-        tokio::spawn(async move {
-            let pages = client.list_pages(course_id).await.unwrap_or_default();
-            let _ = tx.send(pages);
-        });
+    /// Open link `idx` of the picker in the browser and close the picker.
+    pub fn open_picked_link(&mut self, idx: usize) {
+        let Some(link) = self.link_picker.as_ref().and_then(|l| l.get(idx)).cloned() else {
+            return;
+        };
+        self.link_picker = None;
+        self.status_message = match open_in_browser(&link.url) {
+            Ok(()) => format!("Opened {}", link.url),
+            Err(e) => format!("Could not open {}: {e}", link.url),
+        };
     }
 
-    /// Poll for the completed page-list fetch. Opens the picker when ready.
-    pub fn poll_course_pages(&mut self) -> bool {
-        let result = match self.course_pages_rx.as_mut() {
-            None => return false,
-            Some(rx) => match rx.try_recv() {
-                Ok(r) => r,
-                Err(oneshot::error::TryRecvError::Empty) => return false,
-                Err(oneshot::error::TryRecvError::Closed) => {
-                    self.course_pages_rx = None;
-                    self.course_pages_loading = false;
-                    return false;
+    /// The entity the detail pane shows, as Canvas sent it (give or take the
+    /// fields the models drop), with a short name for it.
+    fn detail_raw_json(&self) -> Option<(String, serde_json::Value)> {
+        let value = match self.active_tab {
+            Tab::Courses => {
+                let c = self.selected_course()?;
+                (format!("course-{}", c.id), serde_json::to_value(c))
+            }
+            Tab::Announcements => {
+                let a = self.selected_announcement()?;
+                (format!("announcement-{}", a.id), serde_json::to_value(a))
+            }
+            Tab::Assignments if self.unified_view_mode == UnifiedViewMode::CalendarView => {
+                let item = self.calendar_items.get(self.calendar_list_state.selected)?;
+                match item.event_id.and_then(|id| self.calendar_events.iter().find(|e| e.id == id)) {
+                    Some(e) => (format!("event-{}", e.id), serde_json::to_value(e)),
+                    None => {
+                        let (_, a) = self.detail_assignment()?;
+                        (format!("assignment-{}", a.id), serde_json::to_value(a))
+                    }
                 }
-            },
+            }
+            Tab::Dashboard | Tab::Assignments => {
+                let (_, a) = self.detail_assignment()?;
+                (format!("assignment-{}", a.id), serde_json::to_value(a))
+            }
         };
-        self.course_pages_rx = None;
-        self.course_pages_loading = false;
+        let (name, json) = value;
+        Some((name, json.ok()?))
+    }
 
-        if result.is_empty() {
-            self.status_message = "No pages found for this course.".into();
+    /// Show the detail pane's entity as pretty-printed JSON in `$PAGER`
+    /// (default `less`), for checking what Canvas actually sent.
+    pub fn view_raw_detail(&mut self) {
+        let Some((name, json)) = self.detail_raw_json() else {
+            self.status_message = "Nothing selected to show.".into();
+            return;
+        };
+        let path = std::env::temp_dir().join(format!("canvas-tui-{name}.json"));
+        let text = serde_json::to_string_pretty(&json).unwrap_or_default();
+        if let Err(e) = std::fs::write(&path, text) {
+            self.status_message = format!("Could not write {}: {e}", path.display());
+            return;
+        }
+        self.external_command = Some(external::ExternalCommand::PageFile { path });
+    }
+
+    /// Start choosing the file to upload: the newest files in
+    /// `submit_watch_dirs` when there are any, otherwise the path prompt.
+    /// Directories that can't be read are noted in the status bar.
+    pub fn choose_submission_file(&mut self) {
+        self.submission_input.clear();
+        self.submission_file = None;
+        let (files, errors) = recent_files::recent_files(&self.submit_watch_dirs);
+        if !errors.is_empty() {
+            self.status_message = format!("Couldn't read submit_watch_dirs: {}", errors.join("; "));
+        }
+        self.recent_file_state.set_len(files.len());
+        self.recent_file_state.selected = 0;
+        self.recent_files = files;
+        self.submission_state = if self.recent_files.is_empty() {
+            SubmissionState::FileInput
         } else {
-            self.course_pages_list_state.set_len(result.len());
-            self.course_pages_list_state.selected = 0;
-            self.course_pages = result;
-            self.show_course_pages_picker = true;
+            SubmissionState::FilePicker
+        };
+    }
+
+    /// Let the user write the text of a text entry or discussion reply in
+    /// their editor (see `external::editor_command`).  The modal stays
+    /// hidden until the editor returns.
+    pub fn edit_submission_text(&mut self) {
+        let assignment_id = self.submission_target.map_or(0, |(_, id)| id);
+        let draft = self.recovered_drafts.remove(&assignment_id);
+        let initial = draft
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .unwrap_or_default();
+        let file = match external::create_submission_file(assignment_id, &initial) {
+            Ok(file) => file,
+            Err(e) => {
+                self.status_message = format!("Could not create a file to edit: {e}");
+                self.submission_state = SubmissionState::TypePicker;
+                return;
+            }
+        };
+        if let Some(path) = draft {
+            // Its text is in the new file now.
+            let _ = std::fs::remove_file(path);
+            self.status_message = "Restored your unsent text from an earlier session.".into();
         }
-        true
+        let editor = external::editor_command(self.editor.as_deref());
+        self.external_command = Some(external::ExternalCommand::EditSubmission { editor, file });
+        self.submission_state = SubmissionState::Hidden;
     }
 
-    /// Fetch the body of a specific page selected from the picker.
-    pub fn fetch_selected_page(&mut self) {
-        let Some(page) = self.course_pages.get(self.course_pages_list_state.selected) else {
+    /// Look for submission text left behind by a crashed session.
+    pub fn find_recovered_drafts(&mut self) {
+        self.recovered_drafts = external::orphaned_drafts().into_iter().collect();
+        match self.recovered_drafts.len() {
+            0 => {}
+            1 => {
+                self.status_message =
+                    "Found unsent submission text from an earlier session; it opens in the \
+                     editor when you submit that assignment again."
+                        .into()
+            }
+            n => {
+                self.status_message = format!(
+                    "Found {n} unsent submission texts from earlier sessions; each opens in \
+                     the editor when you submit its assignment again."
+                )
+            }
+        }
+    }
+
+    /// Pick up after `request` ran with the TUI suspended.
+    pub fn finish_external_command(
+        &mut self,
+        request: external::ExternalCommand,
+        result: std::io::Result<std::process::ExitStatus>,
+    ) {
+        match request {
+            external::ExternalCommand::EditSubmission { editor, file } => {
+                let content = std::fs::read_to_string(&file).unwrap_or_default();
+                // Deletes the file.
+                drop(file);
+                let failure = match result {
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                        Some(format!("Editor '{editor}' not found — is it installed?"))
+                    }
+                    Err(e) => Some(format!("Could not start editor '{editor}': {e}")),
+                    Ok(status) if status.code() == Some(127) => Some(format!(
+                        "Editor '{editor}' exited with status 127 — is it installed?"
+                    )),
+                    Ok(status) if !status.success() => Some(match status.code() {
+                        Some(code) => format!("Editor '{editor}' exited with status {code}."),
+                        None => format!("Editor '{editor}' was killed by a signal."),
+                    }),
+                    Ok(_) => None,
+                };
+                if let Some(message) = failure {
+                    self.submission_state = SubmissionState::TypePicker;
+                    self.status_message = message;
+                } else if content.trim().is_empty() {
+                    self.submission_state = SubmissionState::TypePicker;
+                    self.status_message =
+                        "Editor closed with no content — submission cancelled.".into();
+                } else {
+                    self.submission_input = content;
+                    self.submission_state = SubmissionState::TextPreview;
+                }
+            }
+            external::ExternalCommand::PageFile { path } => {
+                let _ = std::fs::remove_file(&path);
+                if let Err(e) = result {
+                    self.status_message = format!("Could not run the pager: {e}");
+                }
+            }
+            external::ExternalCommand::OpenHandler { command } => {
+                self.status_message = open_handler_outcome(&command, result);
+            }
+        }
+    }
+
+    /// Run the first `open_handlers` command matching the assignment the
+    /// detail pane shows: with the terminal, or in the background when
+    /// the handler is `detach`ed.  A `dry_run` handler only shows the
+    /// command.
+    pub fn run_open_handler(&mut self) {
+        let Some((_, a)) = self.detail_assignment() else {
+            self.status_message = "Select an assignment to run its command.".into();
             return;
         };
-        let Some(page_url) = page.url.clone() else {
-            self.status_message = "Page has no URL.".into();
+        let course = a.course_id.and_then(|id| self.courses.iter().find(|c| c.id == id));
+        let Some(handler) = open_handler::find(&self.open_handlers, course, a) else {
+            self.status_message = "No open_handlers entry matches this assignment.".into();
             return;
         };
-        let Some(course) = self.selected_course() else {
+        let command = match open_handler::expand(&handler.command, course, a) {
+            Ok(command) => command,
+            Err(e) => {
+                self.status_message = format!("open_handlers: {e}");
+                return;
+            }
+        };
+        if handler.dry_run {
+            self.status_message = format!("Would run: {command}");
+        } else if !handler.detach {
+            self.external_command = Some(external::ExternalCommand::OpenHandler { command });
+        } else if self.open_handler_rx.is_some() {
+            self.status_message = "A command is already running.".into();
+        } else {
+            let mut cmd = tokio::process::Command::from(open_handler::shell_command(&command));
+            cmd.stdin(std::process::Stdio::null())
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null());
+            let mut child = match cmd.spawn() {
+                Ok(child) => child,
+                Err(e) => {
+                    self.status_message = open_handler_outcome(&command, Err(e));
+                    return;
+                }
+            };
+            let (tx, rx) = oneshot::channel();
+            self.open_handler_rx = Some(rx);
+            self.status_message = format!("Started: {command}");
+            tokio::spawn(async move {
+                let _ = tx.send(open_handler_outcome(&command, child.wait().await));
+            });
+        }
+    }
+
+    /// Report a detached open handler that has exited.
+    pub fn poll_open_handler(&mut self) {
+        let Some(rx) = self.open_handler_rx.as_mut() else {
             return;
         };
-        let course_id = course.id;
-        let client = self.client.clone();
-        let (tx, rx) = oneshot::channel();
-        self.course_detail_rx = Some(rx);
+        let message = match rx.try_recv() {
+            Ok(message) => message,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => "The command ended unexpectedly.".into(),
+        };
+        self.open_handler_rx = None;
+        self.status_message = message;
+    }
+
+    /// Whether assignment `id` turned up in the last sync or the last 24
+    /// hours, for the "new" badge.
+    pub fn is_newly_seen(&self, id: u64) -> bool {
+        self.first_seen.seen_at(id).is_some_and(|seen| {
+            Some(seen) == self.cached_at || Utc::now() - seen < chrono::Duration::hours(24)
+        })
+    }
+
+    /// Switch the dashboard between Upcoming and Recently graded.
+    pub fn toggle_dashboard_panel(&mut self) {
+        self.dashboard_panel = self.dashboard_panel.toggle();
+        let graded = self.recently_graded().len();
+        self.graded_list_state.set_len(graded);
+    }
+
+    /// Show the selected Recently graded assignment in the Schedule tab's
+    /// list view.
+    pub fn open_graded_in_schedule(&mut self) {
+        let Some(id) = self.get_selected_dashboard_assignment().map(|(_, a)| a.id) else {
+            return;
+        };
+        let Some(pos) = self.schedule_rows().position(id) else {
+            return;
+        };
+        self.active_tab = Tab::Assignments;
+        self.unified_view_mode = UnifiedViewMode::ListView;
+        self.assignment_list_state.selected = pos;
+        self.assignment_list_state.needs_center = true;
+    }
+
+    /// Whether the dashboard selection is on the "… N more" fold row.
+    pub fn dashboard_fold_selected(&self) -> bool {
+        if self.dashboard_panel != DashboardPanel::Upcoming {
+            return false;
+        }
+        let upcoming = self.dashboard_upcoming();
+        upcoming.later > 0 && self.dashboard_list_state.selected == upcoming.items.len()
+    }
+
+    /// Expand or collapse the dashboard's "later" fold.  The fold row sits
+    /// after the visible items, so after collapsing the selection follows it
+    /// to its new index; after expanding it lands on the first newly shown
+    /// assignment, which took the fold row's old index.
+    pub fn toggle_dashboard_later(&mut self) {
+        let on_fold = self.dashboard_fold_selected();
+        self.dashboard_show_later = !self.dashboard_show_later;
+        let upcoming = self.dashboard_upcoming();
+        let (rows, fold_idx) = (upcoming.row_count(), upcoming.items.len());
+        self.dashboard_list_state.set_len(rows);
+        if on_fold && !self.dashboard_show_later {
+            self.dashboard_list_state.selected = fold_idx;
+        }
+    }
+
+    /// Pick up the settings from `config` the app reads directly.
+    pub fn apply_config(&mut self, config: &Config) {
+        self.dashboard_days = config.dashboard_days.unwrap_or(DEFAULT_DASHBOARD_DAYS);
+        let rows = self.dashboard_upcoming().row_count();
+        self.dashboard_list_state.set_len(rows);
+
+        let patterns = |configured: &Option<Vec<String>>, default: &[&str]| -> Vec<String> {
+            match configured {
+                Some(list) => list.iter().map(|p| p.to_lowercase()).filter(|p| !p.is_empty()).collect(),
+                None => default.iter().map(|p| p.to_string()).collect(),
+            }
+        };
+        self.event_highlight = patterns(&config.event_highlight, &DEFAULT_EVENT_HIGHLIGHT);
+        self.event_deemphasize = patterns(&config.event_deemphasize, &DEFAULT_EVENT_DEEMPHASIZE);
+        self.max_assignments_per_course = config.max_assignments_per_course;
+        self.editor = config.editor.clone();
+        self.submit_watch_dirs = config.submit_watch_dirs.clone().unwrap_or_default();
+        self.estimate_default_hours = config
+            .estimate_default_hours
+            .unwrap_or(time_budget::DEFAULT_ESTIMATE_HOURS);
+        self.daily_capacity_hours = config
+            .daily_capacity_hours
+            .unwrap_or(time_budget::DEFAULT_DAILY_CAPACITY_HOURS);
+        self.hint_labels = config.hint_labels.clone().unwrap_or_default().into_iter().collect();
+        self.wrap_navigation = config.wrap_navigation.unwrap_or(false);
+        self.wrap_names = config.wrap_names.unwrap_or(false);
+        self.vim_gg = config.vim_gg.unwrap_or(false);
+        self.date_sections = config.date_sections.unwrap_or(false);
+        self.exit_summary = config.exit_summary.unwrap_or(false);
+        self.open_handlers = config.open_handlers.clone().unwrap_or_default();
+        self.correct_clock_skew = config.correct_clock_skew.unwrap_or(false);
+        self.merge_cross_listed = config.merge_cross_listed.unwrap_or(true);
+        self.color_mode = config.color_mode.unwrap_or_else(ui::theme::detect_color_mode);
+        self.due_soon_section = config.due_soon_section.unwrap_or_default();
+        self.recount_filtered_assignments();
+        self.cache_prune_days = config
+            .cache_prune
+            .unwrap_or(true)
+            .then(|| config.cache_keep_ended_days.unwrap_or(cache::DEFAULT_KEEP_ENDED_DAYS));
+        self.rebuild_calendar_items();
+    }
+
+    /// Show or hide routine (`EventEmphasis::Dimmed`) calendar events.
+    pub fn toggle_dimmed_events(&mut self) {
+        self.hide_dimmed_events = !self.hide_dimmed_events;
+        self.rebuild_calendar_items();
+        self.status_message = if self.hide_dimmed_events {
+            "Hiding routine events (office hours, lectures…).".into()
+        } else {
+            "Showing all events.".into()
+        };
+    }
+
+    /// Join the event links into `assignments` (see `event_links::apply`),
+    /// first catching up on events that moved.  Data fresh from Canvas or
+    /// the cache has no linked dates yet: clear `linked_due` before calling.
+    fn apply_event_links(&mut self) {
+        if event_links::refresh(&mut self.event_links, &self.calendar_events) {
+            self.save_event_links();
+        }
+        self.linked_due = event_links::apply(&mut self.assignments, &self.event_links, &self.linked_due);
+    }
+
+    /// `assignments` as Canvas has them, without the linked due dates, for
+    /// a sync to fall back on and cache.
+    fn assignments_without_links(&self) -> Vec<CourseAssignments> {
+        let mut assignments = self.assignments.clone();
+        for a in assignments.iter_mut().flat_map(|group| group.assignments.iter_mut()) {
+            if self.linked_due.contains(&a.id) {
+                a.due_at = None;
+            }
+        }
+        assignments
+    }
+
+    fn save_event_links(&mut self) {
+        if let Err(e) = event_links::save_event_links(&self.paths.event_links_file(), &self.event_links) {
+            self.status_message = format!("Couldn't save event links: {e:#}");
+        }
+    }
+
+    /// The assignment linked to calendar event `event_id`, if any.
+    pub fn event_linked_assignment(&self, event_id: u64) -> Option<u64> {
+        self.event_links
+            .iter()
+            .find(|(_, link)| link.event_id == event_id)
+            .map(|(id, _)| *id)
+    }
+
+    /// `l` in the calendar: on an event, pick an undated assignment of its
+    /// course to link it to, or unlink it when it is linked; on an
+    /// assignment dated by a link, unlink it.
+    pub fn toggle_event_link(&mut self) {
+        let Some(item) = self.calendar_items.get(self.calendar_list_state.selected) else {
+            return;
+        };
+        if let Some(id) = item.assignment_id.filter(|id| self.linked_due.contains(id)) {
+            self.unlink_event(id);
+            return;
+        }
+        let (Some(event_id), "event") = (item.event_id, item.item_type) else {
+            self.status_message = "Select a calendar event to link it to an assignment.".into();
+            return;
+        };
+        if let Some(id) = self.event_linked_assignment(event_id) {
+            self.unlink_event(id);
+            return;
+        }
+        let (Some(course_id), Some(start_at)) = (item.course_id, item.start_at) else {
+            self.status_message = "Only dated course events can be linked to an assignment.".into();
+            return;
+        };
+        let candidates: Vec<(u64, String)> = self
+            .assignments
+            .iter()
+            .flat_map(|group| group.assignments.iter())
+            .filter(|a| a.course_id == Some(course_id) && a.due_at.is_none())
+            .map(|a| (a.id, a.name.clone().unwrap_or_else(|| "Unnamed".into())))
+            .collect();
+        if candidates.is_empty() {
+            let course = item.course_name.as_deref().unwrap_or("this course");
+            self.status_message = format!("{course} has no assignments without a due date.");
+            return;
+        }
+        self.event_link_picker_state.set_len(candidates.len());
+        self.event_link_picker_state.selected = 0;
+        self.event_link_picker = Some(EventLinkPicker {
+            event_id,
+            event_title: item.title.clone(),
+            start_at,
+            candidates,
+        });
+    }
+
+    /// Link the picked assignment to the picker's event.
+    pub fn link_picked_assignment(&mut self, idx: usize) {
+        let Some(picker) = self.event_link_picker.take() else {
+            return;
+        };
+        let Some((assignment_id, name)) = picker.candidates.get(idx).cloned() else {
+            return;
+        };
+        self.event_links.insert(
+            assignment_id,
+            event_links::EventLink {
+                event_id: picker.event_id,
+                title: picker.event_title.clone(),
+                start_at: picker.start_at,
+            },
+        );
+        self.save_event_links();
+        self.relink(picker.event_id);
+        self.status_message = format!("{name} is now due with {}.", picker.event_title);
+    }
+
+    fn unlink_event(&mut self, assignment_id: u64) {
+        let Some(link) = self.event_links.remove(&assignment_id) else {
+            return;
+        };
+        self.save_event_links();
+        self.relink(link.event_id);
+        let name = self
+            .get_assignment_by_id(assignment_id)
+            .and_then(|(_, a)| a.name.clone())
+            .unwrap_or_else(|| "The assignment".into());
+        self.status_message = format!("{name} is no longer linked to {}.", link.title);
+    }
+
+    /// Redo everything that depends on due dates after a link changed,
+    /// keeping event `event_id` selected in the calendar.
+    fn relink(&mut self, event_id: u64) {
+        self.apply_event_links();
+        self.rebuild_course_display_order();
+        self.recount_filtered_assignments();
+        self.rebuild_calendar_items();
+        self.focal_assignment_id = self.compute_focal_assignment_id();
+        if let Some(idx) = self.calendar_items.iter().position(|i| i.event_id == Some(event_id)) {
+            self.calendar_list_state.selected = idx;
+            self.calendar_list_state.needs_center = true;
+        }
+    }
+
+    /// Open the submission modal from the Dashboard upcoming-assignments list.
+    pub fn open_dashboard_submission_modal(&mut self) {
+        let resolved: Option<(u64, u64, Vec<String>)> =
+            self.get_selected_dashboard_assignment().map(|(_, a)| {
+                (
+                    a.course_id.unwrap_or(0),
+                    a.id,
+                    a.submission_types.clone().unwrap_or_default(),
+                )
+            });
+        self.finish_open_submission(resolved);
+    }
+
+    /// Open the submission modal for the currently selected assignment.
+    /// Works in both CalendarView and ListView modes.
+    /// Shows a status message and does nothing if the assignment doesn't
+    /// support any online submission type we handle.
+    pub fn open_submission_modal(&mut self) {
+        // Extract (course_id, assignment_id, submission_types) without holding borrows.
+        let resolved: Option<(u64, u64, Vec<String>)> =
+            if self.unified_view_mode == UnifiedViewMode::CalendarView {
+                // Calendar view: selected is an index into calendar_items.
+                let Some(item) = self.calendar_items.get(self.calendar_list_state.selected) else {
+                    self.status_message = "No assignment selected.".into();
+                    return;
+                };
+                let Some(aid) = item.assignment_id else {
+                    self.status_message = "Selected item is not an assignment.".into();
+                    return;
+                };
+                let course_id = item.course_id;
+                // Collect what we need into owned values so the borrow ends here.
+                let found = self.get_assignment_by_id(aid).map(|(_, a)| {
+                    (
+                        a.course_id.unwrap_or(0),
+                        a.id,
+                        a.submission_types.clone().unwrap_or_default(),
+                    )
+                });
+                if found.is_none() {
+                    // A deadline from the calendar whose assignment wasn't
+                    // loaded with the course's: fetch just that one.
+                    match course_id {
+                        Some(course_id) => self.fetch_calendar_assignment(course_id, aid),
+                        None => {
+                            self.status_message =
+                                "Canvas didn't say which course this assignment is in.".into();
+                        }
+                    }
+                    return;
+                }
+                found
+            } else {
+                // List view: use the existing helper.
+                self.get_selected_assignment().map(|(_, a)| {
+                    (
+                        a.course_id.unwrap_or(0),
+                        a.id,
+                        a.submission_types.clone().unwrap_or_default(),
+                    )
+                })
+            };
+
+        self.finish_open_submission(resolved);
+    }
+
+    /// Fetch assignment `assignment_id` of `course_id`, which the calendar
+    /// shows but the course's assignments don't include, and open the
+    /// submission modal for it once it arrives.
+    fn fetch_calendar_assignment(&mut self, course_id: u64, assignment_id: u64) {
+        if self.calendar_assignment_rx.is_some() {
+            return;
+        }
+        self.status_message = if self.stale_sections.courses.contains(&course_id) {
+            "Assignment data isn't loaded for this course — fetching this one…".into()
+        } else {
+            "This assignment isn't among those loaded — fetching it…".into()
+        };
+        let client = self.client.clone();
+        let (tx, rx) = oneshot::channel();
+        self.calendar_assignment_rx = Some(rx);
+        tokio::spawn(async move {
+            let result = client
+                .get_assignment(course_id, assignment_id)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send((course_id, result));
+        });
+    }
+
+    pub fn poll_calendar_assignment(&mut self) {
+        let Some(rx) = self.calendar_assignment_rx.as_mut() else {
+            return;
+        };
+        let (course_id, result) = match rx.try_recv() {
+            Ok(r) => r,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => {
+                self.calendar_assignment_rx = None;
+                return;
+            }
+        };
+        self.calendar_assignment_rx = None;
+        let mut assignment = match result {
+            Ok(a) => a,
+            Err(e) => {
+                self.status_message =
+                    format!("Couldn't load this assignment: {e} — press r to sync again.");
+                return;
+            }
+        };
+        let id = assignment.id;
+        let name = assignment.name.clone().unwrap_or_else(|| "the assignment".into());
+        assignment.course_id.get_or_insert(course_id);
+        if self.get_assignment_by_id(id).is_none() {
+            self.course_group_mut(course_id).assignments.push(assignment);
+            self.apply_event_links();
+            self.recount_filtered_assignments();
+            self.rebuild_calendar_items();
+            self.focal_assignment_id = self.compute_focal_assignment_id();
+        }
+        self.status_message = format!("Loaded {name}.");
+        // Carry on into the modal unless the user has moved on meanwhile.
+        let still_selected = self.unified_view_mode == UnifiedViewMode::CalendarView
+            && self
+                .calendar_items
+                .get(self.calendar_list_state.selected)
+                .is_some_and(|item| item.assignment_id == Some(id));
+        if still_selected && self.submission_state.is_hidden() {
+            self.open_submission_modal();
+        }
+    }
+
+    /// `course_id`'s group in `assignments`, added (empty) if it has none.
+    fn course_group_mut(&mut self, course_id: u64) -> &mut CourseAssignments {
+        let pos = match self.assignments.iter().position(|g| g.course_id == course_id) {
+            Some(pos) => pos,
+            None => {
+                let name = self
+                    .courses
+                    .iter()
+                    .find(|c| c.id == course_id)
+                    .and_then(|c| c.name.clone())
+                    .unwrap_or_else(|| "Unnamed".into());
+                let assignments = Vec::new();
+                self.assignments.push(CourseAssignments { course_id, name, assignments });
+                self.assignments.len() - 1
+            }
+        };
+        &mut self.assignments[pos]
+    }
+
+    /// Shared validation and state setup for opening the submission modal.
+    fn finish_open_submission(&mut self, resolved: Option<(u64, u64, Vec<String>)>) {
+        let Some((course_id, assignment_id, types)) = resolved else {
+            self.status_message = "No assignment selected.".into();
+            return;
+        };
+
+        if course_id == 0 {
+            self.status_message = "Cannot determine course for this assignment.".into();
+            return;
+        }
+        if self.courses.iter().any(|c| c.id == course_id && c.is_staff()) {
+            self.status_message =
+                "You're on the staff of this course, so submitting is turned off.".into();
+            return;
+        }
+
+        let assignment = self.get_assignment_by_id(assignment_id).map(|(_, a)| a);
+        if let Some(unlock) = assignment.and_then(|a| a.locked_until(self.now())) {
+            let opens = unlock.with_timezone(&Local).format("%a %b %d at %H:%M");
+            let left = ui::remaining_text(unlock - self.now());
+            self.status_message = format!(
+                "This assignment opens {opens} (in {left}) — it can't be submitted before then."
+            );
+            return;
+        }
+        let discussion_topic = assignment
+            .and_then(|a| a.discussion_topic.as_ref())
+            .map(|t| t.id);
+        let preflight =
+            preflight::SubmissionPreflight::new(&types, assignment, discussion_topic.is_some());
+        let mut supported = preflight.supported();
+        if preflight.needs_browser() {
+            supported.push(SubmissionKind::OpenInBrowser);
+        }
+
+        if supported.is_empty() {
+            self.status_message =
+                "This assignment does not support online submission.".into();
+            return;
+        }
+
+        self.submission_target = Some((course_id, assignment_id));
+        self.submission_discussion_topic = discussion_topic;
+        self.submission_type_state.set_len(supported.len());
+        self.submission_type_state.selected = 0;
+        self.submission_supported_kinds = supported;
+        self.submission_preflight = preflight;
+        self.submission_input.clear();
+        self.submission_file = None;
+        self.submission_kind = None;
+        self.submission_state = SubmissionState::TypePicker;
+
+        let group_category_id = self
+            .get_assignment_by_id(assignment_id)
+            .and_then(|(_, a)| a.group_category_id);
+        self.submission_group_rx = None;
+        self.submission_group = match group_category_id {
+            Some(category_id) => {
+                self.check_submission_group(category_id);
+                SubmissionGroup::Checking
+            }
+            None => SubmissionGroup::Individual,
+        };
+    }
+
+    /// "Open in browser" in the type picker: hand the modal's assignment
+    /// to the Canvas website and close the modal.
+    pub fn open_submission_in_browser(&mut self) {
+        let url = self
+            .submission_target
+            .and_then(|(_, id)| self.get_assignment_by_id(id))
+            .and_then(|(_, a)| a.html_url.clone());
+        self.submission_state = SubmissionState::Hidden;
+        let Some(url) = url else {
+            self.status_message = "Canvas gave no link for this assignment.".into();
+            return;
+        };
+        self.status_message = match open_in_browser(&url) {
+            Ok(()) => "Opened the assignment in browser.".into(),
+            Err(e) => format!("Could not open browser: {e}"),
+        };
+    }
+
+    /// Look up, in the background, which of the user's groups belongs to
+    /// `category_id`.
+    fn check_submission_group(&mut self, category_id: u64) {
+        let client = self.client.clone();
+        let (tx, rx) = oneshot::channel();
+        self.submission_group_rx = Some(rx);
+        tokio::spawn(async move {
+            let group = match client.list_own_groups().await {
+                Ok(groups) => groups
+                    .into_iter()
+                    .find(|g| g.group_category_id == Some(category_id))
+                    .map_or(SubmissionGroup::NotMember, |g| {
+                        SubmissionGroup::Member(g.name.unwrap_or_else(|| format!("Group {}", g.id)))
+                    }),
+                Err(e) => SubmissionGroup::Unknown(e.to_string()),
+            };
+            let _ = tx.send(group);
+        });
+    }
+
+    pub fn poll_submission_group(&mut self) {
+        let Some(rx) = self.submission_group_rx.as_mut() else {
+            return;
+        };
+        let group = match rx.try_recv() {
+            Ok(group) => group,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => {
+                SubmissionGroup::Unknown("lookup ended unexpectedly".into())
+            }
+        };
+        self.submission_group_rx = None;
+        self.submission_group = group;
+    }
+
+    /// Load the linked discussion's replies for the `DiscussionContext` pager.
+    pub fn fetch_discussion_entries(&mut self) {
+        let (Some((course_id, _)), Some(topic_id)) =
+            (self.submission_target, self.submission_discussion_topic)
+        else {
+            return;
+        };
+        let client = self.client.clone();
+        let (tx, rx) = oneshot::channel();
+        self.discussion_entries = None;
+        self.discussion_entries_rx = Some(rx);
+        self.discussion_scroll = 0;
+        tokio::spawn(async move {
+            let entries = client
+                .list_discussion_entries(course_id, topic_id)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(entries);
+        });
+    }
+
+    pub fn poll_discussion_entries(&mut self) {
+        let Some(rx) = self.discussion_entries_rx.as_mut() else {
+            return;
+        };
+        let entries = match rx.try_recv() {
+            Ok(entries) => entries,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => Err("request ended unexpectedly".into()),
+        };
+        self.discussion_entries_rx = None;
+        self.discussion_entries = Some(entries);
+    }
+
+    /// Record a successful submission on the local copy of the assignment
+    /// so lists show it as done before the next sync lands.
+    fn mark_submitted_locally(&mut self, assignment_id: u64) {
+        let assignment = self
+            .assignments
+            .iter_mut()
+            .flat_map(|group| group.assignments.iter_mut())
+            .find(|a| a.id == assignment_id);
+        let Some(assignment) = assignment else {
+            return;
+        };
+        let submission = assignment.submission.get_or_insert(Submission {
+            id: None,
+            assignment_id: Some(assignment_id),
+            user_id: None,
+            score: None,
+            grade: None,
+            submitted_at: None,
+            graded_at: None,
+            workflow_state: None,
+            late: None,
+            missing: None,
+            attempt: None,
+            url: None,
+            body: None,
+            attachments: Vec::new(),
+        });
+        submission.workflow_state = Some("submitted".into());
+        submission.submitted_at = Some(Utc::now());
+        submission.missing = Some(false);
+        self.rebuild_calendar_items();
+        self.focal_assignment_id = self.compute_focal_assignment_id();
+    }
+
+    /// Why the current submission can't be sent yet, if it can't.
+    pub fn submission_blocked_reason(&self) -> Option<&'static str> {
+        match self.submission_group {
+            SubmissionGroup::Checking => Some("Still checking your group membership — try again in a moment."),
+            SubmissionGroup::NotMember => Some(
+                "This is a group assignment and you're not in a group for it — join one in Canvas first.",
+            ),
+            _ => None,
+        }
+    }
+
+    /// Kick off a background submission task using the current
+    /// `submission_kind` and `submission_input`.  No-ops while a previous
+    /// submission is still in flight so a repeated `y`/Enter can't POST twice.
+    pub fn start_submission(&mut self) {
+        if self.submission_rx.is_some() {
+            tracing::debug!("start_submission ignored: a submission is already in flight");
+            return;
+        }
+        let Some((course_id, assignment_id)) = self.submission_target else {
+            self.submission_state = SubmissionState::Done {
+                success: false,
+                message: "No assignment selected.".into(),
+                receipt: None,
+            };
+            return;
+        };
+        let Some(ref kind) = self.submission_kind.clone() else {
+            self.submission_state = SubmissionState::Done {
+                success: false,
+                message: "No submission type selected.".into(),
+                receipt: None,
+            };
+            return;
+        };
+
+        let content = self.submission_input.clone();
+        let file = self.submission_file.clone();
+        let client = self.client.clone();
+        let kind = kind.clone();
+        let topic_id = self.submission_discussion_topic;
+        let previous_attempt = self
+            .assignments
+            .iter()
+            .flat_map(|group| group.assignments.iter())
+            .find(|a| a.id == assignment_id)
+            .and_then(|a| a.submission.as_ref())
+            .and_then(|s| s.attempt);
+        let gate = SubmissionGate::default();
+
+        let (tx, rx) = oneshot::channel();
+        self.submission_rx = Some(rx);
+        self.submission_gate = gate.clone();
+        self.submission_cancelling = false;
+        self.submission_state = SubmissionState::Submitting;
+
+        let handle = tokio::spawn(async move {
+            let target = SubmissionTarget { course_id, assignment_id, discussion_topic: topic_id };
+            let result = run_submission(&client, &gate, target, kind, &content, file).await;
+            // A cancelled task drops `tx` without sending so the UI sees the
+            // channel close and knows the abort took effect.
+            let Some(result) = result else {
+                return;
+            };
+
+            let submit_result = match result {
+                Ok(submission) => {
+                    let receipt =
+                        submission.map(|s| SubmitReceipt::new(s, previous_attempt));
+                    let message = if receipt.as_ref().is_some_and(|r| r.attempt_unchanged) {
+                        "Canvas answered, but the attempt number didn't go up — it may \
+                         have kept your previous submission. Check it in the browser."
+                    } else {
+                        "Submission successful!"
+                    };
+                    SubmitResult {
+                        success: true,
+                        message: message.into(),
+                        receipt,
+                    }
+                }
+                Err(e) => SubmitResult {
+                    success: false,
+                    message: format!("Submission failed: {e}"),
+                    receipt: None,
+                },
+            };
+
+            let _ = tx.send(submit_result);
+        });
+        self.submission_task = Some(handle.abort_handle());
+    }
+
+    /// Handle Esc while a submission is running.  Aborts the task if the
+    /// final request hasn't been sent yet; otherwise keeps waiting.
+    pub fn cancel_submission(&mut self) {
+        if self.submission_rx.is_none() || self.submission_cancelling {
+            return;
+        }
+        if self.submission_gate.cancel() {
+            if let Some(task) = self.submission_task.as_ref() {
+                task.abort();
+            }
+            self.submission_cancelling = true;
+            self.status_message = "Cancelling submission…".into();
+        } else {
+            self.status_message =
+                "Too late to cancel — request already sent, waiting for result".into();
+        }
+    }
+
+    /// Whether quitting now would lose a submission: one being sent, or
+    /// text from the editor that hasn't been confirmed yet.
+    pub fn submission_unfinished(&self) -> bool {
+        self.submission_rx.is_some()
+            || matches!(self.submission_state, SubmissionState::TextPreview)
+    }
+
+    /// Quit, or ask first when a submission is unfinished.
+    pub fn request_quit(&mut self) {
+        if self.submission_unfinished() {
+            self.confirm_quit = true;
+        } else {
+            self.running = false;
+        }
+    }
+
+    /// Run what was typed after `:` on Enter: `q` quits, `q!` quits at
+    /// once, without the exit summary or asking about an unfinished
+    /// submission, `export md [path]` saves the Markdown report and
+    /// `sync-history` shows the last syncs.
+    pub fn run_command_line(&mut self) {
+        let Some(command) = self.command_line.take() else {
+            return;
+        };
+        let (name, args) = split_command(command.trim());
+        match (name, args) {
+            ("q", "") => {
+                self.skip_exit_summary = false;
+                self.request_quit();
+            }
+            ("q!", "") => {
+                self.skip_exit_summary = true;
+                self.running = false;
+            }
+            ("export", args) => match split_command(args) {
+                ("md", path) => self.export_report_to(path),
+                _ => self.status_message = "Usage: :export md <path>".into(),
+            },
+            ("sync-history", "") => {
+                self.show_sync_stats = true;
+                self.sync_stats_history = true;
+            }
+            ("", _) => {}
+            _ => self.status_message = format!("Not a command: :{}", command.trim()),
+        }
+    }
+
+    /// The text to print after the terminal is restored, when
+    /// `exit_summary` is on.
+    pub fn exit_summary(&self) -> Option<String> {
+        (self.exit_summary && !self.skip_exit_summary).then(|| exit_summary::summary(self))
+    }
+
+    /// Poll for a completed background submission. Returns true when done.
+    pub fn poll_submission_result(&mut self) -> bool {
+        let result = match self.submission_rx.as_mut() {
+            None => return false,
+            Some(rx) => match rx.try_recv() {
+                Ok(r) => r,
+                Err(oneshot::error::TryRecvError::Empty) => return false,
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    // The task ended without reporting: either our abort
+                    // landed, or it panicked.
+                    self.submission_rx = None;
+                    self.submission_task = None;
+                    if self.submission_cancelling {
+                        self.submission_cancelling = false;
+                        self.submission_state = SubmissionState::TypePicker;
+                        self.status_message = "Submission cancelled.".into();
+                    } else {
+                        self.submission_state = SubmissionState::Done {
+                            success: false,
+                            message: "Submission task ended unexpectedly.".into(),
+                            receipt: None,
+                        };
+                    }
+                    return true;
+                }
+            },
+        };
+        self.submission_rx = None;
+        self.submission_task = None;
+        self.submission_cancelling = false;
+        let success = result.success;
+        let unchanged = result.receipt.as_ref().is_some_and(|r| r.attempt_unchanged);
+        self.submission_state = SubmissionState::Done {
+            success,
+            message: result.message,
+            receipt: result.receipt.map(Box::new),
+        };
+        if success {
+            if let (Some((_, assignment_id)), false) = (self.submission_target, unchanged) {
+                self.mark_submitted_locally(assignment_id);
+            }
+            self.needs_refresh = true;
+        }
+        true
+    }
+
+    /// Fetch the list of available pages for the currently selected course.
+    pub fn fetch_course_pages(&mut self) {
+        let Some(course) = self.selected_course() else {
+            return;
+        };
+        let course_id = course.id;
+        let client = self.client.clone();
+        let (tx, rx) = oneshot::channel();
+        self.course_pages_rx = Some(rx);
+        self.course_pages_loading = true;
+        self.course_pages.clear();
+        self.course_detail_content = None;
+        self.show_course_pages_picker = false;
+
+        tokio::spawn(async move {
+            let pages = client.list_pages(course_id).await.unwrap_or_default();
+            let _ = tx.send(pages);
+        });
+    }
+
+    /// Poll for the completed page-list fetch. Opens the picker when ready.
+    pub fn poll_course_pages(&mut self) -> bool {
+        let result = match self.course_pages_rx.as_mut() {
+            None => return false,
+            Some(rx) => match rx.try_recv() {
+                Ok(r) => r,
+                Err(oneshot::error::TryRecvError::Empty) => return false,
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    self.course_pages_rx = None;
+                    self.course_pages_loading = false;
+                    return false;
+                }
+            },
+        };
+        self.course_pages_rx = None;
+        self.course_pages_loading = false;
+
+        if result.is_empty() {
+            self.status_message = "No pages found for this course.".into();
+        } else {
+            self.course_pages_list_state.set_len(result.len());
+            self.course_pages_list_state.selected = 0;
+            self.course_pages = result;
+            self.show_course_pages_picker = true;
+        }
+        true
+    }
+
+    /// Fetch the body of a specific page selected from the picker.
+    pub fn fetch_selected_page(&mut self) {
+        let Some(page) = self.course_pages.get(self.course_pages_list_state.selected) else {
+            return;
+        };
+        let Some(page_url) = page.url.clone() else {
+            self.status_message = "Page has no URL.".into();
+            return;
+        };
+        let Some(course) = self.selected_course() else {
+            return;
+        };
+        let course_id = course.id;
+        let client = self.client.clone();
+        let (tx, rx) = oneshot::channel();
+        self.course_detail_rx = Some(rx);
         self.course_detail_loading = true;
         self.course_detail_content = None;
         self.show_course_pages_picker = false;
 
-        tokio::spawn(async move {
-            let result = client
-                .get_page(course_id, &page_url)
-                .await
-                .ok()
-                .and_then(|p| p.body);
-            let _ = tx.send(result);
-        });
+        tokio::spawn(async move {
+            let result = client
+                .get_page(course_id, &page_url)
+                .await
+                .ok()
+                .and_then(|p| p.body);
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Poll for a completed course page body fetch.
+    /// Validate the new-event form and create the event in the background.
+    /// Validation errors stay in the form.
+    pub fn submit_event_form(&mut self) {
+        let Some(form) = self.event_form.as_mut() else {
+            return;
+        };
+        if form.saving {
+            return;
+        }
+        let event = match form.validate() {
+            Ok(v) => v,
+            Err(e) => {
+                form.error = Some(e);
+                return;
+            }
+        };
+        let Some(user_id) = self.user.as_ref().map(|u| u.id) else {
+            form.error = Some("Not signed in yet — wait for the sync to finish".into());
+            return;
+        };
+        form.error = None;
+        form.saving = true;
+
+        let client = self.client.clone();
+        let (tx, rx) = oneshot::channel();
+        self.event_form_rx = Some(rx);
+        tokio::spawn(async move {
+            let result = client
+                .create_calendar_event(
+                    user_id,
+                    &event.title,
+                    event.start_at,
+                    event.end_at,
+                    event.description.as_deref(),
+                )
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Apply a finished event creation: add it to the calendar and close the
+    /// form, or show the error in the form so the input isn't lost.
+    pub fn poll_event_form(&mut self) {
+        let result = match self.event_form_rx.as_mut() {
+            None => return,
+            Some(rx) => match rx.try_recv() {
+                Ok(r) => r,
+                Err(oneshot::error::TryRecvError::Empty) => return,
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    Err("Event request ended unexpectedly".into())
+                }
+            },
+        };
+        self.event_form_rx = None;
+        match result {
+            Ok(event) => {
+                let title = event.title.clone().unwrap_or_default();
+                self.calendar_events.push(event);
+                self.rebuild_calendar_items();
+                self.event_form = None;
+                self.status_message = format!("Added \"{title}\" to your calendar.");
+            }
+            Err(e) => {
+                if let Some(form) = self.event_form.as_mut() {
+                    form.saving = false;
+                    form.error = Some(e);
+                }
+            }
+        }
+    }
+
+    /// Due date of the soonest assignment in course `course_id` that is
+    /// still open: due in the future and not yet submitted or graded.
+    pub fn next_due_for_course(&self, course_id: u64) -> Option<DateTime<Utc>> {
+        let now = Utc::now();
+        self.assignments
+            .iter()
+            .filter(|group| group.course_id == course_id)
+            .flat_map(|group| group.assignments.iter())
+            .filter(|a| assignment_status_priority(a) <= 2)
+            .filter_map(|a| a.due_at)
+            .filter(|d| *d > now)
+            .min()
+    }
+
+    /// Attachments of the selected announcement (empty if none).
+    /// The page where `review` of `assignment` is done.
+    fn peer_review_url(&self, assignment: &Assignment, review: &PeerReview) -> Option<String> {
+        let course_id = assignment.course_id?;
+        let user_id = review.user_id?;
+        let path = format!(
+            "/courses/{course_id}/assignments/{}/submissions/{user_id}",
+            assignment.id
+        );
+        self.client.base_url().join(&path).ok().map(String::from)
+    }
+
+    /// My peer reviews of the assignment in the detail pane:
+    /// (completed, total).
+    pub fn peer_review_progress(&self, assignment_id: u64) -> Option<(usize, usize)> {
+        let reviews = self.peer_reviews.get(&assignment_id)?;
+        let done = reviews.iter().filter(|r| r.is_completed()).count();
+        Some((done, reviews.len()))
+    }
+
+    /// Whether `o` means "open peer review" here rather than its calendar
+    /// meaning: a peer review row is selected, or (outside the calendar)
+    /// the assignment in the detail pane has reviews.
+    pub fn has_peer_review_to_open(&self) -> bool {
+        match (self.active_tab, self.unified_view_mode) {
+            (Tab::Assignments, UnifiedViewMode::CalendarView) => self
+                .calendar_items
+                .get(self.calendar_list_state.selected)
+                .is_some_and(|item| item.review_url.is_some()),
+            _ => self
+                .detail_assignment()
+                .is_some_and(|(_, a)| self.peer_reviews.contains_key(&a.id)),
+        }
+    }
+
+    /// Open the selected peer review in the browser; outside the calendar,
+    /// the first one still to do for the detail assignment (or the first).
+    pub fn open_peer_review(&mut self) {
+        let url = match (self.active_tab, self.unified_view_mode) {
+            (Tab::Assignments, UnifiedViewMode::CalendarView) => self
+                .calendar_items
+                .get(self.calendar_list_state.selected)
+                .and_then(|item| item.review_url.clone()),
+            _ => self.detail_assignment().and_then(|(_, a)| {
+                let reviews = self.peer_reviews.get(&a.id)?;
+                let review = reviews
+                    .iter()
+                    .find(|r| !r.is_completed())
+                    .or_else(|| reviews.first())?;
+                self.peer_review_url(a, review)
+            }),
+        };
+        let Some(url) = url else {
+            self.status_message = "No peer review to open here.".into();
+            return;
+        };
+        self.status_message = match open_in_browser(&url) {
+            Ok(()) => "Opened peer review in browser.".into(),
+            Err(e) => format!("Could not open browser: {e}"),
+        };
+    }
+
+    /// Where an announcement comes from: its course's code (or name), its
+    /// group's name, or "Institution" for account notifications.
+    pub fn announcement_source(&self, ann: &DiscussionTopic) -> Option<String> {
+        let code = ann.context_code.as_deref()?;
+        if code == INSTITUTION_CONTEXT {
+            return Some("Institution".into());
+        }
+        if let Some(id) = code.strip_prefix("group_").and_then(|id| id.parse().ok()) {
+            return self.group_names.get(&id).cloned();
+        }
+        let id: u64 = code.strip_prefix("course_")?.parse().ok()?;
+        let course = self.courses.iter().find(|c| c.id == id)?;
+        course.course_code.clone().or_else(|| course.name.clone())
+    }
+
+    /// The selected course's newest announcements, for its summary on the
+    /// Courses tab.
+    pub fn course_announcements(&self) -> Vec<&DiscussionTopic> {
+        let Some(course) = self.selected_course() else {
+            return Vec::new();
+        };
+        let context = format!("course_{}", course.id);
+        self.announcements
+            .iter()
+            .filter(|a| a.context_code.as_deref() == Some(context.as_str()))
+            .take(COURSE_ANNOUNCEMENT_COUNT)
+            .collect()
+    }
+
+    /// Whether the Courses tab's detail pane shows the course summary
+    /// rather than a page or the page list.
+    pub fn course_summary_shown(&self) -> bool {
+        self.course_detail_content.is_none()
+            && !self.course_detail_loading
+            && !self.course_pages_loading
+    }
+
+    /// Show announcement `id` on the Announcements tab.  Returns false,
+    /// leaving the view alone, when it isn't among the loaded ones.
+    pub fn select_announcement(&mut self, id: u64) -> bool {
+        if !self.visible_announcements().iter().any(|a| a.id == id) {
+            return false;
+        }
+        if self.archived_announcements.contains(&id) && !self.show_archived {
+            self.toggle_show_archived();
+        }
+        let list = self.announcement_list();
+        let Some(pos) = (0..list.row_count()).position(|row| list.get(row).is_some_and(|a| a.id == id))
+        else {
+            return false;
+        };
+        self.active_tab = Tab::Announcements;
+        self.announcement_list_state.selected = pos;
+        self.announcement_list_state.needs_center = true;
+        self.announcement_attachment = 0;
+        true
+    }
+
+    /// Open the announcement selected in the course summary.
+    pub fn open_course_announcement(&mut self) {
+        let id = self
+            .course_announcements()
+            .get(self.course_announcement_state.selected)
+            .map(|a| a.id);
+        if let Some(id) = id {
+            self.select_announcement(id);
+        }
+    }
+
+    pub fn selected_announcement_attachments(&self) -> &[FileAttachment] {
+        self.selected_announcement()
+            .and_then(|a| a.attachments.as_deref())
+            .unwrap_or(&[])
+    }
+
+    /// Move the attachment selection by `delta`, wrapping around.
+    pub fn cycle_announcement_attachment(&mut self, delta: isize) {
+        let count = self.selected_announcement_attachments().len();
+        if count == 0 {
+            return;
+        }
+        let current = self.announcement_attachment.min(count - 1) as isize;
+        self.announcement_attachment = (current + delta).rem_euclid(count as isize) as usize;
+    }
+
+    /// Download the selected announcement attachment into the download
+    /// directory in the background.
+    pub fn download_announcement_attachment(&mut self) {
+        if self.download_rx.is_some() {
+            self.status_message = "A download is already running.".into();
+            return;
+        }
+        let attachments = self.selected_announcement_attachments();
+        let Some(attachment) = attachments.get(self.announcement_attachment.min(attachments.len().saturating_sub(1)))
+        else {
+            self.status_message = "This announcement has no attachments.".into();
+            return;
+        };
+        let Some(url) = attachment.url.clone() else {
+            self.status_message = format!("Canvas gave no download link for {}.", attachment.name());
+            return;
+        };
+        let name = attachment.name().to_string();
+        let dest = unique_download_path(&self.paths.download_dir, &name);
+        let client = self.client.clone();
+        let (tx, rx) = oneshot::channel();
+        self.download_rx = Some(rx);
+        self.status_message = format!("Downloading {name}…");
+        tokio::spawn(async move {
+            let result = client
+                .download_file(&url, &dest)
+                .await
+                .map(|_| dest)
+                .map_err(|e| format!("Download of {name} failed: {e}"));
+            let _ = tx.send(result);
+        });
+    }
+
+    /// `B` on the Courses tab: download all of the selected course's files
+    /// into its own folder in the download directory, in the background.
+    pub fn start_bulk_download(&mut self) {
+        if self.bulk_download.is_some() {
+            self.status_message = "A download of course files is already running.".into();
+            return;
+        }
+        let Some(course) = self.selected_course() else {
+            return;
+        };
+        let name = course
+            .course_code
+            .clone()
+            .or_else(|| course.name.clone())
+            .unwrap_or_else(|| format!("course {}", course.id));
+        let dir = self.paths.download_dir.join(safe_file_name(&name, "course"));
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let task = tokio::spawn(bulk_download::run(self.client.clone(), course.id, dir.clone(), tx));
+        self.bulk_download = Some(BulkDownload {
+            progress: BulkProgress { course: name, dir, ..BulkProgress::default() },
+            rx,
+            task,
+        });
+        self.bulk_summary = None;
+    }
+
+    /// Esc while a bulk download runs: start no more files and drop those
+    /// in flight.  Files already saved stay.
+    pub fn cancel_bulk_download(&mut self) {
+        let Some(download) = self.bulk_download.take() else {
+            return;
+        };
+        download.task.abort();
+        let p = download.progress;
+        self.status_message = match p.planned {
+            Some((total, _)) => format!(
+                "Cancelled the download of {} files after {} of {total}.",
+                p.course, p.saved
+            ),
+            None => format!("Cancelled the download of {} files.", p.course),
+        };
+    }
+
+    /// Apply what the bulk download reported since the last call.  When it
+    /// is done, sum it up in the status bar, or in a popup if files failed.
+    pub fn poll_bulk_download(&mut self) {
+        use bulk_download::BulkEvent;
+        let Some(download) = self.bulk_download.as_mut() else {
+            return;
+        };
+        let progress = &mut download.progress;
+        let mut outcome = None;
+        loop {
+            match download.rx.try_recv() {
+                Ok(BulkEvent::Planned { total, bytes, skipped }) => {
+                    progress.planned = Some((total, bytes));
+                    progress.skipped = skipped;
+                }
+                Ok(BulkEvent::Saved { bytes }) => {
+                    progress.saved += 1;
+                    progress.bytes += bytes;
+                }
+                Ok(BulkEvent::Failed { name, error }) => {
+                    tracing::warn!("downloading {name} of {}: {error}", progress.course);
+                    progress.failures.push((name, error));
+                }
+                Ok(BulkEvent::ListFailed(e)) => {
+                    outcome = Some(Err(e));
+                    break;
+                }
+                Ok(BulkEvent::Finished) => {
+                    outcome = Some(Ok(()));
+                    break;
+                }
+                Err(tokio::sync::mpsc::error::TryRecvError::Empty) => break,
+                Err(tokio::sync::mpsc::error::TryRecvError::Disconnected) => {
+                    outcome = Some(Err("the download ended unexpectedly".into()));
+                    break;
+                }
+            }
+        }
+        let Some(outcome) = outcome else {
+            return;
+        };
+        let Some(download) = self.bulk_download.take() else {
+            return;
+        };
+        let p = download.progress;
+        self.status_message = match outcome {
+            Err(e) => format!("Couldn't download the files of {}: {e}", p.course),
+            Ok(()) if p.failures.is_empty() => format!(
+                "Saved {} files of {} to {} ({} already up to date).",
+                p.saved,
+                p.course,
+                p.dir.display(),
+                p.skipped
+            ),
+            Ok(()) => {
+                let failed = p.failures.len();
+                self.bulk_summary = Some(p);
+                format!("{failed} files couldn't be downloaded.")
+            }
+        };
+    }
+
+    /// Write the Markdown report of what is loaded to a dated file in the
+    /// download directory.
+    pub fn export_report(&mut self) {
+        let name = format!("canvas-report-{}.md", chrono::Local::now().format("%Y-%m-%d"));
+        let dest = unique_download_path(&self.paths.download_dir, &name);
+        self.write_report(&dest);
+    }
+
+    /// `:export md <path>`: the report to a chosen file, with `~` and
+    /// environment variables expanded as in the config.  Without a path it
+    /// goes to the download directory, as with `X`.
+    pub fn export_report_to(&mut self, raw: &str) {
+        if raw.is_empty() {
+            return self.export_report();
+        }
+        match crate::config::expand_path(raw) {
+            Ok(dest) => self.write_report(&dest),
+            Err(e) => self.status_message = format!("Couldn't save the report: {e}"),
+        }
+    }
+
+    fn write_report(&mut self, dest: &Path) {
+        let report = report::markdown_report(
+            &self.courses,
+            &self.assignments,
+            self.cached_at,
+            Utc::now(),
+        );
+        let written = match dest.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            Some(dir) => std::fs::create_dir_all(dir),
+            None => Ok(()),
+        }
+        .and_then(|_| std::fs::write(dest, report));
+        self.status_message = match written {
+            Ok(()) => format!("Report saved to {}", dest.display()),
+            Err(e) => format!("Couldn't save the report: {e}"),
+        };
+    }
+
+    pub fn poll_download(&mut self) {
+        let Some(rx) = self.download_rx.as_mut() else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => Err("Download ended unexpectedly.".into()),
+        };
+        self.download_rx = None;
+        self.status_message = match result {
+            Ok(path) => format!("Saved {}", path.display()),
+            Err(e) => e,
+        };
+    }
+
+    pub fn poll_course_detail(&mut self) -> bool {
+        let result = match self.course_detail_rx.as_mut() {
+            None => return false,
+            Some(rx) => match rx.try_recv() {
+                Ok(r) => r,
+                Err(oneshot::error::TryRecvError::Empty) => return false,
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    self.course_detail_rx = None;
+                    self.course_detail_loading = false;
+                    return false;
+                }
+            },
+        };
+        self.course_detail_rx = None;
+        self.course_detail_loading = false;
+        self.course_detail_content = Some(
+            result.unwrap_or_else(|| "No Details Found".into()),
+        );
+        true
+    }
+
+    /// Rebuild the display order for the courses list.
+    /// Categorized courses (valid course code before first '-') come first,
+    /// uncategorized at the bottom.
+    pub fn rebuild_course_display_order(&mut self) {
+        let mut categorized = Vec::new();
+        let mut uncategorized = Vec::new();
+        for (i, course) in self.courses.iter().enumerate() {
+            if !self.course_in_term(course.id) {
+                continue;
+            }
+            let name = course.name.as_deref().unwrap_or("");
+            let has_valid_code = name.find('-').is_some_and(|dash| {
+                is_valid_course_code(name[..dash].trim())
+            });
+            if has_valid_code {
+                categorized.push(i);
+            } else {
+                uncategorized.push(i);
+            }
+        }
+        // Sort within each group so the Uncategorized header stays last.
+        self.sort_course_indices(&mut categorized);
+        self.sort_course_indices(&mut uncategorized);
+        categorized.extend(uncategorized);
+        self.course_display_order = categorized;
+        self.course_list_state.set_len(self.course_display_order.len());
+    }
+
+    fn sort_course_indices(&self, indices: &mut [usize]) {
+        let name = |i: &usize| self.courses[*i].name.as_deref().unwrap_or("").to_lowercase();
+        match self.course_sort {
+            CourseSort::Api => {}
+            CourseSort::Name => indices.sort_by_key(name),
+            CourseSort::Code => indices.sort_by_key(|i| {
+                let c = &self.courses[*i];
+                c.course_code.as_deref().or(c.name.as_deref()).unwrap_or("").to_lowercase()
+            }),
+            CourseSort::Grade => indices.sort_by(|a, b| {
+                let score = |i: &usize| self.courses[*i].current_score();
+                match (score(a), score(b)) {
+                    (Some(x), Some(y)) => x.total_cmp(&y),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => name(a).cmp(&name(b)),
+                }
+            }),
+            CourseSort::NextDue => indices.sort_by_key(|i| {
+                let due = self.next_due_for_course(self.courses[*i].id);
+                // `None` sorts first for Option, so put it last explicitly.
+                (due.is_none(), due)
+            }),
+        }
+    }
+
+    /// Switch to the next course sort, keeping the same course selected.
+    pub fn cycle_course_sort(&mut self) {
+        let selected = self.selected_course_idx();
+        self.course_sort = self.course_sort.next();
+        self.rebuild_course_display_order();
+        if let Some(pos) = selected.and_then(|i| self.course_display_order.iter().position(|&c| c == i)) {
+            self.course_list_state.selected = pos;
+        }
+    }
+
+    /// Type-ahead: select the next course (after the current one, wrapping)
+    /// whose name starts with `c`, case-insensitively.
+    pub fn jump_to_course_starting_with(&mut self, c: char) {
+        let len = self.course_display_order.len();
+        let start = self.course_list_state.selected;
+        let found = (1..=len).map(|off| (start + off) % len).find(|&pos| {
+            let course = &self.courses[self.course_display_order[pos]];
+            course
+                .name
+                .as_deref()
+                .and_then(|n| n.trim_start().chars().next())
+                .is_some_and(|first| first.to_lowercase().eq(c.to_lowercase()))
+        });
+        if let Some(pos) = found {
+            self.course_list_state.selected = pos;
+        }
+    }
+
+    /// Open the Courses tab on the course `pending_course` names, or say why
+    /// it couldn't be picked.  Waits while no courses are loaded yet.
+    pub fn select_pending_course(&mut self) {
+        if self.courses.is_empty() {
+            return;
+        }
+        let Some(query) = self.pending_course.take() else {
+            return;
+        };
+        match course_match::match_course(&self.courses, &query) {
+            Ok(course) => {
+                let id = course.id;
+                if let Some(pos) = self
+                    .course_display_order
+                    .iter()
+                    .position(|&i| self.courses[i].id == id)
+                {
+                    self.active_tab = Tab::Courses;
+                    self.course_list_state.selected = pos;
+                }
+            }
+            Err(e) => self.status_message = format!("--course: {e}"),
+        }
+    }
+
+    /// Open the Schedule tab on the assignment `pending_assignment` names.
+    /// When the cached data doesn't have it, it is looked for again after
+    /// the sync, and then the status bar says it wasn't found.
+    pub fn select_pending_assignment(&mut self) {
+        let Some(id) = self.pending_assignment else {
+            return;
+        };
+        if self.assignments.is_empty() && self.fetch_rx.is_some() {
+            return;
+        }
+        if self.select_in_schedule(id) {
+            self.pending_assignment = None;
+            return;
+        }
+        let known = self.get_assignment_by_id(id).is_some();
+        self.status_message = if known {
+            format!("--assignment: {id} is hidden by the course filter.")
+        } else if self.fetch_rx.is_some() {
+            format!("--assignment: {id} isn't in the cached data; looking again after the sync.")
+        } else {
+            format!("--assignment: no assignment {id} in your courses.")
+        };
+        if known || self.fetch_rx.is_none() {
+            self.pending_assignment = None;
+        }
+    }
+
+    /// Select assignment `id` on the Schedule tab: in the calendar when
+    /// that is the view and it is there, else in the list.  False when the
+    /// schedule doesn't show it.
+    fn select_in_schedule(&mut self, id: u64) -> bool {
+        let in_calendar = self
+            .calendar_items
+            .iter()
+            .position(|i| i.item_type == "assignment" && i.assignment_id == Some(id));
+        match (self.unified_view_mode, in_calendar) {
+            (UnifiedViewMode::CalendarView, Some(pos)) => {
+                self.calendar_list_state.selected = pos;
+                self.calendar_list_state.needs_center = true;
+            }
+            _ => {
+                let Some(pos) = self.schedule_rows().position(id) else {
+                    return false;
+                };
+                self.unified_view_mode = UnifiedViewMode::ListView;
+                self.assignment_list_state.selected = pos;
+                self.assignment_list_state.needs_center = true;
+            }
+        }
+        self.active_tab = Tab::Assignments;
+        true
+    }
+
+    /// Get the actual course index for the currently selected display position.
+    pub fn selected_course_idx(&self) -> Option<usize> {
+        self.course_display_order.get(self.course_list_state.selected).copied()
+    }
+
+    /// Get a reference to the currently selected course.
+    pub fn selected_course(&self) -> Option<&Course> {
+        self.selected_course_idx().and_then(|i| self.courses.get(i))
+    }
+
+    /// Where keyboard navigation goes: the open popup, else the pane the
+    /// user focused.
+    pub fn focus(&self) -> Focus {
+        let popup_open = self.show_course_filter
+            || self.show_term_picker
+            || self.bulk_summary.is_some()
+            || self.show_course_pages_picker
+            || self.show_sync_stats
+            || self.show_profile
+            || self.link_picker.is_some()
+            || self.event_link_picker.is_some();
+        if popup_open {
+            Focus::Popup
+        } else {
+            self.pane_focus
+        }
+    }
+
+    /// Move keyboard focus between the list and the detail pane.
+    pub fn toggle_pane_focus(&mut self) {
+        self.pane_focus = match self.pane_focus {
+            Focus::List => Focus::Detail,
+            _ => Focus::List,
+        };
+    }
+
+    /// What the navigation keys move right now, if anything.
+    pub fn nav_target(&mut self) -> Option<NavTarget<'_>> {
+        match self.focus() {
+            Focus::Popup if self.show_course_filter => {
+                Some(NavTarget::List(&mut self.filter_list_state))
+            }
+            Focus::Popup if self.show_term_picker => {
+                Some(NavTarget::List(&mut self.term_list_state))
+            }
+            Focus::Popup if self.show_course_pages_picker => {
+                Some(NavTarget::List(&mut self.course_pages_list_state))
+            }
+            Focus::Popup if self.link_picker.is_some() => {
+                Some(NavTarget::List(&mut self.link_picker_state))
+            }
+            Focus::Popup if self.event_link_picker.is_some() => {
+                Some(NavTarget::List(&mut self.event_link_picker_state))
+            }
+            Focus::Popup => None,
+            Focus::Detail if self.active_tab == Tab::Courses && self.course_summary_shown() => {
+                let count = self.course_announcements().len();
+                self.course_announcement_state.set_len(count);
+                Some(NavTarget::List(&mut self.course_announcement_state))
+            }
+            Focus::Detail => {
+                let max = self.detail_scroll_max.get();
+                Some(NavTarget::Scroll(&mut self.detail_scroll, max))
+            }
+            Focus::List => Some(NavTarget::List(self.active_list_state_mut())),
+        }
+    }
+
+    /// `v` in a focused detail pane: start selecting lines, from the top
+    /// one in view.
+    pub fn start_copy_mode(&mut self) {
+        let text = self.detail_text.borrow();
+        let scroll = usize::from(self.detail_scroll);
+        let Some(top) = text.lines.iter().rposition(|(_, row)| *row <= scroll) else {
+            return;
+        };
+        drop(text);
+        self.copy_selection = Some(CopySelection { anchor: top, cursor: top });
+    }
+
+    /// Move copy mode's cursor by `delta` lines, scrolling to keep it in
+    /// view.
+    pub fn move_copy_cursor(&mut self, delta: isize) {
+        let Some(selection) = self.copy_selection.as_mut() else {
+            return;
+        };
+        let text = self.detail_text.borrow();
+        let last = text.lines.len().saturating_sub(1);
+        selection.cursor = selection.cursor.saturating_add_signed(delta).min(last);
+        let Some((_, row)) = text.lines.get(selection.cursor) else {
+            return;
+        };
+        let rows_to_next = text
+            .lines
+            .get(selection.cursor + 1)
+            .map_or(1, |(_, next)| next - row);
+        let scroll = usize::from(self.detail_scroll);
+        let scroll = if *row < scroll {
+            *row
+        } else if row + rows_to_next > scroll + text.height {
+            (row + rows_to_next).saturating_sub(text.height)
+        } else {
+            scroll
+        };
+        self.detail_scroll = scroll.min(usize::from(u16::MAX)) as u16;
+    }
+
+    /// `y` in copy mode: hand the selected lines to the main loop for the
+    /// clipboard and leave copy mode.
+    pub fn copy_selected_lines(&mut self) {
+        let Some(selection) = self.copy_selection.take() else {
+            return;
+        };
+        let text = self.detail_text.borrow();
+        let lines: Vec<&str> = text
+            .lines
+            .get(selection.range())
+            .unwrap_or_default()
+            .iter()
+            .map(|(line, _)| line.as_str())
+            .collect();
+        let count = lines.len();
+        let copied = lines.join("\n");
+        drop(text);
+        self.clipboard = Some(copied);
+        self.status_message = match count {
+            1 => "Copied 1 line.".into(),
+            n => format!("Copied {n} lines."),
+        };
+    }
+
+    /// What the detail pane is showing, so its scroll can start over when
+    /// that changes.
+    pub fn detail_subject(&mut self) -> (Tab, UnifiedViewMode, DashboardPanel, usize) {
+        let selected = self.active_list_state_mut().selected;
+        (self.active_tab, self.unified_view_mode, self.dashboard_panel, selected)
+    }
+
+    pub fn active_list_state_mut(&mut self) -> &mut ListState {
+        match self.active_tab {
+            Tab::Dashboard => match self.dashboard_panel {
+                DashboardPanel::Upcoming => &mut self.dashboard_list_state,
+                DashboardPanel::RecentlyGraded => &mut self.graded_list_state,
+            },
+            Tab::Courses => &mut self.course_list_state,
+            Tab::Assignments => match self.unified_view_mode {
+                UnifiedViewMode::CalendarView => &mut self.calendar_list_state,
+                UnifiedViewMode::ListView => &mut self.assignment_list_state,
+            },
+            Tab::Announcements => &mut self.announcement_list_state,
+        }
+    }
+}
+
+// ─── Background submission (runs in a spawned task) ──────────────────────────
+
+/// The assignment a submission goes to.
+struct SubmissionTarget {
+    course_id: u64,
+    assignment_id: u64,
+    /// The linked discussion, for discussion replies.
+    discussion_topic: Option<u64>,
+}
+
+/// Perform a submission, checking `gate` between steps.  Returns `None` if
+/// the user cancelled before the final request was sent.
+async fn run_submission(
+    client: &CanvasClient,
+    gate: &SubmissionGate,
+    target: SubmissionTarget,
+    kind: SubmissionKind,
+    content: &str,
+    file: Option<PathBuf>,
+) -> Option<Result<Option<Submission>, String>> {
+    let SubmissionTarget { course_id, assignment_id, discussion_topic } = target;
+    let result = match kind {
+        SubmissionKind::DiscussionReply => {
+            let Some(topic_id) = discussion_topic else {
+                return Some(Err("This assignment has no linked discussion.".into()));
+            };
+            if !gate.commit() {
+                return None;
+            }
+            client
+                .post_discussion_entry(course_id, topic_id, content)
+                .await
+                .map(|_| None)
+        }
+        SubmissionKind::TextEntry => {
+            if !gate.commit() {
+                return None;
+            }
+            client
+                .submit_text_entry(course_id, assignment_id, content)
+                .await
+                .map(Some)
+        }
+        SubmissionKind::Url => {
+            if !gate.commit() {
+                return None;
+            }
+            client
+                .submit_url(course_id, assignment_id, content)
+                .await
+                .map(Some)
+        }
+        SubmissionKind::FileUpload => {
+            let Some(path) = file else {
+                return Some(Err("No file chosen.".into()));
+            };
+            let file_id = match client
+                .upload_submission_file(course_id, assignment_id, &path)
+                .await
+            {
+                Ok(id) => id,
+                Err(e) => return Some(Err(e.to_string())),
+            };
+            // Abort point: the upload is done but nothing has been submitted.
+            if !gate.commit() {
+                return None;
+            }
+            client
+                .submit_uploaded_files(course_id, assignment_id, &[file_id])
+                .await
+                .map(Some)
+        }
+        SubmissionKind::OpenInBrowser => return Some(Err("Nothing to submit from here.".into())),
+    };
+    Some(result.map_err(|e| e.to_string()))
+}
+
+/// Hand `url` to the platform's default browser.  Only `http` and `https`
+/// URLs are opened, so a link in course content can't start a local
+/// program or open a file.
+pub(crate) fn open_in_browser(url: &str) -> std::io::Result<()> {
+    use std::process::{Command, Stdio};
+    let scheme = url::Url::parse(url).map(|u| u.scheme().to_string());
+    if !matches!(scheme.as_deref(), Ok("http" | "https")) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "only http and https links can be opened",
+        ));
+    }
+    let mut cmd = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        // Not `cmd /C start`: cmd would treat `&`, `|` and `^` in the URL
+        // as its own syntax.  rundll32 passes the URL through untouched.
+        let mut c = Command::new("rundll32");
+        c.arg("url.dll,FileProtocolHandler");
+        c
+    } else {
+        Command::new("xdg-open")
+    };
+    // Keep the launcher's output from drawing over the TUI.
+    cmd.arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(drop)
+}
+
+/// The file a typed upload path names.  Quotes around it (as Windows
+/// Explorer's "Copy as path" adds) are dropped, and unless the path exists
+/// as typed, `~` and `$VAR`s are expanded.
+pub fn typed_file_path(input: &str) -> anyhow::Result<PathBuf> {
+    let trimmed = input.trim();
+    let unquoted = ['"', '\'']
+        .into_iter()
+        .find_map(|q| trimmed.strip_prefix(q)?.strip_suffix(q))
+        .unwrap_or(trimmed);
+    let path = if Path::new(unquoted).is_file() {
+        PathBuf::from(unquoted)
+    } else {
+        crate::config::expand_path(unquoted)?
+    };
+    if !path.is_file() {
+        anyhow::bail!("{} is not a file", path.display());
+    }
+    Ok(path)
+}
+
+/// A `:` command's name and the rest of it, trimmed.
+fn split_command(command: &str) -> (&str, &str) {
+    command
+        .split_once(char::is_whitespace)
+        .map_or((command, ""), |(name, args)| (name, args.trim()))
+}
+
+/// A path in `dir` for a file Canvas calls `name`: reduced to its final
+/// component so it can't escape `dir`, with characters Windows doesn't
+/// allow in file names replaced there, and " (1)", " (2)", … added before
+/// the extension if the name is taken.
+fn unique_download_path(dir: &Path, name: &str) -> PathBuf {
+    let name = safe_file_name(name, "attachment");
+    let name = name.as_str();
+    let candidate = dir.join(name);
+    if !candidate.exists() {
+        return candidate;
+    }
+    let (stem, ext) = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem, format!(".{ext}")),
+        _ => (name, String::new()),
+    };
+    (1..)
+        .map(|n| dir.join(format!("{stem} ({n}){ext}")))
+        .find(|p| !p.exists())
+        .unwrap_or(candidate)
+}
+
+/// `name` as a single path component: its last part, with the characters
+/// Windows forbids replaced, or `fallback` when nothing is left.
+fn safe_file_name(name: &str, fallback: &str) -> String {
+    let name = Path::new(name)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .filter(|n| !n.is_empty())
+        .unwrap_or(fallback);
+    name.chars()
+        .map(|c| match c {
+            ':' | '*' | '?' | '"' | '<' | '>' | '|' if cfg!(windows) => '_',
+            c => c,
+        })
+        .collect()
+}
+
+// ─── Background fetch (runs in a spawned task) ───────────────────────────────
+
+/// What a background sync needs besides the client.
+struct SyncJob {
+    cache_file: PathBuf,
+    /// The instance synced from, recorded in the cache.
+    canvas_host: Option<String>,
+    /// Whose data is shown now.  If the profile says the token is someone
+    /// else's, nothing of theirs is kept.
+    user_id: Option<u64>,
+    /// Content hash of the cache as last written.
+    cache_hash: Option<u64>,
+    /// `Some` when cache pruning is on.  Such syncs are also "light": they
+    /// leave assignment descriptions out and load them on demand.
+    prune_days: Option<u64>,
+    /// Descriptions already loaded, with the `updated_at` they belong to,
+    /// to fill in after a light sync.
+    known_descriptions: HashMap<u64, (Option<DateTime<Utc>>, String)>,
+    /// `max_assignments_per_course`, and the courses exempt from it.
+    max_assignments: Option<usize>,
+    uncapped_courses: HashSet<u64>,
+    /// What is shown now, kept for sections that fail or aren't retried.
+    previous: PreviousData,
+    /// Fetch only these sections (a retry after a partial failure).
+    retry: Option<StaleSections>,
+    /// The syncs so far, and the file this one is added to.
+    history: sync_history::SyncHistory,
+    history_file: PathBuf,
+}
+
+/// The data a sync falls back on, section by section.
+#[derive(Default)]
+struct PreviousData {
+    assignments: Vec<CourseAssignments>,
+    calendar_events: Vec<CalendarEvent>,
+    announcements: Vec<DiscussionTopic>,
+    unread_announcements: Option<u64>,
+    course_colors: BTreeMap<u64, String>,
+    group_names: BTreeMap<u64, String>,
+    peer_reviews: BTreeMap<u64, Vec<PeerReview>>,
+    truncated_courses: HashSet<u64>,
+    first_seen: FirstSeen,
+}
+
+impl PreviousData {
+    fn course_assignments(&self, course_id: u64) -> &[Assignment] {
+        self.assignments
+            .iter()
+            .find(|group| group.course_id == course_id)
+            .map_or(&[], |group| group.assignments.as_slice())
     }
+}
 
-    /// Poll for a completed course page body fetch.
-    pub fn poll_course_detail(&mut self) -> bool {
-        let result = match self.course_detail_rx.as_mut() {
-            None => return false,
-            Some(rx) => match rx.try_recv() {
-                Ok(r) => r,
-                Err(oneshot::error::TryRecvError::Empty) => return false,
-                Err(oneshot::error::TryRecvError::Closed) => {
-                    self.course_detail_rx = None;
-                    self.course_detail_loading = false;
-                    return false;
+/// How one section of a sync (a course's assignments, the calendar,
+/// announcements) came back.  Only a failure or a partial list falls back on
+/// older data: an empty answer from the server really means there is nothing.
+enum Section<T> {
+    Fetched(Vec<T>),
+    Empty,
+    /// A later page didn't decode; these are the pages before it.
+    Partial(Vec<T>),
+    /// `maintenance`: Canvas answered with a maintenance page.
+    Failed { maintenance: bool },
+    /// Not requested this time (see `SyncJob::retry`).
+    Skipped,
+}
+
+impl<T: Clone> Section<T> {
+    fn from_result(result: Result<Vec<T>, crate::api::CanvasError>, what: &str) -> Self {
+        match result {
+            Ok(items) if items.is_empty() => Self::Empty,
+            Ok(items) => Self::Fetched(items),
+            Err(e) => {
+                tracing::warn!("fetching {what}: {e}; keeping previous data");
+                Self::Failed {
+                    maintenance: matches!(e, crate::api::CanvasError::Maintenance { .. }),
                 }
-            },
-        };
-        self.course_detail_rx = None;
-        self.course_detail_loading = false;
-        self.course_detail_content = Some(
-            result.unwrap_or_else(|| "No Details Found".into()),
-        );
-        true
+            }
+        }
     }
 
-    /// Rebuild the display order for the courses list.
-    /// Categorized courses (valid course code before first '-') come first,
-    /// uncategorized at the bottom.
-    pub fn rebuild_course_display_order(&mut self) {
-        let mut categorized = Vec::new();
-        let mut uncategorized = Vec::new();
-        for (i, course) in self.courses.iter().enumerate() {
-            let name = course.name.as_deref().unwrap_or("");
-            let has_valid_code = name.find('-').is_some_and(|dash| {
-                is_valid_course_code(name[..dash].trim())
-            });
-            if has_valid_code {
-                categorized.push(i);
-            } else {
-                uncategorized.push(i);
+    /// Like `from_result`, for a list that may have come back partial.
+    fn from_capped(
+        result: Result<crate::api::Capped<T>, crate::api::CanvasError>,
+        what: &str,
+    ) -> Self {
+        match result {
+            Ok(capped) if capped.partial => {
+                tracing::warn!(
+                    "fetching {what}: a later page did not decode; keeping previous data"
+                );
+                Self::Partial(capped.items)
             }
+            result => Self::from_result(result.map(|capped| capped.items), what),
         }
-        categorized.extend(uncategorized);
-        self.course_display_order = categorized;
-        self.course_list_state.set_len(self.courses.len());
     }
 
-    /// Get the actual course index for the currently selected display position.
-    pub fn selected_course_idx(&self) -> Option<usize> {
-        self.course_display_order.get(self.course_list_state.selected).copied()
+    fn outcome(&self) -> sync_history::SectionOutcome {
+        match self {
+            Self::Fetched(_) | Self::Empty => sync_history::SectionOutcome::Ok,
+            Self::Partial(_) | Self::Failed { .. } => sync_history::SectionOutcome::Failed,
+            Self::Skipped => sync_history::SectionOutcome::Skipped,
+        }
     }
 
-    /// Get a reference to the currently selected course.
-    pub fn selected_course(&self) -> Option<&Course> {
-        self.selected_course_idx().and_then(|i| self.courses.get(i))
+    fn hit_maintenance(&self) -> bool {
+        matches!(self, Self::Failed { maintenance: true })
     }
 
-    pub fn active_list_state_mut(&mut self) -> &mut ListState {
-        match self.active_tab {
-            Tab::Dashboard => &mut self.dashboard_list_state,
-            Tab::Courses => &mut self.course_list_state,
-            Tab::Assignments => match self.unified_view_mode {
-                UnifiedViewMode::CalendarView => &mut self.calendar_list_state,
-                UnifiedViewMode::ListView => &mut self.assignment_list_state,
-            },
-            Tab::Announcements => &mut self.announcement_list_state,
+    /// The data to keep, and whether it is stale.  A partial list is only
+    /// kept when there is nothing older.
+    fn or_previous(self, previous: &[T]) -> (Vec<T>, bool) {
+        match self {
+            Self::Fetched(items) => (items, false),
+            Self::Empty => (Vec::new(), false),
+            Self::Partial(items) if previous.is_empty() => (items, true),
+            Self::Partial(_) => (previous.to_vec(), true),
+            Self::Failed { .. } => (previous.to_vec(), true),
+            Self::Skipped => (previous.to_vec(), false),
         }
     }
 }
 
-// ─── Background fetch (runs in a spawned task) ───────────────────────────────
-
-async fn fetch_canvas_data(client: CanvasClient) -> FetchResult {
-    let mut result = FetchResult {
+async fn fetch_canvas_data(client: CanvasClient, mut job: SyncJob) -> FetchResult {
+    let result = FetchResult {
         user: None,
         courses: Vec::new(),
         assignments: Vec::new(),
         calendar_events: Vec::new(),
         announcements: Vec::new(),
+        unread_announcements: None,
+        course_colors: BTreeMap::new(),
+        group_names: BTreeMap::new(),
+        peer_reviews: BTreeMap::new(),
         fetched_at: Utc::now(),
         error: None,
         is_auth_error: false,
+        maintenance: false,
+        stats: sync_stats::SyncStats::default(),
+        cache_hash: None,
+        truncated_courses: Vec::new(),
+        stale: StaleSections::default(),
+        first_seen: job.previous.first_seen.clone(),
+        sections: sync_history::SectionOutcomes::default(),
+        history: job.history.clone(),
+        other_user: false,
+    };
+    let started_at = Utc::now();
+    let mut timer = sync_stats::SyncTimer::start(client.request_count());
+    let mut result = fetch_into(&client, &mut job, &mut timer, result).await;
+    let stats = timer.finish();
+    tracing::info!("{}", stats.summary());
+
+    // Recorded here rather than with the cache, so failed syncs count too.
+    result.history.push(sync_history::SyncRecord {
+        started_at,
+        duration_ms: stats.total.as_millis() as u64,
+        sections: result.sections.clone(),
+        courses: result.courses.len(),
+        assignments: result.assignments.iter().map(|group| group.assignments.len()).sum(),
+        events: result.calendar_events.len(),
+        announcements: result.announcements.len(),
+        error: result.error.clone(),
+    });
+    if let Err(e) = sync_history::save_sync_history(&job.history_file, &result.history) {
+        tracing::warn!("saving sync history: {e}");
+    }
+    FetchResult { stats, ..result }
+}
+
+/// A course id and every one of its assignments, from `load_all_assignments`.
+type LoadAllResult = (u64, Result<Vec<Assignment>, String>);
+
+/// A course id and one of its assignments, from `fetch_calendar_assignment`.
+type CalendarAssignmentResult = (u64, Result<Assignment, String>);
+
+/// What `fetch_assignments` brings back.
+struct AssignmentFetch {
+    assignments: Vec<CourseAssignments>,
+    /// Courses whose assignments were cut off at the per-course cap.
+    truncated: Vec<u64>,
+    /// Courses whose fetch failed and kept their previous assignments.
+    stale: HashSet<u64>,
+    /// A course's fetch got a maintenance page.
+    maintenance: bool,
+    /// My peer reviews, by assignment id.
+    peer_reviews: BTreeMap<u64, Vec<PeerReview>>,
+    phases: Vec<sync_stats::PhaseTiming>,
+}
+
+/// Every course's assignments, in course order, with a phase per course.
+/// Light syncs leave descriptions out; known ones are filled back in.
+async fn fetch_assignments(
+    client: &CanvasClient,
+    courses: &[Course],
+    my_id: Option<u64>,
+    job: &SyncJob,
+) -> AssignmentFetch {
+    let light = job.prune_days.is_some();
+    let mut timer = sync_stats::SyncTimer::start(client.request_count());
+    let mut fetched = AssignmentFetch {
+        assignments: Vec::new(),
+        truncated: Vec::new(),
+        stale: HashSet::new(),
+        maintenance: false,
+        peer_reviews: BTreeMap::new(),
+        phases: Vec::new(),
+    };
+    for course in courses {
+        let name = course.name.clone().unwrap_or_else(|| "Unnamed".into());
+        let previous = job.previous.course_assignments(course.id);
+        let section = if job.retry.as_ref().is_some_and(|r| !r.courses.contains(&course.id)) {
+            if job.previous.truncated_courses.contains(&course.id) {
+                fetched.truncated.push(course.id);
+            }
+            Section::Skipped
+        } else {
+            let cap = job
+                .max_assignments
+                .filter(|_| !job.uncapped_courses.contains(&course.id));
+            let result = client.list_assignments(course.id, true, !light, cap).await;
+            if result.as_ref().is_ok_and(|capped| capped.truncated && !capped.partial) {
+                fetched.truncated.push(course.id);
+            }
+            Section::from_capped(result, &format!("assignments for {name}"))
+        };
+
+        let fresh = matches!(section, Section::Fetched(_) | Section::Empty);
+        fetched.maintenance |= section.hit_maintenance();
+        let (mut assignments, stale) = section.or_previous(previous);
+        if stale {
+            fetched.stale.insert(course.id);
+            if job.previous.truncated_courses.contains(&course.id) {
+                fetched.truncated.push(course.id);
+            }
+        }
+        for a in assignments.iter_mut().filter(|a| a.description.is_none()) {
+            if let Some((updated_at, desc)) = job.known_descriptions.get(&a.id) {
+                if *updated_at == a.updated_at {
+                    a.description = Some(desc.clone());
+                }
+            }
+        }
+
+        // My peer reviews, fetched along with fresh assignments.
+        for a in assignments.iter().filter(|a| a.peer_reviews == Some(true)) {
+            let previous = job.previous.peer_reviews.get(&a.id);
+            let reviews = if fresh {
+                match client.list_peer_reviews(course.id, a.id).await {
+                    Ok(reviews) => Some(
+                        reviews
+                            .into_iter()
+                            .filter(|r| r.assessor_id.is_some_and(|id| Some(id) == my_id))
+                            .collect(),
+                    ),
+                    Err(e) => {
+                        tracing::warn!("fetching peer reviews for {name}: {e}");
+                        previous.cloned()
+                    }
+                }
+            } else {
+                previous.cloned()
+            };
+            if let Some(reviews) = reviews.filter(|r: &Vec<PeerReview>| !r.is_empty()) {
+                fetched.peer_reviews.insert(a.id, reviews);
+            }
+        }
+        if fresh {
+            timer.lap(format!("assignments: {name}"), client.request_count());
+        }
+
+        if !assignments.is_empty() {
+            fetched.assignments.push(CourseAssignments { course_id: course.id, name, assignments });
+        }
+    }
+    fetched.phases = timer.finish().phases;
+    fetched
+}
+
+/// Await `fut` as sync phase `name`.  `client` is the one `fut` uses, with
+/// its own request counter.
+async fn timed<T>(
+    name: &str,
+    client: &CanvasClient,
+    fut: impl std::future::Future<Output = T>,
+) -> (T, sync_stats::PhaseTiming) {
+    let started = std::time::Instant::now();
+    let before = client.request_count();
+    let out = fut.await;
+    let phase = sync_stats::PhaseTiming {
+        name: name.into(),
+        duration: started.elapsed(),
+        requests: client.request_count() - before,
     };
+    (out, phase)
+}
+
+/// The body of `fetch_canvas_data`, timing each phase in `timer`.
+async fn fetch_into(
+    client: &CanvasClient,
+    job: &mut SyncJob,
+    timer: &mut sync_stats::SyncTimer,
+    mut result: FetchResult,
+) -> FetchResult {
+    use sync_history::SectionOutcome;
 
     match client.get_self().await {
         Ok(user) => result.user = Some(user),
         Err(crate::api::CanvasError::Unauthorized) => {
             result.is_auth_error = true;
             result.error = Some("Unauthorized – check your API token".into());
+            result.sections.profile = SectionOutcome::Failed;
             return result;
         }
         Err(e) => {
+            result.maintenance = matches!(e, crate::api::CanvasError::Maintenance { .. });
             result.error = Some(format!("fetching profile: {e}"));
+            result.sections.profile = SectionOutcome::Failed;
             return result;
         }
     }
+    result.sections.profile = SectionOutcome::Ok;
+    timer.lap("profile", client.request_count());
+
+    // The cache was someone else's: fetch everything, keep nothing of it.
+    let synced_as = result.user.as_ref().map(|u| u.id);
+    if job.user_id.is_some() && job.user_id != synced_as {
+        tracing::warn!(
+            "cache was synced as user {:?}, not {synced_as:?}; discarding it",
+            job.user_id
+        );
+        job.previous = PreviousData::default();
+        job.retry = None;
+        job.known_descriptions.clear();
+        result.first_seen = FirstSeen::default();
+        result.other_user = true;
+    }
 
     match client.list_courses().await {
         Ok(courses) => result.courses = courses,
         Err(e) => {
+            result.maintenance = matches!(e, crate::api::CanvasError::Maintenance { .. });
             result.error = Some(format!("fetching courses: {e}"));
+            result.sections.courses = SectionOutcome::Failed;
             return result;
         }
     }
-
-    for course in &result.courses {
-        let name = course.name.clone().unwrap_or_else(|| "Unnamed".into());
-        if let Ok(assignments) = client.list_assignments(course.id, true).await {
-            if !assignments.is_empty() {
-                result.assignments.push((name, assignments));
-            }
+    result.sections.courses = SectionOutcome::Ok;
+    timer.lap("courses", client.request_count());
+
+    // Groups in current courses (or none) add their announcements and events.
+    match client.list_own_groups().await {
+        Ok(groups) => {
+            result.group_names = groups
+                .into_iter()
+                .filter(|g| {
+                    g.course_id
+                        .is_none_or(|id| result.courses.iter().any(|c| c.id == id))
+                })
+                .map(|g| (g.id, g.name.unwrap_or_else(|| format!("Group {}", g.id))))
+                .collect();
+        }
+        Err(e) => {
+            tracing::warn!("fetching groups: {e}; keeping previous groups");
+            result.group_names = job.previous.group_names.clone();
         }
     }
+    timer.lap("groups", client.request_count());
+
+    // Colors only tint the lists, so a failure keeps the last ones.
+    result.course_colors = client.course_colors().await.unwrap_or_else(|e| {
+        tracing::warn!("fetching course colors: {e}; keeping previous colors");
+        job.previous.course_colors.clone()
+    });
+    timer.lap("colors", client.request_count());
 
     let now = Utc::now();
     let start = now.format("%Y-%m-%d").to_string();
@@ -1324,40 +5619,923 @@ async fn fetch_canvas_data(client: CanvasClient) -> FetchResult {
         .courses
         .iter()
         .map(|c| format!("course_{}", c.id))
+        .chain(result.group_names.keys().map(|id| format!("group_{id}")))
         .collect();
 
-    if let Ok(mut events) = client
-        .list_calendar_events(&context_codes, &start, &end)
-        .await
-    {
-        if let Ok(deadlines) = client
-            .list_upcoming_events(&context_codes, &start, &end)
-            .await
-        {
-            events.extend(deadlines);
-        }
-        events.sort_by(|a, b| a.start_at.cmp(&b.start_at));
-        result.calendar_events = events;
-    }
-
-    if let Ok(announcements) = client.list_announcements(&context_codes).await {
-        result.announcements = announcements;
+    // Assignments, calendar and announcements only need the course list, so
+    // they run side by side.  Each branch counts its own requests.
+    let (assignment_client, calendar_client, announcement_client) = (
+        client.with_own_counter(),
+        client.with_own_counter(),
+        client.with_own_counter(),
+    );
+    let retry_calendar = job.retry.as_ref().is_none_or(|r| r.calendar);
+    let retry_announcements = job.retry.as_ref().is_none_or(|r| r.announcements);
+    let (assignments, (calendar, calendar_phase), ((announcements, unread), announcement_phase)) = tokio::join!(
+        fetch_assignments(
+            &assignment_client,
+            &result.courses,
+            result.user.as_ref().map(|u| u.id),
+            job,
+        ),
+        timed("calendar", &calendar_client, async {
+            if !retry_calendar {
+                return Section::Skipped;
+            }
+            let (events, deadlines) = tokio::join!(
+                calendar_client.list_calendar_events(&context_codes, &start, &end),
+                calendar_client.list_upcoming_events(&context_codes, &start, &end),
+            );
+            // Both halves or neither, so the calendar is never half old.
+            let mut calendar = match (events, deadlines) {
+                (Ok(mut events), Ok(deadlines)) => {
+                    events.extend(deadlines);
+                    Ok(events)
+                }
+                (Err(e), _) | (_, Err(e)) => Err(e),
+            };
+            // Stable, so events keep coming before deadlines at equal times.
+            if let Ok(calendar) = &mut calendar {
+                calendar.sort_by_key(|a| a.start_at);
+            }
+            Section::from_result(calendar, "calendar")
+        }),
+        timed("announcements", &announcement_client, async {
+            if !retry_announcements {
+                return (Section::Skipped, job.previous.unread_announcements);
+            }
+            let (announcements, notifications, unread) = tokio::join!(
+                announcement_client.list_announcements(&context_codes),
+                announcement_client.list_account_notifications(),
+                announcement_client.unread_announcement_total(),
+            );
+            // Without Canvas's count, the fetched ones are counted instead.
+            let unread = unread
+                .map_err(|e| tracing::warn!("fetching the unread announcement count: {e}"))
+                .ok();
+            // Institution notices are optional; keep the last ones if they fail.
+            let notifications: Vec<DiscussionTopic> = match notifications {
+                Ok(notifications) => notifications.into_iter().map(DiscussionTopic::from).collect(),
+                Err(e) => {
+                    tracing::warn!("fetching account notifications: {e}");
+                    job.previous
+                        .announcements
+                        .iter()
+                        .filter(|a| a.context_code.as_deref() == Some(INSTITUTION_CONTEXT))
+                        .cloned()
+                        .collect()
+                }
+            };
+            let announcements = announcements.map(|mut announcements| {
+                announcements.extend(notifications);
+                announcements.sort_by_key(|a| std::cmp::Reverse(a.posted_at));
+                announcements
+            });
+            (Section::from_result(announcements, "announcements"), unread)
+        }),
+    );
+    for phase in assignments.phases.into_iter().chain([calendar_phase, announcement_phase]) {
+        timer.record(phase);
     }
+    result.sections.assignments = if !assignments.stale.is_empty() {
+        SectionOutcome::Failed
+    } else if job.retry.as_ref().is_some_and(|r| r.courses.is_empty()) {
+        SectionOutcome::Skipped
+    } else {
+        SectionOutcome::Ok
+    };
+    result.sections.calendar = calendar.outcome();
+    result.sections.announcements = announcements.outcome();
+    result.maintenance =
+        assignments.maintenance || calendar.hit_maintenance() || announcements.hit_maintenance();
+    result.assignments = assignments.assignments;
+    result.truncated_courses = assignments.truncated;
+    result.stale.courses = assignments.stale;
+    result.peer_reviews = assignments.peer_reviews;
+    (result.calendar_events, result.stale.calendar) =
+        calendar.or_previous(&job.previous.calendar_events);
+    (result.announcements, result.stale.announcements) =
+        announcements.or_previous(&job.previous.announcements);
+    result.unread_announcements = unread;
+    timer.lap("parallel fetch (wall)", client.request_count());
 
     result.fetched_at = Utc::now();
+    let ids = result.assignments.iter().flat_map(|group| group.assignments.iter().map(|a| a.id));
+    result.first_seen.record(ids, result.fetched_at);
 
     // Save cache from within the background task so the main thread never blocks.
-    let cache = CacheData {
+    let mut cache = CacheData {
+        version: cache::CACHE_VERSION,
         cached_at: result.fetched_at,
+        canvas_host: job.canvas_host.clone(),
         user: result.user.clone(),
         courses: result.courses.clone(),
         assignments: result.assignments.clone(),
         calendar_events: result.calendar_events.clone(),
         announcements: result.announcements.clone(),
+        unread_announcements: result.unread_announcements,
+        course_colors: result.course_colors.clone(),
+        group_names: result.group_names.clone(),
+        peer_reviews: result.peer_reviews.clone(),
+        truncated_courses: result.truncated_courses.clone(),
+        first_seen: result.first_seen.clone(),
+        content_hash: None,
     };
-    if let Err(e) = save_cache(&cache) {
-        result.error = Some(format!("saving cache: {e}"));
+    // Only the cached copy is pruned; this session keeps everything.
+    if let Some(days) = job.prune_days {
+        let pruned = cache.prune(days, result.fetched_at);
+        tracing::info!(
+            "cache pruned: assignments of {} ended courses, {} long descriptions, {} old announcements, {} first-seen times",
+            pruned.ended_courses,
+            pruned.descriptions,
+            pruned.announcements,
+            pruned.first_seen
+        );
     }
+    match save_cache(&job.cache_file, &mut cache, job.cache_hash) {
+        Ok(_) => result.cache_hash = cache.content_hash,
+        Err(e) => result.error = Some(format!("saving cache: {e}")),
+    }
+    timer.lap("cache save", client.request_count());
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use test_fixtures::assignment;
+
+    /// An assignment with `points_possible` and `grading_type` as given.
+    fn graded(points: Option<f64>, grading_type: Option<&str>) -> Assignment {
+        assignment(1, 1, "Essay", json!({ "points_possible": points, "grading_type": grading_type }))
+    }
+
+    #[test]
+    fn points_label_tells_missing_points_from_zero() {
+        assert_eq!(points_label(&graded(Some(10.0), None)), "10 pts");
+        assert_eq!(points_label(&graded(Some(7.5), Some("points"))), "7.5 pts");
+        assert_eq!(points_label(&graded(Some(0.0), Some("points"))), "0 pts");
+        assert_eq!(points_label(&graded(None, Some("points"))), "Ungraded");
+        assert_eq!(points_label(&graded(None, None)), "Ungraded");
+        assert_eq!(points_label(&graded(Some(1.0), Some("pass_fail"))), "Pass/fail");
+        assert_eq!(points_label(&graded(None, Some("pass_fail"))), "Pass/fail");
+    }
+
+    #[test]
+    fn format_score_over_points_and_scores() {
+        // (points possible, score, grade) → shown
+        let cases = [
+            (Some(10.0), Some(8.5), Some("8.5"), Some("8.5/10")),
+            (Some(3.0), Some(2.0 / 3.0), None, Some("0.67/3")),
+            (Some(10.0), None, None, None),
+            (None, Some(5.0), Some("5"), Some("5 pts (ungraded)")),
+            (Some(0.0), Some(5.0), Some("5"), Some("5 pts (ungraded)")),
+            (Some(0.0), Some(0.0), Some("0"), Some("0 pts (ungraded)")),
+            (None, None, Some("A"), Some("A")),
+            (None, None, None, None),
+        ];
+        for (points, score, grade, shown) in cases {
+            let a = graded(points, None);
+            assert_eq!(
+                format_score(&a, score, grade).as_deref(),
+                shown,
+                "points {points:?}, score {score:?}, grade {grade:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn format_score_pass_fail_is_complete_or_incomplete() {
+        for points in [Some(1.0), Some(0.0), None] {
+            let a = graded(points, Some("pass_fail"));
+            assert_eq!(format_score(&a, Some(1.0), Some("complete")).as_deref(), Some("Complete"));
+            assert_eq!(format_score(&a, Some(0.0), Some("incomplete")).as_deref(), Some("Incomplete"));
+            assert_eq!(format_score(&a, None, Some("pass")).as_deref(), Some("Complete"));
+            assert_eq!(format_score(&a, None, Some("fail")).as_deref(), Some("Incomplete"));
+            // Without a grade, the score decides.
+            assert_eq!(format_score(&a, Some(1.0), None).as_deref(), Some("Complete"));
+            assert_eq!(format_score(&a, Some(0.0), None).as_deref(), Some("Incomplete"));
+            assert_eq!(format_score(&a, None, None), None);
+        }
+    }
+
+    #[test]
+    fn open_in_browser_refuses_non_web_links() {
+        for url in ["file:///etc/passwd", "javascript:alert(1)", "calc.exe", "ms-settings:"] {
+            let err = open_in_browser(url).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{url}");
+        }
+    }
+
+    #[test]
+    fn format_score_percent() {
+        let a = graded(Some(20.0), Some("percent"));
+        assert_eq!(format_score(&a, Some(17.0), Some("85%")).as_deref(), Some("85%"));
+        assert_eq!(format_score(&a, Some(20.0 / 3.0), None).as_deref(), Some("33.33%"));
+        // Without points possible, Canvas's own "85%" grade is shown.
+        let unpointed = graded(None, Some("percent"));
+        assert_eq!(format_score(&unpointed, Some(17.0), Some("85%")).as_deref(), Some("85%"));
+        assert_eq!(format_score(&unpointed, None, None), None);
+    }
+
+    #[test]
+    fn format_score_letter_and_gpa() {
+        for grading_type in ["letter_grade", "gpa_scale"] {
+            let a = graded(Some(10.0), Some(grading_type));
+            assert_eq!(format_score(&a, Some(8.5), Some("B+")).as_deref(), Some("B+ (8.5/10)"));
+            assert_eq!(format_score(&a, None, Some("B+")).as_deref(), Some("B+"));
+            assert_eq!(format_score(&a, Some(8.5), None).as_deref(), Some("8.5/10"));
+            assert_eq!(format_score(&a, None, None), None);
+            let unpointed = graded(None, Some(grading_type));
+            assert_eq!(format_score(&unpointed, Some(3.7), Some("A-")).as_deref(), Some("A-"));
+        }
+    }
+
+    #[test]
+    fn format_score_not_graded() {
+        let a = graded(None, Some("not_graded"));
+        assert_eq!(format_score(&a, None, None).as_deref(), Some("Not graded"));
+        assert_eq!(format_score(&a, Some(1.0), Some("x")).as_deref(), Some("Not graded"));
+        assert_eq!(points_label(&a), "Not graded");
+    }
+
+    #[test]
+    fn score_label_by_grading_type() {
+        let cases = [
+            (None, Some(8.0), "Score"),
+            (None, None, "Grade"),
+            (Some("points"), Some(8.0), "Score"),
+            (Some("percent"), Some(8.0), "Score"),
+            (Some("pass_fail"), Some(1.0), "Grade"),
+            (Some("letter_grade"), Some(8.0), "Grade"),
+            (Some("gpa_scale"), Some(3.7), "Grade"),
+            (Some("not_graded"), None, "Grade"),
+        ];
+        for (grading_type, score, label) in cases {
+            assert_eq!(score_label(&graded(Some(10.0), grading_type), score), label, "{grading_type:?}");
+        }
+    }
+
+    fn list(len: usize, selected: usize) -> ListState {
+        let mut state = ListState::new();
+        state.set_len(len);
+        state.selected = selected;
+        state
+    }
+
+    #[test]
+    fn list_moves_clamp_at_the_ends() {
+        let mut state = list(5, 0);
+        state.select_prev(false);
+        assert_eq!(state.selected, 0);
+        state.select_next(false);
+        assert_eq!(state.selected, 1);
+        state.move_by(10, false);
+        assert_eq!(state.selected, 4);
+        state.select_next(false);
+        assert_eq!(state.selected, 4);
+        state.move_by(-3, false);
+        assert_eq!(state.selected, 1);
+        state.move_by(isize::MIN, false);
+        assert_eq!(state.selected, 0);
+        state.move_by(isize::MAX, false);
+        assert_eq!(state.selected, 4);
+    }
+
+    #[test]
+    fn list_moves_wrap_around_the_ends() {
+        let mut state = list(5, 0);
+        state.select_prev(true);
+        assert_eq!(state.selected, 4);
+        state.select_next(true);
+        assert_eq!(state.selected, 0);
+        state.move_by(7, true);
+        assert_eq!(state.selected, 2);
+        state.move_by(-8, true);
+        assert_eq!(state.selected, 4);
+        state.move_by(isize::MIN, true);
+        assert!(state.selected < 5);
+        state.move_by(isize::MAX, true);
+        assert!(state.selected < 5);
+    }
+
+    #[test]
+    fn empty_and_single_item_lists() {
+        for wrap in [false, true] {
+            let mut empty = list(0, 0);
+            empty.select_next(wrap);
+            empty.select_prev(wrap);
+            empty.move_by(-3, wrap);
+            assert_eq!(empty.selected, 0);
+
+            let mut one = list(1, 0);
+            one.select_next(wrap);
+            assert_eq!(one.selected, 0);
+            one.select_prev(wrap);
+            assert_eq!(one.selected, 0);
+            one.move_by(5, wrap);
+            assert_eq!(one.selected, 0);
+        }
+    }
+
+    #[test]
+    fn shrinking_a_list_clamps_the_selection() {
+        let mut state = list(10, 8);
+        state.set_len(3);
+        assert_eq!(state.selected, 2);
+        // Emptied lists keep the index for when items come back.
+        state.set_len(0);
+        assert_eq!(state.selected, 2);
+        state.set_len(20);
+        assert_eq!(state.selected, 2);
+    }
+
+    /// Poll `poll` every 10ms until it says it is done.
+    async fn wait_for(what: &str, mut poll: impl FnMut() -> bool) {
+        for _ in 0..300 {
+            if poll() {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("{what} never finished");
+    }
+
+    #[tokio::test]
+    async fn a_submission_during_a_sync_refreshes_after_it() {
+        use crate::test_server::{Reply, TestServer};
+        let server = TestServer::start(|request| {
+            let reply = match (request.method.as_str(), request.path()) {
+                ("POST", _) => Reply::json(r#"{"id": 1, "attempt": 1, "workflow_state": "submitted"}"#),
+                (_, "/api/v1/users/self") => Reply::json(r#"{"id": 1, "name": "Sam"}"#),
+                _ => Reply::json("[]"),
+            };
+            reply.delayed(std::time::Duration::from_millis(150))
+        })
+        .await;
+        let syncs = |server: &TestServer| {
+            server.log().iter().filter(|r| r.path() == "/api/v1/users/self").count()
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::app(&server.url, dir.path());
+
+        app.start_fetch();
+        assert!(app.fetch_rx.is_some());
+        app.submission_target = Some((1, 10));
+        app.submission_kind = Some(SubmissionKind::Url);
+        app.submission_input = "https://example.com/essay".into();
+        app.submission_state = SubmissionState::Submitting;
+        app.start_submission();
+        wait_for("submission", || app.poll_submission_result()).await;
+        assert!(matches!(app.submission_state, SubmissionState::Done { success: true, .. }));
+
+        // The sync is still running, so the refresh waits.
+        assert!(app.fetch_rx.is_some());
+        assert!(app.needs_refresh);
+        app.start_requested_fetch();
+        assert!(app.needs_refresh);
+
+        wait_for("first sync", || app.poll_fetch_result()).await;
+        assert_eq!(syncs(&server), 1);
+        app.start_requested_fetch();
+        assert!(!app.needs_refresh);
+        assert!(app.fetch_rx.is_some());
+        wait_for("second sync", || app.poll_fetch_result()).await;
+        assert_eq!(syncs(&server), 2);
+    }
+
+    /// An app loaded from a cache with `assignments` in course 1 and
+    /// `announcements` there, as Canvas JSON.
+    fn synced(dir: &Path, assignments: serde_json::Value, announcements: serde_json::Value) -> App {
+        let mut app = test_fixtures::offline_app(dir);
+        app.load_from_cache(test_fixtures::from_json(json!({
+            "version": 2,
+            "cached_at": "2025-04-01T12:00:00Z",
+            "user": { "id": 1, "name": "Sam" },
+            "courses": [{ "id": 1, "name": "Biology" }],
+            "assignments": [{ "course_id": 1, "name": "Biology", "assignments": assignments }],
+            "announcements": announcements,
+        })));
+        app
+    }
+
+    /// `days` from now, as Canvas writes dates.
+    fn in_days(days: i64) -> String {
+        (Utc::now() + chrono::Duration::days(days)).to_rfc3339()
+    }
+
+    fn due_in(id: u64, days: i64) -> serde_json::Value {
+        json!({ "id": id, "course_id": 1, "name": format!("A{id}"), "due_at": in_days(days) })
+    }
+
+    fn announcement(id: u64, days: i64, read: &str) -> serde_json::Value {
+        json!({ "id": id, "title": format!("N{id}"), "posted_at": in_days(days),
+                "context_code": "course_1", "read_state": read })
+    }
+
+    #[test]
+    fn t_on_the_dashboard_goes_to_the_top_of_either_panel() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = synced(dir.path(), json!([due_in(1, 1), due_in(2, 2), due_in(3, 3)]), json!([]));
+        app.active_tab = Tab::Dashboard;
+        app.dashboard_list_state.selected = 2;
+        app.jump_to_today_active();
+        assert_eq!(app.dashboard_list_state.selected, 0);
+
+        app.dashboard_panel = DashboardPanel::RecentlyGraded;
+        app.graded_list_state.selected = 1;
+        app.jump_to_today_active();
+        assert_eq!(app.graded_list_state.selected, 0);
+        // The other panel's selection is left alone.
+        app.dashboard_list_state.selected = 2;
+        app.jump_to_today_active();
+        assert_eq!(app.dashboard_list_state.selected, 2);
+    }
+
+    #[test]
+    fn t_on_announcements_goes_to_the_newest_unread() {
+        let dir = tempfile::tempdir().unwrap();
+        let mixed = json!([
+            announcement(1, -1, "read"),
+            announcement(2, -2, "unread"),
+            announcement(3, -3, "unread"),
+        ]);
+        let mut app = synced(dir.path(), json!([]), mixed);
+        app.active_tab = Tab::Announcements;
+        app.jump_to_today_active();
+        assert_eq!(app.selected_announcement().map(|a| a.id), Some(2));
+
+        // Canvas sends them newest first.
+        let all_read = json!([announcement(2, -1, "read"), announcement(1, -3, "read")]);
+        let mut app = synced(dir.path(), json!([]), all_read);
+        app.active_tab = Tab::Announcements;
+        app.announcement_list_state.selected = 1;
+        app.jump_to_today_active();
+        assert_eq!(app.selected_announcement().map(|a| a.id), Some(2));
+
+        let mut app = synced(dir.path(), json!([]), json!([]));
+        app.active_tab = Tab::Announcements;
+        app.jump_to_today_active();
+        assert_eq!(app.announcement_list_state.selected, 0);
+        assert!(app.selected_announcement().is_none());
+    }
+
+    #[test]
+    fn t_on_courses_does_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = synced(dir.path(), json!([]), json!([]));
+        app.courses.push(test_fixtures::course(2, "Chemistry"));
+        app.rebuild_course_display_order();
+        app.active_tab = Tab::Courses;
+        app.course_list_state.set_len(2);
+        app.course_list_state.selected = 1;
+        app.jump_to_today_active();
+        assert_eq!(app.course_list_state.selected, 1);
+    }
+
+    #[test]
+    fn t_on_the_schedule_goes_to_the_next_due_item() {
+        let dir = tempfile::tempdir().unwrap();
+        let assignments = json!([due_in(1, -3), due_in(2, 1), due_in(3, 5)]);
+        let mut app = synced(dir.path(), assignments, json!([]));
+        app.active_tab = Tab::Assignments;
+
+        app.unified_view_mode = UnifiedViewMode::CalendarView;
+        app.calendar_list_state.selected = 0;
+        app.jump_to_today_active();
+        let item = &app.calendar_items[app.calendar_list_state.selected];
+        assert_eq!(item.assignment_id, Some(2));
+
+        app.unified_view_mode = UnifiedViewMode::ListView;
+        app.assignment_list_state.selected = 0;
+        app.jump_to_today_active();
+        assert_eq!(app.get_selected_assignment().map(|(_, a)| a.id), Some(2));
+    }
+
+    #[test]
+    fn t_on_a_schedule_with_everything_past_or_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = synced(dir.path(), json!([due_in(1, -5), due_in(2, -3)]), json!([]));
+        app.active_tab = Tab::Assignments;
+        app.unified_view_mode = UnifiedViewMode::CalendarView;
+        app.jump_to_today_active();
+        assert_eq!(app.calendar_list_state.selected, app.calendar_items.len() - 1);
+
+        let mut app = synced(dir.path(), json!([]), json!([]));
+        app.active_tab = Tab::Assignments;
+        for mode in [UnifiedViewMode::CalendarView, UnifiedViewMode::ListView] {
+            app.unified_view_mode = mode;
+            app.jump_to_today_active();
+            assert_eq!(app.active_list_state_mut().selected, 0);
+        }
+    }
+
+    /// An exit status with `code`, as the editor would leave.
+    fn exit_status(code: i32) -> std::process::ExitStatus {
+        #[cfg(unix)]
+        {
+            std::os::unix::process::ExitStatusExt::from_raw(code << 8)
+        }
+        #[cfg(windows)]
+        {
+            std::os::windows::process::ExitStatusExt::from_raw(code as u32)
+        }
+    }
+
+    /// Start editing a submission with `editor`, then write `text` to the
+    /// file as the editor would, returning the request the main loop gets.
+    fn edit(app: &mut App, editor: &str, text: &str) -> external::ExternalCommand {
+        app.editor = Some(editor.into());
+        app.edit_submission_text();
+        assert!(matches!(app.submission_state, SubmissionState::Hidden));
+        let request = app.external_command.take().expect("an editor request");
+        let external::ExternalCommand::EditSubmission { editor: used, file } = &request else {
+            panic!("not an editor request: {request:?}");
+        };
+        assert_eq!(used, editor);
+        std::fs::write(file, text).unwrap();
+        request
+    }
+
+    fn edited_file(request: &external::ExternalCommand) -> PathBuf {
+        match request {
+            external::ExternalCommand::EditSubmission { file, .. } => file.to_path_buf(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn edited_text_goes_to_the_preview() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::offline_app(dir.path());
+        let request = edit(&mut app, "myeditor", "My answer\n");
+        let file = edited_file(&request);
+
+        app.finish_external_command(request, Ok(exit_status(0)));
+        assert!(matches!(app.submission_state, SubmissionState::TextPreview));
+        assert_eq!(app.submission_input, "My answer\n");
+        assert!(!file.exists(), "the temp file is deleted");
+    }
+
+    #[test]
+    fn empty_text_cancels_back_to_the_type_picker() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::offline_app(dir.path());
+        let request = edit(&mut app, "myeditor", " \n\n");
+
+        app.finish_external_command(request, Ok(exit_status(0)));
+        assert!(matches!(app.submission_state, SubmissionState::TypePicker));
+        assert_eq!(app.status_message, "Editor closed with no content — submission cancelled.");
+    }
+
+    #[test]
+    fn editor_failures_are_reported_and_discard_the_text() {
+        let not_found = || Err(std::io::Error::from(std::io::ErrorKind::NotFound));
+        let cases: [(std::io::Result<std::process::ExitStatus>, &str); 4] = [
+            (not_found(), "Editor 'myeditor' not found — is it installed?"),
+            (
+                Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied)),
+                "Could not start editor 'myeditor': permission denied",
+            ),
+            (Ok(exit_status(127)), "Editor 'myeditor' exited with status 127 — is it installed?"),
+            (Ok(exit_status(2)), "Editor 'myeditor' exited with status 2."),
+        ];
+        for (result, message) in cases {
+            let dir = tempfile::tempdir().unwrap();
+            let mut app = test_fixtures::offline_app(dir.path());
+            let request = edit(&mut app, "myeditor", "half-written answer");
+            let file = edited_file(&request);
+
+            app.finish_external_command(request, result);
+            assert!(matches!(app.submission_state, SubmissionState::TypePicker));
+            assert_eq!(app.status_message, message);
+            assert!(app.submission_input.is_empty());
+            assert!(!file.exists());
+        }
+    }
+
+    #[test]
+    fn paged_files_are_deleted_afterwards() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::offline_app(dir.path());
+        let path = dir.path().join("raw.json");
+
+        std::fs::write(&path, "{}").unwrap();
+        let before = app.status_message.clone();
+        let request = external::ExternalCommand::PageFile { path: path.clone() };
+        app.finish_external_command(request, Ok(exit_status(0)));
+        assert!(!path.exists());
+        assert_eq!(app.status_message, before);
+
+        std::fs::write(&path, "{}").unwrap();
+        let request = external::ExternalCommand::PageFile { path: path.clone() };
+        app.finish_external_command(request, Err(std::io::ErrorKind::NotFound.into()));
+        assert!(!path.exists());
+        assert!(app.status_message.starts_with("Could not run the pager: "));
+    }
+
+    fn run(app: &mut App, command: &str) {
+        app.command_line = Some(command.into());
+        app.run_command_line();
+    }
+
+    #[test]
+    fn export_md_writes_the_report_to_the_given_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = synced(dir.path(), json!([due_in(1, 3)]), json!([]));
+        let dest = dir.path().join("reports/status.md");
+
+        run(&mut app, &format!("  export md {}  ", dest.display()));
+        assert_eq!(app.status_message, format!("Report saved to {}", dest.display()));
+        let report = std::fs::read_to_string(&dest).unwrap();
+        assert!(report.starts_with("# Canvas report\n"));
+        assert!(report.contains("## Biology\n") && report.contains("| A1 |"), "{report}");
+    }
+
+    #[test]
+    fn export_md_without_a_path_uses_the_download_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = synced(dir.path(), json!([]), json!([]));
+
+        run(&mut app, "export md");
+        let saved: Vec<_> = std::fs::read_dir(&app.paths.download_dir).unwrap().collect();
+        assert_eq!(saved.len(), 1);
+        let name = saved[0].as_ref().unwrap().file_name();
+        assert!(name.to_string_lossy().starts_with("canvas-report-"));
+    }
+
+    #[test]
+    fn other_export_formats_and_unknown_commands_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::offline_app(dir.path());
+
+        run(&mut app, "export pdf out.pdf");
+        assert_eq!(app.status_message, "Usage: :export md <path>");
+        run(&mut app, "q now");
+        assert_eq!(app.status_message, "Not a command: :q now");
+        assert!(app.running);
+        run(&mut app, "nope");
+        assert_eq!(app.status_message, "Not a command: :nope");
+        assert!(app.command_line.is_none());
+    }
+
+    #[test]
+    fn sync_history_opens_the_history_view() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::offline_app(dir.path());
+
+        run(&mut app, "sync-history");
+        assert!(app.show_sync_stats && app.sync_stats_history);
+    }
+
+    /// An app loaded from a cache with an undated exam (5), an assignment
+    /// due in ten days (6) and an exam event (50) starting at `start`.
+    fn with_exam_event(dir: &Path, start: &str) -> App {
+        let mut app = test_fixtures::offline_app(dir);
+        app.load_from_cache(test_fixtures::from_json(json!({
+            "version": 2,
+            "cached_at": "2025-04-01T12:00:00Z",
+            "courses": [{ "id": 1, "name": "Biology" }],
+            "assignments": [{ "course_id": 1, "name": "Biology", "assignments": [
+                { "id": 5, "course_id": 1, "name": "Exam" },
+                due_in(6, 10),
+            ] }],
+            "calendar_events": [{ "id": 50, "title": "Midterm", "start_at": start,
+                                  "context_code": "course_1", "type": "event" }],
+        })));
+        app
+    }
+
+    fn select_event(app: &mut App, event_id: u64) {
+        let idx = app.calendar_items.iter().position(|i| i.event_id == Some(event_id)).unwrap();
+        app.calendar_list_state.selected = idx;
+    }
+
+    fn exam_due(app: &App) -> Option<DateTime<Utc>> {
+        app.get_assignment_by_id(5).and_then(|(_, a)| a.due_at)
+    }
+
+    #[test]
+    fn linking_an_event_dates_the_assignment_until_unlinked() {
+        let dir = tempfile::tempdir().unwrap();
+        let midterm = in_days(3);
+        let mut app = with_exam_event(dir.path(), &midterm);
+        let start = app.calendar_events[0].start_at;
+        assert_eq!(app.focal_assignment_id, Some(6));
+
+        select_event(&mut app, 50);
+        app.toggle_event_link();
+        let picker = app.event_link_picker.as_ref().unwrap();
+        assert_eq!(picker.candidates, vec![(5, "Exam".to_string())]);
+        app.link_picked_assignment(0);
+
+        assert_eq!(exam_due(&app), start);
+        assert_eq!(app.focal_assignment_id, Some(5));
+        assert_eq!(app.event_linked_assignment(50), Some(5));
+        assert!(app.calendar_items.iter().any(|i| i.assignment_id == Some(5)));
+        // What a sync falls back on and caches stays as Canvas has it.
+        let plain = app.assignments_without_links();
+        assert!(plain[0].assignments.iter().all(|a| a.id != 5 || a.due_at.is_none()));
+
+        // The link outlives the app.
+        let mut app = with_exam_event(dir.path(), &midterm);
+        assert_eq!(exam_due(&app), start);
+
+        select_event(&mut app, 50);
+        app.toggle_event_link();
+        assert!(app.event_link_picker.is_none());
+        assert_eq!(exam_due(&app), None);
+        assert_eq!(app.focal_assignment_id, Some(6));
+        assert!(with_exam_event(dir.path(), &midterm).event_links.is_empty());
+    }
+
+    #[test]
+    fn a_canvas_due_date_overrides_a_link() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = with_exam_event(dir.path(), &in_days(3));
+        select_event(&mut app, 50);
+        app.toggle_event_link();
+        app.link_picked_assignment(0);
+
+        let mut cache: CacheData = test_fixtures::from_json(json!({
+            "version": 2,
+            "cached_at": "2025-04-01T12:00:00Z",
+            "courses": [{ "id": 1, "name": "Biology" }],
+            "assignments": [{ "course_id": 1, "name": "Biology", "assignments": [
+                { "id": 5, "course_id": 1, "name": "Exam", "due_at": in_days(20) },
+            ] }],
+        }));
+        let canvas_due = cache.assignments[0].assignments[0].due_at;
+        cache.calendar_events = app.calendar_events.clone();
+        app.load_from_cache(cache);
+        assert_eq!(exam_due(&app), canvas_due);
+        assert!(app.linked_due.is_empty());
+    }
+
+    #[test]
+    fn only_course_events_with_undated_assignments_can_be_linked() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::offline_app(dir.path());
+        app.load_from_cache(test_fixtures::from_json(json!({
+            "version": 2,
+            "cached_at": "2025-04-01T12:00:00Z",
+            "courses": [{ "id": 1, "name": "Biology" }],
+            "assignments": [{ "course_id": 1, "name": "Biology", "assignments": [due_in(6, 10)] }],
+            "calendar_events": [
+                { "id": 50, "title": "Midterm", "start_at": in_days(3), "context_code": "course_1" },
+                { "id": 51, "title": "Dentist", "start_at": in_days(4), "context_code": "user_1" },
+            ],
+        })));
+
+        select_event(&mut app, 50);
+        app.toggle_event_link();
+        assert!(app.event_link_picker.is_none());
+        assert_eq!(app.status_message, "Biology has no assignments without a due date.");
+
+        select_event(&mut app, 51);
+        app.toggle_event_link();
+        assert!(app.event_link_picker.is_none());
+        assert_eq!(app.status_message, "Only dated course events can be linked to an assignment.");
+    }
+
+    #[test]
+    fn now_adds_the_clock_skew_only_when_asked() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::offline_app(dir.path());
+        let skew = chrono::Duration::minutes(41);
+        let near = |t: DateTime<Utc>, expected: DateTime<Utc>| (t - expected).num_seconds().abs() <= 1;
+
+        assert!(near(app.now(), Utc::now()));
+        app.clock_skew = Some(skew);
+        assert!(near(app.now(), Utc::now()));
+        app.correct_clock_skew = true;
+        assert!(near(app.now(), Utc::now() + skew));
+        app.clock_skew = Some(-skew);
+        assert!(near(app.now(), Utc::now() - skew));
+        app.clock_skew = None;
+        assert!(near(app.now(), Utc::now()));
+    }
+
+    #[tokio::test]
+    async fn a_maintenance_page_keeps_the_cached_section() {
+        use crate::test_server::{Reply, TestServer};
+        let page = "<html><title>Canvas is down for maintenance</title></html>";
+        let server = TestServer::start(move |request| match request.path() {
+            "/api/v1/users/self" => Reply::json(r#"{"id": 1, "name": "Sam"}"#),
+            "/api/v1/courses" => Reply::json(r#"[{"id": 5, "name": "Physics"}]"#),
+            "/api/v1/courses/5/assignments" => Reply::html(503, page),
+            "/api/v1/calendar_events" => Reply::html(200, page),
+            _ => Reply::json("[]"),
+        })
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::app(&server.url, dir.path());
+        app.load_from_cache(test_fixtures::from_json(json!({
+            "version": 2,
+            "cached_at": "2025-04-01T12:00:00Z",
+            "user": { "id": 1, "name": "Sam" },
+            "courses": [{ "id": 5, "name": "Physics" }],
+            "assignments": [
+                { "course_id": 5, "name": "Physics", "assignments": [
+                    { "id": 50, "course_id": 5, "name": "Lab" },
+                ] },
+            ],
+        })));
+
+        app.start_fetch();
+        wait_for("sync", || app.poll_fetch_result()).await;
+        assert!(app.get_assignment_by_id(50).is_some());
+        assert_eq!(app.status_message, MAINTENANCE_STATUS);
+        assert!(app.stale_sections.count() >= 2, "{:?}", app.stale_sections);
+    }
+
+    #[tokio::test]
+    async fn a_list_cut_short_by_a_bad_page_keeps_the_cached_section() {
+        use crate::test_server::{Reply, TestServer};
+        let server = TestServer::start(|request| {
+            if request.target.contains("page=2") {
+                return Reply::json(r#"[{"id": "not a number"}]"#);
+            }
+            let next = format!("<{}?page=2>; rel=\"next\"", request.path());
+            match request.path() {
+                "/api/v1/users/self" => Reply::json(r#"{"id": 1, "name": "Sam"}"#),
+                "/api/v1/courses" => Reply::json(r#"[{"id": 5, "name": "Physics"}]"#),
+                "/api/v1/courses/5/assignments" => {
+                    Reply::json(r#"[{"id": 51, "course_id": 5}]"#).header("Link", &next)
+                }
+                "/api/v1/announcements" => {
+                    Reply::json(r#"[{"id": 71, "title": "New"}]"#).header("Link", &next)
+                }
+                _ => Reply::json("[]"),
+            }
+        })
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::app(&server.url, dir.path());
+        app.load_from_cache(test_fixtures::from_json(json!({
+            "version": 2,
+            "cached_at": "2025-04-01T12:00:00Z",
+            "user": { "id": 1, "name": "Sam" },
+            "courses": [{ "id": 5, "name": "Physics" }],
+            "assignments": [
+                { "course_id": 5, "name": "Physics", "assignments": [
+                    { "id": 50, "course_id": 5, "name": "Lab" },
+                ] },
+            ],
+            "announcements": [{ "id": 70, "title": "Old", "context_code": "course_5" }],
+        })));
+
+        app.start_fetch();
+        wait_for("sync", || app.poll_fetch_result()).await;
+        assert!(app.get_assignment_by_id(50).is_some());
+        assert!(app.get_assignment_by_id(51).is_none());
+        assert!(app.stale_sections.courses.contains(&5));
+        // Not cut off by a cap, so no "press A to load all".
+        assert!(app.truncated_courses.is_empty());
+        let announcements: Vec<u64> = app.announcements.iter().map(|a| a.id).collect();
+        assert_eq!(announcements, [70]);
+        assert!(app.stale_sections.announcements);
+    }
+
+    #[tokio::test]
+    async fn a_sync_as_another_user_replaces_their_data() {
+        use crate::test_server::{Reply, TestServer};
+        let server = TestServer::start(|request| match request.path() {
+            "/api/v1/users/self" => Reply::json(r#"{"id": 2, "name": "Alex"}"#),
+            "/api/v1/courses" => Reply::json(r#"[{"id": 5, "name": "Physics"}]"#),
+            // Normally the cached assignments would stand in.
+            "/api/v1/courses/5/assignments" => Reply::status(500, "oops"),
+            _ => Reply::json("[]"),
+        })
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = test_fixtures::app(&server.url, dir.path());
+        app.load_from_cache(test_fixtures::from_json(json!({
+            "version": 2,
+            "cached_at": "2025-04-01T12:00:00Z",
+            "user": { "id": 1, "name": "Sam" },
+            "courses": [{ "id": 1, "name": "Biology" }, { "id": 5, "name": "Physics" }],
+            "assignments": [
+                { "course_id": 1, "name": "Biology", "assignments": [{ "id": 10, "course_id": 1 }] },
+                { "course_id": 5, "name": "Physics", "assignments": [
+                    { "id": 50, "course_id": 5, "name": "Lab",
+                      "submission": { "workflow_state": "graded", "score": 9.0 } },
+                ] },
+            ],
+        })));
+
+        app.start_fetch();
+        wait_for("sync", || app.poll_fetch_result()).await;
+        assert_eq!(app.user.as_ref().map(|u| u.id), Some(2));
+        let names: Vec<_> = app.courses.iter().map(|c| c.name.as_deref().unwrap()).collect();
+        assert_eq!(names, ["Physics"]);
+        assert!(app.get_assignment_by_id(10).is_none());
+        assert!(app.get_assignment_by_id(50).is_none());
+        assert!(app.status_message.ends_with("The cached data was another user's; replaced it."));
+        let saved = crate::cache::load_cache(&app.paths).unwrap();
+        assert_eq!(saved.user.map(|u| u.id), Some(2));
+        assert_eq!(saved.canvas_host.as_deref(), Some("127.0.0.1"));
+    }
+}
@@ -0,0 +1,275 @@
+//! The `[[open_handlers]]` config table: shell commands run with `x` on
+//! the assignments they match, such as cloning a course's starter repo.
+//! Values put into a command's placeholders are quoted for the shell, so
+//! an assignment name can't run anything of its own; a placeholder the
+//! template itself puts in quotes is refused, as that would undo it.
+
+use std::process::Command;
+
+use crate::config::OpenHandler;
+use crate::models::{Assignment, Course};
+
+/// The placeholders a command may use.
+const PLACEHOLDERS: [&str; 4] = ["assignment_name", "course_code", "html_url", "due_iso"];
+
+/// Whether `text` contains `pattern`, ignoring case.
+fn contains(text: Option<&str>, pattern: &str) -> bool {
+    text.is_some_and(|t| t.to_lowercase().contains(&pattern.to_lowercase()))
+}
+
+/// The first handler matching `assignment` in `course`: its `course`
+/// pattern is in the course's code or name, and its `assignment` pattern
+/// in the assignment's name.  A handler without patterns matches all.
+pub fn find<'a>(
+    handlers: &'a [OpenHandler],
+    course: Option<&Course>,
+    assignment: &Assignment,
+) -> Option<&'a OpenHandler> {
+    handlers.iter().find(|h| {
+        let course_matches = h.course.as_deref().is_none_or(|p| {
+            course.is_some_and(|c| {
+                contains(c.course_code.as_deref(), p)
+                    || contains(c.name.as_deref(), p)
+                    || contains(c.original_name.as_deref(), p)
+            })
+        });
+        let assignment_matches = h
+            .assignment
+            .as_deref()
+            .is_none_or(|p| contains(assignment.name.as_deref(), p));
+        course_matches && assignment_matches
+    })
+}
+
+/// `template` with each `{placeholder}` replaced by its value for
+/// `assignment`, quoted for the shell.  Braces around anything other than
+/// a lowercase word (`${HOME}`, `{a,b}`) are left for the shell; a
+/// lowercase word that isn't a placeholder is an error, as it is most
+/// likely a typo.  So is a placeholder inside quotes (`"{html_url}"`):
+/// its own quoting would end the template's, leaving the value open to
+/// the shell.
+pub fn expand(
+    template: &str,
+    course: Option<&Course>,
+    assignment: &Assignment,
+) -> Result<String, String> {
+    let value = |name: &str| -> String {
+        match name {
+            "assignment_name" => assignment.name.clone().unwrap_or_default(),
+            "course_code" => course
+                .and_then(|c| c.course_code.clone().or_else(|| c.name.clone()))
+                .unwrap_or_default(),
+            "html_url" => assignment.html_url.clone().unwrap_or_default(),
+            "due_iso" => assignment
+                .due_at
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default(),
+            _ => String::new(),
+        }
+    };
+    let mut out = String::new();
+    let mut quotes = ShellQuotes::default();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        quotes.read(&rest[..start]);
+        let after = &rest[start + 1..];
+        let name = after.find('}').map(|end| &after[..end]).filter(|name| {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+        });
+        match name {
+            Some(name) if PLACEHOLDERS.contains(&name) && quotes.open.is_some() => {
+                return Err(format!(
+                    "{{{name}}} is inside quotes (leave it bare; its value gets quoted)"
+                ))
+            }
+            Some(name) if PLACEHOLDERS.contains(&name) => {
+                out.push_str(&quote(&value(name)));
+                rest = &after[name.len() + 1..];
+            }
+            Some(name) => {
+                return Err(format!(
+                    "unknown placeholder {{{name}}} (use {})",
+                    PLACEHOLDERS.map(|p| format!("{{{p}}}")).join(", ")
+                ))
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// The quote the shell is inside at a point of a command line, followed
+/// through the text before it.
+#[derive(Default)]
+struct ShellQuotes {
+    open: Option<char>,
+    escaped: bool,
+}
+
+impl ShellQuotes {
+    fn read(&mut self, text: &str) {
+        // cmd.exe only has double quotes, and no backslash escapes.
+        let posix = !cfg!(windows);
+        for c in text.chars() {
+            if self.escaped {
+                self.escaped = false;
+                continue;
+            }
+            match (self.open, c) {
+                (None | Some('"'), '\\') if posix => self.escaped = true,
+                (None, '\'') if posix => self.open = Some('\''),
+                (None, '"') => self.open = Some('"'),
+                (Some(open), c) if c == open => self.open = None,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// `value` as one word for `sh`: single-quoted, with each `'` closed,
+/// escaped and reopened.
+#[cfg(not(windows))]
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// `value` as one word for `cmd.exe`: double-quoted.  cmd has no way to
+/// escape `"`, `%` or `!` inside quotes, so they are left out.
+#[cfg(windows)]
+fn quote(value: &str) -> String {
+    let kept: String = value
+        .chars()
+        .filter(|c| !matches!(c, '"' | '%' | '!'))
+        .collect();
+    format!("\"{kept}\"")
+}
+
+/// The process running `line` in the platform's shell.
+pub fn shell_command(line: &str) -> Command {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut cmd = Command::new(shell);
+    cmd.arg(flag).arg(line);
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::test_fixtures::{assignment, course};
+    use serde_json::json;
+
+    fn lab(name: &str) -> Assignment {
+        assignment(
+            10,
+            1,
+            name,
+            json!({
+                "html_url": "https://school.instructure.com/courses/1/assignments/10",
+                "due_at": "2025-04-01T12:00:00Z",
+            }),
+        )
+    }
+
+    fn expand_for(template: &str, name: &str) -> Result<String, String> {
+        expand(template, Some(&course(1, "CS 101")), &lab(name))
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn placeholders_are_single_quoted() {
+        assert_eq!(
+            expand_for("git clone {html_url} && cd {course_code}", "Lab 1").unwrap(),
+            "git clone 'https://school.instructure.com/courses/1/assignments/10' && cd 'CS 101'"
+        );
+        assert_eq!(
+            expand_for("echo {due_iso}", "Lab 1").unwrap(),
+            "echo '2025-04-01T12:00:00+00:00'"
+        );
+        assert_eq!(
+            expand_for("echo {assignment_name}", "Bob's lab").unwrap(),
+            r"echo 'Bob'\''s lab'"
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn the_shell_gets_values_as_they_are() {
+        for name in ["Bob's lab", "$(echo hi)", "`echo hi`", "a; echo hi", "*", "\"x\" \\ y"] {
+            let line = expand_for("printf %s {assignment_name}", name).unwrap();
+            let output = shell_command(&line).output().unwrap();
+            assert_eq!(String::from_utf8_lossy(&output.stdout), name, "{line}");
+        }
+    }
+
+    #[test]
+    fn unknown_placeholders_are_errors() {
+        let err = expand_for("open {url}", "Lab 1").unwrap_err();
+        assert!(err.starts_with("unknown placeholder {url}"), "{err}");
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn other_braces_are_left_for_the_shell() {
+        assert_eq!(
+            expand_for("cp ${HOME}/{a,b} {Lab} {course_code}", "Lab 1").unwrap(),
+            "cp ${HOME}/{a,b} {Lab} 'CS 101'"
+        );
+        assert_eq!(expand_for("echo {", "Lab 1").unwrap(), "echo {");
+    }
+
+    #[test]
+    fn placeholders_inside_quotes_are_errors() {
+        for template in [
+            "git clone \"{html_url}\"",
+            "cd \"labs/{assignment_name}\"",
+            #[cfg(not(windows))]
+            "echo '{assignment_name}'",
+            #[cfg(not(windows))]
+            "echo \"it's\" \"{assignment_name}",
+        ] {
+            let err = expand_for(template, "$(rm -rf ~)").unwrap_err();
+            assert!(err.contains("is inside quotes"), "{template}: {err}");
+        }
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn placeholders_after_closed_or_escaped_quotes_are_fine() {
+        let template = r#"echo "done:" {course_code} \"{course_code}\" 'a"b' {course_code}"#;
+        assert_eq!(
+            expand_for(template, "Lab 1").unwrap(),
+            r#"echo "done:" 'CS 101' \"'CS 101'\" 'a"b' 'CS 101'"#
+        );
+    }
+
+    #[test]
+    fn the_first_matching_handler_wins() {
+        let handler = |course: Option<&str>, assignment: Option<&str>, command: &str| OpenHandler {
+            course: course.map(str::to_string),
+            assignment: assignment.map(str::to_string),
+            command: command.into(),
+            ..OpenHandler::default()
+        };
+        let handlers = [
+            handler(Some("bio"), None, "bio"),
+            handler(Some("cs"), Some("LAB"), "cs lab"),
+            handler(None, None, "any"),
+        ];
+        let cs = course(1, "CS 101");
+        let found = |course: Option<&Course>, name: &str, handlers: &[OpenHandler]| {
+            find(handlers, course, &lab(name)).map(|h| h.command.clone())
+        };
+        assert_eq!(found(Some(&cs), "Lab 2", &handlers).as_deref(), Some("cs lab"));
+        assert_eq!(found(Some(&cs), "Quiz 2", &handlers).as_deref(), Some("any"));
+        assert_eq!(found(None, "Lab 2", &handlers[..2]), None);
+    }
+}
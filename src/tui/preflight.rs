@@ -0,0 +1,110 @@
+//! What an assignment accepts, checked when the submission modal opens:
+//! every submission type with whether it can be done here, the allowed
+//! file extensions and the attempts left.  Shown above the type picker,
+//! and when nothing can be done here it says why and offers the browser.
+
+use super::SubmissionKind;
+use crate::models::Assignment;
+
+/// One submission type the assignment accepts.
+#[derive(Debug, Clone)]
+pub struct PreflightType {
+    pub label: String,
+    /// How it is submitted here, or `None` when it can't be.
+    pub kind: Option<SubmissionKind>,
+    /// Why it can't be submitted here.
+    pub reason: Option<&'static str>,
+    /// It can't be submitted here but can on the Canvas website (unlike
+    /// paper submissions).
+    pub browser: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SubmissionPreflight {
+    pub types: Vec<PreflightType>,
+    /// File extensions uploads must have; empty for any.
+    pub allowed_extensions: Vec<String>,
+    /// Attempts made so far and the most allowed (`None` for unlimited).
+    pub attempts: (u64, Option<u64>),
+}
+
+impl SubmissionPreflight {
+    /// The check for submission types `types` of assignment `a` (when it
+    /// is still loaded).  Discussion replies need the topic's id, which
+    /// Canvas may leave out, so `has_topic` says whether it is known.
+    pub fn new(types: &[String], a: Option<&Assignment>, has_topic: bool) -> Self {
+        let turnitin = a.is_some_and(|a| a.turnitin_enabled == Some(true));
+        let types = types.iter().map(|t| classify(t, turnitin, has_topic)).collect();
+        let Some(a) = a else {
+            return Self { types, ..Self::default() };
+        };
+        let allowed_extensions = a
+            .allowed_extensions
+            .iter()
+            .flatten()
+            .map(|e| e.trim_start_matches('.').to_lowercase())
+            .filter(|e| !e.is_empty())
+            .collect();
+        let used = a.submission.as_ref().and_then(|s| s.attempt).unwrap_or(0);
+        // Canvas uses -1 (or nothing) for unlimited.
+        let allowed = a.allowed_attempts.and_then(|n| u64::try_from(n).ok()).filter(|n| *n > 0);
+        Self { types, allowed_extensions, attempts: (used, allowed) }
+    }
+
+    /// The types that can be submitted here, in the assignment's order.
+    pub fn supported(&self) -> Vec<SubmissionKind> {
+        self.types.iter().filter_map(|t| t.kind.clone()).collect()
+    }
+
+    /// Whether any accepted type has to be done on the Canvas website;
+    /// paper and no-submission assignments don't count.
+    pub fn needs_browser(&self) -> bool {
+        self.types.iter().any(|t| t.browser)
+    }
+
+    /// Every attempt is used up, so Canvas will refuse another.
+    pub fn out_of_attempts(&self) -> bool {
+        matches!(self.attempts, (used, Some(allowed)) if used >= allowed)
+    }
+
+    /// "Attempts: 1 of 3 used", or `None` when they're unlimited.
+    pub fn attempts_line(&self) -> Option<String> {
+        let (used, allowed) = self.attempts;
+        allowed.map(|allowed| format!("Attempts: {used} of {allowed} used"))
+    }
+
+    /// "Files: pdf, docx", or `None` when any file will do.
+    pub fn extensions_line(&self) -> Option<String> {
+        let uploads = self.types.iter().any(|t| t.kind == Some(SubmissionKind::FileUpload));
+        (uploads && !self.allowed_extensions.is_empty())
+            .then(|| format!("Files: {}", self.allowed_extensions.join(", ")))
+    }
+}
+
+fn classify(api_type: &str, turnitin: bool, has_topic: bool) -> PreflightType {
+    let kind = SubmissionKind::from_api_type(api_type)
+        .filter(|k| *k != SubmissionKind::DiscussionReply || has_topic);
+    let (label, reason) = match api_type {
+        "online_text_entry" => ("Text entry", None),
+        "online_url" => ("Website URL", None),
+        "online_upload" => ("File upload", None),
+        "discussion_topic" if has_topic => ("Discussion reply", None),
+        "discussion_topic" => ("Discussion reply", Some("Canvas didn't say which discussion")),
+        "media_recording" => ("Media recording", Some("recorded in the browser")),
+        "student_annotation" => ("Annotation", Some("annotated in the browser")),
+        "online_quiz" => ("Quiz", Some("taken in the browser")),
+        "external_tool" | "basic_lti_launch" if turnitin => {
+            ("External tool", Some("requires Turnitin via external tool"))
+        }
+        "external_tool" | "basic_lti_launch" => {
+            ("External tool", Some("submitted through an external tool"))
+        }
+        "on_paper" => ("On paper", Some("handed in on paper")),
+        "none" => ("No submission", Some("nothing to hand in")),
+        _ => ("", Some("not supported here")),
+    };
+    // Types this build doesn't know go by their API name.
+    let label = if label.is_empty() { api_type.replace('_', " ") } else { label.to_string() };
+    let browser = kind.is_none() && !matches!(api_type, "on_paper" | "none");
+    PreflightType { label, kind, reason, browser }
+}
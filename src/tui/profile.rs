@@ -0,0 +1,60 @@
+//! The profile popup (`U`): who is signed in, to which Canvas, how, and
+//! what the cache holds.  Walking the cache directory can take a moment on
+//! a slow or network disk, so its size is measured in the background each
+//! time the popup opens.
+
+use std::path::Path;
+
+use crate::models::User;
+
+/// How much room the cache takes on disk.
+#[derive(Debug, Clone, Default)]
+pub struct DiskUsage {
+    /// The data cache itself, when it has been saved.
+    pub cache_file: Option<u64>,
+    /// Everything in the cache directory: the cache, the log, estimates,
+    /// sync history and saved bad responses.
+    pub cache_dir: u64,
+    pub files: usize,
+}
+
+/// Measure `cache_file` and everything below `cache_dir`.  Blocks; run it
+/// off the render loop.
+pub fn measure(cache_file: &Path, cache_dir: &Path) -> DiskUsage {
+    let mut usage = DiskUsage {
+        cache_file: std::fs::metadata(cache_file).ok().map(|m| m.len()),
+        ..DiskUsage::default()
+    };
+    let mut dirs = vec![cache_dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            // Not followed through symlinks, which could loop.
+            let Ok(meta) = entry.path().symlink_metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                dirs.push(entry.path());
+            } else {
+                usage.cache_dir += meta.len();
+                usage.files += 1;
+            }
+        }
+    }
+    usage
+}
+
+/// Up to two letters for the header badge: the first letters of the first
+/// and last words of the user's name, or "?" before the profile is in.
+pub fn initials(user: Option<&User>) -> String {
+    let name = user.and_then(|u| u.short_name.as_deref().or(u.name.as_deref())).unwrap_or("");
+    let words: Vec<&str> = name.split_whitespace().collect();
+    let letters: String = match words.as_slice() {
+        [] => return "?".into(),
+        [only] => only.chars().take(1).collect(),
+        [first, .., last] => first.chars().take(1).chain(last.chars().take(1)).collect(),
+    };
+    letters.to_uppercase()
+}
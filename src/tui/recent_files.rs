@@ -0,0 +1,70 @@
+//! The newest files in the `submit_watch_dirs` directories, offered before
+//! typing a path for a file upload.
+
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::config::expand_path;
+
+/// Files listed in the picker, newest first.
+pub const RECENT_FILE_COUNT: usize = 15;
+
+/// Directory entries looked at per directory, so a huge Downloads folder
+/// can't stall the picker.
+const MAX_ENTRIES_PER_DIR: usize = 2000;
+
+#[derive(Debug, Clone)]
+pub struct RecentFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+impl RecentFile {
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+}
+
+/// The most recently modified files directly inside `dirs` (not in
+/// subdirectories), skipping hidden ones.  Directories that can't be read
+/// are reported as messages alongside whatever the others gave.
+pub fn recent_files(dirs: &[String]) -> (Vec<RecentFile>, Vec<String>) {
+    let mut files = Vec::new();
+    let mut errors = Vec::new();
+    for raw in dirs {
+        let entries = expand_path(raw)
+            .map_err(|e| format!("{e:#}"))
+            .and_then(|dir| std::fs::read_dir(&dir).map_err(|e| e.to_string()));
+        let entries = match entries {
+            Ok(entries) => entries,
+            Err(e) => {
+                errors.push(format!("{raw}: {e}"));
+                continue;
+            }
+        };
+        for entry in entries.flatten().take(MAX_ENTRIES_PER_DIR) {
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            // Follows symlinks, so a linked file counts as a file.
+            let Ok(meta) = std::fs::metadata(entry.path()) else {
+                continue;
+            };
+            if !meta.is_file() {
+                continue;
+            }
+            files.push(RecentFile {
+                path: entry.path(),
+                size: meta.len(),
+                modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            });
+        }
+    }
+    files.sort_by_key(|f| std::cmp::Reverse(f.modified));
+    files.truncate(RECENT_FILE_COUNT);
+    (files, errors)
+}
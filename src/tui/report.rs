@@ -0,0 +1,219 @@
+//! A Markdown status report built from the synced data, for
+//! `canvas-tui --report` and the `X` key.  Grades, points and statuses come
+//! from the same helpers as the screens, so the two can't disagree.
+
+use chrono::{DateTime, Local, Utc};
+
+use super::ui::assignment_status;
+use super::{points_label, App};
+use crate::cache::CourseAssignments;
+use crate::models::{Assignment, Course};
+
+/// Statuses `assignment_status` gives published work past its due date
+/// that hasn't been turned in.
+const OVERDUE_STATUSES: [&str; 2] = ["Missing!", "Past due"];
+
+/// The report for `courses` (those of the current term) and their
+/// `assignments` as of `now`.  Courses are ordered by name and assignments
+/// by due date, then name, so reports of unchanged data are identical
+/// apart from the generated time.
+pub fn markdown_report(
+    courses: &[Course],
+    assignments: &[CourseAssignments],
+    synced_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> String {
+    let mut courses: Vec<&Course> = courses
+        .iter()
+        .filter(|c| App::is_current_quarter_course(c))
+        .collect();
+    courses.sort_by_key(|c| (course_title(c).to_lowercase(), c.id));
+
+    let mut out = String::from("# Canvas report\n\n");
+    out.push_str(&format!("Generated {}", format_time(now)));
+    if let Some(synced) = synced_at {
+        out.push_str(&format!(" from data synced {}", format_time(synced)));
+    }
+    out.push_str(".\n");
+
+    let mut overdue: Vec<(&Course, &Assignment, String)> = Vec::new();
+    for course in &courses {
+        let mut list: Vec<&Assignment> = assignments
+            .iter()
+            .filter(|group| group.course_id == course.id)
+            .flat_map(|group| group.assignments.iter())
+            .filter(|a| !a.is_unpublished())
+            .collect();
+        list.sort_by_key(|a| (a.due_at.is_none(), a.due_at, a.name.clone(), a.id));
+
+        out.push_str(&format!("\n## {}\n\n", escape(course_title(course))));
+        let grade = course.current_grade().unwrap_or_else(|| "—".into());
+        out.push_str(&format!("Current grade: {}\n\n", escape(&grade)));
+
+        let upcoming: Vec<&&Assignment> = list
+            .iter()
+            .filter(|a| a.due_at.is_some_and(|d| d >= now))
+            .collect();
+        if upcoming.is_empty() {
+            out.push_str("Nothing upcoming.\n");
+        } else {
+            out.push_str("| Assignment | Due | Points | Status |\n");
+            out.push_str("|---|---|---|---|\n");
+            for a in upcoming {
+                let (status, _) = assignment_status(a);
+                out.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    escape(assignment_title(a)),
+                    a.due_at.map(format_time).unwrap_or_default(),
+                    escape(&points_label(a)),
+                    escape(&status),
+                ));
+            }
+        }
+
+        for a in list {
+            let (status, _) = assignment_status(a);
+            if OVERDUE_STATUSES.contains(&status.as_str()) {
+                overdue.push((course, a, status));
+            }
+        }
+    }
+
+    out.push_str("\n## Missing or overdue\n\n");
+    if overdue.is_empty() {
+        out.push_str("Nothing missing or overdue.\n");
+    } else {
+        out.push_str("| Course | Assignment | Due | Points | Status |\n");
+        out.push_str("|---|---|---|---|---|\n");
+        for (course, a, status) in overdue {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                escape(course_title(course)),
+                escape(assignment_title(a)),
+                a.due_at.map(format_time).unwrap_or_default(),
+                escape(&points_label(a)),
+                escape(&status),
+            ));
+        }
+    }
+    out
+}
+
+fn course_title(course: &Course) -> &str {
+    course.name.as_deref().unwrap_or("Unnamed course")
+}
+
+fn assignment_title(a: &Assignment) -> &str {
+    a.name.as_deref().unwrap_or("Unnamed")
+}
+
+/// Local time to the minute, in a form that sorts and diffs well.
+fn format_time(t: DateTime<Utc>) -> String {
+    t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()
+}
+
+/// Keep Canvas text from breaking a table row or being read as Markdown.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(ch, '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '\\') {
+            out.push('\\');
+        }
+        out.push(if ch == '\n' { ' ' } else { ch });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::tui::test_fixtures::{assert_snapshot, assignment, from_json};
+
+    fn course(id: u64, name: &str, code: &str, grade: Value) -> Course {
+        from_json(json!({
+            "id": id,
+            "name": name,
+            "course_code": code,
+            "enrollments": [{ "type": "student", "computed_current_score": grade }],
+        }))
+    }
+
+    fn group(course_id: u64, assignments: Vec<Assignment>) -> CourseAssignments {
+        CourseAssignments {
+            course_id,
+            name: String::new(),
+            assignments,
+        }
+    }
+
+    /// Due dates far in the past and future, so the statuses, which read
+    /// the clock, don't depend on when the test runs.
+    #[test]
+    fn report_for_a_fixture_matches_the_snapshot() {
+        let now = Utc.with_ymd_and_hms(2025, 4, 1, 12, 0, 0).unwrap();
+        let synced = Utc.with_ymd_and_hms(2025, 4, 1, 11, 30, 0).unwrap();
+        let upcoming = "2099-05-01T23:59:00Z";
+        let later = "2099-06-01T23:59:00Z";
+        let overdue = "2001-03-01T23:59:00Z";
+        let courses = vec![
+            course(2, "Chemistry", "CHEM 110", json!(91.5)),
+            course(1, "Art | History", "ARTH 200", Value::Null),
+            course(3, "Old Course", "FA01 HIST", json!(70.0)),
+        ];
+        let assignments = vec![
+            group(
+                2,
+                vec![
+                    assignment(21, 2, "Final exam", json!({ "due_at": later, "points_possible": 100.0 })),
+                    assignment(22, 2, "Lab *1*", json!({ "due_at": upcoming, "points_possible": 10.0 })),
+                    assignment(
+                        23,
+                        2,
+                        "Problem set",
+                        json!({
+                            "due_at": overdue,
+                            "points_possible": 20.0,
+                            "submission": { "workflow_state": "unsubmitted", "missing": true },
+                        }),
+                    ),
+                    assignment(24, 2, "Draft", json!({ "due_at": upcoming, "published": false })),
+                ],
+            ),
+            group(1, vec![assignment(11, 1, "Reading_notes", json!({ "due_at": overdue }))]),
+            group(3, vec![assignment(31, 3, "Old essay", json!({ "due_at": overdue }))]),
+        ];
+
+        let mut report = markdown_report(&courses, &assignments, Some(synced), now);
+        // Times are in the local zone; name them so the snapshot doesn't
+        // depend on where it runs.
+        for (label, time) in [
+            ("<now>", now),
+            ("<synced>", synced),
+            ("<upcoming>", upcoming.parse().unwrap()),
+            ("<later>", later.parse().unwrap()),
+            ("<overdue>", overdue.parse().unwrap()),
+        ] {
+            report = report.replace(&format_time(time), label);
+        }
+        assert_snapshot("snapshots/report.md", &report);
+    }
+
+    #[test]
+    fn report_without_data_says_so() {
+        let now = Utc.with_ymd_and_hms(2025, 4, 1, 12, 0, 0).unwrap();
+        let courses = [course(1, "Chemistry", "CHEM 110", Value::Null)];
+        let report = markdown_report(&courses, &[], None, now);
+        assert!(report.contains("Current grade: —\n\nNothing upcoming.\n"), "{report}");
+        assert!(report.ends_with("Nothing missing or overdue.\n"), "{report}");
+        assert!(!report.contains("synced"), "{report}");
+    }
+
+    #[test]
+    fn escape_keeps_markdown_and_table_characters_literal() {
+        assert_eq!(escape("a|b *c* [d](e)\nf"), "a\\|b \\*c\\* \\[d\\](e) f");
+        assert_eq!(escape("plain text"), "plain text");
+    }
+}
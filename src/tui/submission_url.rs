@@ -0,0 +1,112 @@
+//! Checking a typed URL before it is submitted.  Canvas takes anything for
+//! an `online_url` submission, including `docs.google.com/...` without a
+//! scheme, which it then stores as a link graders can't open.
+
+use url::Url;
+
+/// Characters that come along around a link copied from a chat app or
+/// a document: angle brackets, straight and curly quotes, backticks.
+const WRAPPERS: [(char, char); 6] =
+    [('<', '>'), ('"', '"'), ('\'', '\''), ('“', '”'), ('‘', '’'), ('`', '`')];
+
+/// `input` as the http(s) URL to submit: trimmed and unwrapped, with
+/// `https://` added when it has no scheme.  Anything else is an error
+/// message for the URL prompt.
+pub fn normalize(input: &str) -> Result<Url, String> {
+    let mut text = input.trim();
+    while let Some(inner) = WRAPPERS.iter().find_map(|(open, close)| {
+        text.strip_prefix(*open).and_then(|t| t.strip_suffix(*close))
+    }) {
+        text = inner.trim();
+    }
+    if text.is_empty() {
+        return Err("Enter a URL.".into());
+    }
+
+    let has_scheme = match Url::parse(text) {
+        // `docs.google.com:443/x` and `localhost:8080` parse with the host
+        // as the scheme; a real scheme has no dot and isn't followed by a
+        // port.
+        Ok(url) => {
+            let after = &text[url.scheme().len() + 1..];
+            !url.scheme().contains('.') && !after.starts_with(|c: char| c.is_ascii_digit())
+        }
+        Err(_) => false,
+    };
+    let url = if has_scheme {
+        Url::parse(text).map_err(|e| format!("Not a valid URL: {e}"))?
+    } else {
+        let url = Url::parse(&format!("https://{text}"))
+            .map_err(|e| format!("Not a valid URL: {e}"))?;
+        // Without a scheme, a single word is more likely a slip than a
+        // host name.
+        let host = url.host_str().unwrap_or_default();
+        if !host.contains('.') && host != "localhost" {
+            return Err(format!("'{text}' doesn't look like a web address."));
+        }
+        url
+    };
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!(
+            "Only http and https links can be submitted, not {}:.",
+            url.scheme()
+        ));
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err("The URL has no host.".into());
+    }
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(input: &str) -> String {
+        normalize(input).unwrap().to_string()
+    }
+
+    fn err(input: &str) -> String {
+        normalize(input).unwrap_err()
+    }
+
+    #[test]
+    fn a_missing_scheme_becomes_https() {
+        assert_eq!(ok("docs.google.com/x"), "https://docs.google.com/x");
+        assert_eq!(ok("docs.google.com:443/x"), "https://docs.google.com/x");
+        assert_eq!(ok("localhost:8080"), "https://localhost:8080/");
+        assert_eq!(ok("http://example.com/a"), "http://example.com/a");
+    }
+
+    #[test]
+    fn wrappers_and_whitespace_are_stripped() {
+        assert_eq!(ok("  https://example.com/a \n"), "https://example.com/a");
+        assert_eq!(ok("<https://example.com/a>"), "https://example.com/a");
+        assert_eq!(ok("“https://example.com/a”"), "https://example.com/a");
+        assert_eq!(ok("\"< `example.com/a` >\""), "https://example.com/a");
+        // Only matching pairs come off.
+        assert!(normalize("“https://example.com/a\"").is_err());
+    }
+
+    #[test]
+    fn other_schemes_are_refused() {
+        assert_eq!(
+            err("ftp://example.com/f"),
+            "Only http and https links can be submitted, not ftp:."
+        );
+        assert_eq!(
+            err("mailto:me@example.com"),
+            "Only http and https links can be submitted, not mailto:."
+        );
+    }
+
+    #[test]
+    fn empty_or_hostless_input_is_refused() {
+        assert_eq!(err(""), "Enter a URL.");
+        assert_eq!(err("   "), "Enter a URL.");
+        assert_eq!(err("<>"), "Enter a URL.");
+        assert_eq!(err("homework"), "'homework' doesn't look like a web address.");
+        assert_eq!(err("http://"), "'http://' doesn't look like a web address.");
+    }
+}
@@ -0,0 +1,122 @@
+//! How the last few syncs went, section by section, for the history view of
+//! the sync stats popup (`S`, then `h`).  Kept in a small file beside the
+//! cache so failed syncs, which never save the cache, are remembered too.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+
+/// Syncs remembered, newest first.
+pub const SYNC_HISTORY_LEN: usize = 20;
+
+/// How one section of a sync went.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SectionOutcome {
+    Ok,
+    Failed,
+    /// Not fetched: a retry that left it out, or a sync that stopped early.
+    #[default]
+    Skipped,
+}
+
+/// Each section of a sync, in the order they run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SectionOutcomes {
+    pub profile: SectionOutcome,
+    pub courses: SectionOutcome,
+    /// Failed when any course's assignments did.
+    pub assignments: SectionOutcome,
+    pub calendar: SectionOutcome,
+    pub announcements: SectionOutcome,
+}
+
+impl SectionOutcomes {
+    /// With short names, for the history table's column headers.
+    pub fn named(&self) -> [(&'static str, SectionOutcome); 5] {
+        [
+            ("Prof", self.profile),
+            ("Crs", self.courses),
+            ("Asgn", self.assignments),
+            ("Cal", self.calendar),
+            ("Ann", self.announcements),
+        ]
+    }
+}
+
+/// One finished sync.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncRecord {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub sections: SectionOutcomes,
+    /// Items held after the sync (older data included for failed sections).
+    #[serde(default)]
+    pub courses: usize,
+    #[serde(default)]
+    pub assignments: usize,
+    #[serde(default)]
+    pub events: usize,
+    #[serde(default)]
+    pub announcements: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SyncRecord {
+    /// Canvas couldn't be reached at all: the sync stopped before the
+    /// course list.
+    pub fn failed(&self) -> bool {
+        self.sections.profile == SectionOutcome::Failed
+            || self.sections.courses == SectionOutcome::Failed
+    }
+}
+
+/// The last `SYNC_HISTORY_LEN` syncs, newest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SyncHistory {
+    records: VecDeque<SyncRecord>,
+}
+
+impl SyncHistory {
+    pub fn push(&mut self, record: SyncRecord) {
+        self.records.push_front(record);
+        self.records.truncate(SYNC_HISTORY_LEN);
+    }
+
+    /// Newest first.
+    pub fn records(&self) -> impl Iterator<Item = &SyncRecord> {
+        self.records.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Syncs in a row, counting back from the newest, that couldn't reach
+    /// Canvas.
+    pub fn consecutive_failures(&self) -> usize {
+        self.records.iter().take_while(|r| r.failed()).count()
+    }
+}
+
+/// The history in `path`.  A missing or unreadable file is an empty history.
+pub fn load_sync_history(path: &Path) -> SyncHistory {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_sync_history(path: &Path, history: &SyncHistory) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(history)?)?;
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! Wall-clock timing of a background sync, phase by phase, for the sync
+//! stats popup (`S`) and the log.
+
+use std::time::{Duration, Instant};
+
+/// One step of `fetch_canvas_data`.
+#[derive(Debug, Clone)]
+pub struct PhaseTiming {
+    pub name: String,
+    pub duration: Duration,
+    /// HTTP requests made during the phase (pagination included).
+    pub requests: u64,
+}
+
+/// The timings of a finished sync.
+#[derive(Debug, Clone, Default)]
+pub struct SyncStats {
+    /// In the order the phases ran.
+    pub phases: Vec<PhaseTiming>,
+    pub total: Duration,
+    pub requests: u64,
+}
+
+impl SyncStats {
+    /// Phases ordered slowest first.
+    pub fn slowest(&self) -> Vec<&PhaseTiming> {
+        let mut phases: Vec<_> = self.phases.iter().collect();
+        phases.sort_by_key(|p| std::cmp::Reverse(p.duration));
+        phases
+    }
+
+    /// One-line summary for the log.
+    pub fn summary(&self) -> String {
+        let slowest = self
+            .slowest()
+            .iter()
+            .take(3)
+            .map(|p| format!("{} {}", p.name, format_duration(p.duration)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "sync took {} over {} requests; slowest: {slowest}",
+            format_duration(self.total),
+            self.requests
+        )
+    }
+}
+
+/// Accumulates phase timings as laps: each `lap` closes the phase that
+/// started at the previous lap (or at `start`).  Request counts come from
+/// the caller as a running total, so the timer doesn't need the client.
+/// Phases that ran concurrently are timed on their own and `record`ed.
+pub struct SyncTimer {
+    started: Instant,
+    lap_started: Instant,
+    lap_requests: u64,
+    phases: Vec<PhaseTiming>,
+}
+
+impl SyncTimer {
+    pub fn start(requests: u64) -> Self {
+        Self::start_at(Instant::now(), requests)
+    }
+
+    /// `start` with an explicit clock reading.
+    pub fn start_at(now: Instant, requests: u64) -> Self {
+        Self {
+            started: now,
+            lap_started: now,
+            lap_requests: requests,
+            phases: Vec::new(),
+        }
+    }
+
+    /// End the current phase, naming it `name`.
+    pub fn lap(&mut self, name: impl Into<String>, requests: u64) {
+        self.lap_at(Instant::now(), name, requests);
+    }
+
+    /// `lap` with an explicit clock reading.
+    pub fn lap_at(&mut self, now: Instant, name: impl Into<String>, requests: u64) {
+        self.phases.push(PhaseTiming {
+            name: name.into(),
+            duration: now.saturating_duration_since(self.lap_started),
+            requests: requests.saturating_sub(self.lap_requests),
+        });
+        self.lap_started = now;
+        self.lap_requests = requests;
+    }
+
+    /// Add a phase timed separately, e.g. one that overlapped others.
+    /// Laps are unaffected: the next one still starts at the previous lap.
+    pub fn record(&mut self, phase: PhaseTiming) {
+        self.phases.push(phase);
+    }
+
+    pub fn finish(self) -> SyncStats {
+        self.finish_at(Instant::now())
+    }
+
+    /// `finish` with an explicit clock reading.
+    pub fn finish_at(self, now: Instant) -> SyncStats {
+        SyncStats {
+            total: now.saturating_duration_since(self.started),
+            requests: self.phases.iter().map(|p| p.requests).sum(),
+            phases: self.phases,
+        }
+    }
+}
+
+/// "850ms" or "2.4s".
+pub fn format_duration(d: Duration) -> String {
+    if d < Duration::from_secs(1) {
+        format!("{}ms", d.as_millis())
+    } else {
+        format!("{:.1}s", d.as_secs_f64())
+    }
+}
@@ -0,0 +1,65 @@
+//! Shared setup for the tests in `tui`: an `App` whose client talks to a
+//! test server, and models built from JSON as Canvas would send it.
+
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::App;
+use crate::api::CanvasClient;
+use crate::config::Paths;
+use crate::models::{Assignment, Course};
+
+/// Paths that keep everything under `dir`.
+pub fn paths(dir: &Path) -> Paths {
+    Paths {
+        cache_dir: dir.join("cache"),
+        download_dir: dir.join("downloads"),
+        as_user_id: None,
+        canvas_host: Some("127.0.0.1".into()),
+    }
+}
+
+/// An app with no data, signed in to `base_url`, keeping its files in `dir`.
+pub fn app(base_url: &str, dir: &Path) -> App {
+    let client = CanvasClient::new(base_url, "test-token").unwrap();
+    App::new(client, paths(dir))
+}
+
+/// An app for tests that make no requests.
+pub fn offline_app(dir: &Path) -> App {
+    app("http://127.0.0.1:9", dir)
+}
+
+/// `value` decoded the way a Canvas response would be.
+pub fn from_json<T: DeserializeOwned>(value: Value) -> T {
+    serde_json::from_value(value).unwrap()
+}
+
+pub fn course(id: u64, name: &str) -> Course {
+    from_json(serde_json::json!({ "id": id, "name": name, "course_code": name }))
+}
+
+/// An assignment in `course_id`; `extra` adds or overrides fields.
+pub fn assignment(id: u64, course_id: u64, name: &str, extra: Value) -> Assignment {
+    let mut value = serde_json::json!({ "id": id, "course_id": course_id, "name": name });
+    if let (Some(value), Value::Object(extra)) = (value.as_object_mut(), extra) {
+        value.extend(extra);
+    }
+    from_json(value)
+}
+
+/// Compare `actual` with the file at `path` under `src/tui`.  With
+/// `UPDATE_SNAPSHOTS=1` set, write it there instead.
+pub fn assert_snapshot(path: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/tui").join(path);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("{}: {e} (run with UPDATE_SNAPSHOTS=1)", path.display()));
+    assert!(actual == expected, "{} differs:\n{actual}", path.display());
+}
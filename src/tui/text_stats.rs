@@ -0,0 +1,92 @@
+/// Word and character counts of a text submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextStats {
+    pub words: usize,
+    pub chars: usize,
+}
+
+pub fn text_stats(text: &str) -> TextStats {
+    TextStats {
+        words: text.split_whitespace().count(),
+        chars: text.trim_end().chars().count(),
+    }
+}
+
+/// Left-over placeholder text, compared lowercased.
+const PLACEHOLDERS: &[&str] = &[
+    "lorem ipsum",
+    "your answer here",
+    "type your answer",
+    "[insert",
+    "<insert",
+    "todo:",
+];
+
+/// Likely mistakes in a text submission, worded for the preview.  They are
+/// only warnings: the text can still be submitted.
+pub fn text_warnings(text: &str) -> Vec<&'static str> {
+    let mut warnings = Vec::new();
+    let lower = text.to_lowercase();
+    if PLACEHOLDERS.iter().any(|p| lower.contains(p)) {
+        warnings.push("Still contains placeholder text (lorem ipsum, [insert …], TODO:)");
+    }
+    // Smart quotes saved as Windows-1252 and read back as UTF-8 (or the
+    // other way round) show up as "â€œ" or stray C1 control characters,
+    // and Canvas would store them escaped as they are.
+    if text.contains("â€") || text.chars().any(|c| ('\u{80}'..='\u{9f}').contains(&c)) {
+        warnings.push("Contains garbled smart quotes (Windows-1252 text); re-save as UTF-8");
+    }
+    let tail = &text[text.trim_end().len()..];
+    if !matches!(tail, "" | "\n" | "\r\n") {
+        warnings.push("Ends with blank lines or trailing whitespace");
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_words_and_characters() {
+        assert_eq!(text_stats(""), TextStats { words: 0, chars: 0 });
+        assert_eq!(text_stats("  \n\t "), TextStats { words: 0, chars: 0 });
+        // Trailing whitespace is not counted; leading whitespace is.
+        assert_eq!(text_stats("  hi  \n"), TextStats { words: 1, chars: 4 });
+        assert_eq!(text_stats("One two\nthree\n"), TextStats { words: 3, chars: 13 });
+        // Characters, not bytes.
+        assert_eq!(text_stats("naïve café"), TextStats { words: 2, chars: 10 });
+    }
+
+    #[test]
+    fn clean_text_has_no_warnings() {
+        assert!(text_warnings("A finished answer.\n").is_empty());
+        assert!(text_warnings("A finished answer.\r\n").is_empty());
+        assert!(text_warnings("No newline at all").is_empty());
+        assert!(text_warnings("“Curly” quotes are fine.\n").is_empty());
+    }
+
+    #[test]
+    fn placeholders_are_flagged_in_any_case() {
+        for text in ["Lorem ipsum dolor", "[Insert citation]", "TODO: conclusion", "Your answer here"] {
+            assert_eq!(text_warnings(text).len(), 1, "{text}");
+        }
+    }
+
+    #[test]
+    fn garbled_smart_quotes_are_flagged() {
+        assert_eq!(text_warnings("He said â€œhiâ€\u{9d}").len(), 1);
+        assert_eq!(text_warnings("stray \u{93}control\u{94}").len(), 1);
+    }
+
+    #[test]
+    fn trailing_blank_lines_are_flagged() {
+        for text in ["done\n\n", "done  ", "done\n \n", "done\t"] {
+            assert_eq!(
+                text_warnings(text),
+                ["Ends with blank lines or trailing whitespace"],
+                "{text:?}"
+            );
+        }
+    }
+}
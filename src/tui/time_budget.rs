@@ -0,0 +1,202 @@
+//! Effort estimates for assignments, kept in a local file and never sent
+//! to Canvas, and the hours of work they add up to day by day.
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Default for the `estimate_default_hours` config key: the effort of an
+/// assignment without an estimate of its own.
+pub const DEFAULT_ESTIMATE_HOURS: f32 = 2.0;
+
+/// Default for the `daily_capacity_hours` config key.
+pub const DEFAULT_DAILY_CAPACITY_HOURS: f32 = 4.0;
+
+/// Days the dashboard's time budget looks ahead, today included.
+pub const BUDGET_DAYS: usize = 7;
+
+/// Deadlines this long after midnight are worked on the evening before.
+const WORK_DAY_CUTOFF_HOURS: i64 = 3;
+
+/// The day the work for a deadline at `due` is done, in `tz`: the date of
+/// the deadline, except that one before 03:00 counts for the day before
+/// (an assignment due at 00:30 is the previous evening's work).
+pub fn work_day<Tz: TimeZone>(due: DateTime<Utc>, tz: &Tz) -> NaiveDate {
+    (due.with_timezone(tz) - chrono::Duration::hours(WORK_DAY_CUTOFF_HOURS)).date_naive()
+}
+
+/// Hours of work per day for the `days` days from `start`, from
+/// `(deadline, hours)` pairs.  Each deadline's hours go to its `work_day`;
+/// a work day before `start` (tonight's 00:30 deadline, seen after
+/// midnight) counts for `start`, and later ones past the window are left
+/// out.
+pub fn daily_hours<Tz: TimeZone>(
+    items: impl IntoIterator<Item = (DateTime<Utc>, f32)>,
+    tz: &Tz,
+    start: NaiveDate,
+    days: usize,
+) -> Vec<f32> {
+    let mut totals = vec![0.0; days];
+    for (due, hours) in items {
+        let day = work_day(due, tz).max(start);
+        let offset = (day - start).num_days() as usize;
+        if let Some(total) = totals.get_mut(offset) {
+            *total += hours;
+        }
+    }
+    totals
+}
+
+/// Parse a typed estimate: hours ("2", "1.5", "2h") or minutes ("45m").
+/// Empty input is `None`, for going back to the default.
+pub fn parse_estimate(input: &str) -> Result<Option<f32>, String> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    let (number, per_hour) = match input.strip_suffix('m') {
+        Some(minutes) => (minutes, 60.0),
+        None => (input.strip_suffix('h').unwrap_or(&input), 1.0),
+    };
+    match number.trim().parse::<f32>() {
+        Ok(n) if n.is_finite() && n > 0.0 && n / per_hour <= 200.0 => Ok(Some(n / per_hour)),
+        _ => Err(format!("Not a number of hours: '{input}' (try 2, 1.5h or 45m)")),
+    }
+}
+
+/// "2h", "1.5h", "0.5h".
+pub fn format_hours(hours: f32) -> String {
+    if (hours - hours.round()).abs() < 0.05 {
+        format!("{:.0}h", hours.round())
+    } else {
+        format!("{hours:.1}h")
+    }
+}
+
+// ─── Storage ─────────────────────────────────────────────────────────────────
+
+/// Estimates by assignment id.  A missing or unreadable file is an empty
+/// set of estimates.
+pub fn load_estimates(path: &Path) -> BTreeMap<u64, f32> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_estimates(path: &Path, estimates: &BTreeMap<u64, f32>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(estimates)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn work_day_moves_early_morning_deadlines_to_the_evening_before() {
+        assert_eq!(work_day(utc("2025-04-10T23:59:00Z"), &Utc), date("2025-04-10"));
+        assert_eq!(work_day(utc("2025-04-10T03:00:00Z"), &Utc), date("2025-04-10"));
+        assert_eq!(work_day(utc("2025-04-10T02:59:00Z"), &Utc), date("2025-04-09"));
+        assert_eq!(work_day(utc("2025-04-10T00:00:00Z"), &Utc), date("2025-04-09"));
+    }
+
+    #[test]
+    fn work_day_is_taken_in_the_given_time_zone() {
+        let new_york = FixedOffset::west_opt(4 * 3600).unwrap();
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        // 03:59 UTC on the 10th is 23:59 on the 9th in New York.
+        let due = utc("2025-04-10T03:59:00Z");
+        assert_eq!(work_day(due, &Utc), date("2025-04-10"));
+        assert_eq!(work_day(due, &new_york), date("2025-04-09"));
+        // ...and 12:59 on the 10th in Tokyo.
+        assert_eq!(work_day(due, &tokyo), date("2025-04-10"));
+        // 15:30 UTC is 00:30 the next day in Tokyo: that evening's work.
+        assert_eq!(work_day(utc("2025-04-10T15:30:00Z"), &tokyo), date("2025-04-10"));
+        // 18:00 UTC is 03:00 in Tokyo: the new day.
+        assert_eq!(work_day(utc("2025-04-10T18:00:00Z"), &tokyo), date("2025-04-11"));
+    }
+
+    #[test]
+    fn daily_hours_adds_up_each_work_day() {
+        let items = [
+            (utc("2025-04-10T23:59:00Z"), 2.0),
+            (utc("2025-04-11T00:30:00Z"), 1.5), // the 10th's evening
+            (utc("2025-04-12T12:00:00Z"), 1.0),
+            (utc("2025-04-16T12:00:00Z"), 4.0), // last day of the window
+            (utc("2025-04-17T12:00:00Z"), 8.0), // past it
+        ];
+        assert_eq!(
+            daily_hours(items, &Utc, date("2025-04-10"), BUDGET_DAYS),
+            [3.5, 0.0, 1.0, 0.0, 0.0, 0.0, 4.0]
+        );
+    }
+
+    #[test]
+    fn daily_hours_counts_overdue_work_for_the_first_day() {
+        let items = [
+            (utc("2025-04-10T01:00:00Z"), 1.0), // tonight's deadline, seen after midnight
+            (utc("2025-04-01T12:00:00Z"), 2.0),
+        ];
+        assert_eq!(daily_hours(items, &Utc, date("2025-04-10"), 2), [3.0, 0.0]);
+    }
+
+    #[test]
+    fn daily_hours_across_a_time_zone_boundary() {
+        let tokyo = FixedOffset::east_opt(9 * 3600).unwrap();
+        // Both due on the 10th in UTC, but 15:00 UTC is midnight in Tokyo.
+        let items = [(utc("2025-04-10T14:00:00Z"), 1.0), (utc("2025-04-10T18:30:00Z"), 2.0)];
+        assert_eq!(daily_hours(items, &Utc, date("2025-04-10"), 2), [3.0, 0.0]);
+        assert_eq!(daily_hours(items, &tokyo, date("2025-04-10"), 2), [1.0, 2.0]);
+    }
+
+    #[test]
+    fn daily_hours_with_no_days_or_items() {
+        assert!(daily_hours([(utc("2025-04-10T12:00:00Z"), 1.0)], &Utc, date("2025-04-10"), 0).is_empty());
+        assert_eq!(daily_hours([], &Utc, date("2025-04-10"), 3), [0.0; 3]);
+    }
+
+    #[test]
+    fn estimates_parse_as_hours_or_minutes() {
+        assert_eq!(parse_estimate("2"), Ok(Some(2.0)));
+        assert_eq!(parse_estimate(" 1.5H "), Ok(Some(1.5)));
+        assert_eq!(parse_estimate("45m"), Ok(Some(0.75)));
+        assert_eq!(parse_estimate(""), Ok(None));
+        for bad in ["0", "-1", "abc", "NaN", "inf", "201", "12001m"] {
+            assert!(parse_estimate(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn hours_are_formatted_to_one_decimal() {
+        assert_eq!(format_hours(2.0), "2h");
+        assert_eq!(format_hours(1.96), "2h");
+        assert_eq!(format_hours(1.5), "1.5h");
+        assert_eq!(format_hours(0.3), "0.3h");
+    }
+
+    #[test]
+    fn estimates_round_trip_and_bad_files_are_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("estimates.json");
+        assert!(load_estimates(&path).is_empty());
+        let estimates = BTreeMap::from([(10, 1.5), (20, 0.75)]);
+        save_estimates(&path, &estimates).unwrap();
+        assert_eq!(load_estimates(&path), estimates);
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load_estimates(&path).is_empty());
+    }
+}
@@ -0,0 +1,220 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use super::components::{
+    course_stripe, detail_border, detail_scroll, format_size, selectable_row, strip_html, RowStyle,
+};
+use super::{AMBER, AMBER_SOFT, DANGER, TEXT, TEXT_DIM, TEXT_MUTED};
+use crate::models::DiscussionTopic;
+use crate::tui::App;
+
+// ─── Announcements ───────────────────────────────────────────────────────────
+
+pub(super) fn render_announcements(f: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(38), Constraint::Percentage(62)])
+        .split(area);
+
+    let list = app.announcement_list();
+    let selected = app.announcement_list_state.selected;
+    // Archived announcements are listed dimmed, without the unread dot.
+    let row = |i: usize, ann: &DiscussionTopic, archived: bool| {
+        let title = ann.title.clone().unwrap_or_else(|| "Untitled".into());
+        // "CS 101 · Jane Doe"; institution notices have no author.
+        let (source, author) = match (app.announcement_source(ann), ann.user_name.as_deref()) {
+            (Some(source), Some(author)) => (format!("{source} · "), author.to_string()),
+            (Some(source), None) => (source, String::new()),
+            (None, author) => (String::new(), author.unwrap_or("Unknown").to_string()),
+        };
+        let date = ann
+            .posted_at
+            .map(|d| d.format("%b %d").to_string())
+            .unwrap_or_default();
+
+        let is_unread = ann.read_state.as_deref() == Some("unread") && !archived;
+        let is_selected = i == selected;
+        let RowStyle { marker, marker_fg, bg } = selectable_row(is_selected, false);
+        let course_id = ann
+            .context_code
+            .as_deref()
+            .and_then(|c| c.strip_prefix("course_"))
+            .and_then(|id| id.parse().ok());
+
+        let title_style = if is_unread {
+            Style::default().fg(TEXT).bg(bg).add_modifier(Modifier::BOLD)
+        } else if archived {
+            Style::default().fg(TEXT_MUTED).bg(bg)
+        } else {
+            Style::default().fg(TEXT_DIM).bg(bg)
+        };
+
+        ListItem::new(vec![
+            Line::from(vec![
+                course_stripe(app, course_id, bg),
+                Span::styled(format!("{} ", marker), Style::default().fg(marker_fg).bg(bg)),
+                if is_unread {
+                    Span::styled("● ", Style::default().fg(DANGER).bg(bg))
+                } else {
+                    Span::styled("  ", Style::default().bg(bg))
+                },
+                Span::styled(title, title_style),
+            ]),
+            Line::from(vec![
+                Span::styled("      ", Style::default().bg(bg)),
+                Span::styled(source, Style::default().fg(AMBER_SOFT).bg(bg)),
+                Span::styled(author, Style::default().fg(TEXT_MUTED).bg(bg)),
+                Span::styled(format!("  {date}"), Style::default().fg(TEXT_MUTED).bg(bg)),
+            ]),
+        ])
+    };
+
+    let mut items: Vec<ListItem> = list
+        .active
+        .iter()
+        .enumerate()
+        .map(|(i, ann)| row(i, ann, false))
+        .collect();
+    if let Some(more) = list.more_row() {
+        let older = app.older_announcements.as_ref().map(|l| (l.is_loading(), l.error()));
+        let (text, color) = match older {
+            Some((true, _)) => ("loading older announcements…".to_string(), TEXT_MUTED),
+            Some((false, Some(e))) => (format!("couldn't load older announcements: {e} (Enter to retry)"), DANGER),
+            _ => ("load older announcements".to_string(), TEXT_DIM),
+        };
+        let RowStyle { marker, bg, .. } = selectable_row(selected == more, false);
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(format!(" {marker} "), Style::default().fg(AMBER).bg(bg)),
+            Span::styled(text, Style::default().fg(color).bg(bg)),
+        ])));
+    }
+    if let Some(fold) = list.fold_row() {
+        let text = if list.expanded {
+            format!("▴ hide archived ({})", list.archived.len())
+        } else {
+            format!("show archived ({})", list.archived.len())
+        };
+        let RowStyle { marker, bg, .. } = selectable_row(selected == fold, false);
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(format!(" {marker} "), Style::default().fg(AMBER).bg(bg)),
+            Span::styled(text, Style::default().fg(TEXT_DIM).bg(bg)),
+        ])));
+        if list.expanded {
+            items.extend(list.archived.iter().enumerate().map(|(i, ann)| row(fold + 1 + i, ann, true)));
+        }
+    }
+    let active = list.active.len();
+    let unread = app.unread_announcements_shown();
+    let title = if unread > 0 {
+        format!(" Announcements ({active} · {unread} unread) ")
+    } else {
+        format!(" Announcements ({active}) ")
+    };
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(TEXT_MUTED))
+            .title(title)
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD)),
+    );
+
+    app.announcement_list_state
+        .inner
+        .select(Some(app.announcement_list_state.selected));
+    f.render_stateful_widget(list, chunks[0], &mut app.announcement_list_state.inner);
+
+    let detail = if let Some(ann) = app.selected_announcement() {
+        let title = ann.title.as_deref().unwrap_or("Untitled");
+        let author = match (&ann.user_name, app.announcement_source(ann)) {
+            (Some(name), _) => name.clone(),
+            (None, Some(source)) => source,
+            (None, None) => "Unknown".into(),
+        };
+        let date = ann
+            .posted_at
+            .map(|d| d.format("%B %d, %Y at %H:%M").to_string())
+            .unwrap_or_default();
+        let body = strip_html(ann.message.as_deref().unwrap_or("(no content)"));
+
+        let mut lines = vec![
+            Line::from(Span::styled(
+                title,
+                Style::default().fg(TEXT).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                format!("by {author}  ─  {date}"),
+                Style::default().fg(TEXT_MUTED),
+            )),
+            Line::from(""),
+        ];
+
+        let attachments = ann.attachments.as_deref().unwrap_or(&[]);
+        if !attachments.is_empty() {
+            let selected = app.announcement_attachment.min(attachments.len() - 1);
+            lines.push(Line::from(Span::styled(
+                format!("Attachments ({})", attachments.len()),
+                Style::default().fg(AMBER_SOFT),
+            )));
+            for (i, file) in attachments.iter().enumerate() {
+                let is_selected = i == selected;
+                let size = file.size.map(format_size).unwrap_or_default();
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        if is_selected { " ▶ " } else { "   " },
+                        Style::default().fg(AMBER),
+                    ),
+                    Span::styled(
+                        file.name().to_string(),
+                        Style::default().fg(if is_selected { TEXT } else { TEXT_DIM }),
+                    ),
+                    Span::styled(format!("  {size}"), Style::default().fg(TEXT_MUTED)),
+                ]));
+            }
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(body, Style::default().fg(TEXT_DIM))));
+
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(detail_border(app))
+            .title(" Detail ")
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD));
+        if !attachments.is_empty() {
+            block = block.title_bottom(Line::from(vec![
+                Span::styled(" [ ]", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" select attachment  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("d", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" download ", Style::default().fg(TEXT_DIM)),
+            ]));
+        }
+
+        let scroll = detail_scroll(app, &mut lines, chunks[1]);
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .scroll(scroll)
+            .block(block)
+    } else {
+        Paragraph::new(Line::from(Span::styled(
+            "  Select an announcement to view details.",
+            Style::default().fg(TEXT_DIM),
+        )))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(detail_border(app))
+                .title(" Detail ")
+                .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD)),
+        )
+    };
+
+    f.render_widget(detail, chunks[1]);
+}
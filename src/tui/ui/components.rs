@@ -0,0 +1,901 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::{
+    spinner_char, AMBER, AMBER_SOFT, CAUTION, DANGER, FOCAL, FOCAL_BG, INFO, SEL_BG, SUCCESS,
+    TEXT, TEXT_DIM, TEXT_MUTED,
+};
+use crate::models::Assignment;
+use crate::tui::links::{extract_links, Link};
+use crate::tui::time_budget::format_hours;
+use crate::tui::{format_score, points_label, score_label, App, DescriptionLoad, Focus};
+use chrono::{Local, Utc};
+use unicode_width::UnicodeWidthStr;
+
+// ─── Lists ───────────────────────────────────────────────────────────────────
+
+/// How a list row is drawn: its selection marker, the marker's color and
+/// the row background.
+pub(super) struct RowStyle {
+    pub marker: &'static str,
+    pub marker_fg: Color,
+    pub bg: Color,
+}
+
+/// The look of a list row: "▶" on the selection background when selected,
+/// "»" on the focal background for the next thing to do, else plain.
+pub(super) fn selectable_row(is_selected: bool, is_focal: bool) -> RowStyle {
+    if is_selected {
+        RowStyle { marker: "▶", marker_fg: AMBER, bg: SEL_BG }
+    } else if is_focal {
+        RowStyle { marker: "»", marker_fg: FOCAL, bg: FOCAL_BG }
+    } else {
+        RowStyle { marker: " ", marker_fg: TEXT_MUTED, bg: Color::Reset }
+    }
+}
+
+/// The stripe at a list row's left edge, in its course's color, taking
+/// the place of the space before the selection marker.
+pub(super) fn course_stripe(app: &App, course_id: Option<u64>, bg: Color) -> Span<'static> {
+    let (glyph, color) = app.course_stripe(course_id);
+    Span::styled(glyph, Style::default().fg(color).bg(bg))
+}
+
+/// An assignment's status for lists and detail panes: the grade, whether
+/// it is submitted, missing or past due, or when it opens.
+pub(in crate::tui) fn assignment_status(a: &Assignment) -> (String, Color) {
+    let now = Utc::now();
+    if let Some(unlock) = a.locked_until(now) {
+        return super::opens_timer(unlock, now);
+    }
+    // Nobody is expected to have submitted an unpublished assignment.
+    let past_due = !a.is_unpublished() && a.due_at.is_some_and(|d| d < now);
+    if let Some(ref sub) = a.submission {
+        match sub.workflow_state.as_deref() {
+            Some("graded") => {
+                let grade = format_score(a, sub.score, sub.grade.as_deref())
+                    .unwrap_or_else(|| "Graded".into());
+                (grade, SUCCESS)
+            }
+            Some("submitted") => ("Submitted".into(), INFO),
+            _ => {
+                if past_due {
+                    if sub.missing.unwrap_or(false) {
+                        ("Missing!".into(), DANGER)
+                    } else {
+                        ("Past due".into(), CAUTION)
+                    }
+                } else {
+                    ("Not submitted".into(), TEXT_DIM)
+                }
+            }
+        }
+    } else if past_due {
+        ("Past due".into(), CAUTION)
+    } else {
+        ("─".into(), TEXT_MUTED)
+    }
+}
+
+/// The "● " badge for an assignment that turned up in the last sync or the
+/// last day, else nothing.
+pub(super) fn new_badge(app: &App, a: &Assignment, bg: Color) -> Span<'static> {
+    if app.is_newly_seen(a.id) {
+        Span::styled("● ", Style::default().fg(SUCCESS).bg(bg))
+    } else {
+        Span::styled("", Style::default().bg(bg))
+    }
+}
+
+/// An assignment's name for list rows, tagged when it is unpublished.
+pub(super) fn assignment_row_name(a: &Assignment) -> String {
+    let name = a.name.as_deref().unwrap_or("Unnamed");
+    if a.is_unpublished() {
+        format!("{name} (unpublished)")
+    } else {
+        name.to_string()
+    }
+}
+
+/// "  filter: N courses" when the course filter is active, else empty.
+pub(super) fn filter_hint(app: &App) -> String {
+    if app.course_filter_none {
+        "  filter: no courses".to_string()
+    } else if app.course_filter.is_empty() {
+        String::new()
+    } else {
+        format!("  filter: {} course{}", app.course_filter.len(),
+            if app.course_filter.len() == 1 { "" } else { "s" })
+    }
+}
+
+// ─── Popups ──────────────────────────────────────────────────────────────────
+
+/// A `width` × `height` rectangle centred in `area`, shrunk to fit.
+pub(super) fn popup_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let w = width.min(area.width.saturating_sub(4));
+    let h = height.min(area.height.saturating_sub(2));
+    Rect::new(
+        area.x + (area.width.saturating_sub(w)) / 2,
+        area.y + (area.height.saturating_sub(h)) / 2,
+        w,
+        h,
+    )
+}
+
+// ─── Detail panes ────────────────────────────────────────────────────────────
+
+/// Border of a detail pane: amber while it has keyboard focus.
+pub(super) fn detail_border(app: &App) -> Style {
+    if app.focus() == Focus::Detail {
+        Style::default().fg(AMBER)
+    } else {
+        Style::default().fg(TEXT_MUTED)
+    }
+}
+
+/// Scroll offset for a detail pane showing `lines` wrapped in `area`.
+/// Records how far the pane can scroll so `j` stops at the last line; the
+/// wrapped height is estimated by display width, which is close enough.
+pub(super) fn detail_scroll(app: &App, lines: &mut [Line<'_>], area: Rect) -> (u16, u16) {
+    let width = usize::from(area.width.saturating_sub(2)).max(1);
+    let height = usize::from(area.height.saturating_sub(2));
+    let mut text = Vec::with_capacity(lines.len());
+    let mut rows = 0;
+    for line in lines.iter() {
+        text.push((plain_text(line), rows));
+        rows += line.width().div_ceil(width).max(1);
+    }
+    *app.detail_text.borrow_mut() = crate::tui::DetailText { lines: text, height };
+
+    // Copy mode: the selected lines on the selection background.
+    if let Some(selection) = app.copy_selection {
+        for line in lines.iter_mut().take(selection.range().end() + 1).skip(*selection.range().start()) {
+            line.style = line.style.bg(SEL_BG);
+            for span in &mut line.spans {
+                span.style = span.style.bg(SEL_BG);
+            }
+        }
+    }
+
+    let max = rows.saturating_sub(height);
+    let max = max.min(usize::from(u16::MAX)) as u16;
+    app.detail_scroll_max.set(max);
+    (app.detail_scroll.min(max), 0)
+}
+
+/// A line's text without its styling, trailing spaces dropped.
+fn plain_text(line: &Line<'_>) -> String {
+    let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+    text.trim_end().to_string()
+}
+
+/// A detail pane's frame, titled `title`.
+pub(super) fn detail_block<'a>(app: &App, title: impl Into<Line<'a>>) -> Block<'a> {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(detail_border(app))
+        .title(title)
+        .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD));
+    if app.copy_selection.is_none() {
+        return block;
+    }
+    block.title_bottom(Line::from(vec![
+        Span::styled(" j/k ", Style::default().fg(AMBER_SOFT)),
+        Span::styled("select  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("y ", Style::default().fg(AMBER_SOFT)),
+        Span::styled("copy  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("Esc ", Style::default().fg(AMBER_SOFT)),
+        Span::styled("cancel ", Style::default().fg(TEXT_DIM)),
+    ]))
+}
+
+/// A detail pane holding just a dimmed `hint`, for when nothing is selected.
+pub(super) fn render_detail_hint(f: &mut Frame, area: Rect, block: Block<'_>, hint: &str) {
+    f.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            hint.to_string(),
+            Style::default().fg(TEXT_DIM),
+        )))
+        .block(block),
+        area,
+    );
+}
+
+/// One line per field: the label in a ten-column amber gutter, then the
+/// value in its own style.
+pub(super) fn field_rows(fields: &[(&str, String, Style)]) -> Vec<Line<'static>> {
+    fields
+        .iter()
+        .map(|(label, value, style)| {
+            Line::from(vec![
+                Span::styled(format!("  {label:<10}"), Style::default().fg(AMBER_SOFT)),
+                Span::styled(value.clone(), *style),
+            ])
+        })
+        .collect()
+}
+
+/// `field_rows` with the stripe of course `course_id` before the value of
+/// the "Course" field.
+pub(super) fn course_field_rows(
+    app: &App,
+    course_id: Option<u64>,
+    fields: &[(&str, String, Style)],
+) -> Vec<Line<'static>> {
+    let mut rows = field_rows(fields);
+    if let Some(i) = fields.iter().position(|(label, ..)| *label == "Course") {
+        let (glyph, color) = app.course_stripe(course_id);
+        rows[i].spans.insert(1, Span::styled(format!("{glyph} "), Style::default().fg(color)));
+    }
+    rows
+}
+
+/// Which rows an assignment detail pane shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DetailRows {
+    /// Every row.
+    All,
+    /// Without the Published and Reviews rows, as the Dashboard has always
+    /// shown it.
+    Dashboard,
+}
+
+/// The "Assignment Detail" pane for `selected`, or a hint when nothing is
+/// selected.  Used by the Dashboard and the Schedule list.
+pub(super) fn render_assignment_pane(
+    f: &mut Frame,
+    app: &App,
+    area: Rect,
+    selected: Option<(&str, &Assignment)>,
+    rows: DetailRows,
+) {
+    let block = detail_block(app, " Assignment Detail ");
+    match selected {
+        Some((course_name, assignment)) => {
+            render_assignment_detail(f, area, block, course_name, assignment, app, rows)
+        }
+        None => render_detail_hint(f, area, block, "  Select an assignment to view details."),
+    }
+}
+
+/// An assignment's details in `detail_block`: its fields, description,
+/// links and Canvas URL.  Shared by every pane that shows an assignment.
+pub(super) fn render_assignment_detail(
+    f: &mut Frame,
+    area: Rect,
+    detail_block: Block<'_>,
+    course_name: &str,
+    assignment: &Assignment,
+    app: &App,
+    rows: DetailRows,
+) {
+    let name = assignment.name.as_deref().unwrap_or("Unnamed");
+    let now = Utc::now();
+    let today = now.date_naive();
+
+    let due_str = assignment
+        .due_at
+        .map(|d| {
+            let formatted = d.format("%B %d, %Y at %H:%M").to_string();
+            if d.date_naive() == today {
+                format!("{formatted}  (Today)")
+            } else if d < now {
+                format!("{formatted}  (Past due)")
+            } else {
+                formatted
+            }
+        })
+        .unwrap_or_else(|| "No due date".into());
+    let linked = app
+        .linked_due
+        .contains(&assignment.id)
+        .then(|| app.event_links.get(&assignment.id))
+        .flatten();
+    let due_str = if linked.is_some() {
+        format!("{due_str}  (linked to event)")
+    } else {
+        due_str
+    };
+
+    let points_str = points_label(assignment);
+
+    let types_str = assignment
+        .submission_types
+        .as_ref()
+        .map(|t| t.join(", "))
+        .unwrap_or_else(|| "─".into());
+
+    let (status, status_color) = assignment_status(assignment);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {name}"),
+            Style::default().fg(TEXT).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+
+    let value_style = Style::default().fg(TEXT);
+
+    let fields: Vec<(&str, String, Style)> = {
+        let mut flds = vec![
+            ("Course", course_name.to_string(), value_style),
+            ("Due", due_str, value_style),
+        ];
+        if let Some(link) = linked {
+            flds.push(("Event", link.title.clone(), Style::default().fg(INFO)));
+        }
+        if let Some(unlock) = assignment.locked_until(app.now()) {
+            let opens = unlock.with_timezone(&Local).format("%B %d, %Y at %H:%M");
+            flds.push(("Opens", format!("{opens}  (not open yet)"), Style::default().fg(TEXT_DIM)));
+        }
+        flds.extend([
+            ("Points", points_str, value_style),
+            ("Types", types_str, value_style),
+            ("Status", status.clone(), Style::default().fg(status_color)),
+        ]);
+        if rows == DetailRows::All && assignment.is_unpublished() {
+            let note = "No — students can't see it yet".to_string();
+            flds.push(("Published", note, Style::default().fg(CAUTION)));
+        }
+
+        if let Some(ref sub) = assignment.submission {
+            if let Some(score) = format_score(assignment, sub.score, sub.grade.as_deref()) {
+                let label = score_label(assignment, sub.score);
+                flds.push((label, score, Style::default().fg(SUCCESS)));
+            }
+            if let Some(submitted) = sub.submitted_at {
+                flds.push((
+                    "Submitted",
+                    submitted.format("%B %d, %Y at %H:%M").to_string(),
+                    value_style,
+                ));
+            }
+            if let Some(graded) = sub.graded_at {
+                flds.push((
+                    "Graded",
+                    graded.format("%B %d, %Y").to_string(),
+                    value_style,
+                ));
+            }
+            if let Some(attempt) = sub.attempt {
+                flds.push(("Attempt", attempt.to_string(), value_style));
+            }
+            if let Some(late) = sub.late {
+                let (text, color) = if late {
+                    ("Yes", DANGER)
+                } else {
+                    ("No", value_style.fg.unwrap_or(TEXT))
+                };
+                flds.push(("Late", text.to_string(), Style::default().fg(color)));
+            }
+            if let Some(missing) = sub.missing {
+                if missing {
+                    flds.push(("Missing", "Yes".to_string(), Style::default().fg(DANGER)));
+                }
+            }
+        }
+
+        let progress = app.peer_review_progress(assignment.id).filter(|_| rows == DetailRows::All);
+        if let Some((done, total)) = progress {
+            let pending = total - done;
+            let (text, color) = if pending == 0 {
+                (format!("all {total} done"), SUCCESS)
+            } else {
+                (format!("{pending} pending  (o: open)"), CAUTION)
+            };
+            flds.push(("Reviews", text, Style::default().fg(color)));
+        }
+
+        if !assignment.is_submitted() {
+            let (hours, own) = app.estimate_hours(assignment);
+            let text = if own {
+                format_hours(hours)
+            } else {
+                format!("{} (default, e: set)", format_hours(hours))
+            };
+            let color = if own { TEXT } else { TEXT_DIM };
+            flds.push(("Effort", text, Style::default().fg(color)));
+        }
+
+        // Only worth showing when it was added well after Canvas says it
+        // was created (or Canvas doesn't say).
+        if let Some(seen) = app.first_seen.seen_at(assignment.id) {
+            let late = assignment
+                .created_at
+                .is_none_or(|created| seen - created > chrono::Duration::hours(24));
+            if late {
+                let mut text = seen.with_timezone(&Local).format("%B %d, %Y").to_string();
+                if let Some(created) = assignment.created_at {
+                    let created = created.with_timezone(&Local).format("%b %d");
+                    text.push_str(&format!("  (created {created})"));
+                }
+                flds.push(("Appeared", text, Style::default().fg(CAUTION)));
+            }
+        }
+
+        flds
+    };
+
+    lines.extend(course_field_rows(app, assignment.course_id, &fields));
+
+    push_description_lines(&mut lines, app, assignment);
+
+    if let Some(ref url) = assignment.html_url {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  ── Link ─────────────────────────────────────",
+            Style::default().fg(TEXT_MUTED),
+        )));
+        lines.push(Line::from(Span::styled(
+            format!("  {url}"),
+            Style::default().fg(INFO),
+        )));
+    }
+
+    let scroll = detail_scroll(app, &mut lines, area);
+    let detail = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll(scroll)
+        .block(detail_block);
+
+    f.render_widget(detail, area);
+}
+
+/// An assignment's Description and Links sections, or a placeholder while
+/// a description left out of the sync is loading.
+pub(super) fn push_description_lines(lines: &mut Vec<Line<'_>>, app: &App, assignment: &Assignment) {
+    let header = |lines: &mut Vec<Line<'_>>| {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  ── Description ──────────────────────────────",
+            Style::default().fg(TEXT_MUTED),
+        )));
+        lines.push(Line::from(""));
+    };
+
+    match (app.description_load(assignment.id), assignment.description.as_deref()) {
+        (Some(DescriptionLoad::Loading), _) => {
+            header(lines);
+            lines.push(Line::from(Span::styled(
+                format!("  {} Loading description…", spinner_char(app.frame_count)),
+                Style::default().fg(TEXT_DIM),
+            )));
+        }
+        (Some(DescriptionLoad::Failed), None) => {
+            header(lines);
+            lines.push(Line::from(Span::styled(
+                "  Description unavailable offline.",
+                Style::default().fg(TEXT_DIM),
+            )));
+        }
+        (_, Some(desc)) => {
+            let stripped = strip_html(desc);
+            if !stripped.trim().is_empty() {
+                header(lines);
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", stripped.trim()),
+                    Style::default().fg(TEXT_DIM),
+                )));
+            }
+            push_link_lines(lines, &extract_links(desc, app.client.base_url()));
+        }
+        (_, None) => {}
+    }
+}
+
+/// The numbered "Links" section of a detail pane; the numbers match the
+/// link picker (`L`).
+pub(super) fn push_link_lines(lines: &mut Vec<Line<'_>>, links: &[Link]) {
+    if links.is_empty() {
+        return;
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  ── Links ────────────────────────────────────",
+        Style::default().fg(TEXT_MUTED),
+    )));
+    for (i, link) in links.iter().enumerate() {
+        let icon = if link.is_image { "▣ " } else { "" };
+        let mut spans = vec![Span::styled(
+            format!("  [{}] ", i + 1),
+            Style::default().fg(AMBER_SOFT),
+        )];
+        if link.text != link.url {
+            spans.push(Span::styled(
+                format!("{icon}{}", link.text),
+                Style::default().fg(TEXT_DIM),
+            ));
+            lines.push(Line::from(spans));
+            spans = vec![Span::raw("      ")];
+        } else if !icon.is_empty() {
+            spans.push(Span::styled(icon, Style::default().fg(TEXT_DIM)));
+        }
+        spans.push(Span::styled(link.url.clone(), Style::default().fg(INFO)));
+        lines.push(Line::from(spans));
+    }
+}
+
+// ─── Text ────────────────────────────────────────────────────────────────────
+
+/// "just now", "5m ago", "3h ago", "2d ago", or the date for older times.
+pub(super) fn format_ago(t: chrono::DateTime<Utc>) -> String {
+    let secs = (Utc::now() - t).num_seconds().max(0);
+    match secs {
+        0..=59 => "just now".into(),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86_399 => format!("{}h ago", secs / 3600),
+        86_400..=604_799 => format!("{}d ago", secs / 86_400),
+        _ => t.with_timezone(&Local).format("%b %d").to_string(),
+    }
+}
+
+/// Byte count as "512 B", "14.2 KB", "3.1 MB".
+pub(super) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+/// Pad `s` with spaces to `width` display columns (no-op if already wider).
+pub(super) fn pad_to_width(s: &str, width: usize) -> String {
+    format!("{s}{}", " ".repeat(width.saturating_sub(s.width())))
+}
+
+/// How much further in than the name a wrapped name's next line starts.
+pub(super) const CONTINUATION_INDENT: usize = 2;
+
+/// A list row's name in at most two lines: with `wrap_names` on, `name`
+/// breaks between words into a first line of `width` columns and a second
+/// one `CONTINUATION_INDENT` narrower, cut short with "…" if it still
+/// doesn't fit; otherwise one line truncated to `width`.
+pub(super) fn name_lines(app: &App, name: &str, width: usize) -> Vec<String> {
+    if !app.wrap_names || name.width() <= width {
+        return vec![truncate_to_width(name, width)];
+    }
+    let rest_width = width.saturating_sub(CONTINUATION_INDENT);
+    let (first, rest) = split_at_width(name, width);
+    let rest = rest.trim_start();
+    if rest.is_empty() {
+        return vec![first.trim_end().to_string()];
+    }
+    vec![first.trim_end().to_string(), truncate_to_width(rest, rest_width)]
+}
+
+/// Split `s` where its first `width` columns end, moving back to the last
+/// space in them when there is one so a word isn't cut in two.
+fn split_at_width(s: &str, width: usize) -> (&str, &str) {
+    let mut end = 0;
+    let mut last_space = None;
+    let mut used = 0;
+    for (i, ch) in s.char_indices() {
+        let cw = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
+        if used + cw > width {
+            break;
+        }
+        if ch == ' ' {
+            last_space = Some(i);
+        }
+        used += cw;
+        end = i + ch.len_utf8();
+    }
+    if end < s.len() {
+        if let Some(space) = last_space.filter(|&i| i > 0) {
+            end = space;
+        }
+    }
+    s.split_at(end)
+}
+
+/// The second line of a wrapped name (see `name_lines`), if there is one:
+/// indented past `name_col`, where the name starts, and padded to `row_w`
+/// so the row's background covers it.
+pub(super) fn continuation_line(
+    lines: &[String],
+    name_col: usize,
+    row_w: usize,
+    style: Style,
+    bg: Color,
+) -> Option<Line<'static>> {
+    let rest = lines.get(1)?;
+    let indent = name_col + CONTINUATION_INDENT;
+    Some(Line::from(vec![
+        Span::styled(" ".repeat(indent), Style::default().bg(bg)),
+        Span::styled(pad_to_width(rest, row_w.saturating_sub(indent)), style),
+    ]))
+}
+
+/// The list offset that puts item `selected` about `rows` rows below the
+/// top, given each item's height in rows.
+pub(super) fn offset_above(heights: &[usize], selected: usize, rows: usize) -> usize {
+    let mut room = rows;
+    let mut offset = selected.min(heights.len());
+    while offset > 0 && heights[offset - 1] <= room {
+        room -= heights[offset - 1];
+        offset -= 1;
+    }
+    offset
+}
+
+/// Truncate a string to fit within `max_width` display columns, appending "…"
+/// if truncated.  Correctly handles CJK / full-width characters.
+pub(super) fn truncate_to_width(s: &str, max_width: usize) -> String {
+    let w = s.width();
+    if w <= max_width {
+        return s.to_string();
+    }
+    let target = max_width.saturating_sub(1); // reserve 1 column for "…"
+    let mut cur = 0;
+    let mut end = 0;
+    for (i, ch) in s.char_indices() {
+        let cw = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
+        if cur + cw > target {
+            break;
+        }
+        cur += cw;
+        end = i + ch.len_utf8();
+    }
+    format!("{}…", &s[..end])
+}
+
+/// The end of `s` that fits within `max_width` display columns, with "…"
+/// in front if cut; for inputs, whose end is where the cursor is.
+pub(super) fn tail_to_width(s: &str, max_width: usize) -> String {
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+    let target = max_width.saturating_sub(1); // reserve 1 column for "…"
+    let mut cur = 0;
+    let mut start = s.len();
+    for (i, ch) in s.char_indices().rev() {
+        let cw = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
+        if cur + cw > target {
+            break;
+        }
+        cur += cw;
+        start = i;
+    }
+    format!("…{}", &s[start..])
+}
+
+pub(in crate::tui) fn strip_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut in_tag = false;
+    for ch in input.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => {
+                in_tag = false;
+                out.push(' ');
+            }
+            _ if !in_tag => out.push(ch),
+            _ => {}
+        }
+    }
+    decode_entities(&out)
+}
+
+/// Decode HTML character references in a single pass so that `&amp;lt;`
+/// becomes the literal text `&lt;` rather than `<`.  Handles decimal and
+/// hex numeric references plus the named entities Canvas' rich-text editor
+/// emits; anything unrecognised is left as written.
+pub(in crate::tui) fn decode_entities(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp + 1..];
+        // Entity names are short; don't scan the whole document for ';'.
+        let decoded = after
+            .char_indices()
+            .take(32)
+            .find(|(_, c)| *c == ';')
+            .and_then(|(semi, _)| decode_entity(&after[..semi]).map(|c| (c, semi)));
+        match decoded {
+            Some((ch, semi)) => {
+                out.push(ch);
+                rest = &after[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Decode the body of one character reference (the part between `&` and `;`).
+fn decode_entity(name: &str) -> Option<char> {
+    if let Some(num) = name.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse::<u32>().ok()?,
+        };
+        // NUL and invalid scalar values decode to U+FFFD, as browsers do.
+        return Some(
+            char::from_u32(code)
+                .filter(|c| *c != '\0')
+                .unwrap_or('\u{FFFD}'),
+        );
+    }
+    let ch = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "ensp" | "emsp" | "thinsp" => ' ',
+        "shy" | "zwnj" | "zwj" => '\u{200B}',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "sbquo" => '‚',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "bdquo" => '„',
+        "laquo" => '«',
+        "raquo" => '»',
+        "bull" => '•',
+        "middot" => '·',
+        "deg" => '°',
+        "plusmn" => '±',
+        "times" => '×',
+        "divide" => '÷',
+        "minus" => '−',
+        "le" => '≤',
+        "ge" => '≥',
+        "ne" => '≠',
+        "asymp" => '≈',
+        "infin" => '∞',
+        "sup1" => '¹',
+        "sup2" => '²',
+        "sup3" => '³',
+        "frac14" => '¼',
+        "frac12" => '½',
+        "frac34" => '¾',
+        "micro" => 'µ',
+        "para" => '¶',
+        "sect" => '§',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "euro" => '€',
+        "pound" => '£',
+        "yen" => '¥',
+        "cent" => '¢',
+        "larr" => '←',
+        "rarr" => '→',
+        "uarr" => '↑',
+        "darr" => '↓',
+        "harr" => '↔',
+        "rArr" => '⇒',
+        "lArr" => '⇐',
+        "hArr" => '⇔',
+        "forall" => '∀',
+        "exist" => '∃',
+        "isin" => '∈',
+        "sum" => '∑',
+        "prod" => '∏',
+        "radic" => '√',
+        "alpha" => 'α',
+        "beta" => 'β',
+        "gamma" => 'γ',
+        "delta" => 'δ',
+        "epsilon" => 'ε',
+        "theta" => 'θ',
+        "lambda" => 'λ',
+        "mu" => 'μ',
+        "pi" => 'π',
+        "sigma" => 'σ',
+        "tau" => 'τ',
+        "phi" => 'φ',
+        "omega" => 'ω',
+        "Delta" => 'Δ',
+        "Sigma" => 'Σ',
+        "Omega" => 'Ω',
+        "agrave" => 'à',
+        "aacute" => 'á',
+        "acirc" => 'â',
+        "auml" => 'ä',
+        "ccedil" => 'ç',
+        "egrave" => 'è',
+        "eacute" => 'é',
+        "ecirc" => 'ê',
+        "euml" => 'ë',
+        "iacute" => 'í',
+        "iuml" => 'ï',
+        "ntilde" => 'ñ',
+        "oacute" => 'ó',
+        "ocirc" => 'ô',
+        "ouml" => 'ö',
+        "uacute" => 'ú',
+        "uuml" => 'ü',
+        "szlig" => 'ß',
+        "Aacute" => 'Á',
+        "Eacute" => 'É',
+        "Ntilde" => 'Ñ',
+        "Ouml" => 'Ö',
+        "Uuml" => 'Ü',
+        "iexcl" => '¡',
+        "iquest" => '¿',
+        _ => return None,
+    };
+    Some(ch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_named_entities() {
+        assert_eq!(decode_entities("It&rsquo;s due &mdash; soon&hellip;"), "It’s due — soon…");
+        assert_eq!(decode_entities("&lt;b&gt; &amp; &quot;x&quot; &apos;y&apos;"), "<b> & \"x\" 'y'");
+        assert_eq!(decode_entities("caf&eacute;&nbsp;&copy;"), "café ©");
+    }
+
+    #[test]
+    fn decodes_decimal_and_hex_references() {
+        assert_eq!(decode_entities("1&#8211;2"), "1–2");
+        assert_eq!(decode_entities("don&#x2019;t &#X2019;"), "don’t ’");
+        assert_eq!(decode_entities("&#128512;"), "😀");
+        // NUL and values that aren't characters become U+FFFD.
+        assert_eq!(decode_entities("&#0;&#xD800;&#x110000;"), "\u{FFFD}\u{FFFD}\u{FFFD}");
+    }
+
+    #[test]
+    fn leaves_malformed_references_as_written() {
+        for text in [
+            "&notanentity;",
+            "AT&T",
+            "fish & chips",
+            "&#;",
+            "&#x;",
+            "&#xZZ;",
+            "&#12a;",
+            "&amp",
+            "trailing &",
+        ] {
+            assert_eq!(decode_entities(text), text);
+        }
+        // Only a short name is looked for before the `;`.
+        let long = format!("&{};", "a".repeat(40));
+        assert_eq!(decode_entities(&long), long);
+    }
+
+    #[test]
+    fn decodes_only_once() {
+        assert_eq!(decode_entities("&amp;lt;"), "&lt;");
+        assert_eq!(decode_entities("&amp;amp;"), "&amp;");
+        assert_eq!(strip_html("<p>Tom &amp;amp; Jerry</p>"), " Tom &amp; Jerry ");
+    }
+
+    #[test]
+    fn strips_tags_before_decoding() {
+        // An escaped tag stays text instead of being stripped.
+        assert_eq!(strip_html("<b>&lt;i&gt;x</b>"), " <i>x ");
+        assert_eq!(strip_html("Q&amp;A<br>next"), "Q&A next");
+    }
+}
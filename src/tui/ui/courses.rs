@@ -0,0 +1,459 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use super::components::{
+    course_stripe, detail_border, detail_scroll, field_rows, pad_to_width, popup_rect,
+    push_link_lines, selectable_row, strip_html, truncate_to_width, RowStyle,
+};
+use super::{
+    countdown_timer, spinner_char, AMBER, AMBER_SOFT, CAUTION, DANGER, SUCCESS, TEXT, TEXT_DIM,
+    TEXT_MUTED,
+};
+use crate::models::DiscussionTopic;
+use crate::tui::links::extract_links;
+use crate::tui::{is_valid_course_code, App, Focus};
+use chrono::Local;
+use unicode_width::UnicodeWidthStr;
+
+// ─── Courses ─────────────────────────────────────────────────────────────────
+
+pub(super) fn render_courses(f: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(area);
+
+    // Parse course names: before first '-' = course ID, the rest = name.
+    struct ParsedInfo {
+        course_id: String,
+        details: String,
+    }
+
+    let mut parsed: Vec<(usize, Option<ParsedInfo>)> = Vec::new();
+    let mut has_uncategorized = false;
+
+    for &course_idx in &app.course_display_order {
+        let course = &app.courses[course_idx];
+        let name = course.name.as_deref().unwrap_or("Unnamed Course");
+        let first_dash = name.find('-');
+        let has_valid_code = first_dash.is_some_and(|d| {
+            is_valid_course_code(name[..d].trim())
+        });
+        if let (Some(first_dash), true) = (first_dash, has_valid_code) {
+            let course_id = name[..first_dash].trim().to_string();
+            let details = name[first_dash + 1..].trim().to_string();
+            parsed.push((course_idx, Some(ParsedInfo { course_id, details })));
+        } else {
+            has_uncategorized = true;
+            parsed.push((course_idx, None));
+        }
+    }
+
+    // Per-course column values: term, my grade, time until the next open
+    // assignment.  Any of them may be missing.
+    struct RowInfo {
+        term: String,
+        grade: String,
+        next_due: Option<(String, Color)>,
+    }
+    let row_info: Vec<RowInfo> = parsed
+        .iter()
+        .map(|(course_idx, _)| {
+            let course = &app.courses[*course_idx];
+            let term = course
+                .term
+                .as_ref()
+                .and_then(|t| t.name.clone())
+                .unwrap_or_default();
+            let next_due = app
+                .next_due_for_course(course.id)
+                .map(|due| {
+                    let (text, color) = countdown_timer(due, app.now());
+                    // Only the largest unit: "2d", "5h", "40m".
+                    let text = text.split(' ').next().unwrap_or_default().to_string();
+                    (format!("due {text}"), color)
+                });
+            RowInfo {
+                term: truncate_to_width(&term, 14),
+                grade: course.current_grade().unwrap_or_default(),
+                next_due,
+            }
+        })
+        .collect();
+
+    // Column widths (display width, so CJK names still line up).  Columns
+    // no course has a value for take no space at all.
+    let max_id_w = parsed.iter()
+        .filter_map(|(_, p)| p.as_ref())
+        .map(|p| p.course_id.width())
+        .max()
+        .unwrap_or(0)
+        .min(12);
+    let term_w = row_info.iter().map(|r| r.term.width()).max().unwrap_or(0);
+    let grade_w = row_info.iter().map(|r| r.grade.width()).max().unwrap_or(0);
+    let due_w = row_info
+        .iter()
+        .filter_map(|r| r.next_due.as_ref())
+        .map(|(t, _)| t.width())
+        .max()
+        .unwrap_or(0);
+    // Marker (3) plus each present column and its 2-column gap.
+    let list_inner_w = chunks[0].width.saturating_sub(2) as usize;
+    let fixed_w = 3
+        + [max_id_w, term_w, grade_w, due_w]
+            .iter()
+            .filter(|w| **w > 0)
+            .map(|w| w + 2)
+            .sum::<usize>();
+    let name_w = list_inner_w.saturating_sub(fixed_w);
+
+    // Build list items. Track which absolute row indices are selectable vs header.
+    let mut items: Vec<ListItem> = Vec::new();
+    // Maps selectable index → absolute row index (to sync ratatui scroll).
+    let mut selectable_abs: Vec<usize> = Vec::new();
+    let mut uncategorized_header_inserted = false;
+
+    for (selectable_idx, ((course_idx, info), row)) in parsed.iter().zip(&row_info).enumerate() {
+        let course = &app.courses[*course_idx];
+        let is_current = App::is_current_quarter_course(course);
+
+        // Insert uncategorized header before first uncategorized course.
+        if info.is_none() && !uncategorized_header_inserted && has_uncategorized {
+            uncategorized_header_inserted = true;
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled("   ", Style::default()),
+                Span::styled(
+                    "── Uncategorized ──",
+                    Style::default().fg(TEXT_DIM).add_modifier(Modifier::DIM),
+                ),
+            ])));
+        }
+
+        let is_selected = selectable_idx == app.course_list_state.selected;
+        selectable_abs.push(items.len());
+
+        let RowStyle { marker, marker_fg, bg } = selectable_row(is_selected, false);
+        let marker = if is_selected { marker } else { "○" };
+        let name_fg = if is_current { TEXT } else { TEXT_MUTED };
+        let id_fg = if is_current { AMBER } else { TEXT_MUTED };
+        let bold = if is_selected && is_current { Modifier::BOLD } else { Modifier::empty() };
+
+        // Code column, then the name: for categorized courses the code is
+        // split off and the rest of the name fills the name column; for
+        // uncategorized ones the full name spans both.
+        let (code, name) = match info {
+            Some(info) => (info.course_id.as_str(), info.details.as_str()),
+            None => ("", course.name.as_deref().unwrap_or("Unnamed Course")),
+        };
+        let mut spans = vec![
+            course_stripe(app, Some(course.id), bg),
+            Span::styled(format!("{} ", marker), Style::default().fg(marker_fg).bg(bg)),
+        ];
+        let name_span_w = if info.is_some() {
+            if max_id_w > 0 {
+                spans.push(Span::styled(
+                    pad_to_width(&truncate_to_width(code, max_id_w), max_id_w + 2),
+                    Style::default().fg(id_fg).bg(bg).add_modifier(bold),
+                ));
+            }
+            name_w
+        } else {
+            name_w + if max_id_w > 0 { max_id_w + 2 } else { 0 }
+        };
+        spans.push(Span::styled(
+            pad_to_width(&truncate_to_width(name, name_span_w), name_span_w),
+            Style::default().fg(name_fg).bg(bg).add_modifier(if info.is_none() { bold } else { Modifier::empty() }),
+        ));
+        if term_w > 0 {
+            spans.push(Span::styled(
+                format!("  {}", pad_to_width(&row.term, term_w)),
+                Style::default().fg(TEXT_MUTED).bg(bg),
+            ));
+        }
+        if grade_w > 0 {
+            spans.push(Span::styled(
+                format!("  {}", pad_to_width(&row.grade, grade_w)),
+                Style::default().fg(if is_current { SUCCESS } else { TEXT_MUTED }).bg(bg),
+            ));
+        }
+        if due_w > 0 {
+            let (text, color) = row.next_due.clone().unwrap_or_default();
+            spans.push(Span::styled(
+                format!("  {}", pad_to_width(&text, due_w)),
+                Style::default().fg(if is_current { color } else { TEXT_MUTED }).bg(bg),
+            ));
+        }
+        items.push(ListItem::new(Line::from(spans)));
+    }
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(TEXT_MUTED))
+            .title(format!(
+                " Courses ({})   Sort: {}   Enter: details ",
+                app.courses.len(),
+                app.course_sort.label()
+            ))
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD)),
+    );
+
+    // Map the logical selection to the absolute row index (skipping header rows).
+    let abs_idx = selectable_abs
+        .get(app.course_list_state.selected)
+        .copied()
+        .unwrap_or(0);
+    app.course_list_state.inner.select(Some(abs_idx));
+    f.render_stateful_widget(list, chunks[0], &mut app.course_list_state.inner);
+
+    render_course_detail(f, app, chunks[1]);
+
+    if app.show_course_pages_picker {
+        render_course_pages_picker(f, app, area);
+    }
+}
+
+fn render_course_detail(f: &mut Frame, app: &App, area: Rect) {
+    let course_name = app
+        .selected_course()
+        .and_then(|c| c.name.as_deref())
+        .unwrap_or("Course");
+
+    let has_content = app.course_detail_content.is_some()
+        || app.course_detail_loading
+        || app.course_pages_loading;
+
+    let detail_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(detail_border(app))
+        .title(format!(" {} ", course_name))
+        .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+        .title_bottom(Line::from(Span::styled(
+            if has_content { " Esc: close " } else { "" },
+            Style::default().fg(TEXT_DIM),
+        )));
+
+    // Nothing loaded yet — show the course summary and a prompt.
+    if !has_content {
+        let mut lines = vec![Line::from("")];
+        if let Some(course) = app.selected_course() {
+            let value_style = Style::default().fg(TEXT);
+            let fields: Vec<(&str, String, Style)> = [
+                ("Code", course.course_code.clone()),
+                ("Term", course.term.as_ref().and_then(|t| t.name.clone())),
+                ("Grade", course.current_grade()),
+                ("Students", course.total_students.map(|n| n.to_string())),
+            ]
+            .into_iter()
+            .filter_map(|(label, value)| Some((label, value?, value_style)))
+            .collect();
+            lines.extend(field_rows(&fields));
+            lines.push(Line::from(""));
+        }
+        let focused = app.focus() == Focus::Detail;
+        let announcements = app.course_announcements();
+        if !announcements.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  Announcements",
+                Style::default().fg(AMBER_SOFT),
+            )));
+            lines.extend(announcement_rows(app, &announcements, focused, area.width));
+            lines.push(Line::from(""));
+        }
+        let prompt = if focused && !announcements.is_empty() {
+            "  Enter: open announcement  ·  Esc: back"
+        } else if announcements.is_empty() {
+            "  Press Enter to browse course pages."
+        } else {
+            "  Enter: course pages  ·  w: announcements"
+        };
+        lines.push(Line::from(Span::styled(prompt, Style::default().fg(TEXT_DIM))));
+        let para = Paragraph::new(lines).block(detail_block);
+        f.render_widget(para, area);
+        return;
+    }
+
+    if app.course_detail_loading || app.course_pages_loading {
+        let spin = spinner_char(app.frame_count);
+        let msg = if app.course_pages_loading {
+            "Fetching course pages…"
+        } else {
+            "Loading page content…"
+        };
+        let para = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(format!("  {spin}  "), Style::default().fg(CAUTION)),
+                Span::styled(
+                    msg,
+                    Style::default().fg(TEXT).add_modifier(Modifier::BOLD),
+                ),
+            ]),
+        ])
+        .block(detail_block);
+        f.render_widget(para, area);
+        return;
+    }
+
+    let content = app
+        .course_detail_content
+        .as_deref()
+        .unwrap_or("No Details Found");
+
+    let is_no_details = content == "No Details Found";
+
+    if is_no_details {
+        let para = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "  No Details Found",
+                Style::default().fg(TEXT_DIM),
+            )),
+        ])
+        .block(detail_block);
+        f.render_widget(para, area);
+        return;
+    }
+
+    let stripped = strip_html(content);
+    let mut lines = vec![Line::from("")];
+
+    for text_line in stripped.lines() {
+        let trimmed = text_line.trim();
+        if trimmed.is_empty() {
+            lines.push(Line::from(""));
+        } else {
+            lines.push(Line::from(Span::styled(
+                format!("  {trimmed}"),
+                Style::default().fg(TEXT),
+            )));
+        }
+    }
+
+    push_link_lines(&mut lines, &extract_links(content, app.client.base_url()));
+
+    let scroll = detail_scroll(app, &mut lines, area);
+    let para = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll(scroll)
+        .block(detail_block);
+
+    f.render_widget(para, area);
+}
+
+/// The course summary's announcement rows: an unread marker, the title and
+/// the date, with the selected one marked while the pane has focus.
+fn announcement_rows(
+    app: &App,
+    announcements: &[&DiscussionTopic],
+    focused: bool,
+    width: u16,
+) -> Vec<Line<'static>> {
+    // Borders, marker, unread dot, the date column and a space after it.
+    let title_w = usize::from(width).saturating_sub(2 + 3 + 2 + 8 + 1);
+    announcements
+        .iter()
+        .enumerate()
+        .map(|(i, ann)| {
+            let is_selected = focused && i == app.course_announcement_state.selected;
+            let RowStyle { marker, marker_fg, bg } = selectable_row(is_selected, false);
+            let marker = if is_selected { marker } else { " " };
+            let is_unread = ann.read_state.as_deref() == Some("unread");
+            let title = ann.title.as_deref().unwrap_or("Untitled");
+            let date = ann
+                .posted_at
+                .map(|d| d.with_timezone(&Local).format("%b %d").to_string())
+                .unwrap_or_default();
+            let title_style = if is_unread {
+                Style::default().fg(TEXT).bg(bg).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(TEXT_DIM).bg(bg)
+            };
+            Line::from(vec![
+                Span::styled(format!(" {marker} "), Style::default().fg(marker_fg).bg(bg)),
+                Span::styled(
+                    if is_unread { "● " } else { "  " },
+                    Style::default().fg(DANGER).bg(bg),
+                ),
+                Span::styled(pad_to_width(&truncate_to_width(title, title_w), title_w), title_style),
+                Span::styled(format!("  {date}"), Style::default().fg(TEXT_MUTED).bg(bg)),
+            ])
+        })
+        .collect()
+}
+
+fn render_course_pages_picker(f: &mut Frame, app: &mut App, area: Rect) {
+    let pages = &app.course_pages;
+    let count = pages.len();
+    if count == 0 {
+        return;
+    }
+
+    let max_name_len = pages
+        .iter()
+        .map(|p| p.title.as_deref().unwrap_or("Untitled").len())
+        .max()
+        .unwrap_or(10);
+    let popup_w = (max_name_len as u16).saturating_add(10).min(area.width.saturating_sub(4));
+    let popup_h = (count as u16).saturating_add(4).min(area.height.saturating_sub(2));
+    let popup = popup_rect(popup_w, popup_h, area);
+
+    f.render_widget(Clear, popup);
+
+    let items: Vec<ListItem> = pages
+        .iter()
+        .enumerate()
+        .map(|(i, page)| {
+            let title = page.title.as_deref().unwrap_or("Untitled");
+            let is_selected = i == app.course_pages_list_state.selected;
+            let RowStyle { marker, marker_fg, bg } = selectable_row(is_selected, false);
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {} ", marker),
+                    Style::default().fg(marker_fg).bg(bg),
+                ),
+                Span::styled(
+                    title,
+                    Style::default()
+                        .fg(if is_selected { TEXT } else { TEXT_DIM })
+                        .bg(bg)
+                        .add_modifier(if is_selected {
+                            Modifier::BOLD
+                        } else {
+                            Modifier::empty()
+                        }),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(AMBER_SOFT))
+            .title(format!(" Select Page ({}) ", count))
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+            .title_bottom(Line::from(vec![
+                Span::styled(" j/k ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("move  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("Enter ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("select  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("Esc ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("cancel ", Style::default().fg(TEXT_DIM)),
+            ])),
+    );
+
+    app.course_pages_list_state
+        .inner
+        .select(Some(app.course_pages_list_state.selected));
+    f.render_stateful_widget(list, popup, &mut app.course_pages_list_state.inner);
+}
@@ -0,0 +1,392 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+use super::components::{
+    assignment_status, continuation_line, course_stripe, filter_hint, format_ago, name_lines,
+    new_badge, render_assignment_pane, selectable_row, DetailRows, RowStyle,
+};
+use super::modals::{render_course_filter_popup, render_submission_modal};
+use super::{
+    countdown_timer, opens_timer, AMBER, AMBER_SOFT, CAUTION, DANGER, SUCCESS, TEXT, TEXT_DIM,
+    TEXT_MUTED,
+};
+use crate::tui::time_budget::format_hours;
+use crate::tui::{format_score, App, DashboardPanel};
+use chrono::{Local, Utc};
+use unicode_width::UnicodeWidthStr;
+
+// ─── Dashboard ───────────────────────────────────────────────────────────────
+
+pub(super) fn render_dashboard(f: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(6), Constraint::Min(0)])
+        .split(area);
+
+    // ── Overview panel ────────────────────────────────────────────────────
+    let user_name = app
+        .user
+        .as_ref()
+        .and_then(|u| u.name.clone())
+        .unwrap_or_else(|| "Student".into());
+    let unread_count = app.unread_announcements_shown();
+    let upcoming_count = app.calendar_events.len();
+
+    let overview = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled("  ◈  ", Style::default().fg(AMBER)),
+            Span::styled(
+                format!("Welcome back, {}.", user_name),
+                Style::default().fg(TEXT).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("     ●  ", Style::default().fg(AMBER_SOFT)),
+            Span::styled(
+                format!("{} courses enrolled", app.courses.len()),
+                Style::default().fg(TEXT),
+            ),
+            Span::styled("     ○  ", Style::default().fg(TEXT_DIM)),
+            Span::styled(
+                format!("{} upcoming events", upcoming_count),
+                Style::default().fg(TEXT_DIM),
+            ),
+            Span::styled("     ", Style::default()),
+            Span::styled(
+                if unread_count > 0 { "●  " } else { "○  " },
+                Style::default().fg(if unread_count > 0 { DANGER } else { TEXT_MUTED }),
+            ),
+            Span::styled(
+                format!(
+                    "{} unread announcement{}",
+                    unread_count,
+                    if unread_count == 1 { "" } else { "s" }
+                ),
+                Style::default().fg(if unread_count > 0 { DANGER } else { TEXT_DIM }),
+            ),
+        ]),
+        time_budget_line(app),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(TEXT_MUTED))
+            .title(" Overview ")
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD)),
+    );
+
+    f.render_widget(overview, chunks[0]);
+
+    // ── Bottom split: Upcoming list (left) + Detail (right) ──────────────
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(38), Constraint::Percentage(62)])
+        .split(chunks[1]);
+
+    match app.dashboard_panel {
+        DashboardPanel::Upcoming => render_upcoming_assignments(f, app, bottom[0]),
+        DashboardPanel::RecentlyGraded => render_recently_graded(f, app, bottom[0]),
+    }
+    render_assignment_pane(
+        f,
+        app,
+        bottom[1],
+        app.get_selected_dashboard_assignment(),
+        DetailRows::Dashboard,
+    );
+
+    if app.show_course_filter {
+        render_course_filter_popup(f, app, area);
+    }
+
+    if !app.submission_state.is_hidden() {
+        render_submission_modal(f, app, area);
+    }
+}
+
+/// "◷  14h of work in the next 7 days   Fri 2h  Sat 6h  …", with the days
+/// over `daily_capacity_hours` in red.
+fn time_budget_line(app: &App) -> Line<'static> {
+    let days = app.time_budget();
+    let total: f32 = days.iter().sum();
+    let mut spans = vec![
+        Span::styled("     ◷  ", Style::default().fg(AMBER_SOFT)),
+        Span::styled(
+            format!("{} of work in the next {} days", format_hours(total), days.len()),
+            Style::default().fg(TEXT),
+        ),
+        Span::raw("   "),
+    ];
+    let today = Local::now().date_naive();
+    for (day, hours) in today.iter_days().zip(&days) {
+        let over = *hours > app.daily_capacity_hours;
+        let style = if over {
+            Style::default().fg(DANGER).add_modifier(Modifier::BOLD)
+        } else if *hours > 0.0 {
+            Style::default().fg(TEXT_DIM)
+        } else {
+            Style::default().fg(TEXT_MUTED)
+        };
+        spans.push(Span::styled(format!("{} ", day.format("%a")), Style::default().fg(TEXT_DIM)));
+        spans.push(Span::styled(
+            format!("{}{}  ", format_hours(*hours), if over { "!" } else { "" }),
+            style,
+        ));
+    }
+    Line::from(spans)
+}
+
+fn render_upcoming_assignments(f: &mut Frame, app: &mut App, area: Rect) {
+    let today = Utc::now().date_naive();
+    let focal_id = app.focal_assignment_id;
+
+    let rows = app.dashboard_upcoming().row_count();
+    app.dashboard_list_state.set_len(rows);
+    let upcoming = app.dashboard_upcoming();
+
+    let mut items: Vec<ListItem> = if upcoming.items.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            format!("  ○  Nothing due in the next {} days", app.dashboard_days),
+            Style::default().fg(TEXT_DIM),
+        )))]
+    } else {
+        upcoming
+            .items
+            .iter()
+            .enumerate()
+            .map(|(idx, (course_name, a))| {
+                let is_selected = idx == app.dashboard_list_state.selected;
+                let is_focal = Some(a.id) == focal_id;
+
+                let name = a.name.as_deref().unwrap_or("Unnamed");
+                let is_today = a.due_at.map(|d| d.date_naive() == today).unwrap_or(false);
+                let due = a
+                    .due_at
+                    .map(|d| {
+                        if is_today {
+                            format!("Today {}", d.format("%H:%M"))
+                        } else {
+                            d.format("%b %d").to_string()
+                        }
+                    })
+                    .unwrap_or_default();
+                let (status, status_color) = assignment_status(a);
+
+                let unlock = a.locked_until(app.now());
+                let (timer_text, timer_color) = match unlock {
+                    Some(unlock) => opens_timer(unlock, app.now()),
+                    None => a.due_at.map(|d| countdown_timer(d, app.now())).unwrap_or_default(),
+                };
+
+                let RowStyle { marker, marker_fg, bg } = selectable_row(is_selected, is_focal);
+                let badge = new_badge(app, a, bg);
+
+                // " ▶ " = 3 display columns, badge, timer + trailing space
+                let prefix_len = 3 + badge.width();
+                let timer_display = format!(" {} ", timer_text);
+                let timer_len = timer_display.width();
+                let avail = (area.width as usize).saturating_sub(prefix_len + timer_len + 2);
+                let names = name_lines(app, name, avail);
+                let pad = avail.saturating_sub(names[0].width());
+                // Dimmed until it opens.
+                let name_style = Style::default()
+                    .fg(if unlock.is_some() { TEXT_DIM } else { TEXT })
+                    .bg(bg)
+                    .add_modifier(
+                        if is_selected || is_focal {
+                            Modifier::BOLD
+                        } else {
+                            Modifier::empty()
+                        },
+                    );
+
+                let first = Line::from(vec![
+                    course_stripe(app, a.course_id, bg),
+                    Span::styled(
+                        format!("{} ", marker),
+                        Style::default().fg(marker_fg).bg(bg),
+                    ),
+                    badge,
+                    Span::styled(names[0].clone(), name_style),
+                    Span::styled(
+                        " ".repeat(pad),
+                        Style::default().bg(bg),
+                    ),
+                    Span::styled(
+                        timer_display,
+                        Style::default().fg(timer_color).bg(bg),
+                    ),
+                ]);
+                let row_w = first.width();
+                let mut lines = vec![first];
+                lines.extend(continuation_line(&names, prefix_len, row_w, name_style, bg));
+                lines.extend([
+                    Line::from(vec![
+                        Span::styled("    ", Style::default().bg(bg)),
+                        Span::styled(
+                            format!("{:<14}", due),
+                            Style::default()
+                                .fg(if is_today { CAUTION } else { TEXT_DIM })
+                                .bg(bg)
+                                .add_modifier(
+                                    if is_today {
+                                        Modifier::BOLD
+                                    } else {
+                                        Modifier::empty()
+                                    },
+                                ),
+                        ),
+                        Span::styled(
+                            format!(" {}", status),
+                            Style::default().fg(status_color).bg(bg),
+                        ),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("    ", Style::default().bg(bg)),
+                        Span::styled(
+                            course_name.to_string(),
+                            Style::default().fg(TEXT_MUTED).bg(bg),
+                        ),
+                    ]),
+                ]);
+                ListItem::new(lines)
+            })
+            .collect()
+    };
+
+    // Fold row for assignments past the window.
+    if upcoming.later > 0 {
+        let is_selected = app.dashboard_list_state.selected == upcoming.items.len();
+        let after = upcoming.window_end.with_timezone(&Local).format("%b %d");
+        let text = if app.dashboard_show_later {
+            format!("▴ hide {} due after {after}", upcoming.later)
+        } else {
+            format!("… {} more after {after}", upcoming.later)
+        };
+        let RowStyle { marker, bg, .. } = selectable_row(is_selected, false);
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(format!(" {marker} "), Style::default().fg(AMBER).bg(bg)),
+            Span::styled(text, Style::default().fg(TEXT_DIM).bg(bg)),
+        ])));
+    }
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(TEXT_MUTED))
+            .title(format!(
+                " Upcoming ({}){}   Enter: submit ",
+                upcoming.items.len(),
+                filter_hint(app)
+            ))
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD)),
+    );
+
+    app.dashboard_list_state
+        .inner
+        .select(Some(app.dashboard_list_state.selected));
+    f.render_stateful_widget(list, area, &mut app.dashboard_list_state.inner);
+}
+
+fn render_recently_graded(f: &mut Frame, app: &mut App, area: Rect) {
+    let graded = app.recently_graded();
+    let selected = app.graded_list_state.selected;
+
+    let items: Vec<ListItem> = if graded.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "  ○  Nothing graded yet",
+            Style::default().fg(TEXT_DIM),
+        )))]
+    } else {
+        graded
+            .iter()
+            .enumerate()
+            .map(|(idx, (course_name, a, graded_at))| {
+                let is_selected = idx == selected;
+                let is_new = app.newly_graded.contains(&a.id);
+                let RowStyle { marker, bg, .. } = selectable_row(is_selected, false);
+                let badge = if is_new { "● " } else { "" };
+
+                let name = a.name.as_deref().unwrap_or("Unnamed");
+                let sub = a.submission.as_ref();
+                let score = format_score(
+                    a,
+                    sub.and_then(|s| s.score),
+                    sub.and_then(|s| s.grade.as_deref()),
+                )
+                .unwrap_or_else(|| "graded".into());
+
+                // " ▶ " = 3 display columns, then badge, name and score
+                let score_display = format!(" {score} ");
+                let avail = (area.width as usize)
+                    .saturating_sub(3 + badge.width() + score_display.width() + 2);
+                let names = name_lines(app, name, avail);
+                let pad = avail.saturating_sub(names[0].width());
+                let name_style = Style::default().fg(TEXT).bg(bg).add_modifier(
+                    if is_selected || is_new {
+                        Modifier::BOLD
+                    } else {
+                        Modifier::empty()
+                    },
+                );
+
+                let first = Line::from(vec![
+                    course_stripe(app, a.course_id, bg),
+                    Span::styled(format!("{marker} "), Style::default().fg(AMBER).bg(bg)),
+                    Span::styled(badge, Style::default().fg(SUCCESS).bg(bg)),
+                    Span::styled(names[0].clone(), name_style),
+                    Span::styled(" ".repeat(pad), Style::default().bg(bg)),
+                    Span::styled(score_display, Style::default().fg(SUCCESS).bg(bg)),
+                ]);
+                let row_w = first.width();
+                let mut lines = vec![first];
+                lines.extend(continuation_line(&names, 3 + badge.width(), row_w, name_style, bg));
+                lines.push(Line::from(vec![
+                    Span::styled("    ", Style::default().bg(bg)),
+                    Span::styled(
+                        format!("{:<14}", format_ago(*graded_at)),
+                        Style::default().fg(if is_new { SUCCESS } else { TEXT_DIM }).bg(bg),
+                    ),
+                    Span::styled(
+                        format!(" {course_name}"),
+                        Style::default().fg(TEXT_MUTED).bg(bg),
+                    ),
+                ]));
+                ListItem::new(lines)
+            })
+            .collect()
+    };
+
+    let new_count = graded
+        .iter()
+        .filter(|(_, a, _)| app.newly_graded.contains(&a.id))
+        .count();
+    let new_hint = if new_count > 0 {
+        format!(", {new_count} new")
+    } else {
+        String::new()
+    };
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(TEXT_MUTED))
+            .title(format!(
+                " Recently graded ({}{new_hint}){}   Enter: open ",
+                graded.len(),
+                filter_hint(app)
+            ))
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD)),
+    );
+
+    app.graded_list_state.inner.select(Some(selected));
+    f.render_stateful_widget(list, area, &mut app.graded_list_state.inner);
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use unicode_width::UnicodeWidthStr;
+
+use super::components::truncate_to_width;
+use crate::tui::{App, DashboardPanel, Tab, UnifiedViewMode};
+
+/// How readily a hint is dropped when the status bar is too narrow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Keep {
+    /// Dropped first: keys most people learn on the first day.
+    Low,
+    Normal,
+    /// Never dropped: quit and the screen's main action.
+    Always,
+}
+
+/// One status bar hint: its keys and what they do.  `id` names it in the
+/// `hint_labels` config table, which replaces `label`.
+struct Hint {
+    id: &'static str,
+    keys: String,
+    label: &'static str,
+    keep: Keep,
+}
+
+impl Hint {
+    /// "q quit", with the label from `labels` when given.
+    fn text(&self, labels: &HashMap<String, String>) -> String {
+        let label = labels.get(self.id).map_or(self.label, String::as_str);
+        format!("{} {label}", self.keys)
+    }
+}
+
+fn hint(id: &'static str, keys: &str, label: &'static str, keep: Keep) -> Hint {
+    Hint { id, keys: keys.to_string(), label, keep }
+}
+
+/// The hints for what is on screen, most useful first within each part.
+fn hints_for(app: &App) -> Vec<Hint> {
+    use Keep::*;
+
+    if !app.submission_state.is_hidden() {
+        return vec![
+            hint("navigate", "j/k", "navigate", Low),
+            hint("select", "Space/Enter", "select", Always),
+            hint("back", "Esc", "back", Always),
+            hint("confirm", "y", "confirm", Normal),
+            hint("cancel", "n", "cancel", Normal),
+        ];
+    }
+
+    let mut hints = vec![
+        hint("quit", "q", "quit", Always),
+        hint("switch", &format!("Tab/{}", Tab::digit_range()), "switch", Low),
+        hint("navigate", "j/k", "nav", Low),
+    ];
+    hints.extend(match (app.active_tab, app.unified_view_mode) {
+        (Tab::Assignments, UnifiedViewMode::CalendarView) => vec![
+            hint("list_view", "v", "list-view", Normal),
+            hint("filter", "f", "filter", Normal),
+            hint("routine_events", "o", "routine events", Low),
+            hint("link_event", "l", "link", Low),
+            hint("submit", "Enter", "submit", Always),
+            hint("effort", "e", "effort", Low),
+            hint("links", "L", "links", Low),
+            hint("today", "t", "today", Normal),
+            hint("date", "J", "date", Normal),
+            hint("add_event", "a", "add event", Low),
+            hint("refresh", "r", "refresh", Normal),
+        ],
+        (Tab::Assignments, UnifiedViewMode::ListView) => vec![
+            hint("calendar_view", "v", "cal-view", Normal),
+            hint("sort", "s", "sort", Normal),
+            hint("filter", "f", "filter", Normal),
+            hint("submit", "Enter", "submit", Always),
+            hint("effort", "e", "effort", Low),
+            hint("links", "L", "links", Low),
+            hint("date", "J", "date", Normal),
+            hint("add_event", "a", "add event", Low),
+            hint("refresh", "r", "refresh", Normal),
+        ],
+        (Tab::Courses, _) => vec![
+            hint("sort", "s", "sort", Normal),
+            hint("jump", "a-z", "jump", Low),
+            hint("download_files", "B", "download files", Low),
+            hint("details", "Enter", "details", Always),
+            hint("links", "L", "links", Low),
+            hint("close", "Esc", "close", Normal),
+            hint("refresh", "r", "refresh", Normal),
+        ],
+        (Tab::Announcements, _) => vec![
+            hint("attachment", "[/]", "attachment", Always),
+            hint("download", "d", "download", Normal),
+            hint("archive", "x", "archive", Low),
+            hint("newest_unread", "t", "newest unread", Low),
+            hint("refresh", "r", "refresh", Normal),
+        ],
+        (Tab::Dashboard, _) => match app.dashboard_panel {
+            DashboardPanel::Upcoming => vec![
+                hint("recently_graded", "v", "recently graded", Normal),
+                hint("filter", "f", "filter", Normal),
+                hint("submit", "Enter", "submit", Always),
+                hint("effort", "e", "effort", Low),
+                hint("links", "L", "links", Low),
+                hint("top", "t", "top", Low),
+                hint("refresh", "r", "refresh", Normal),
+            ],
+            DashboardPanel::RecentlyGraded => vec![
+                hint("upcoming", "v", "upcoming", Normal),
+                hint("filter", "f", "filter", Normal),
+                hint("open_in_schedule", "Enter", "open in schedule", Always),
+                hint("links", "L", "links", Low),
+                hint("top", "t", "top", Low),
+                hint("refresh", "r", "refresh", Normal),
+            ],
+        },
+    });
+    if !app.open_handlers.is_empty() && app.detail_assignment().is_some() {
+        hints.push(hint("open_handler", "x", "run", Low));
+    }
+    hints
+}
+
+const SEPARATOR: &str = "   ";
+
+/// Columns the hints that are never dropped need.
+pub(super) fn essential_width(app: &App, labels: &HashMap<String, String>) -> usize {
+    let texts: Vec<String> = hints_for(app)
+        .into_iter()
+        .filter(|h| h.keep == Keep::Always)
+        .map(|h| h.text(labels))
+        .collect();
+    texts.join(SEPARATOR).width()
+}
+
+/// The status bar hints for what is on screen, in at most `width`
+/// columns.  When they don't all fit, `Low` hints are dropped before
+/// `Normal` ones, the rightmost first; if the `Always` ones still don't
+/// fit, the text is cut short with "…".
+pub(super) fn status_hints(app: &App, labels: &HashMap<String, String>, width: usize) -> String {
+    let texts: Vec<(Keep, String)> = hints_for(app)
+        .into_iter()
+        .map(|h| (h.keep, h.text(labels)))
+        .collect();
+    let joined = |shown: &[bool]| {
+        texts
+            .iter()
+            .zip(shown)
+            .filter(|(_, shown)| **shown)
+            .map(|((_, text), _)| text.as_str())
+            .collect::<Vec<_>>()
+            .join(SEPARATOR)
+    };
+
+    let mut shown = vec![true; texts.len()];
+    // Drop order: lowest `Keep` first, rightmost first within it.
+    let mut droppable: Vec<usize> = (0..texts.len())
+        .filter(|&i| texts[i].0 != Keep::Always)
+        .collect();
+    droppable.sort_by_key(|&i| (texts[i].0, std::cmp::Reverse(i)));
+    let mut droppable = droppable.into_iter();
+    while joined(&shown).width() > width {
+        match droppable.next() {
+            Some(i) => shown[i] = false,
+            None => break,
+        }
+    }
+    let text = joined(&shown);
+    if width == 0 {
+        String::new()
+    } else {
+        truncate_to_width(&text, width)
+    }
+}
@@ -0,0 +1,726 @@
+mod announcements;
+mod components;
+mod courses;
+mod dashboard;
+mod hints;
+mod modals;
+mod schedule;
+pub mod theme;
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Tabs},
+    Frame,
+};
+
+use super::{profile, App, BulkProgress, Tab};
+use crate::api::clock;
+use chrono::{Local, Utc};
+use unicode_width::UnicodeWidthStr;
+
+use announcements::render_announcements;
+use components::{format_size, truncate_to_width};
+use courses::render_courses;
+use dashboard::render_dashboard;
+use modals::{
+    render_bulk_summary, render_estimate_prompt, render_event_link_picker, render_link_picker,
+    render_profile, render_quit_confirm, render_sync_stats, render_term_picker,
+};
+use schedule::render_schedule;
+use theme::TRUECOLOR;
+
+pub(super) use components::{assignment_status, decode_entities, strip_html};
+
+// ─── Palette ─────────────────────────────────────────────────────────────────
+
+// Screens draw in the truecolor theme; see `theme` for what each color is
+// for and for the fallbacks.
+const AMBER: Color = TRUECOLOR.amber;
+const AMBER_SOFT: Color = TRUECOLOR.amber_soft;
+const TEXT: Color = TRUECOLOR.text;
+const TEXT_DIM: Color = TRUECOLOR.text_dim;
+const TEXT_MUTED: Color = TRUECOLOR.text_muted;
+const SEL_BG: Color = TRUECOLOR.sel_bg;
+const FOCAL_BG: Color = TRUECOLOR.focal_bg;
+const FOCAL: Color = TRUECOLOR.focal;
+const HDR_BG: Color = TRUECOLOR.hdr_bg;
+const SUCCESS: Color = TRUECOLOR.success;
+const CAUTION: Color = TRUECOLOR.caution;
+const DANGER: Color = TRUECOLOR.danger;
+const INFO: Color = TRUECOLOR.info;
+
+/// Status messages keep at least this many columns, even if it means
+/// cutting the hints short.
+const MIN_MESSAGE_WIDTH: usize = 24;
+
+// ─── Spinner ─────────────────────────────────────────────────────────────────
+
+const SPINNER: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+fn spinner_char(frame: u64) -> &'static str {
+    SPINNER[frame as usize % SPINNER.len()]
+}
+
+// ─── Countdown Timer ─────────────────────────────────────────────────────────
+
+/// Returns a human-readable countdown from `now` to `due` and a color that
+/// progresses from green (≥7 days) → yellow (1–7 days) → orange (<24h) →
+/// red (<6h) → bold red (<1h).
+pub(super) fn countdown_timer(
+    due: chrono::DateTime<Utc>,
+    now: chrono::DateTime<Utc>,
+) -> (String, Color) {
+    let remaining = due.signed_duration_since(now);
+
+    if remaining.num_seconds() <= 0 {
+        return ("Past due".into(), DANGER);
+    }
+
+    let text = remaining_text(remaining);
+    let days = remaining.num_days();
+    let hours = remaining.num_hours() % 24;
+
+    let color = if days >= 7 {
+        SUCCESS                           // ≥ 1 week — green
+    } else if days >= 3 {
+        TRUECOLOR.days_left               // 3–7 days — yellow-green
+    } else if days >= 1 {
+        CAUTION                           // 1–3 days — orange/yellow
+    } else if hours >= 6 {
+        TRUECOLOR.hours_left              // 6–24h — deep orange
+    } else {
+        DANGER                            // < 6h — red
+    };
+
+    (text, color)
+}
+
+/// "3d 4h 12m", "4h 12m" or "12m".
+pub(super) fn remaining_text(remaining: chrono::Duration) -> String {
+    let total_mins = remaining.num_minutes();
+    let days = remaining.num_days();
+    let hours = (total_mins / 60) % 24;
+    let mins = total_mins % 60;
+    if days > 0 {
+        format!("{days}d {hours}h {mins}m")
+    } else if hours > 0 {
+        format!("{hours}h {mins}m")
+    } else {
+        format!("{mins}m")
+    }
+}
+
+/// The countdown to an assignment opening, `countdown_timer`'s variant for
+/// not-yet-open assignments: "🔒 Opens in 3h 12m", dimmed.
+pub(super) fn opens_timer(
+    unlock: chrono::DateTime<Utc>,
+    now: chrono::DateTime<Utc>,
+) -> (String, Color) {
+    // Under a minute left still reads "Opens in 0m", never nothing.
+    let remaining = unlock.signed_duration_since(now).max(chrono::Duration::zero());
+    (format!("🔒 Opens in {}", remaining_text(remaining)), TEXT_MUTED)
+}
+
+// ─── Main render ─────────────────────────────────────────────────────────────
+
+pub fn render(f: &mut Frame, app: &mut App) {
+    // A terminal squeezed to nothing (a tiling window manager can do this)
+    // has no cell to draw in.
+    if f.area().is_empty() {
+        return;
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(f.area());
+
+    render_tabs(f, app, chunks[0]);
+    render_clock(f, app, chunks[0]);
+
+    match app.active_tab {
+        Tab::Dashboard => render_dashboard(f, app, chunks[1]),
+        Tab::Courses => render_courses(f, app, chunks[1]),
+        Tab::Assignments => render_schedule(f, app, chunks[1]),
+        Tab::Announcements => render_announcements(f, app, chunks[1]),
+    }
+
+    if app.link_picker.is_some() {
+        render_link_picker(f, app, chunks[1]);
+    }
+    if app.show_term_picker {
+        render_term_picker(f, app, chunks[1]);
+    }
+    if app.event_link_picker.is_some() {
+        render_event_link_picker(f, app, chunks[1]);
+    }
+    if app.show_sync_stats {
+        render_sync_stats(f, app, chunks[1]);
+    }
+    if app.show_profile {
+        render_profile(f, app, chunks[1]);
+    }
+    if app.bulk_summary.is_some() {
+        render_bulk_summary(f, app, chunks[1]);
+    }
+    if app.estimate_prompt.is_some() {
+        render_estimate_prompt(f, app, chunks[1]);
+    }
+
+    render_status_bar(f, app, chunks[2]);
+    if app.confirm_quit {
+        render_quit_confirm(f, app, chunks[1]);
+    }
+    theme::repaint(f.buffer_mut(), app.color_mode);
+}
+
+// ─── Tab Bar ─────────────────────────────────────────────────────────────────
+
+fn render_tabs(f: &mut Frame, app: &App, area: Rect) {
+    let titles: Vec<Line> = Tab::ALL
+        .iter()
+        .map(|tab| {
+            let active = *tab == app.active_tab;
+            Line::from(vec![
+                Span::styled(
+                    format!(" {} ", tab.number()),
+                    Style::default().fg(if active { AMBER_SOFT } else { TEXT_MUTED }),
+                ),
+                Span::styled(
+                    format!("{} ", tab.title()),
+                    Style::default()
+                        .fg(if active { TEXT } else { TEXT_DIM })
+                        .add_modifier(if active { Modifier::BOLD } else { Modifier::empty() }),
+                ),
+            ])
+        })
+        .collect();
+
+    let selected = Tab::ALL.iter().position(|t| *t == app.active_tab).unwrap_or(0);
+
+    let mut title = vec![Span::raw(" ◈ Canvas TUI ")];
+    if let Some(term) = app.term_filter_name() {
+        title.push(Span::styled(format!("· {term} "), Style::default().fg(AMBER_SOFT)));
+    }
+    if let Some(id) = app.client.as_user_id() {
+        let who = app
+            .user
+            .as_ref()
+            .and_then(|u| u.name.clone())
+            .unwrap_or_else(|| format!("user {id}"));
+        title.push(Span::styled(
+            format!(" MASQUERADING AS {} ", who.to_uppercase()),
+            Style::default()
+                .fg(Color::White)
+                .bg(DANGER)
+                .add_modifier(Modifier::BOLD),
+        ));
+        title.push(Span::raw(" "));
+    }
+
+    let tabs = Tabs::new(titles)
+        .block(
+            Block::default()
+                .borders(Borders::BOTTOM)
+                .border_style(Style::default().fg(TEXT_MUTED))
+                .title(Line::from(title))
+                .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD)),
+        )
+        .select(selected)
+        .divider(Span::styled(" │ ", Style::default().fg(TEXT_MUTED)))
+        .highlight_style(
+            Style::default()
+                .fg(AMBER)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        );
+
+    f.render_widget(tabs, area);
+}
+
+// ─── Clock ───────────────────────────────────────────────────────────────────
+
+/// The clock at the top right, with the signed-in user's initials in a
+/// badge before it.
+fn render_clock(f: &mut Frame, app: &App, tab_area: Rect) {
+    let time_str = format!(" {} ", Local::now().format("%a %b %d  %H:%M:%S"));
+    let badge = format!(" {} ", profile::initials(app.user.as_ref()));
+    let line = Line::from(vec![
+        Span::styled(
+            badge,
+            Style::default()
+                .fg(HDR_BG)
+                .bg(app.user_badge_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(time_str, Style::default().fg(TEXT_DIM)),
+    ]);
+    let width = (line.width() as u16).min(tab_area.width);
+    let clock_area = Rect {
+        x: tab_area.right() - width,
+        y: tab_area.y,
+        width,
+        height: tab_area.height.min(1),
+    };
+    f.render_widget(Paragraph::new(line).alignment(Alignment::Right), clock_area);
+}
+
+// ─── Status Bar ──────────────────────────────────────────────────────────────
+
+/// The status bar's message while a bulk download runs.
+fn bulk_progress_message(p: &BulkProgress) -> String {
+    match p.planned {
+        None => format!("Listing the files of {}… (Esc cancels)", p.course),
+        Some((total, bytes)) => format!(
+            "Downloading {} files: {} of {total}, {} of {}{} (Esc cancels)",
+            p.course,
+            p.saved + p.failures.len(),
+            format_size(p.bytes),
+            format_size(bytes),
+            if p.failures.is_empty() { String::new() } else { format!(", {} failed", p.failures.len()) },
+        ),
+    }
+}
+
+fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
+    let (indicator, ind_color) = if app.loading {
+        (spinner_char(app.frame_count), CAUTION)
+    } else {
+        ("●", SUCCESS)
+    };
+
+    // The indicator, a pending count and the sync time always show; the
+    // message and the hints share the rest, hints shortened first.
+    let chord = app.pending_chord.map(|(key, _)| key);
+    let count = match (app.pending_count, chord) {
+        _ if !app.submission_state.is_hidden() => String::new(),
+        _ if app.command_line.is_some() => {
+            format!("   :{}_", app.command_line.as_deref().unwrap_or(""))
+        }
+        (Some(n), Some(key)) => format!("   count {n}{key}_"),
+        (Some(n), None) => format!("   count {n}_"),
+        (None, Some(key)) => format!("   {key}_"),
+        (None, None) => String::new(),
+    };
+    let sync_hint = app
+        .cached_at
+        .map(|t| format!("   synced {}", t.with_timezone(&Local).format("%b %d %H:%M")))
+        .unwrap_or_default();
+    let right = format!("{count}{sync_hint}  ");
+
+    let right_w = (right.width() as u16).min(area.width.saturating_sub(3));
+    let [left_area, right_area] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(right_w)])
+        .areas(area);
+
+    const SEPARATOR: &str = "   │   ";
+    let room = (left_area.width as usize).saturating_sub(3);
+
+    // A clock that is off stays in view, since every countdown depends on
+    // it; shortened when the bar is narrow.
+    let skew = app.clock_skew.map(|skew| {
+        let by = clock::describe(skew);
+        let consequence = if app.correct_clock_skew {
+            "countdowns use the server's time"
+        } else {
+            "deadlines may be misjudged"
+        };
+        let full = format!("system clock differs from server by {by} — {consequence}{SEPARATOR}");
+        if full.width() + MIN_MESSAGE_WIDTH <= room {
+            full
+        } else {
+            format!("⚠ clock off by {by}{SEPARATOR}")
+        }
+    });
+    let skew = skew.unwrap_or_default();
+    let room = room.saturating_sub(skew.width());
+    let essential = hints::essential_width(app, &app.hint_labels) + SEPARATOR.width();
+    let message_room = room.saturating_sub(essential).max(room.min(MIN_MESSAGE_WIDTH));
+    let message = match app.bulk_download.as_ref() {
+        Some(download) => bulk_progress_message(&download.progress),
+        None => app.status_message.clone(),
+    };
+    let message = truncate_to_width(&message, message_room);
+    let hint_room = room.saturating_sub(message.width() + SEPARATOR.width());
+    let hints = hints::status_hints(app, &app.hint_labels, hint_room);
+    let hints = if hints.is_empty() { hints } else { format!("{SEPARATOR}{hints}") };
+
+    let bar = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!(" {} ", indicator),
+            Style::default().fg(ind_color).bg(HDR_BG),
+        ),
+        Span::styled(skew, Style::default().fg(CAUTION).bg(HDR_BG)),
+        Span::styled(message, Style::default().fg(TEXT).bg(HDR_BG)),
+        Span::styled(hints, Style::default().fg(TEXT_MUTED).bg(HDR_BG)),
+    ]))
+    .style(Style::default().bg(HDR_BG));
+    f.render_widget(bar, left_area);
+
+    let right = Paragraph::new(Span::styled(right, Style::default().fg(TEXT_MUTED)))
+        .alignment(Alignment::Right)
+        .style(Style::default().bg(HDR_BG));
+    f.render_widget(right, right_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::test_fixtures;
+    use crate::tui::{DashboardPanel, UnifiedViewMode};
+    use ratatui::{backend::TestBackend, Terminal};
+    use serde_json::json;
+    use std::path::Path;
+
+    /// Each row of `draw`'s output as text.
+    fn draw_with(width: u16, height: u16, draw: impl FnOnce(&mut Frame)) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(draw).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect::<String>())
+            .collect()
+    }
+
+    fn screen(app: &mut App, width: u16, height: u16) -> Vec<String> {
+        draw_with(width, height, |f| render(f, app))
+    }
+
+    /// `lines` without what changes from run to run: the clock row at the
+    /// top, the status bar (its sync time is local) and weekday names.
+    fn stable(lines: &[String]) -> String {
+        let body = &lines[1..lines.len() - 1];
+        let mut text = String::new();
+        for line in body {
+            let mut line = line.trim_end().to_string();
+            for day in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"] {
+                line = line.replace(&format!("{day} "), "Day ");
+            }
+            text.push_str(&line);
+            text.push('\n');
+        }
+        text
+    }
+
+    fn assert_snapshot(name: &str, actual: &str) {
+        test_fixtures::assert_snapshot(&format!("ui/snapshots/{name}.txt"), actual);
+    }
+
+    /// An app showing a small cache: two courses and undated assignments (so
+    /// nothing depends on today's date), one of them graded at noon UTC
+    /// (the same date in nearly every timezone).
+    fn sample_app(dir: &Path) -> App {
+        let mut app = test_fixtures::offline_app(dir);
+        app.load_from_cache(test_fixtures::from_json(json!({
+            "version": 2,
+            "cached_at": "2025-04-01T12:00:00Z",
+            "user": { "id": 1, "name": "Sam" },
+            "courses": [
+                { "id": 1, "name": "Biology", "course_code": "BIO-101" },
+                { "id": 2, "name": "Chemistry", "course_code": "CHEM-110" },
+            ],
+            "assignments": [
+                { "course_id": 1, "name": "Biology", "assignments": [
+                    { "id": 10, "course_id": 1, "name": "Lab report", "published": false,
+                      "points_possible": 10.0, "submission_types": ["online_text_entry"],
+                      "description": "<p>Write up week 3.</p>" },
+                ] },
+                { "course_id": 2, "name": "Chemistry", "assignments": [
+                    { "id": 20, "course_id": 2, "name": "Problem set", "points_possible": 20.0,
+                      "submission_types": ["online_upload"],
+                      "submission": { "workflow_state": "graded", "score": 18.0, "grade": "18",
+                                      "graded_at": "2025-03-20T12:00:00Z" } },
+                ] },
+            ],
+            "peer_reviews": {
+                "10": [{ "id": 1, "workflow_state": "assigned" }],
+                "20": [{ "id": 2, "workflow_state": "assigned" }],
+            },
+        })));
+        app
+    }
+
+    fn lab_report(app: &App) -> (&str, &crate::models::Assignment) {
+        app.get_assignment_by_id(10).unwrap()
+    }
+
+    #[test]
+    fn dashboard_detail_has_no_published_or_reviews_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = sample_app(dir.path());
+        let lines = draw_with(50, 16, |f| {
+            let area = f.area();
+            components::render_assignment_pane(
+                f,
+                &app,
+                area,
+                Some(lab_report(&app)),
+                components::DetailRows::Dashboard,
+            );
+        });
+        assert_eq!(
+            lines,
+            [
+                "╭ Assignment Detail ─────────────────────────────╮",
+                "│                                                │",
+                "│  Lab report                                    │",
+                "│                                                │",
+                "│  Course    ▎ Biology                           │",
+                "│  Due       No due date                         │",
+                "│  Points    10 pts                              │",
+                "│  Types     online_text_entry                   │",
+                "│  Status    ─                                   │",
+                "│  Effort    2h (default, e: set)                │",
+                "│                                                │",
+                "│  ── Description ────────────────────────────── │",
+                "│                                                │",
+                "│  Write up week 3.                              │",
+                "│                                                │",
+                "╰────────────────────────────────────────────────╯",
+            ]
+        );
+    }
+
+    #[test]
+    fn schedule_detail_has_published_and_reviews_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = sample_app(dir.path());
+        let lines = draw_with(50, 12, |f| {
+            let area = f.area();
+            components::render_assignment_pane(
+                f,
+                &app,
+                area,
+                Some(lab_report(&app)),
+                components::DetailRows::All,
+            );
+        });
+        assert_eq!(lines[9], "│  Published No — students can't see it yet      │");
+        assert_eq!(lines[10], "│  Reviews   1 pending  (o: open)                │");
+    }
+
+    #[test]
+    fn empty_detail_pane_shows_the_hint() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = sample_app(dir.path());
+        let lines = draw_with(44, 3, |f| {
+            let area = f.area();
+            components::render_assignment_pane(f, &app, area, None, components::DetailRows::All);
+        });
+        assert_eq!(lines[1], "│  Select an assignment to view details.   │");
+    }
+
+    #[test]
+    fn tab_screens_match_their_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = sample_app(dir.path());
+        for (name, tab) in [
+            ("dashboard", Tab::Dashboard),
+            ("courses", Tab::Courses),
+            ("schedule_calendar", Tab::Assignments),
+            ("announcements", Tab::Announcements),
+        ] {
+            app.active_tab = tab;
+            assert_snapshot(name, &stable(&screen(&mut app, 100, 24)));
+        }
+        app.active_tab = Tab::Assignments;
+        app.unified_view_mode = UnifiedViewMode::ListView;
+        assert_snapshot("schedule_list", &stable(&screen(&mut app, 100, 24)));
+    }
+
+    #[test]
+    fn dashboard_detail_matches_its_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = sample_app(dir.path());
+        app.dashboard_panel = DashboardPanel::RecentlyGraded;
+        assert_snapshot("dashboard_graded", &stable(&screen(&mut app, 100, 30)));
+    }
+
+    #[test]
+    fn popups_match_their_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = sample_app(dir.path());
+        app.active_tab = Tab::Assignments;
+        app.unified_view_mode = UnifiedViewMode::ListView;
+        app.show_course_filter = true;
+        assert_snapshot("course_filter", &stable(&screen(&mut app, 100, 24)));
+        app.show_course_filter = false;
+        app.submission_target = Some((1, 10));
+        app.submission_input = "My answer.\n".into();
+        app.submission_state = crate::tui::SubmissionState::TextPreview;
+        assert_snapshot("text_preview", &stable(&screen(&mut app, 100, 24)));
+        app.request_quit();
+        assert_snapshot("quit_confirm", &stable(&screen(&mut app, 100, 24)));
+    }
+
+    #[test]
+    fn status_bar_shows_the_message_and_hints() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = sample_app(dir.path());
+        app.status_message = "Synced.".into();
+        let lines = screen(&mut app, 100, 24);
+        let bar = &lines[23];
+        assert!(bar.starts_with(" ● Synced."), "{bar}");
+        assert!(bar.contains("q quit"), "{bar}");
+        assert!(bar.trim_end().ends_with(&format!(
+            "synced {}",
+            app.cached_at.unwrap().with_timezone(&Local).format("%b %d %H:%M")
+        )), "{bar}");
+    }
+
+    /// A named change to `sample_app` that shows one screen or popup.
+    type View = (&'static str, fn(&mut App));
+
+    /// Every screen and popup.
+    fn every_view() -> Vec<View> {
+        use crate::tui::{EventForm, EventLinkPicker, SubmissionState};
+
+        fn submitting(app: &mut App, state: SubmissionState) {
+            app.active_tab = Tab::Assignments;
+            app.submission_target = Some((1, 10));
+            app.submission_input = "My answer, with ünïcode.\n".into();
+            app.submission_state = state;
+        }
+        vec![
+            ("dashboard", |app| app.active_tab = Tab::Dashboard),
+            ("courses", |app| app.active_tab = Tab::Courses),
+            ("schedule calendar", |app| app.active_tab = Tab::Assignments),
+            ("schedule list", |app| {
+                app.active_tab = Tab::Assignments;
+                app.unified_view_mode = UnifiedViewMode::ListView;
+            }),
+            ("announcements", |app| app.active_tab = Tab::Announcements),
+            ("command line", |app| app.command_line = Some("export md ~/report.md".into())),
+            ("course filter", |app| {
+                app.active_tab = Tab::Assignments;
+                app.show_course_filter = true;
+            }),
+            ("dashboard course filter", |app| app.show_course_filter = true),
+            ("date jump", |app| {
+                app.active_tab = Tab::Assignments;
+                app.date_jump_input = Some("next fri".into());
+                app.date_jump_error = Some("Not a date".into());
+            }),
+            ("event form", |app| {
+                app.active_tab = Tab::Assignments;
+                app.event_form = Some(EventForm {
+                    error: Some("Title is required".into()),
+                    ..EventForm::default()
+                });
+            }),
+            ("course pages", |app| {
+                app.active_tab = Tab::Courses;
+                app.show_course_pages_picker = true;
+            }),
+            ("link picker", |app| {
+                app.link_picker = Some(vec![crate::tui::links::Link {
+                    text: "Syllabus".into(),
+                    url: "https://canvas.example.edu/files/1".into(),
+                    is_image: false,
+                }]);
+            }),
+            ("term picker", |app| app.open_term_picker()),
+            ("event link picker", |app| {
+                app.event_link_picker = Some(EventLinkPicker {
+                    event_id: 1,
+                    event_title: "Midterm".into(),
+                    start_at: chrono::Utc::now(),
+                    candidates: vec![(10, "Lab report".into())],
+                });
+            }),
+            ("sync stats", |app| {
+                app.last_sync_stats = Some(Default::default());
+                app.show_sync_stats = true;
+            }),
+            ("sync history", |app| {
+                app.show_sync_stats = true;
+                app.sync_stats_history = true;
+            }),
+            ("profile", |app| app.show_profile = true),
+            ("bulk summary", |app| {
+                app.bulk_summary = Some(crate::tui::BulkProgress {
+                    course: "Biology".into(),
+                    planned: Some((3, 4096)),
+                    failures: vec![("notes.pdf".into(), "Forbidden".into())],
+                    ..Default::default()
+                });
+            }),
+            ("estimate prompt", |app| {
+                app.active_tab = Tab::Assignments;
+                app.open_estimate_prompt();
+            }),
+            ("type picker", |app| submitting(app, SubmissionState::TypePicker)),
+            ("url input", |app| submitting(app, SubmissionState::UrlInput)),
+            ("file picker", |app| submitting(app, SubmissionState::FilePicker)),
+            ("file input", |app| submitting(app, SubmissionState::FileInput)),
+            ("text preview", |app| submitting(app, SubmissionState::TextPreview)),
+            ("confirming", |app| submitting(app, SubmissionState::Confirming)),
+            ("discussion context", |app| submitting(app, SubmissionState::DiscussionContext)),
+            ("submitting", |app| submitting(app, SubmissionState::Submitting)),
+            ("done", |app| {
+                let state = SubmissionState::Done {
+                    success: false,
+                    message: "Canvas said no.".into(),
+                    receipt: None,
+                };
+                submitting(app, state);
+            }),
+            ("quit confirm", |app| {
+                submitting(app, SubmissionState::TextPreview);
+                app.request_quit();
+            }),
+        ]
+    }
+
+    #[test]
+    fn every_view_draws_in_tiny_and_flat_terminals() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, open) in every_view() {
+            let mut app = sample_app(dir.path());
+            open(&mut app);
+            for (width, height) in [(0, 0), (1, 1), (10, 3), (200, 2), (3, 200), (20, 6)] {
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    screen(&mut app, width, height)
+                }));
+                let lines = result.unwrap_or_else(|_| panic!("{name} at {width}x{height} panicked"));
+                assert_eq!(lines.len(), height as usize, "{name} at {width}x{height}");
+            }
+        }
+    }
+
+    #[test]
+    fn countdowns_count_from_the_given_time() {
+        let now = chrono::Utc::now();
+        let due = now + chrono::Duration::minutes(3 * 24 * 60 + 4 * 60 + 12);
+        assert_eq!(countdown_timer(due, now), ("3d 4h 12m".into(), TRUECOLOR.days_left));
+        // A clock 41 minutes slow, corrected.
+        let corrected = now + chrono::Duration::minutes(41);
+        assert_eq!(countdown_timer(due, corrected).0, "3d 3h 31m");
+        assert_eq!(countdown_timer(now, corrected), ("Past due".into(), DANGER));
+    }
+
+    #[test]
+    fn status_bar_warns_about_clock_skew() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = sample_app(dir.path());
+        app.clock_skew = Some(chrono::Duration::minutes(-41));
+        let bar = screen(&mut app, 160, 24).remove(23);
+        assert!(
+            bar.contains("system clock differs from server by 41m — deadlines may be misjudged"),
+            "{bar}"
+        );
+        app.correct_clock_skew = true;
+        let bar = screen(&mut app, 160, 24).remove(23);
+        assert!(bar.contains("by 41m — countdowns use the server's time"), "{bar}");
+        let bar = screen(&mut app, 60, 24).remove(23);
+        assert!(bar.contains("⚠ clock off by 41m"), "{bar}");
+        app.clock_skew = None;
+        let bar = screen(&mut app, 160, 24).remove(23);
+        assert!(!bar.contains("clock"), "{bar}");
+    }
+}
@@ -0,0 +1,1473 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use super::components::{
+    course_stripe, field_rows, format_ago, format_size, pad_to_width, popup_rect, selectable_row,
+    strip_html, tail_to_width, truncate_to_width, RowStyle,
+};
+use super::{
+    spinner_char, AMBER, AMBER_SOFT, CAUTION, DANGER, INFO, SUCCESS, TEXT, TEXT_DIM, TEXT_MUTED,
+};
+use crate::models::Submission;
+use crate::tui::sync_stats::format_duration;
+use crate::tui::text_stats::{text_stats, text_warnings};
+use crate::tui::time_budget::format_hours;
+use crate::tui::{
+    App, EventForm, SubmissionGroup, SubmissionKind, SubmissionState, SubmitReceipt,
+};
+use chrono::Local;
+use unicode_width::UnicodeWidthStr;
+
+// ─── Submission Modal ─────────────────────────────────────────────────────────
+
+pub(super) fn render_submission_modal(f: &mut Frame, app: &mut App, area: Rect) {
+    match &app.submission_state {
+        SubmissionState::TypePicker => render_type_picker(f, app, area),
+        SubmissionState::UrlInput => render_text_input_modal(
+            f,
+            area,
+            " Submit URL ",
+            "Enter the URL to submit:",
+            &app.submission_input,
+            app.submission_url_error.as_deref(),
+            "Enter to confirm  ·  Esc to go back",
+        ),
+        SubmissionState::FilePicker => render_file_picker(f, app, area),
+        SubmissionState::FileInput => render_text_input_modal(
+            f,
+            area,
+            " Submit File ",
+            "Enter the full file path:",
+            &app.submission_input,
+            None,
+            "Enter to confirm  ·  Esc to go back",
+        ),
+        SubmissionState::TextPreview => render_text_preview(f, app, area),
+        SubmissionState::Confirming => render_confirm_modal(f, app, area),
+        SubmissionState::DiscussionContext => render_discussion_context(f, app, area),
+        SubmissionState::Submitting => render_submitting_modal(f, app, area),
+        SubmissionState::Done { success, message, receipt } => {
+            render_done_modal(f, area, *success, message.clone(), receipt.as_deref())
+        }
+        SubmissionState::Hidden => {}
+    }
+}
+
+fn render_type_picker(f: &mut Frame, app: &mut App, area: Rect) {
+    let kinds = &app.submission_supported_kinds;
+    let header = preflight_lines(app);
+    let h = (kinds.len() as u16)
+        .saturating_add(header.len() as u16)
+        .saturating_add(6)
+        .min(area.height.saturating_sub(2));
+    let w = 64u16.min(area.width.saturating_sub(4));
+    let popup = popup_rect(w, h, area);
+
+    f.render_widget(Clear, popup);
+
+    let items: Vec<ListItem> = kinds
+        .iter()
+        .enumerate()
+        .map(|(i, kind)| {
+            let is_sel = i == app.submission_type_state.selected;
+            let RowStyle { marker, marker_fg, bg } = selectable_row(is_sel, false);
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {marker} "),
+                    Style::default().fg(marker_fg).bg(bg),
+                ),
+                Span::styled(
+                    kind.label(),
+                    Style::default()
+                        .fg(if is_sel { TEXT } else { TEXT_DIM })
+                        .bg(bg)
+                        .add_modifier(if is_sel { Modifier::BOLD } else { Modifier::empty() }),
+                ),
+            ]))
+        })
+        .collect();
+
+    app.submission_type_state
+        .inner
+        .select(Some(app.submission_type_state.selected));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(AMBER_SOFT))
+        .title(" Submit Assignment ")
+        .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+        .title_bottom(Line::from(vec![
+            Span::styled(" j/k ", Style::default().fg(AMBER_SOFT)),
+            Span::styled("move  ", Style::default().fg(TEXT_DIM)),
+            Span::styled("Enter ", Style::default().fg(AMBER_SOFT)),
+            Span::styled("select  ", Style::default().fg(TEXT_DIM)),
+            Span::styled("Esc ", Style::default().fg(AMBER_SOFT)),
+            Span::styled("cancel ", Style::default().fg(TEXT_DIM)),
+        ]));
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    // What the assignment accepts, then a blank line, then the choices.
+    let header_h = (header.len() as u16 + 1).min(inner.height);
+    let header_area = Rect { height: header_h, ..inner };
+    let list_area = Rect {
+        y: inner.y + header_h,
+        height: inner.height - header_h,
+        ..inner
+    };
+    f.render_widget(Paragraph::new(header), header_area);
+    f.render_stateful_widget(List::new(items), list_area, &mut app.submission_type_state.inner);
+}
+
+/// The type picker's header: each submission type the assignment accepts,
+/// marked by whether it can be done here (and why not), then the allowed
+/// file types and attempts.
+fn preflight_lines(app: &App) -> Vec<Line<'static>> {
+    let preflight = &app.submission_preflight;
+    let mut lines: Vec<Line> = preflight
+        .types
+        .iter()
+        .map(|t| {
+            let (mark, color) = if t.kind.is_some() { ("✓", SUCCESS) } else { ("✗", TEXT_MUTED) };
+            let mut spans = vec![
+                Span::styled(format!(" {mark} "), Style::default().fg(color)),
+                Span::styled(t.label.clone(), Style::default().fg(TEXT_DIM)),
+            ];
+            if let Some(reason) = t.reason {
+                spans.push(Span::styled(format!(" — {reason}"), Style::default().fg(TEXT_MUTED)));
+            }
+            Line::from(spans)
+        })
+        .collect();
+    if let Some(files) = preflight.extensions_line() {
+        lines.push(Line::styled(format!("   {files}"), Style::default().fg(TEXT_DIM)));
+    }
+    if let Some(attempts) = preflight.attempts_line() {
+        let line = if preflight.out_of_attempts() {
+            Line::styled(format!("   {attempts}; Canvas will refuse another"), Style::default().fg(DANGER))
+        } else {
+            Line::styled(format!("   {attempts}"), Style::default().fg(TEXT_DIM))
+        };
+        lines.push(line);
+    }
+    lines
+}
+
+fn render_file_picker(f: &mut Frame, app: &mut App, area: Rect) {
+    let files = &app.recent_files;
+    let h = (files.len() as u16).saturating_add(2).min(area.height.saturating_sub(2));
+    let w = 76u16.min(area.width.saturating_sub(4));
+    let popup = popup_rect(w, h, area);
+
+    f.render_widget(Clear, popup);
+
+    // Name, then size and age in fixed columns on the right.
+    let name_w = (w as usize).saturating_sub(4 + 3 + 10 + 12);
+    let items: Vec<ListItem> = files
+        .iter()
+        .enumerate()
+        .map(|(i, file)| {
+            let is_sel = i == app.recent_file_state.selected;
+            let RowStyle { marker, marker_fg, bg } = selectable_row(is_sel, false);
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {marker} "), Style::default().fg(marker_fg).bg(bg)),
+                Span::styled(
+                    pad_to_width(&truncate_to_width(&file.name(), name_w), name_w),
+                    Style::default()
+                        .fg(if is_sel { TEXT } else { TEXT_DIM })
+                        .bg(bg)
+                        .add_modifier(if is_sel { Modifier::BOLD } else { Modifier::empty() }),
+                ),
+                Span::styled(
+                    format!("{:>10}", format_size(file.size)),
+                    Style::default().fg(TEXT_DIM).bg(bg),
+                ),
+                Span::styled(
+                    format!("{:>12}", format_ago(file.modified.into())),
+                    Style::default().fg(TEXT_DIM).bg(bg),
+                ),
+            ]))
+        })
+        .collect();
+
+    app.recent_file_state
+        .inner
+        .select(Some(app.recent_file_state.selected));
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(AMBER_SOFT))
+            .title(" Submit File — Recent Files ")
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+            .title_bottom(Line::from(vec![
+                Span::styled(" j/k ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("move  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("Enter ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("select  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("/ ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("type a path  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("Esc ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("back ", Style::default().fg(TEXT_DIM)),
+            ])),
+    );
+
+    f.render_stateful_widget(list, popup, &mut app.recent_file_state.inner);
+}
+
+fn render_text_input_modal(
+    f: &mut Frame,
+    area: Rect,
+    title: &str,
+    prompt: &str,
+    input: &str,
+    error: Option<&str>,
+    footer: &str,
+) {
+    let popup = popup_rect(70, if error.is_some() { 10 } else { 8 }, area);
+    f.render_widget(Clear, popup);
+
+    // Truncate from the left if input is too wide for the box
+    // Keep the end of the input, where the cursor is, and the cursor itself.
+    let inner_w = popup.width.saturating_sub(4) as usize;
+    let cursor_line = format!("{}_", tail_to_width(input, inner_w.saturating_sub(1)));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(prompt, Style::default().fg(TEXT_DIM))),
+        Line::from(""),
+        Line::from(Span::styled(cursor_line, Style::default().fg(TEXT).add_modifier(Modifier::BOLD))),
+    ];
+    if let Some(error) = error {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            truncate_to_width(error, inner_w),
+            Style::default().fg(DANGER),
+        )));
+    }
+    let para = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(AMBER_SOFT))
+            .title(title)
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+            .title_bottom(Line::from(Span::styled(
+                format!(" {footer} "),
+                Style::default().fg(TEXT_DIM),
+            ))),
+    );
+
+    f.render_widget(para, popup);
+}
+
+fn render_text_preview(f: &mut Frame, app: &App, area: Rect) {
+    let popup = popup_rect(72, 22, area);
+    f.render_widget(Clear, popup);
+
+    let is_reply = app.submission_kind == Some(SubmissionKind::DiscussionReply);
+    let content = &app.submission_input;
+    let inner_w = popup.width.saturating_sub(4) as usize;
+    let stats = text_stats(content);
+    let warnings = text_warnings(content);
+    let max_lines = popup.height.saturating_sub(8 + warnings.len() as u16) as usize;
+
+    let preview_lines: Vec<Line> = content
+        .lines()
+        .take(max_lines)
+        .map(|l| {
+            Line::from(Span::styled(
+                format!("  {}", truncate_to_width(l, inner_w)),
+                Style::default().fg(TEXT_DIM),
+            ))
+        })
+        .collect();
+
+    let total_lines = content.lines().count();
+    let truncation_note = if total_lines > max_lines {
+        format!("  … ({} more lines not shown)", total_lines - max_lines)
+    } else {
+        String::new()
+    };
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                if is_reply { "  Preview of your reply:" } else { "  Preview of text to submit:" },
+                Style::default().fg(AMBER_SOFT),
+            ),
+            Span::styled(
+                format!(
+                    "  {} word{} · {} character{}",
+                    stats.words,
+                    if stats.words == 1 { "" } else { "s" },
+                    stats.chars,
+                    if stats.chars == 1 { "" } else { "s" },
+                ),
+                Style::default().fg(TEXT_DIM),
+            ),
+        ]),
+        Line::from(Span::styled(
+            "  ──────────────────────────────────────────────────────────",
+            Style::default().fg(TEXT_MUTED),
+        )),
+    ];
+    lines.extend(preview_lines);
+    if !truncation_note.is_empty() {
+        lines.push(Line::from(Span::styled(
+            truncation_note,
+            Style::default().fg(TEXT_MUTED),
+        )));
+    }
+    lines.push(Line::from(""));
+    if let Some(group_line) = submission_group_line(app) {
+        lines.push(group_line);
+    }
+    for warning in warnings {
+        lines.push(Line::from(Span::styled(
+            format!("  ⚠ {warning}"),
+            Style::default().fg(CAUTION),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        "  ──────────────────────────────────────────────────────────",
+        Style::default().fg(TEXT_MUTED),
+    )));
+    lines.push(Line::from(vec![
+        Span::styled(
+            if is_reply { "  Post this reply?  " } else { "  Submit this text?  " },
+            Style::default().fg(TEXT),
+        ),
+        Span::styled("y ", Style::default().fg(SUCCESS).add_modifier(Modifier::BOLD)),
+        Span::styled("yes  ", Style::default().fg(TEXT_DIM)),
+        Span::styled("n ", Style::default().fg(DANGER).add_modifier(Modifier::BOLD)),
+        Span::styled("no / re-edit", Style::default().fg(TEXT_DIM)),
+    ]));
+
+    let para = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(AMBER_SOFT))
+                .title(if is_reply {
+                    " Discussion Reply — Confirm "
+                } else {
+                    " Text Entry — Confirm Submission "
+                })
+                .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD)),
+        );
+
+    f.render_widget(para, popup);
+}
+
+fn render_discussion_context(f: &mut Frame, app: &App, area: Rect) {
+    let popup = popup_rect(80, area.height.saturating_sub(4), area);
+    f.render_widget(Clear, popup);
+
+    let mut lines: Vec<Line> = Vec::new();
+    match &app.discussion_entries {
+        None => lines.push(Line::from(Span::styled(
+            format!("  {} Loading replies…", spinner_char(app.frame_count)),
+            Style::default().fg(TEXT_DIM),
+        ))),
+        Some(Err(e)) => lines.push(Line::from(Span::styled(
+            format!("  Couldn't load replies: {e}"),
+            Style::default().fg(CAUTION),
+        ))),
+        Some(Ok(entries)) if entries.is_empty() => lines.push(Line::from(Span::styled(
+            "  No replies yet — you're first.",
+            Style::default().fg(TEXT_DIM),
+        ))),
+        Some(Ok(entries)) => {
+            let mut entries: Vec<_> = entries.iter().collect();
+            entries.sort_by_key(|e| e.created_at);
+            for entry in entries {
+                let when = entry
+                    .created_at
+                    .map(|d| d.with_timezone(&Local).format("%b %d %H:%M").to_string())
+                    .unwrap_or_default();
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {}", entry.user_name.as_deref().unwrap_or("Unknown")),
+                        Style::default().fg(AMBER_SOFT).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("  {when}"), Style::default().fg(TEXT_MUTED)),
+                ]));
+                let text = strip_html(entry.message.as_deref().unwrap_or(""));
+                for line in text.lines() {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {line}"),
+                        Style::default().fg(TEXT),
+                    )));
+                }
+                lines.push(Line::from(""));
+            }
+        }
+    }
+
+    let para = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((app.discussion_scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(AMBER_SOFT))
+                .title(" Discussion — existing replies ")
+                .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+                .title_bottom(Line::from(vec![
+                    Span::styled(" j/k", Style::default().fg(AMBER_SOFT)),
+                    Span::styled(" scroll  ", Style::default().fg(TEXT_DIM)),
+                    Span::styled("enter", Style::default().fg(AMBER_SOFT)),
+                    Span::styled(" write reply  ", Style::default().fg(TEXT_DIM)),
+                    Span::styled("esc", Style::default().fg(AMBER_SOFT)),
+                    Span::styled(" back ", Style::default().fg(TEXT_DIM)),
+                ])),
+        );
+    f.render_widget(para, popup);
+}
+
+/// "Submitting for group: …" (or why not) for group assignments.
+fn submission_group_line(app: &App) -> Option<Line<'static>> {
+    let (text, color) = match &app.submission_group {
+        SubmissionGroup::Individual => return None,
+        SubmissionGroup::Checking => ("Checking group membership…".to_string(), TEXT_DIM),
+        SubmissionGroup::Member(name) => (format!("Submitting for group: {name}"), INFO),
+        SubmissionGroup::NotMember => (
+            "You're not in a group for this assignment — submission blocked".to_string(),
+            DANGER,
+        ),
+        SubmissionGroup::Unknown(e) => (format!("Couldn't check your group ({e})"), CAUTION),
+    };
+    Some(Line::from(Span::styled(format!("  {text}"), Style::default().fg(color))))
+}
+
+fn render_confirm_modal(f: &mut Frame, app: &App, area: Rect) {
+    let group_line = submission_group_line(app);
+    // A URL is shown whole, so make room for the lines it wraps onto.
+    let url_lines = if app.submission_kind == Some(SubmissionKind::Url) {
+        (app.submission_input.width() / 52) as u16
+    } else {
+        0
+    };
+    let h = if group_line.is_some() { 14 } else { 12 } + url_lines;
+    let popup = popup_rect(66, h, area);
+    f.render_widget(Clear, popup);
+
+    let kind_label = match &app.submission_kind {
+        Some(k) => k.label(),
+        None => "Unknown",
+    };
+
+    let inner_w = popup.width.saturating_sub(6) as usize;
+    let content = if app.submission_kind == Some(SubmissionKind::Url) {
+        // The whole URL, wrapped, with the host picked out so a wrong
+        // site stands out.
+        url_spans(&app.submission_input)
+    } else {
+        vec![Span::styled(
+            tail_to_width(&app.submission_input, inner_w),
+            Style::default().fg(TEXT).add_modifier(Modifier::BOLD),
+        )]
+    };
+
+    let mut content_line = vec![Span::styled("  Content ", Style::default().fg(AMBER_SOFT))];
+    content_line.extend(content);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Type    ", Style::default().fg(AMBER_SOFT)),
+            Span::styled(kind_label, Style::default().fg(TEXT)),
+        ]),
+        Line::from(""),
+        Line::from(content_line),
+        Line::from(""),
+    ];
+    if let Some(group_line) = group_line {
+        lines.push(group_line);
+        lines.push(Line::from(""));
+    }
+    lines.extend([
+        Line::from(Span::styled(
+            "  ────────────────────────────────────────────────────────",
+            Style::default().fg(TEXT_MUTED),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  Submit this?  ", Style::default().fg(TEXT)),
+            Span::styled("y ", Style::default().fg(SUCCESS).add_modifier(Modifier::BOLD)),
+            Span::styled("yes  ", Style::default().fg(TEXT_DIM)),
+            Span::styled("n ", Style::default().fg(DANGER).add_modifier(Modifier::BOLD)),
+            Span::styled("no / go back", Style::default().fg(TEXT_DIM)),
+        ]),
+    ]);
+
+    let para = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(AMBER_SOFT))
+                .title(" Confirm Submission ")
+                .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD)),
+        );
+
+    f.render_widget(para, popup);
+}
+
+/// `url` with its host in amber and the rest plain.
+fn url_spans(url: &str) -> Vec<Span<'static>> {
+    let plain = Style::default().fg(TEXT);
+    let host = url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string))
+        .and_then(|host| url.find(&host).map(|at| (at, host.len())));
+    match host {
+        Some((at, len)) => vec![
+            Span::styled(url[..at].to_string(), plain),
+            Span::styled(
+                url[at..at + len].to_string(),
+                Style::default().fg(AMBER).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(url[at + len..].to_string(), plain),
+        ],
+        None => vec![Span::styled(url.to_string(), plain.add_modifier(Modifier::BOLD))],
+    }
+}
+
+fn render_submitting_modal(f: &mut Frame, app: &App, area: Rect) {
+    let popup = popup_rect(40, 5, area);
+    f.render_widget(Clear, popup);
+
+    let spin = spinner_char(app.frame_count);
+    let label = if app.submission_cancelling {
+        "Cancelling…"
+    } else {
+        "Submitting…"
+    };
+    let para = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("  {spin}  "), Style::default().fg(CAUTION)),
+            Span::styled(label, Style::default().fg(TEXT).add_modifier(Modifier::BOLD)),
+        ]),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(CAUTION))
+            .title(" Submitting ")
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+            .title_bottom(Line::from(vec![
+                Span::styled(" Esc ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("cancel ", Style::default().fg(TEXT_DIM)),
+            ])),
+    );
+
+    f.render_widget(para, popup);
+}
+
+fn render_done_modal(
+    f: &mut Frame,
+    area: Rect,
+    success: bool,
+    message: String,
+    receipt: Option<&SubmitReceipt>,
+) {
+    let warning = receipt.is_some_and(|r| r.attempt_unchanged);
+    let (icon, heading, title, border_color) = if warning {
+        ("!", "Check submission", " Not Confirmed ", CAUTION)
+    } else if success {
+        ("✓", "Done", " Submitted ", SUCCESS)
+    } else {
+        ("✗", "Error", " Submission Failed ", DANGER)
+    };
+    let msg_color = border_color;
+
+    let popup_w = 62u16;
+    let inner_w = popup_w.saturating_sub(6) as usize;
+    let wrapped: Vec<Line> = message
+        .chars()
+        .collect::<Vec<_>>()
+        .chunks(inner_w)
+        .map(|chunk| {
+            Line::from(Span::styled(
+                format!("  {}", chunk.iter().collect::<String>()),
+                Style::default().fg(msg_color),
+            ))
+        })
+        .collect();
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("  {icon}  "), Style::default().fg(border_color).add_modifier(Modifier::BOLD)),
+            Span::styled(heading, Style::default().fg(border_color).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(""),
+    ];
+    lines.extend(wrapped);
+    if let Some(receipt) = receipt {
+        lines.push(Line::from(""));
+        lines.extend(receipt_rows(&receipt.submission, inner_w.saturating_sub(10)));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Press any key to close",
+        Style::default().fg(TEXT_MUTED),
+    )));
+    let popup = popup_rect(popup_w, (lines.len() as u16).saturating_add(2), area);
+    f.render_widget(Clear, popup);
+
+    let para = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(border_color))
+                .title(title)
+                .title_style(
+                    Style::default()
+                        .fg(border_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+        );
+
+    f.render_widget(para, popup);
+}
+
+/// What Canvas recorded for a submission: the attempt, when, and the URL,
+/// the start of the text or the file names, each cut to `width` columns.
+fn receipt_rows(submission: &Submission, width: usize) -> Vec<Line<'static>> {
+    let value = Style::default().fg(TEXT);
+    let mut fields = Vec::new();
+    if let Some(attempt) = submission.attempt {
+        fields.push(("Attempt", attempt.to_string(), value));
+    }
+    if let Some(at) = submission.submitted_at {
+        let at = at.with_timezone(&Local).format("%b %d %H:%M").to_string();
+        fields.push(("Submitted", at, value));
+    }
+    if let Some(url) = submission.url.as_deref() {
+        fields.push(("URL", truncate_to_width(url, width), Style::default().fg(INFO)));
+    }
+    if let Some(body) = submission.body.as_deref() {
+        let text = strip_html(body).split_whitespace().collect::<Vec<_>>().join(" ");
+        fields.push(("Text", truncate_to_width(&text, width), Style::default().fg(TEXT_DIM)));
+    }
+    if !submission.attachments.is_empty() {
+        let names: Vec<&str> = submission.attachments.iter().map(|a| a.name()).collect();
+        fields.push(("Files", truncate_to_width(&names.join(", "), width), value));
+    }
+    field_rows(&fields)
+}
+
+// ─── Course Filter Popup ─────────────────────────────────────────────────────
+
+pub(super) fn render_event_form(f: &mut Frame, app: &App, area: Rect) {
+    let Some(form) = app.event_form.as_ref() else {
+        return;
+    };
+    let popup_w = 60.min(area.width.saturating_sub(4));
+    let popup_h = form.fields.len() as u16 + 5;
+    let popup_area = popup_rect(popup_w, popup_h, area);
+
+    f.render_widget(Clear, popup_area);
+
+    let label_style = Style::default().fg(AMBER_SOFT);
+    let mut lines = vec![Line::from("")];
+    for (i, value) in form.fields.iter().enumerate() {
+        let focused = i == form.focus && !form.saving;
+        let mut spans = vec![
+            Span::styled(if focused { " ▶ " } else { "   " }, Style::default().fg(AMBER)),
+            Span::styled(format!("{:<7}", EventForm::LABELS[i]), label_style),
+        ];
+        if value.is_empty() && !focused {
+            spans.push(Span::styled(EventForm::PLACEHOLDERS[i], Style::default().fg(TEXT_MUTED)));
+        } else {
+            spans.push(Span::styled(value.clone(), Style::default().fg(TEXT)));
+        }
+        if focused {
+            spans.push(Span::styled("█", Style::default().fg(AMBER_SOFT)));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+    lines.push(match (&form.error, form.saving) {
+        (_, true) => Line::from(Span::styled("   Saving…", Style::default().fg(INFO))),
+        (Some(e), _) => Line::from(Span::styled(format!("   {e}"), Style::default().fg(DANGER))),
+        (None, _) => Line::from(""),
+    });
+
+    let modal = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(AMBER_SOFT))
+            .title(" New calendar event ")
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+            .title_bottom(Line::from(vec![
+                Span::styled(" tab", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" next field  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("enter", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" save  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("esc", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" cancel ", Style::default().fg(TEXT_DIM)),
+            ])),
+    );
+    f.render_widget(modal, popup_area);
+}
+
+pub(super) fn render_date_jump_prompt(f: &mut Frame, app: &App, area: Rect) {
+    let input = app.date_jump_input.as_deref().unwrap_or("");
+    let popup_w = 52.min(area.width.saturating_sub(4));
+    let popup_area = popup_rect(popup_w, 4, area);
+
+    f.render_widget(Clear, popup_area);
+
+    let (hint, hint_color) = match &app.date_jump_error {
+        Some(e) => (e.as_str(), DANGER),
+        None => ("apr 14 · 2025-04-14 · 4/14 · +2w · next mon", TEXT_DIM),
+    };
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(" › ", Style::default().fg(AMBER)),
+            Span::styled(input.to_string(), Style::default().fg(TEXT)),
+            Span::styled("█", Style::default().fg(AMBER_SOFT)),
+        ]),
+        Line::from(Span::styled(format!("   {hint}"), Style::default().fg(hint_color))),
+    ];
+
+    let prompt = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(AMBER_SOFT))
+            .title(" Jump to date ")
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+            .title_bottom(Line::from(vec![
+                Span::styled(" enter", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" jump  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("esc", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" cancel ", Style::default().fg(TEXT_DIM)),
+            ])),
+    );
+    f.render_widget(prompt, popup_area);
+}
+
+pub(super) fn render_quit_confirm(f: &mut Frame, app: &App, area: Rect) {
+    let (question, consequence) = if app.submission_rx.is_some() {
+        ("A submission is in progress — quit anyway?", "It may or may not complete.")
+    } else {
+        ("Your text hasn't been submitted — quit anyway?", "The text will be lost.")
+    };
+    let popup = popup_rect(56, 7, area);
+    f.render_widget(Clear, popup);
+
+    let para = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {question}"),
+            Style::default().fg(TEXT).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(format!("  {consequence}"), Style::default().fg(TEXT_DIM))),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  y ", Style::default().fg(DANGER).add_modifier(Modifier::BOLD)),
+            Span::styled("quit  ", Style::default().fg(TEXT_DIM)),
+            Span::styled("N ", Style::default().fg(SUCCESS).add_modifier(Modifier::BOLD)),
+            Span::styled("stay", Style::default().fg(TEXT_DIM)),
+        ]),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(CAUTION))
+            .title(" Quit? ")
+            .title_style(Style::default().fg(CAUTION).add_modifier(Modifier::BOLD)),
+    );
+    f.render_widget(para, popup);
+}
+
+pub(super) fn render_estimate_prompt(f: &mut Frame, app: &App, area: Rect) {
+    let Some(prompt) = &app.estimate_prompt else {
+        return;
+    };
+    let popup_area = popup_rect(52.min(area.width.saturating_sub(4)), 4, area);
+
+    f.render_widget(Clear, popup_area);
+
+    let default_hint = format!(
+        "2 · 1.5h · 45m — empty for the default ({})",
+        format_hours(app.estimate_default_hours)
+    );
+    let (hint, hint_color) = match &prompt.error {
+        Some(e) => (e.clone(), DANGER),
+        None => (default_hint, TEXT_DIM),
+    };
+    let title_w = popup_area.width.saturating_sub(16) as usize;
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(" › ", Style::default().fg(AMBER)),
+            Span::styled(prompt.input.clone(), Style::default().fg(TEXT)),
+            Span::styled("█", Style::default().fg(AMBER_SOFT)),
+        ]),
+        Line::from(Span::styled(format!("   {hint}"), Style::default().fg(hint_color))),
+    ];
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(AMBER_SOFT))
+            .title(format!(
+                " Effort: {} ",
+                truncate_to_width(&prompt.assignment_name, title_w)
+            ))
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+            .title_bottom(Line::from(vec![
+                Span::styled(" enter", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" save  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("esc", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" cancel ", Style::default().fg(TEXT_DIM)),
+            ])),
+    );
+    f.render_widget(widget, popup_area);
+}
+
+pub(super) fn render_course_filter_popup(f: &mut Frame, app: &mut App, area: Rect) {
+    let courses = app.assignment_courses();
+    let count = courses.len();
+    if count == 0 {
+        return;
+    }
+
+    let counts: Vec<_> = courses
+        .iter()
+        .map(|(id, _)| app.course_filter_counts(*id))
+        .collect();
+    let labels: Vec<String> = counts.iter().map(|c| c.label()).collect();
+
+    // Size the popup: width based on longest name and counts, height based on item count
+    let max_name_len = courses
+        .iter()
+        .zip(&labels)
+        .map(|((_, n), l)| n.chars().count() + 1 + l.chars().count())
+        .max()
+        .unwrap_or(10);
+    let popup_w = (max_name_len as u16).saturating_add(12).min(area.width.saturating_sub(4)); // " [x]  name (counts) "
+    let popup_h = (count as u16).saturating_add(4); // items + border + header + footer
+    let popup_area = popup_rect(popup_w, popup_h, area);
+
+    f.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = courses
+        .iter()
+        .enumerate()
+        .map(|(i, (id, name))| {
+            let is_selected = i == app.filter_list_state.selected;
+            let enabled = app.course_filter_enabled(*id);
+            let idle = counts[i].upcoming == 0;
+            let RowStyle { marker, marker_fg, bg } = selectable_row(is_selected, false);
+
+            let checkbox = if enabled { "[●]" } else { "[ ]" };
+            let check_color = if enabled { SUCCESS } else { TEXT_MUTED };
+
+            ListItem::new(Line::from(vec![
+                course_stripe(app, Some(*id), bg),
+                Span::styled(
+                    format!("{} ", marker),
+                    Style::default().fg(marker_fg).bg(bg),
+                ),
+                Span::styled(
+                    format!("{} ", checkbox),
+                    Style::default().fg(check_color).bg(bg),
+                ),
+                Span::styled(
+                    name.to_string(),
+                    Style::default()
+                        .fg(if enabled && !idle { TEXT } else { TEXT_DIM })
+                        .bg(bg)
+                        .add_modifier(if is_selected {
+                            Modifier::BOLD
+                        } else {
+                            Modifier::empty()
+                        }),
+                ),
+                Span::styled(
+                    format!(" {}", labels[i]),
+                    Style::default()
+                        .fg(if idle { TEXT_MUTED } else { TEXT_DIM })
+                        .bg(bg),
+                ),
+            ]))
+        })
+        .collect();
+
+    let filter_label = if app.course_filter_none {
+        "none".to_string()
+    } else if app.course_filter.is_empty() {
+        "all".to_string()
+    } else {
+        format!("{}/{}", app.course_filter.len(), count)
+    };
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(AMBER_SOFT))
+            .title(format!(" Filter Courses ({}) ", filter_label))
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+            .title_bottom(Line::from(vec![
+                Span::styled(" space", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" toggle  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("a", Style::default().fg(AMBER_SOFT)),
+                Span::styled("/", Style::default().fg(TEXT_DIM)),
+                Span::styled("n", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" all/none  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("u", Style::default().fg(AMBER_SOFT)),
+                Span::styled(
+                    if app.hide_unpublished { " show unpublished  " } else { " hide unpublished  " },
+                    Style::default().fg(TEXT_DIM),
+                ),
+                Span::styled("enter/esc", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" close ", Style::default().fg(TEXT_DIM)),
+            ])),
+    );
+
+    app.filter_list_state
+        .inner
+        .select(Some(app.filter_list_state.selected));
+    f.render_stateful_widget(list, popup_area, &mut app.filter_list_state.inner);
+}
+
+// ─── Term picker ─────────────────────────────────────────────────────────────
+
+pub(super) fn render_term_picker(f: &mut Frame, app: &mut App, area: Rect) {
+    let mut rows = vec![(None, "All".to_string())];
+    rows.extend(app.terms().into_iter().map(|(id, name)| (Some(id), name)));
+    let selected = app.term_list_state.selected;
+
+    let longest = rows.iter().map(|(_, name)| name.chars().count()).max().unwrap_or(3);
+    let popup_w = (longest as u16).saturating_add(12).max(34).min(area.width.saturating_sub(4));
+    let popup_h = (rows.len() as u16).saturating_add(2).min(area.height.saturating_sub(2));
+    let popup = popup_rect(popup_w, popup_h, area);
+
+    f.render_widget(Clear, popup);
+
+    let items: Vec<ListItem> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, (id, name))| {
+            let is_selected = i == selected;
+            let current = *id == app.term_filter;
+            let RowStyle { marker, marker_fg, bg } = selectable_row(is_selected, false);
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {marker} "), Style::default().fg(marker_fg).bg(bg)),
+                Span::styled(
+                    if current { "● " } else { "  " },
+                    Style::default().fg(SUCCESS).bg(bg),
+                ),
+                Span::styled(
+                    name.clone(),
+                    Style::default()
+                        .fg(if is_selected { TEXT } else { TEXT_DIM })
+                        .bg(bg)
+                        .add_modifier(if is_selected {
+                            Modifier::BOLD
+                        } else {
+                            Modifier::empty()
+                        }),
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(AMBER_SOFT))
+            .title(" Term ")
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+            .title_bottom(Line::from(vec![
+                Span::styled(" enter", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" choose  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("esc", Style::default().fg(AMBER_SOFT)),
+                Span::styled(" close ", Style::default().fg(TEXT_DIM)),
+            ])),
+    );
+
+    app.term_list_state.inner.select(Some(selected));
+    f.render_stateful_widget(list, popup, &mut app.term_list_state.inner);
+}
+
+// ─── Link picker ─────────────────────────────────────────────────────────────
+
+pub(super) fn render_link_picker(f: &mut Frame, app: &mut App, area: Rect) {
+    let Some(links) = app.link_picker.as_ref() else {
+        return;
+    };
+    let count = links.len();
+    let selected = app.link_picker_state.selected;
+
+    let popup_w = 72.min(area.width.saturating_sub(4));
+    let popup_h = (count as u16).saturating_mul(2).saturating_add(2).min(area.height.saturating_sub(2));
+    let popup = popup_rect(popup_w, popup_h, area);
+    let inner_w = popup_w.saturating_sub(2) as usize;
+
+    f.render_widget(Clear, popup);
+
+    let items: Vec<ListItem> = links
+        .iter()
+        .enumerate()
+        .map(|(i, link)| {
+            let is_selected = i == selected;
+            let RowStyle { marker, bg, .. } = selectable_row(is_selected, false);
+            let number = if i < 9 { format!("{} ", i + 1) } else { "  ".into() };
+            let icon = if link.is_image { "▣ " } else { "" };
+            let text = truncate_to_width(&format!("{icon}{}", link.text), inner_w.saturating_sub(5));
+            let url = truncate_to_width(&link.url, inner_w.saturating_sub(5));
+
+            ListItem::new(vec![
+                Line::from(vec![
+                    Span::styled(format!(" {marker}"), Style::default().fg(AMBER).bg(bg)),
+                    Span::styled(number, Style::default().fg(AMBER_SOFT).bg(bg)),
+                    Span::styled(
+                        text,
+                        Style::default()
+                            .fg(if is_selected { TEXT } else { TEXT_DIM })
+                            .bg(bg)
+                            .add_modifier(if is_selected {
+                                Modifier::BOLD
+                            } else {
+                                Modifier::empty()
+                            }),
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::styled("    ", Style::default().bg(bg)),
+                    Span::styled(url, Style::default().fg(INFO).bg(bg)),
+                ]),
+            ])
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(AMBER_SOFT))
+            .title(format!(" Open Link ({count}) "))
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+            .title_bottom(Line::from(vec![
+                Span::styled(" j/k ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("move  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("1-9/Enter ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("open  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("Esc ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("cancel ", Style::default().fg(TEXT_DIM)),
+            ])),
+    );
+
+    app.link_picker_state.inner.select(Some(selected));
+    f.render_stateful_widget(list, popup, &mut app.link_picker_state.inner);
+}
+
+/// The assignments `l` can link the selected calendar event to.
+pub(super) fn render_event_link_picker(f: &mut Frame, app: &mut App, area: Rect) {
+    let Some(picker) = app.event_link_picker.as_ref() else {
+        return;
+    };
+    let count = picker.candidates.len();
+    let selected = app.event_link_picker_state.selected;
+
+    let popup_w = 64.min(area.width.saturating_sub(4));
+    let popup_h = (count as u16).saturating_add(5).min(area.height.saturating_sub(2));
+    let popup = popup_rect(popup_w, popup_h, area);
+    let inner_w = popup_w.saturating_sub(2) as usize;
+
+    f.render_widget(Clear, popup);
+
+    let when = picker.start_at.with_timezone(&Local).format("%a %b %d %H:%M");
+    let mut items = vec![
+        ListItem::new(Line::from(Span::styled(
+            truncate_to_width(&format!("  Due with {} ({when}):", picker.event_title), inner_w),
+            Style::default().fg(TEXT_DIM),
+        ))),
+        ListItem::new(""),
+    ];
+    items.extend(picker.candidates.iter().enumerate().map(|(i, (_, name))| {
+        let is_selected = i == selected;
+        let RowStyle { marker, bg, .. } = selectable_row(is_selected, false);
+        let number = if i < 9 { format!("{} ", i + 1) } else { "  ".into() };
+        ListItem::new(Line::from(vec![
+            Span::styled(format!(" {marker}"), Style::default().fg(AMBER).bg(bg)),
+            Span::styled(number, Style::default().fg(AMBER_SOFT).bg(bg)),
+            Span::styled(
+                truncate_to_width(name, inner_w.saturating_sub(5)),
+                Style::default()
+                    .fg(if is_selected { TEXT } else { TEXT_DIM })
+                    .bg(bg)
+                    .add_modifier(if is_selected { Modifier::BOLD } else { Modifier::empty() }),
+            ),
+        ]))
+    }));
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(AMBER_SOFT))
+            .title(" Link to Assignment ")
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+            .title_bottom(Line::from(vec![
+                Span::styled(" j/k ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("move  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("1-9/Enter ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("link  ", Style::default().fg(TEXT_DIM)),
+                Span::styled("Esc ", Style::default().fg(AMBER_SOFT)),
+                Span::styled("cancel ", Style::default().fg(TEXT_DIM)),
+            ])),
+    );
+
+    // Offset by the two heading rows.
+    app.event_link_picker_state.inner.select(Some(selected + 2));
+    f.render_stateful_widget(list, popup, &mut app.event_link_picker_state.inner);
+}
+
+// ─── Sync stats ──────────────────────────────────────────────────────────────
+
+pub(super) fn render_sync_stats(f: &mut Frame, app: &App, area: Rect) {
+    let popup_w = 64.min(area.width.saturating_sub(4));
+    let inner_w = popup_w.saturating_sub(2) as usize;
+
+    let mut lines = vec![Line::from("")];
+    match app.last_sync_stats.as_ref() {
+        _ if app.sync_stats_history => lines.extend(sync_history_rows(app, inner_w)),
+        None => lines.push(Line::from(Span::styled(
+            "  No sync has finished yet this session.",
+            Style::default().fg(TEXT_DIM),
+        ))),
+        Some(stats) => {
+            lines.push(Line::from(vec![
+                Span::styled("  Total     ", Style::default().fg(AMBER_SOFT)),
+                Span::styled(
+                    format!(
+                        "{}   {} requests",
+                        format_duration(stats.total),
+                        stats.requests
+                    ),
+                    Style::default().fg(TEXT).add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            lines.push(Line::from(""));
+
+            // name | bar | duration | requests
+            let name_w = inner_w.saturating_sub(2 + 12 + 8 + 7).max(8);
+            let longest = stats.phases.iter().map(|p| p.duration).max().unwrap_or_default();
+            for phase in stats.slowest() {
+                let filled = if longest.is_zero() {
+                    0
+                } else {
+                    (phase.duration.as_secs_f64() / longest.as_secs_f64() * 10.0).round() as usize
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {}", pad_to_width(&truncate_to_width(&phase.name, name_w), name_w)),
+                        Style::default().fg(TEXT),
+                    ),
+                    Span::styled(
+                        format!(" {:<11}", "█".repeat(filled)),
+                        Style::default().fg(AMBER_SOFT),
+                    ),
+                    Span::styled(
+                        format!("{:>7} ", format_duration(phase.duration)),
+                        Style::default().fg(TEXT),
+                    ),
+                    Span::styled(
+                        format!("{:>3} req", phase.requests),
+                        Style::default().fg(TEXT_DIM),
+                    ),
+                ]));
+            }
+        }
+    }
+
+    let popup_h = (lines.len() as u16).saturating_add(3).min(area.height.saturating_sub(2));
+    let popup = popup_rect(popup_w, popup_h, area);
+    f.render_widget(Clear, popup);
+
+    f.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(AMBER_SOFT))
+                .title(if app.sync_stats_history { " Sync History " } else { " Last Sync " })
+                .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+                .title_bottom(Line::from(vec![
+                    Span::styled(" h ", Style::default().fg(AMBER_SOFT)),
+                    Span::styled(
+                        if app.sync_stats_history { "last sync " } else { "history " },
+                        Style::default().fg(TEXT_DIM),
+                    ),
+                    Span::styled(" Esc ", Style::default().fg(AMBER_SOFT)),
+                    Span::styled("close ", Style::default().fg(TEXT_DIM)),
+                ])),
+        ),
+        popup,
+    );
+}
+
+// ─── Profile ─────────────────────────────────────────────────────────────────
+
+/// Who is signed in, to which Canvas and how, and what the cache holds.
+pub(super) fn render_profile(f: &mut Frame, app: &App, area: Rect) {
+    let popup_w = 56.min(area.width.saturating_sub(4));
+    let value_style = Style::default().fg(TEXT);
+    let dim = Style::default().fg(TEXT_DIM);
+    let known = |v: Option<&String>| v.cloned().unwrap_or_else(|| "─".into());
+
+    let user = app.user.as_ref();
+    let mut fields: Vec<(&str, String, Style)> = vec![
+        ("Name", known(user.and_then(|u| u.name.as_ref())), value_style),
+        ("Login", known(user.and_then(|u| u.login_id.as_ref())), value_style),
+        ("Email", known(user.and_then(|u| u.email.as_ref())), value_style),
+        ("Canvas", app.client.base_url().host_str().unwrap_or("─").to_string(), value_style),
+    ];
+    if let Some(id) = app.client.as_user_id() {
+        let acting = format!("user {id} (masquerading)");
+        fields.push(("Acting as", acting, Style::default().fg(DANGER)));
+    }
+    let sign_in = match app.client.token_expires_at() {
+        _ if !app.client.uses_oauth() => "API token".to_string(),
+        Some(at) => format!(
+            "OAuth, token expires {}",
+            at.with_timezone(&Local).format("%b %d %H:%M")
+        ),
+        None => "OAuth".to_string(),
+    };
+    fields.push(("Sign-in", sign_in, value_style));
+
+    let mut lines = vec![Line::from("")];
+    lines.extend(field_rows(&fields));
+    lines.push(Line::from(""));
+
+    let synced = app
+        .cached_at
+        .map(|t| t.with_timezone(&Local).format("%b %d %H:%M").to_string())
+        .unwrap_or_else(|| "never".into());
+    let (cache_file, cache_dir) = match &app.profile_disk {
+        None => ("measuring…".to_string(), "measuring…".to_string()),
+        Some(disk) => (
+            disk.cache_file.map_or_else(|| "not saved yet".into(), format_size),
+            format!("{} in {} files", format_size(disk.cache_dir), disk.files),
+        ),
+    };
+    let assignments: usize = app.assignments.iter().map(|g| g.assignments.len()).sum();
+    let cache_fields: Vec<(&str, String, Style)> = vec![
+        ("Synced", synced, value_style),
+        ("Cache", cache_file, value_style),
+        ("Folder", cache_dir, dim),
+        ("Holds", format!("{} courses, {assignments} assignments", app.courses.len()), dim),
+        (
+            "",
+            format!(
+                "{} events, {} announcements",
+                app.calendar_events.len(),
+                app.announcements.len()
+            ),
+            dim,
+        ),
+    ];
+    lines.extend(field_rows(&cache_fields));
+
+    let popup_h = (lines.len() as u16).saturating_add(3).min(area.height.saturating_sub(2));
+    let popup = popup_rect(popup_w, popup_h, area);
+    f.render_widget(Clear, popup);
+    f.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(AMBER_SOFT))
+                .title(" Profile ")
+                .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+                .title_bottom(Line::from(vec![
+                    Span::styled(" Esc ", Style::default().fg(AMBER_SOFT)),
+                    Span::styled("close ", Style::default().fg(TEXT_DIM)),
+                ])),
+        ),
+        popup,
+    );
+}
+
+// ─── Bulk download summary ───────────────────────────────────────────────────
+
+/// What a bulk download of course files saved and which files failed.
+pub(super) fn render_bulk_summary(f: &mut Frame, app: &App, area: Rect) {
+    let Some(p) = app.bulk_summary.as_ref() else {
+        return;
+    };
+    let popup_w = 72.min(area.width.saturating_sub(4));
+    let inner_w = popup_w.saturating_sub(2) as usize;
+    let total = p.planned.map_or(0, |(total, _)| total);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            format!(
+                "  Saved {} of {total} files ({}) to {}",
+                p.saved,
+                format_size(p.bytes),
+                p.dir.display()
+            ),
+            Style::default().fg(TEXT),
+        )),
+        Line::from(Span::styled(
+            format!("  {} already up to date", p.skipped),
+            Style::default().fg(TEXT_DIM),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            format!("  {} failed:", p.failures.len()),
+            Style::default().fg(DANGER).add_modifier(Modifier::BOLD),
+        )),
+    ];
+    // Each failure takes two lines; the rest are in the log.
+    let room = (area.height.saturating_sub(2 + 3) as usize).saturating_sub(lines.len()) / 2;
+    for (name, error) in p.failures.iter().take(room) {
+        lines.push(Line::from(Span::styled(
+            format!("  {}", truncate_to_width(name, inner_w.saturating_sub(2))),
+            Style::default().fg(TEXT),
+        )));
+        lines.push(Line::from(Span::styled(
+            format!("    {}", truncate_to_width(error, inner_w.saturating_sub(4))),
+            Style::default().fg(TEXT_DIM),
+        )));
+    }
+    if p.failures.len() > room {
+        lines.push(Line::from(Span::styled(
+            format!("  … and {} more (see the log)", p.failures.len() - room),
+            Style::default().fg(TEXT_DIM),
+        )));
+    }
+
+    let popup_h = (lines.len() as u16).saturating_add(3).min(area.height.saturating_sub(2));
+    let popup = popup_rect(popup_w, popup_h, area);
+    f.render_widget(Clear, popup);
+
+    f.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(AMBER_SOFT))
+                .title(format!(" {} Files ", p.course))
+                .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD))
+                .title_bottom(Line::from(vec![
+                    Span::styled(" Esc ", Style::default().fg(AMBER_SOFT)),
+                    Span::styled("close ", Style::default().fg(TEXT_DIM)),
+                ])),
+        ),
+        popup,
+    );
+}
+
+/// The sync history table: when, how long, ✓/✗ per section and the item
+/// counts, newest first, with the error under a sync that had one.
+fn sync_history_rows(app: &App, inner_w: usize) -> Vec<Line<'static>> {
+    const MARK_W: usize = 5;
+    let history = &app.sync_history;
+    if history.is_empty() {
+        return vec![Line::from(Span::styled(
+            "  No syncs recorded yet.",
+            Style::default().fg(TEXT_DIM),
+        ))];
+    }
+
+    let mut lines = Vec::new();
+    let failures = history.consecutive_failures();
+    lines.push(if failures > 0 {
+        let syncs = if failures == 1 { "sync" } else { "syncs" };
+        Line::from(Span::styled(
+            format!("  Last {failures} {syncs} couldn't reach Canvas."),
+            Style::default().fg(DANGER),
+        ))
+    } else {
+        Line::from(Span::styled(
+            "  ✓ fetched   ✗ failed, older data kept   · not fetched",
+            Style::default().fg(TEXT_DIM),
+        ))
+    });
+    lines.push(Line::from(""));
+
+    let mut header = format!("  {:<12} {:>6} ", "When", "Took");
+    for (name, _) in crate::tui::sync_history::SectionOutcomes::default().named() {
+        header.push_str(&format!("{name:^MARK_W$}"));
+    }
+    header.push_str("Crs/Asg/Evt/Ann");
+    lines.push(Line::from(Span::styled(
+        truncate_to_width(&header, inner_w),
+        Style::default().fg(AMBER_SOFT),
+    )));
+
+    for record in history.records() {
+        let mut spans = vec![
+            Span::styled(
+                format!(
+                    "  {:<12} ",
+                    record.started_at.with_timezone(&Local).format("%b %d %H:%M")
+                ),
+                Style::default().fg(TEXT),
+            ),
+            Span::styled(
+                format!(
+                    "{:>6} ",
+                    format_duration(std::time::Duration::from_millis(record.duration_ms))
+                ),
+                Style::default().fg(TEXT_DIM),
+            ),
+        ];
+        for (_, outcome) in record.sections.named() {
+            use crate::tui::sync_history::SectionOutcome;
+            let (mark, color) = match outcome {
+                SectionOutcome::Ok => ("✓", SUCCESS),
+                SectionOutcome::Failed => ("✗", DANGER),
+                SectionOutcome::Skipped => ("·", TEXT_MUTED),
+            };
+            spans.push(Span::styled(format!("{mark:^MARK_W$}"), Style::default().fg(color)));
+        }
+        spans.push(Span::styled(
+            format!(
+                "{}/{}/{}/{}",
+                record.courses, record.assignments, record.events, record.announcements
+            ),
+            Style::default().fg(TEXT_DIM),
+        ));
+        lines.push(Line::from(spans));
+        if let Some(error) = &record.error {
+            lines.push(Line::from(Span::styled(
+                truncate_to_width(&format!("    └ {error}"), inner_w),
+                Style::default().fg(DANGER),
+            )));
+        }
+    }
+    lines
+}
@@ -0,0 +1,891 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use super::components::{
+    assignment_row_name, assignment_status, continuation_line, course_field_rows, course_stripe,
+    detail_block, detail_scroll, filter_hint, name_lines, new_badge, offset_above, pad_to_width,
+    render_assignment_detail, render_assignment_pane, render_detail_hint, selectable_row,
+    DetailRows, RowStyle,
+};
+use super::modals::{
+    render_course_filter_popup, render_date_jump_prompt, render_event_form,
+    render_submission_modal,
+};
+use super::{
+    countdown_timer, opens_timer, AMBER, AMBER_SOFT, CAUTION, DANGER, FOCAL, HDR_BG, INFO, SUCCESS,
+    TEXT, TEXT_DIM, TEXT_MUTED,
+};
+use crate::models::Assignment;
+use crate::tui::date_sections::DateSection;
+use crate::tui::{
+    points_label, App, AssignmentSort, CalendarItem, EventEmphasis, UnifiedViewMode,
+    DUE_SOON_HOURS,
+};
+use chrono::{Datelike, Local, NaiveDate, Utc};
+use std::collections::BTreeMap;
+
+// ─── Schedule (unified Calendar + Assignments) ────────────────────────────────
+
+pub(super) fn render_schedule(f: &mut Frame, app: &mut App, area: Rect) {
+    match app.unified_view_mode {
+        UnifiedViewMode::CalendarView => render_schedule_calendar(f, app, area),
+        UnifiedViewMode::ListView => render_schedule_list(f, app, area),
+    }
+}
+
+fn render_schedule_calendar(f: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(42), Constraint::Percentage(58)])
+        .split(area);
+
+    render_calendar_list(f, app, chunks[0]);
+    render_schedule_calendar_detail(f, app, chunks[1]);
+
+    if app.show_course_filter {
+        render_course_filter_popup(f, app, area);
+    }
+    if app.date_jump_input.is_some() {
+        render_date_jump_prompt(f, app, area);
+    }
+    if app.event_form.is_some() {
+        render_event_form(f, app, area);
+    }
+    if !app.submission_state.is_hidden() {
+        render_submission_modal(f, app, area);
+    }
+}
+
+fn render_schedule_calendar_detail(f: &mut Frame, app: &App, area: Rect) {
+    let Some(item) = app.calendar_items.get(app.calendar_list_state.selected) else {
+        let block = detail_block(app, " Detail ");
+        render_detail_hint(f, area, block, "  Select an item to view details.");
+        return;
+    };
+
+    // If this CalendarItem is linked to an assignment, show full assignment detail.
+    if let Some(assignment_id) = item.assignment_id {
+        if let Some((course_name, assignment)) = app.get_assignment_by_id(assignment_id) {
+            let block = detail_block(app, " Assignment Detail ");
+            render_assignment_detail(f, area, block, course_name, assignment, app, DetailRows::All);
+            return;
+        }
+    }
+
+    // Fallback: lightweight calendar event detail.
+    let course_code = item
+        .course_id
+        .and_then(|id| app.courses.iter().find(|c| c.id == id))
+        .and_then(|c| c.course_code.as_deref());
+    render_calendar_event_detail(f, area, detail_block(app, " Detail "), item, course_code, app);
+}
+
+// ─── Assignments ─────────────────────────────────────────────────────────────
+
+/// Title note for courses whose assignment list was cut off at
+/// `max_assignments_per_course`.
+fn truncation_hint(app: &App) -> String {
+    if let Some(id) = app.selected_truncated_course() {
+        let shown = app
+            .assignments
+            .iter()
+            .find(|group| group.course_id == id)
+            .map_or(0, |group| group.assignments.len());
+        let code = app
+            .courses
+            .iter()
+            .find(|c| c.id == id)
+            .and_then(|c| c.course_code.as_deref().or(c.name.as_deref()))
+            .unwrap_or("this course");
+        return format!("  ({code}: showing first {shown} — press A to load all)");
+    }
+    match app.truncated_courses.len() {
+        0 => String::new(),
+        1 => "  (1 course capped)".into(),
+        n => format!("  ({n} courses capped)"),
+    }
+}
+
+fn render_schedule_list(f: &mut Frame, app: &mut App, area: Rect) {
+    let sort_label = app.assignment_sort.label();
+    let block_title = format!(
+        " Schedule [List]   s: {}   f: filter{}{}   v: calendar ",
+        sort_label,
+        filter_hint(app),
+        truncation_hint(app)
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(38), Constraint::Percentage(62)])
+        .split(area);
+
+    if app.assignment_sort == AssignmentSort::Course {
+        render_assignments_grouped(f, app, chunks[0], &block_title);
+    } else {
+        render_assignments_flat(f, app, chunks[0], &block_title);
+    }
+
+    render_assignment_pane(f, app, chunks[1], app.get_selected_assignment(), DetailRows::All);
+
+    if app.show_course_filter {
+        render_course_filter_popup(f, app, area);
+    }
+    if app.date_jump_input.is_some() {
+        render_date_jump_prompt(f, app, area);
+    }
+    if app.event_form.is_some() {
+        render_event_form(f, app, area);
+    }
+
+    if !app.submission_state.is_hidden() {
+        render_submission_modal(f, app, area);
+    }
+}
+
+/// How a Schedule list row is drawn: selection marker, background and the
+/// name's style (dimmed for unpublished and not yet open assignments).
+fn row_look(
+    is_selected: bool,
+    is_focal: bool,
+    dimmed: bool,
+) -> (&'static str, Color, Color, Style) {
+    let RowStyle { marker, marker_fg, bg } = selectable_row(is_selected, is_focal);
+    let name_fg = if dimmed { TEXT_DIM } else { TEXT };
+    let name_style = Style::default().fg(name_fg).bg(bg).add_modifier(
+        if is_focal && !is_selected { Modifier::BOLD } else { Modifier::empty() },
+    );
+    (marker, marker_fg, bg, name_style)
+}
+
+/// Whether a row's name is dimmed: the assignment is unpublished or not
+/// open yet.
+fn row_dimmed(app: &App, assignment: &Assignment) -> bool {
+    assignment.is_unpublished() || assignment.locked_until(app.now()).is_some()
+}
+
+/// The pinned "⚠ Due soon" section at the top of the Schedule list: rows
+/// `0..pinned.len()`, with the countdown in place of the due date.  Returns
+/// the list item of the selected row if it is one of these.
+fn push_due_soon_rows(
+    app: &App,
+    items: &mut Vec<ListItem<'static>>,
+    pinned: &[(&str, &Assignment)],
+    selected: usize,
+    focal_id: Option<u64>,
+) -> Option<usize> {
+    if pinned.is_empty() {
+        return None;
+    }
+    items.push(ListItem::new(Line::from(vec![
+        Span::styled(" ⚠  ", Style::default().fg(CAUTION)),
+        Span::styled("Due soon", Style::default().fg(CAUTION).add_modifier(Modifier::BOLD)),
+        Span::styled(
+            format!("  (next {} hours, not submitted) ", DUE_SOON_HOURS),
+            Style::default().fg(TEXT_MUTED),
+        ),
+    ])));
+    let mut selected_item = None;
+    for (idx, (course_name, assignment)) in pinned.iter().enumerate() {
+        let is_selected = idx == selected;
+        if is_selected {
+            selected_item = Some(items.len());
+        }
+        let (marker, marker_fg, bg, name_style) =
+            row_look(is_selected, Some(assignment.id) == focal_id, row_dimmed(app, assignment));
+        let name = assignment.name.as_deref().unwrap_or("Unnamed");
+        let (countdown, countdown_color) = match assignment.locked_until(app.now()) {
+            Some(unlock) => opens_timer(unlock, app.now()),
+            None => assignment
+                .due_at
+                .map(|d| countdown_timer(d, app.now()))
+                .unwrap_or_else(|| (String::new(), TEXT_DIM)),
+        };
+        let badge = new_badge(app, assignment, bg);
+        let name_w = 36 - badge.width();
+        items.push(assignment_item(
+            app,
+            vec![
+                course_stripe(app, assignment.course_id, bg),
+                Span::styled(format!("{} ", marker), Style::default().fg(marker_fg).bg(bg)),
+                badge,
+            ],
+            (name, name_w, name_style),
+            vec![
+                Span::styled(format!(" {:<22}", course_name), Style::default().fg(TEXT_MUTED).bg(bg)),
+                Span::styled(format!(" {countdown:<18}"), Style::default().fg(countdown_color).bg(bg)),
+            ],
+            bg,
+        ));
+    }
+    selected_item
+}
+
+fn render_assignments_grouped(f: &mut Frame, app: &mut App, area: Rect, block_title: &str) {
+    let focal_id = app.focal_assignment_id;
+    let len = app.schedule_rows().len();
+    app.assignment_list_state.set_len(len);
+    let selected = app.assignment_list_state.selected;
+
+    let rows = app.schedule_rows();
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut selected_item_idx = push_due_soon_rows(app, &mut items, &rows.pinned, selected, focal_id)
+        .unwrap_or(0);
+    let mut flat_idx = rows.pinned.len();
+
+    // `rows` is in course order; each run of one course gets a header.
+    let mut start = 0;
+    while start < rows.rows.len() {
+        let (course_name, first) = rows.rows[start];
+        let count = rows.rows[start..]
+            .iter()
+            .take_while(|(_, a)| a.course_id == first.course_id)
+            .count();
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(" ◈  ", Style::default().fg(AMBER_SOFT)),
+            Span::styled(
+                course_name.to_string(),
+                Style::default()
+                    .fg(AMBER_SOFT)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  ({} assignments) ", count),
+                Style::default().fg(TEXT_MUTED),
+            ),
+        ])));
+
+        for (_, assignment) in &rows.rows[start..start + count] {
+            let is_selected = flat_idx == selected;
+            if is_selected {
+                selected_item_idx = items.len();
+            }
+            let (marker, marker_fg, bg, name_style) =
+                row_look(is_selected, Some(assignment.id) == focal_id, row_dimmed(app, assignment));
+
+            let name = assignment_row_name(assignment);
+            let due = assignment
+                .due_at
+                .map(|d| d.format("%b %d  %H:%M").to_string())
+                .unwrap_or_else(|| "No due date".into());
+            let points = points_label(assignment);
+            let (status, status_color) = assignment_status(assignment);
+            let badge = new_badge(app, assignment, bg);
+            let name_w = 40 - badge.width();
+
+            items.push(assignment_item(
+                app,
+                vec![
+                    course_stripe(app, assignment.course_id, bg),
+                    Span::styled(format!("{} ", marker), Style::default().fg(marker_fg).bg(bg)),
+                    badge,
+                ],
+                (&name, name_w, name_style),
+                vec![
+                    Span::styled(format!(" {due:<18}"), Style::default().fg(TEXT_DIM).bg(bg)),
+                    Span::styled(format!(" {points:<10}"), Style::default().fg(TEXT_MUTED).bg(bg)),
+                    Span::styled(format!(" {status}"), Style::default().fg(status_color).bg(bg)),
+                ],
+                bg,
+            ));
+
+            flat_idx += 1;
+        }
+        start += count;
+    }
+
+    if items.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            "  ○  No assignments found.",
+            Style::default().fg(TEXT_DIM),
+        ))));
+    }
+
+    let heights: Vec<usize> = items.iter().map(ListItem::height).collect();
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(TEXT_MUTED))
+            .title(block_title.to_string())
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD)),
+    );
+
+    app.assignment_list_state.inner.select(Some(selected_item_idx));
+
+    if app.assignment_list_state.needs_center {
+        app.assignment_list_state.needs_center = false;
+        *app.assignment_list_state.inner.offset_mut() =
+            centered_offset(app, &heights, selected_item_idx, area);
+    }
+
+    f.render_stateful_widget(list, area, &mut app.assignment_list_state.inner);
+}
+
+fn render_assignments_flat(f: &mut Frame, app: &mut App, area: Rect, block_title: &str) {
+    let focal_id = app.focal_assignment_id;
+    let len = app.schedule_rows().len();
+    app.assignment_list_state.set_len(len);
+    let selected = app.assignment_list_state.selected;
+
+    let rows = app.schedule_rows();
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut selected_item_idx = push_due_soon_rows(app, &mut items, &rows.pinned, selected, focal_id)
+        .unwrap_or(0);
+    if !rows.pinned.is_empty() && !rows.rows.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            "  ──────────────────────────────────────────────",
+            Style::default().fg(TEXT_MUTED),
+        ))));
+    }
+
+    // With `date_sections`, a header starts each run of one section; like
+    // the course headers, they take no row index.
+    let now = app.now();
+    let sections: Vec<DateSection> = if app.shows_date_sections() {
+        rows.rows.iter().map(|(_, a)| DateSection::of(a.due_at, now)).collect()
+    } else {
+        Vec::new()
+    };
+    for (idx, (course_name, assignment)) in rows.rows.iter().enumerate() {
+        if let Some(&section) = sections.get(idx) {
+            if idx == 0 || sections[idx - 1] != section {
+                let count = sections[idx..].iter().take_while(|s| **s == section).count();
+                items.push(date_section_header(section, count));
+            }
+        }
+        let is_selected = rows.pinned.len() + idx == selected;
+        if is_selected {
+            selected_item_idx = items.len();
+        }
+        let (marker, marker_fg, bg, name_style) =
+            row_look(is_selected, Some(assignment.id) == focal_id, row_dimmed(app, assignment));
+
+        let name = assignment_row_name(assignment);
+        let due = assignment
+            .due_at
+            .map(|d| d.format("%b %d  %H:%M").to_string())
+            .unwrap_or_else(|| "No due date".into());
+        let points = points_label(assignment);
+        let (status, status_color) = assignment_status(assignment);
+        let badge = new_badge(app, assignment, bg);
+        let name_w = 36 - badge.width();
+
+        items.push(assignment_item(
+            app,
+            vec![
+                course_stripe(app, assignment.course_id, bg),
+                Span::styled(format!("{} ", marker), Style::default().fg(marker_fg).bg(bg)),
+                badge,
+            ],
+            (&name, name_w, name_style),
+            vec![
+                Span::styled(format!(" {:<22}", course_name), Style::default().fg(TEXT_MUTED).bg(bg)),
+                Span::styled(format!(" {due:<18}"), Style::default().fg(TEXT_DIM).bg(bg)),
+                Span::styled(format!(" {points:<10}"), Style::default().fg(TEXT_MUTED).bg(bg)),
+                Span::styled(format!(" {status}"), Style::default().fg(status_color).bg(bg)),
+            ],
+            bg,
+        ));
+    }
+    if rows.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            "  ○  No assignments found.",
+            Style::default().fg(TEXT_DIM),
+        ))));
+    }
+
+    let heights: Vec<usize> = items.iter().map(ListItem::height).collect();
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(TEXT_MUTED))
+            .title(block_title.to_string())
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD)),
+    );
+
+    app.assignment_list_state.inner.select(Some(selected_item_idx));
+
+    if app.assignment_list_state.needs_center {
+        app.assignment_list_state.needs_center = false;
+        *app.assignment_list_state.inner.offset_mut() =
+            centered_offset(app, &heights, selected_item_idx, area);
+    }
+
+    f.render_stateful_widget(list, area, &mut app.assignment_list_state.inner);
+}
+
+/// The header above a run of `count` assignments in `section`.
+fn date_section_header(section: DateSection, count: usize) -> ListItem<'static> {
+    let color = match section {
+        DateSection::Overdue => DANGER,
+        DateSection::Today => CAUTION,
+        _ => AMBER_SOFT,
+    };
+    ListItem::new(Line::from(vec![
+        Span::styled(" ▸  ", Style::default().fg(color)),
+        Span::styled(section.label(), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+        Span::styled(format!("  ({count}) "), Style::default().fg(TEXT_MUTED)),
+    ]))
+}
+
+/// A Schedule list row: `lead` (marker and badge), the name in a column
+/// `name_w` wide, then `cells`.  With `wrap_names` a long name continues
+/// on an indented second line; otherwise it is written out in full.
+fn assignment_item(
+    app: &App,
+    lead: Vec<Span<'static>>,
+    (name, name_w, name_style): (&str, usize, Style),
+    cells: Vec<Span<'static>>,
+    bg: Color,
+) -> ListItem<'static> {
+    let name_col: usize = lead.iter().map(Span::width).sum();
+    let lines = if app.wrap_names {
+        name_lines(app, name, name_w)
+    } else {
+        vec![name.to_string()]
+    };
+    let mut spans = lead;
+    spans.push(Span::styled(pad_to_width(&lines[0], name_w), name_style));
+    spans.extend(cells);
+    let first = Line::from(spans);
+    let row_w = first.width();
+    let mut rows = vec![first];
+    rows.extend(continuation_line(&lines, name_col, row_w, name_style, bg));
+    ListItem::new(rows)
+}
+
+/// The offset that brings the list's selected item into view: a quarter
+/// of the way down, counting rows when names wrap, as before otherwise.
+fn centered_offset(app: &App, heights: &[usize], selected: usize, area: Rect) -> usize {
+    let rows = usize::from(area.height.saturating_sub(2));
+    if app.wrap_names {
+        offset_above(heights, selected, rows / 4)
+    } else {
+        selected.saturating_sub(rows / 2 / 2)
+    }
+}
+
+// ─── Calendar list (shared by render_schedule_calendar) ──────────────────────
+
+/// Derive the ◆ icon color for a calendar assignment item based on its status:
+///   graded (score)  → green
+///   submitted       → blue
+///   everything else → red  (unsubmitted, missing, past due, upcoming todo)
+fn calendar_item_status_color(item: &CalendarItem, is_focal: bool) -> Color {
+    if is_focal {
+        return FOCAL;
+    }
+    match &item.status {
+        Some(s) if s == "Submitted" => INFO,
+        Some(s) if s.starts_with("Missing") || s.starts_with("Past due") => DANGER,
+        Some(_) => SUCCESS, // score or "Graded"
+        None => DANGER,     // unsubmitted
+    }
+}
+
+/// Color of a calendar item's status text: red for missing, orange past
+/// due, blue submitted, green for a grade.
+fn status_color(status: &str) -> Color {
+    if status.starts_with("Missing") {
+        DANGER
+    } else if status.starts_with("Past due") {
+        CAUTION
+    } else if status.starts_with("Submitted") {
+        INFO
+    } else {
+        SUCCESS
+    }
+}
+
+/// Row icon and its color: assignments by status, events by emphasis.
+fn calendar_item_icon(item: &CalendarItem, is_focal: bool) -> (&'static str, Color) {
+    match item.item_type {
+        "assignment" => return ("◆", calendar_item_status_color(item, is_focal)),
+        "peer_review" => return ("✎", calendar_item_status_color(item, is_focal)),
+        _ => {}
+    }
+    match item.emphasis {
+        EventEmphasis::Highlight => ("◈", CAUTION),
+        EventEmphasis::Dimmed => ("◦", TEXT_DIM),
+        EventEmphasis::Normal => ("◇", INFO),
+    }
+}
+
+/// Calendar items for one week, bucketed by local date and tagged with their
+/// index into `app.calendar_items`.
+type DayBuckets<'a> = BTreeMap<NaiveDate, Vec<(usize, &'a CalendarItem)>>;
+
+fn render_calendar_list(f: &mut Frame, app: &mut App, area: Rect) {
+    let local_now = Local::now();
+    let today = local_now.date_naive();
+    let focal_id = app.focal_assignment_id;
+
+    // Group: (iso_year, iso_week) → NaiveDate → Vec<(original_idx, &CalendarItem)>
+    let mut by_week: BTreeMap<(i32, u32), DayBuckets> = BTreeMap::new();
+    let mut undated: Vec<(usize, &CalendarItem)> = Vec::new();
+
+    for (i, item) in app.calendar_items.iter().enumerate() {
+        if let Some(dt) = item.start_at {
+            let date = dt.with_timezone(&Local).date_naive();
+            let iso = date.iso_week();
+            by_week
+                .entry((iso.year(), iso.week()))
+                .or_default()
+                .entry(date)
+                .or_default()
+                .push((i, item));
+        } else {
+            undated.push((i, item));
+        }
+    }
+
+    let mut list_items: Vec<ListItem> = Vec::new();
+    let mut selected_item_idx = 0usize;
+
+    for ((_, week_num), days) in &by_week {
+        let first_date = *days.keys().next().unwrap();
+        let last_date = *days.keys().last().unwrap();
+
+        // Which weekdays (0=Mon … 6=Sun) have items
+        let active_days: std::collections::HashSet<u32> =
+            days.keys().map(|d| d.weekday().num_days_from_monday()).collect();
+
+        // Week date-range label
+        let week_range = if first_date.month() == last_date.month() {
+            format!("{} – {}", first_date.format("%b %d"), last_date.format("%d"))
+        } else {
+            format!(
+                "{} – {}",
+                first_date.format("%b %d"),
+                last_date.format("%b %d")
+            )
+        };
+
+        // Day-dot strip: M T W T F S S  with ● for active days
+        let day_initials = ["M", "T", "W", "T", "F", "S", "S"];
+        let week_bg = HDR_BG;
+        let mut week_spans: Vec<Span> = vec![
+            Span::styled(
+                format!(" ◈  Wk {week_num:<2}  "),
+                Style::default()
+                    .fg(AMBER)
+                    .bg(week_bg)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{week_range}   "),
+                Style::default().fg(TEXT_DIM).bg(week_bg),
+            ),
+        ];
+        for (d, &init) in day_initials.iter().enumerate() {
+            let has = active_days.contains(&(d as u32));
+            week_spans.push(Span::styled(
+                format!("{}{} ", init, if has { "●" } else { " " }),
+                Style::default().fg(if has { CAUTION } else { TEXT_MUTED }).bg(week_bg),
+            ));
+        }
+        list_items.push(
+            ListItem::new(Line::from(week_spans))
+                .style(Style::default().bg(week_bg)),
+        );
+
+        // Items within the week, grouped by day
+        for (date, day_items) in days {
+            let is_today = *date == today;
+            let is_past = *date < today;
+
+            let (day_color, day_label) = if is_today {
+                (
+                    AMBER,
+                    format!("  ◈ Today · {} ", date.format("%A, %b %d")),
+                )
+            } else if is_past {
+                (
+                    TEXT_MUTED,
+                    format!("  ─ {} ", date.format("%A, %b %d")),
+                )
+            } else {
+                (
+                    TEXT_DIM,
+                    format!("  ─ {} ", date.format("%A, %b %d")),
+                )
+            };
+
+            list_items.push(ListItem::new(Line::from(Span::styled(
+                day_label,
+                Style::default()
+                    .fg(day_color)
+                    .add_modifier(if is_today { Modifier::BOLD } else { Modifier::empty() }),
+            ))));
+
+            for (item_idx, item) in day_items {
+                let is_selected = *item_idx == app.calendar_list_state.selected;
+                let is_focal =
+                    item.assignment_id.is_some() && item.assignment_id == focal_id;
+
+                if is_selected {
+                    selected_item_idx = list_items.len();
+                }
+
+                let RowStyle { marker, marker_fg, bg } = selectable_row(is_selected, is_focal);
+
+                let time = item
+                    .start_at
+                    .map(|d| d.with_timezone(&Local).format("%H:%M").to_string())
+                    .unwrap_or_else(|| "─────".into());
+
+                let (type_icon, type_color) = calendar_item_icon(item, is_focal);
+                let opens = item
+                    .assignment_id
+                    .and_then(|id| app.get_assignment_by_id(id))
+                    .and_then(|(_, a)| a.locked_until(app.now()))
+                    .map(|unlock| opens_timer(unlock, app.now()));
+                let dimmed = is_past || item.emphasis == EventEmphasis::Dimmed || opens.is_some();
+
+                let title_style = Style::default()
+                    .fg(if dimmed { TEXT_DIM } else { TEXT })
+                    .bg(bg)
+                    .add_modifier(
+                        if is_selected || is_focal {
+                            Modifier::BOLD
+                        } else {
+                            Modifier::empty()
+                        },
+                    );
+
+                let status_span = match (&opens, &item.status) {
+                    (Some((text, color)), _) => Span::styled(
+                        format!("  [{text}]"),
+                        Style::default().fg(*color).bg(bg),
+                    ),
+                    (None, Some(s)) => Span::styled(
+                        format!("  [{}]", s),
+                        Style::default().fg(status_color(s)).bg(bg),
+                    ),
+                    (None, None) => Span::styled("", Style::default().bg(bg)),
+                };
+
+                list_items.push(ListItem::new(vec![
+                    Line::from(vec![
+                        course_stripe(app, item.course_id, bg),
+                        Span::styled(
+                            format!("{} ", marker),
+                            Style::default().fg(marker_fg).bg(bg),
+                        ),
+                        Span::styled(
+                            format!("{time}  "),
+                            Style::default().fg(TEXT_DIM).bg(bg),
+                        ),
+                        Span::styled(
+                            format!("{type_icon}  "),
+                            Style::default().fg(type_color).bg(bg),
+                        ),
+                        Span::styled(item.title.clone(), title_style),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("           ", Style::default().bg(bg)),
+                        Span::styled(
+                            item.course_name.as_deref().unwrap_or("").to_string(),
+                            Style::default().fg(TEXT_MUTED).bg(bg),
+                        ),
+                        status_span,
+                    ]),
+                ]));
+            }
+        }
+    }
+
+    // Undated items
+    if !undated.is_empty() {
+        list_items.push(ListItem::new(Line::from(Span::styled(
+            "  ─ No date ─────────────────────────",
+            Style::default().fg(TEXT_MUTED),
+        ))));
+        for (item_idx, item) in &undated {
+            let is_selected = *item_idx == app.calendar_list_state.selected;
+            if is_selected {
+                selected_item_idx = list_items.len();
+            }
+            let RowStyle { marker, marker_fg, bg } = selectable_row(is_selected, false);
+            let (type_icon, type_color) = calendar_item_icon(item, false);
+            list_items.push(ListItem::new(vec![
+                Line::from(vec![
+                    course_stripe(app, item.course_id, bg),
+                    Span::styled(
+                        format!("{} ", marker),
+                        Style::default().fg(marker_fg).bg(bg),
+                    ),
+                    Span::styled(
+                        format!("{type_icon}  "),
+                        Style::default().fg(type_color).bg(bg),
+                    ),
+                    Span::styled(
+                        item.title.clone(),
+                        Style::default()
+                            .fg(TEXT)
+                            .bg(bg)
+                            .add_modifier(if is_selected {
+                                Modifier::BOLD
+                            } else {
+                                Modifier::empty()
+                            }),
+                    ),
+                ]),
+                Line::from(vec![
+                    Span::styled("     ", Style::default().bg(bg)),
+                    Span::styled(
+                        item.course_name.as_deref().unwrap_or("").to_string(),
+                        Style::default().fg(TEXT_MUTED).bg(bg),
+                    ),
+                ]),
+            ]));
+        }
+    }
+
+    if list_items.is_empty() {
+        list_items.push(ListItem::new(Line::from(Span::styled(
+            "  ○  No calendar entries found.",
+            Style::default().fg(TEXT_DIM),
+        ))));
+    }
+
+    let list = List::new(list_items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(TEXT_MUTED))
+            .title(format!(
+                " Schedule [Calendar] ({}){}   v: list   f: filter   Enter: submit ",
+                app.calendar_items.len(),
+                filter_hint(app)
+            ))
+            .title_style(Style::default().fg(AMBER).add_modifier(Modifier::BOLD)),
+    );
+
+    app.calendar_list_state
+        .inner
+        .select(Some(selected_item_idx));
+
+    // Center the selected item in the viewport on initial load / jump-to-today.
+    if app.calendar_list_state.needs_center {
+        app.calendar_list_state.needs_center = false;
+        // area.height - 2 for borders; estimate ~2 lines per item on average.
+        let visible_items = ((area.height.saturating_sub(2)) / 2) as usize;
+        let half = visible_items / 2;
+        *app.calendar_list_state.inner.offset_mut() = selected_item_idx.saturating_sub(half);
+    }
+
+    f.render_stateful_widget(list, area, &mut app.calendar_list_state.inner);
+}
+
+/// Render a lightweight detail view for a pure calendar event (no assignment backing).
+fn render_calendar_event_detail<'a>(
+    f: &mut Frame,
+    area: Rect,
+    detail_block: Block<'a>,
+    item: &CalendarItem,
+    course_code: Option<&str>,
+    app: &App,
+) {
+    let now = Utc::now();
+    let today = now.date_naive();
+    let value_style = Style::default().fg(TEXT);
+
+    let (type_icon, type_color, type_str) = match (item.item_type, item.emphasis) {
+        ("assignment", _) => ("◆", DANGER, "Assignment"),
+        ("peer_review", _) => ("✎", DANGER, "Peer review"),
+        (_, EventEmphasis::Highlight) => ("◈", CAUTION, "Key event"),
+        (_, EventEmphasis::Dimmed) => ("◦", TEXT_DIM, "Routine event"),
+        (_, EventEmphasis::Normal) => ("◇", INFO, "Event"),
+    };
+
+    let (date_line, time_line) = if let Some(dt) = item.start_at {
+        let local = dt.with_timezone(&Local);
+        let date = local.date_naive();
+        let date_str = if date == today {
+            format!("{} (Today)", local.format("%A, %B %d, %Y"))
+        } else {
+            local.format("%A, %B %d, %Y").to_string()
+        };
+        (date_str, local.format("%H:%M").to_string())
+    } else {
+        ("No date".into(), "─".into())
+    };
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("  {type_icon} "), Style::default().fg(type_color)),
+            Span::styled(
+                item.title.clone(),
+                Style::default().fg(TEXT).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  ── ", Style::default().fg(TEXT_MUTED)),
+            Span::styled(type_str, Style::default().fg(type_color)),
+            Span::styled(
+                " ─────────────────────────────",
+                Style::default().fg(TEXT_MUTED),
+            ),
+        ]),
+        Line::from(""),
+    ];
+
+    let mut fields: Vec<(&str, String, Style)> = vec![
+        ("Date", date_line, value_style),
+        ("Time", time_line, value_style),
+    ];
+    if let Some(ref course) = item.course_name {
+        let course = match course_code {
+            Some(code) if code != course => format!("{course} ({code})"),
+            _ => course.clone(),
+        };
+        fields.push(("Course", course, value_style));
+    }
+    if let Some(ref status) = item.status {
+        fields.push(("Status", status.clone(), Style::default().fg(status_color(status))));
+    }
+    let linked = item
+        .event_id
+        .and_then(|id| app.event_linked_assignment(id))
+        .and_then(|id| app.get_assignment_by_id(id));
+    if let Some((_, a)) = linked {
+        let name = a.name.as_deref().unwrap_or("Unnamed");
+        fields.push(("Due for", format!("{name}  (l unlinks)"), Style::default().fg(INFO)));
+    }
+    let unlock = item
+        .assignment_id
+        .and_then(|id| app.get_assignment_by_id(id))
+        .and_then(|(_, a)| a.locked_until(app.now()));
+    if let Some(unlock) = unlock {
+        let (text, color) = opens_timer(unlock, app.now());
+        fields.push(("Opens", text, Style::default().fg(color)));
+    }
+    if let Some(dt) = item.start_at {
+        let (timer_text, timer_color) = countdown_timer(dt, app.now());
+        let timer_label = if matches!(item.item_type, "assignment" | "peer_review") {
+            "Due in"
+        } else {
+            "In"
+        };
+        fields.push((timer_label, timer_text, Style::default().fg(timer_color)));
+    }
+    lines.extend(course_field_rows(app, item.course_id, &fields));
+
+    let scroll = detail_scroll(app, &mut lines, area);
+    let detail = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll(scroll)
+        .block(detail_block);
+
+    f.render_widget(detail, area);
+}
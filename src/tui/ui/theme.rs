@@ -0,0 +1,195 @@
+use ratatui::{buffer::Buffer, style::Color};
+
+use crate::config::ColorMode;
+
+/// The colors of the interface, by what they mean.  Screens are drawn with
+/// the `TRUECOLOR` values (the palette constants in `ui`); on a terminal
+/// with fewer colors `repaint` swaps each for the same field of the
+/// matching fallback theme.
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    /// Primary amber accent — titles, selected markers, focal items.
+    pub amber: Color,
+    /// Softer amber for secondary labels (tab numbers, column headers).
+    pub amber_soft: Color,
+    /// Primary content text — warm off-white.
+    pub text: Color,
+    /// Secondary text — due dates, sub-labels.
+    pub text_dim: Color,
+    /// Very muted — borders, separators, empty markers.
+    pub text_muted: Color,
+    /// Selected row background (warm dark gold).
+    pub sel_bg: Color,
+    /// Focal (next actionable) item background.
+    pub focal_bg: Color,
+    /// Focal item foreground — same amber as the primary accent.
+    pub focal: Color,
+    /// Status bar / header background.
+    pub hdr_bg: Color,
+    /// Good scores, submitted state.
+    pub success: Color,
+    /// Warnings, imminent deadlines.
+    pub caution: Color,
+    /// Errors, missing assignments.
+    pub danger: Color,
+    /// Informational — submitted-but-not-graded state.
+    pub info: Color,
+    /// Countdowns of 3–7 days — yellow-green.
+    pub days_left: Color,
+    /// Countdowns of 6–24 hours — deep orange.
+    pub hours_left: Color,
+}
+
+pub const TRUECOLOR: Theme = Theme {
+    amber: Color::Rgb(255, 185, 50),
+    amber_soft: Color::Rgb(170, 120, 35),
+    text: Color::Rgb(232, 222, 205),
+    text_dim: Color::Rgb(108, 98, 82),
+    text_muted: Color::Rgb(58, 52, 42),
+    sel_bg: Color::Rgb(52, 42, 18),
+    focal_bg: Color::Rgb(62, 42, 0),
+    focal: Color::Rgb(255, 185, 50),
+    hdr_bg: Color::Rgb(16, 14, 11),
+    success: Color::Rgb(125, 195, 95),
+    caution: Color::Rgb(255, 162, 38),
+    danger: Color::Rgb(210, 68, 58),
+    info: Color::Rgb(98, 172, 238),
+    days_left: Color::Rgb(200, 210, 80),
+    hours_left: Color::Rgb(240, 120, 40),
+};
+
+/// The nearest entries of the xterm 256-color table.
+pub const ANSI_256: Theme = Theme {
+    amber: Color::Indexed(214),
+    amber_soft: Color::Indexed(136),
+    text: Color::Indexed(254),
+    text_dim: Color::Indexed(243),
+    text_muted: Color::Indexed(238),
+    sel_bg: Color::Indexed(58),
+    focal_bg: Color::Indexed(94),
+    focal: Color::Indexed(214),
+    hdr_bg: Color::Indexed(233),
+    success: Color::Indexed(107),
+    caution: Color::Indexed(208),
+    danger: Color::Indexed(167),
+    info: Color::Indexed(75),
+    days_left: Color::Indexed(185),
+    hours_left: Color::Indexed(202),
+};
+
+/// The 16 ANSI colors, whose exact shades are up to the terminal.  Amber,
+/// success and danger keep distinct hues (yellow, green, red); the two
+/// row backgrounds differ in hue so the selection still shows on the focal
+/// row.  Text uses the terminal's own foreground.
+pub const ANSI_16: Theme = Theme {
+    amber: Color::Yellow,
+    amber_soft: Color::Yellow,
+    text: Color::Reset,
+    text_dim: Color::Gray,
+    text_muted: Color::DarkGray,
+    sel_bg: Color::Blue,
+    focal_bg: Color::DarkGray,
+    focal: Color::Yellow,
+    hdr_bg: Color::Black,
+    success: Color::Green,
+    caution: Color::LightYellow,
+    danger: Color::Red,
+    info: Color::Cyan,
+    days_left: Color::LightGreen,
+    hours_left: Color::LightRed,
+};
+
+impl Theme {
+    pub fn for_mode(mode: ColorMode) -> &'static Theme {
+        match mode {
+            ColorMode::TrueColor => &TRUECOLOR,
+            ColorMode::Ansi256 => &ANSI_256,
+            ColorMode::Ansi16 => &ANSI_16,
+        }
+    }
+
+    /// Every color with its name, in the order `--color-test` shows them.
+    pub fn swatches(&self) -> [(&'static str, Color); 15] {
+        [
+            ("amber", self.amber),
+            ("amber_soft", self.amber_soft),
+            ("text", self.text),
+            ("text_dim", self.text_dim),
+            ("text_muted", self.text_muted),
+            ("sel_bg", self.sel_bg),
+            ("focal_bg", self.focal_bg),
+            ("focal", self.focal),
+            ("hdr_bg", self.hdr_bg),
+            ("success", self.success),
+            ("caution", self.caution),
+            ("danger", self.danger),
+            ("info", self.info),
+            ("days_left", self.days_left),
+            ("hours_left", self.hours_left),
+        ]
+    }
+
+    /// This theme's color for `color`, one drawn in the truecolor palette.
+    /// Colors outside the palette (`Reset`, named ones) are left alone.
+    fn translate(&self, color: Color) -> Color {
+        TRUECOLOR
+            .swatches()
+            .iter()
+            .zip(self.swatches())
+            .find(|((_, from), _)| *from == color)
+            .map_or(color, |(_, (_, to))| to)
+    }
+}
+
+/// Convert a frame drawn in the truecolor palette to `mode`.
+pub fn repaint(buf: &mut Buffer, mode: ColorMode) {
+    if mode == ColorMode::TrueColor {
+        return;
+    }
+    let theme = Theme::for_mode(mode);
+    for cell in &mut buf.content {
+        cell.fg = theme.translate(cell.fg);
+        cell.bg = theme.translate(cell.bg);
+    }
+}
+
+/// What the terminal can show, going by the environment: `COLORTERM`
+/// announces truecolor; otherwise `TERM` names 256 colors (`…-256color`)
+/// or, for `screen`, `linux`, `xterm-16color` and the like, only 16.
+pub fn detect_color_mode() -> ColorMode {
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default().to_lowercase();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return ColorMode::TrueColor;
+    }
+    let Ok(term) = std::env::var("TERM") else {
+        // Windows consoles don't set TERM and have handled RGB since
+        // Windows 10.
+        return if cfg!(windows) { ColorMode::TrueColor } else { ColorMode::Ansi16 };
+    };
+    let term = term.to_lowercase();
+    let rgb_terms = ["kitty", "alacritty", "wezterm", "foot"];
+    if term.ends_with("-direct") || rgb_terms.iter().any(|t| term.contains(t)) {
+        ColorMode::TrueColor
+    } else if term.contains("256color") {
+        ColorMode::Ansi256
+    } else {
+        ColorMode::Ansi16
+    }
+}
+
+/// Print the palette for `mode` as a block of color and a line of text in
+/// each color, for `canvas-tui --color-test`.
+pub fn print_color_test(mode: ColorMode, detected: bool) {
+    use crossterm::style::Stylize;
+
+    let how = if detected { "detected" } else { "from color_mode" };
+    println!("Color mode: {} ({how})", mode.name());
+    println!();
+    for (name, color) in Theme::for_mode(mode).swatches() {
+        println!(
+            "  {}  {}  {name}",
+            "    ".on(color.into()),
+            "Sample text".with(color.into()),
+        );
+    }
+}
@@ -0,0 +1,14 @@
+/// Crate version from Cargo.toml.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Short commit hash the binary was built from ("unknown" outside git).
+pub const GIT_HASH: &str = env!("CANVAS_TUI_GIT_HASH");
+/// UTC build date, YYYY-MM-DD.
+pub const BUILD_DATE: &str = env!("CANVAS_TUI_BUILD_DATE");
+
+/// Sent with every HTTP request.
+pub const USER_AGENT: &str = concat!("canvas-tui/", env!("CARGO_PKG_VERSION"));
+
+/// One-line build description for `--version`, bug reports and the log.
+pub fn long_version() -> String {
+    format!("canvas-tui {VERSION} ({GIT_HASH}, built {BUILD_DATE})")
+}
@@ -0,0 +1,135 @@
+use anyhow::{bail, Context, Result};
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use std::io::{self, Write};
+use url::Url;
+
+use crate::api::{CanvasClient, CanvasError};
+use crate::config::Config;
+
+/// Walk the user through entering and validating their Canvas URL and API
+/// token, then save the config.  Validation failures loop back to the
+/// relevant prompt instead of exiting.
+pub async fn run() -> Result<Config> {
+    println!();
+    println!("╔══════════════════════════════════════════════════════╗");
+    println!("║   Welcome to canvas-tui!                            ║");
+    println!("║   Let's connect to your Canvas account.             ║");
+    println!("╚══════════════════════════════════════════════════════╝");
+    println!();
+
+    let mut canvas_url = prompt_url()?;
+    loop {
+        println!();
+        println!("Now create an API token in Canvas:");
+        println!("  {canvas_url}/profile/settings → Approved Integrations → + New Access Token");
+        let token = read_hidden("Paste your API token (input is hidden): ")?;
+        if token.is_empty() {
+            println!("No token entered — try again.");
+            continue;
+        }
+
+        print!("Checking… ");
+        io::stdout().flush()?;
+        let client = CanvasClient::new(&canvas_url, &token)?;
+        match client.get_self().await {
+            Ok(user) => {
+                let name = user.name.as_deref().unwrap_or("there");
+                println!("Hello, {name}!");
+                let config = Config {
+                    canvas_url,
+                    api_token: token,
+                    ..Config::default()
+                };
+                config.save()?;
+                println!("Saved your settings. Starting canvas-tui…");
+                return Ok(config);
+            }
+            Err(CanvasError::Unauthorized) => {
+                println!("Canvas rejected that token. Check you copied all of it.");
+            }
+            Err(e) => {
+                println!("Couldn't reach Canvas at {canvas_url}: {e}");
+                canvas_url = prompt_url()?;
+            }
+        }
+    }
+}
+
+/// Prompt until the user enters something that normalizes to a URL.
+fn prompt_url() -> Result<String> {
+    loop {
+        print!("Canvas URL (e.g. https://school.instructure.com): ");
+        io::stdout().flush()?;
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            bail!("Setup aborted");
+        }
+        match normalize_canvas_url(&input) {
+            Ok(url) => return Ok(url),
+            Err(e) => println!("{e:#} — try again."),
+        }
+    }
+}
+
+/// Accept the forms people paste — bare hostnames, trailing slashes, links
+/// to a course page — and reduce them to `https://host[:port]`.
+fn normalize_canvas_url(input: &str) -> Result<String> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        bail!("No URL entered");
+    }
+    let with_scheme = if trimmed.contains("://") {
+        trimmed.to_string()
+    } else {
+        format!("https://{trimmed}")
+    };
+    let url = Url::parse(&with_scheme).with_context(|| format!("'{trimmed}' is not a valid URL"))?;
+    if url.host_str().is_none() {
+        bail!("'{trimmed}' has no host name");
+    }
+    Ok(url.origin().ascii_serialization())
+}
+
+/// Read a line from the terminal without echoing it.  Falls back to a
+/// normal (echoed) read when stdin isn't a terminal.
+pub fn read_hidden(prompt: &str) -> Result<String> {
+    print!("{prompt}");
+    io::stdout().flush()?;
+
+    if enable_raw_mode().is_err() {
+        let mut line = String::new();
+        io::stdin().read_line(&mut line)?;
+        return Ok(line.trim().to_string());
+    }
+
+    let mut buf = String::new();
+    let result = loop {
+        let key = match event::read() {
+            Ok(Event::Key(key)) => key,
+            Ok(_) => continue,
+            Err(e) => break Err(e.into()),
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => break Ok(()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                break Err(anyhow::anyhow!("Setup aborted"));
+            }
+            KeyCode::Char(c) => buf.push(c),
+            KeyCode::Backspace => {
+                buf.pop();
+            }
+            KeyCode::Esc => buf.clear(),
+            _ => {}
+        }
+    };
+    disable_raw_mode()?;
+    println!();
+
+    result.map(|()| buf.trim().to_string())
+}